@@ -0,0 +1,53 @@
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture, Offloaded};
+
+/// Tells whether a password is known from public breach corpora
+pub trait BreachedPasswordCheckerTrait {
+    fn is_breached(&self, password: &str) -> Result<bool, BreachCheckError>;
+}
+
+/// Async counterpart of `BreachedPasswordCheckerTrait`. Checkers read files or call
+/// remote services, implementations shouldn't block the executor threads
+pub trait AsyncBreachedPasswordCheckerTrait: Send + Sync {
+    fn is_breached<'a>(
+        &'a self,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<bool, BreachCheckError>>;
+}
+
+impl<T: BreachedPasswordCheckerTrait + Send> AsyncBreachedPasswordCheckerTrait for Blocking<T> {
+    fn is_breached<'a>(
+        &'a self,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<bool, BreachCheckError>> {
+        ready(self.inner().is_breached(password))
+    }
+}
+
+impl<T: BreachedPasswordCheckerTrait + Send + Sync + 'static> AsyncBreachedPasswordCheckerTrait
+    for Offloaded<T>
+{
+    fn is_breached<'a>(
+        &'a self,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<bool, BreachCheckError>> {
+        let password = password.to_string();
+        let checked = self.run(move |inner| inner.is_breached(&password));
+
+        Box::pin(async move {
+            checked.await.unwrap_or_else(|| {
+                Err(BreachCheckError::SourceError(
+                    "Check is interrupted".to_string(),
+                ))
+            })
+        })
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum BreachCheckError {
+    #[error("Can't read breached passwords source: {0}")]
+    SourceError(String),
+
+    #[error("Breached passwords source is malformed: {0}")]
+    MalformedSource(String),
+}
@@ -1,2 +1,3 @@
+pub mod breached_password;
 pub mod mock;
 pub mod password;
@@ -0,0 +1,39 @@
+use crate::breached_password::{BreachCheckError, BreachedPasswordCheckerTrait};
+
+pub struct BreachedPasswordCheckerMock {
+    breached: Vec<String>,
+}
+
+impl BreachedPasswordCheckerMock {
+    pub fn new(breached: Vec<&str>) -> BreachedPasswordCheckerMock {
+        BreachedPasswordCheckerMock {
+            breached: breached.into_iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+impl BreachedPasswordCheckerTrait for BreachedPasswordCheckerMock {
+    fn is_breached(&self, password: &str) -> Result<bool, BreachCheckError> {
+        Ok(self.breached.iter().any(|p| p == password))
+    }
+}
+
+pub struct BreachedPasswordCheckerMockWithBrokenSource {}
+
+impl BreachedPasswordCheckerMockWithBrokenSource {
+    pub fn new() -> BreachedPasswordCheckerMockWithBrokenSource {
+        BreachedPasswordCheckerMockWithBrokenSource {}
+    }
+}
+
+impl Default for BreachedPasswordCheckerMockWithBrokenSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BreachedPasswordCheckerTrait for BreachedPasswordCheckerMockWithBrokenSource {
+    fn is_breached(&self, _password: &str) -> Result<bool, BreachCheckError> {
+        Err(BreachCheckError::SourceError("Broken source".to_string()))
+    }
+}
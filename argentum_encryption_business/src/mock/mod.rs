@@ -1 +1,2 @@
+pub mod breached_password;
 pub mod password;
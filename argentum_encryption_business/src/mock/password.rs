@@ -1,13 +1,22 @@
-use crate::password::{EncryptionError, Encryptor, Validator};
+use crate::password::{EncryptionError, Encryptor, ValidationError, Validator};
 
-const SALT: &str = "encoded_";
+const PREFIX: &str = "$mock$v=";
+
+/// Produces `$mock$v={version}${password}` strings.
+/// Hashes with a lower version are treated as outdated.
+pub struct EncryptorMock {
+    version: u32,
+}
 
-pub struct EncryptorMock {}
 pub struct ValidatorMock {}
 
 impl EncryptorMock {
     pub fn new() -> EncryptorMock {
-        EncryptorMock {}
+        EncryptorMock::with_version(1)
+    }
+
+    pub fn with_version(version: u32) -> EncryptorMock {
+        EncryptorMock { version }
     }
 }
 
@@ -18,11 +27,15 @@ impl Default for EncryptorMock {
 }
 
 impl Encryptor for EncryptorMock {
-    fn encrypt(&self, password: &str) -> Result<(String, String), EncryptionError> {
-        let salt = String::from(SALT);
-        let hash = [SALT, password].join("");
+    fn encrypt(&self, password: &str) -> Result<String, EncryptionError> {
+        Ok(format!("{}{}${}", PREFIX, self.version, password))
+    }
 
-        Ok((hash, salt))
+    fn needs_rehash(&self, encoded_password: &str) -> bool {
+        match parse(encoded_password) {
+            Some((version, _)) => version < self.version,
+            None => true,
+        }
     }
 }
 
@@ -39,9 +52,61 @@ impl Default for ValidatorMock {
 }
 
 impl Validator for ValidatorMock {
-    fn validate(&self, password: &str, salt: &str, encoded_password: &str) -> bool {
-        let hash = [salt, password].join("");
+    fn validate(&self, password: &str, encoded_password: &str) -> Result<bool, ValidationError> {
+        match parse(encoded_password) {
+            Some((_, hash)) => Ok(hash == password),
+            None => Err(ValidationError::MalformedHash),
+        }
+    }
+}
 
-        encoded_password == hash
+pub struct ValidatorMockWithBrokenBackend {}
+
+impl ValidatorMockWithBrokenBackend {
+    pub fn new() -> ValidatorMockWithBrokenBackend {
+        ValidatorMockWithBrokenBackend {}
     }
 }
+
+impl Default for ValidatorMockWithBrokenBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Validator for ValidatorMockWithBrokenBackend {
+    fn validate(&self, _password: &str, _encoded: &str) -> Result<bool, ValidationError> {
+        Err(ValidationError::BackendError("Broken backend".to_string()))
+    }
+}
+
+pub struct EncryptorMockWithBrokenHashing {}
+
+impl EncryptorMockWithBrokenHashing {
+    pub fn new() -> EncryptorMockWithBrokenHashing {
+        EncryptorMockWithBrokenHashing {}
+    }
+}
+
+impl Default for EncryptorMockWithBrokenHashing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encryptor for EncryptorMockWithBrokenHashing {
+    fn encrypt(&self, _password: &str) -> Result<String, EncryptionError> {
+        Err(EncryptionError::HashError)
+    }
+
+    fn needs_rehash(&self, _encoded_password: &str) -> bool {
+        false
+    }
+}
+
+fn parse(encoded_password: &str) -> Option<(u32, &str)> {
+    let rest = encoded_password.strip_prefix(PREFIX)?;
+    let (version, hash) = rest.split_at(rest.find('$')?);
+
+    Some((version.parse().ok()?, &hash[1..]))
+}
@@ -1,13 +1,110 @@
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture, Offloaded};
+
+/// Encryptor hashes passwords with the current hashing policy.
+/// The result is a self-describing string (PHC string format, or MCF for bcrypt)
+/// which keeps the algorithm, its parameters and the salt together with the hash.
 pub trait Encryptor {
-    fn encrypt(&self, password: &str) -> Result<(String, String), EncryptionError>;
+    fn encrypt(&self, password: &str) -> Result<String, EncryptionError>;
+
+    /// Returns true if the encoded password was produced by another algorithm
+    /// or with weaker parameters than the current policy.
+    fn needs_rehash(&self, encoded_password: &str) -> bool;
 }
 
+/// Returns `Ok(false)` for a wrong password.
+/// Errors are reserved for stored hashes which can't be checked at all.
 pub trait Validator {
-    fn validate(&self, password: &str, salt: &str, encoded_password: &str) -> bool;
+    fn validate(&self, password: &str, encoded_password: &str) -> Result<bool, ValidationError>;
+}
+
+/// Async counterpart of `Encryptor`. Hashing is slow on purpose, implementations
+/// shouldn't run it on the executor threads
+pub trait AsyncEncryptor: Send + Sync {
+    fn encrypt<'a>(&'a self, password: &'a str) -> BoxFuture<'a, Result<String, EncryptionError>>;
+
+    /// Only parses the encoded password, so it stays synchronous
+    fn needs_rehash(&self, encoded_password: &str) -> bool;
+}
+
+/// Async counterpart of `Validator`
+pub trait AsyncValidator: Send + Sync {
+    fn validate<'a>(
+        &'a self,
+        password: &'a str,
+        encoded_password: &'a str,
+    ) -> BoxFuture<'a, Result<bool, ValidationError>>;
+}
+
+impl<T: Encryptor + Send> AsyncEncryptor for Blocking<T> {
+    fn encrypt<'a>(&'a self, password: &'a str) -> BoxFuture<'a, Result<String, EncryptionError>> {
+        ready(self.inner().encrypt(password))
+    }
+
+    fn needs_rehash(&self, encoded_password: &str) -> bool {
+        self.inner().needs_rehash(encoded_password)
+    }
+}
+
+impl<T: Validator + Send> AsyncValidator for Blocking<T> {
+    fn validate<'a>(
+        &'a self,
+        password: &'a str,
+        encoded_password: &'a str,
+    ) -> BoxFuture<'a, Result<bool, ValidationError>> {
+        ready(self.inner().validate(password, encoded_password))
+    }
+}
+
+impl<T: Encryptor + Send + Sync + 'static> AsyncEncryptor for Offloaded<T> {
+    fn encrypt<'a>(&'a self, password: &'a str) -> BoxFuture<'a, Result<String, EncryptionError>> {
+        let password = password.to_string();
+        let hashed = self.run(move |inner| inner.encrypt(&password));
+
+        Box::pin(async move { hashed.await.unwrap_or(Err(EncryptionError::HashError)) })
+    }
+
+    fn needs_rehash(&self, encoded_password: &str) -> bool {
+        self.inner().needs_rehash(encoded_password)
+    }
+}
+
+impl<T: Validator + Send + Sync + 'static> AsyncValidator for Offloaded<T> {
+    fn validate<'a>(
+        &'a self,
+        password: &'a str,
+        encoded_password: &'a str,
+    ) -> BoxFuture<'a, Result<bool, ValidationError>> {
+        let password = password.to_string();
+        let encoded_password = encoded_password.to_string();
+        let validated = self.run(move |inner| inner.validate(&password, &encoded_password));
+
+        Box::pin(async move {
+            validated.await.unwrap_or_else(|| {
+                Err(ValidationError::BackendError(
+                    "Validation is interrupted".to_string(),
+                ))
+            })
+        })
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum EncryptionError {
     #[error("Can't generate a salt")]
     SaltError,
+
+    #[error("Can't hash a password")]
+    HashError,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ValidationError {
+    #[error("Stored password hash is malformed")]
+    MalformedHash,
+
+    #[error("Password hashing algorithm `{0}` is not supported")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Password validation backend failure: {0}")]
+    BackendError(String),
 }
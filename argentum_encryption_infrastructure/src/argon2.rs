@@ -0,0 +1,255 @@
+use crate::phc::PhcString;
+use ::argon2::{Algorithm, Params, Version};
+use argentum_encryption_business::password::{
+    EncryptionError, Encryptor, ValidationError, Validator,
+};
+use ring::constant_time::verify_slices_are_equal;
+use ring::rand;
+use ring::rand::SecureRandom;
+
+pub const ALGORITHM: &str = "argon2id";
+const VERSION: u32 = 0x13;
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+
+// Upper bounds for costs read from stored hashes: 256 MiB, 16 passes, 16 lanes
+const MAX_MEMORY_COST: u32 = 256 * 1024;
+const MAX_TIME_COST: u32 = 16;
+const MAX_PARALLELISM: u32 = 16;
+
+/// Argon2id password hashing. Costs are the memory size in KiB, the number of passes
+/// and the degree of parallelism. Validation uses the costs stored in the PHC string,
+/// but refuses costs above the maximums, so a tampered row can't exhaust the server.
+pub struct Argon2 {
+    memory_cost: u32,
+    time_cost: u32,
+    parallelism: u32,
+    max_costs: (u32, u32, u32),
+}
+
+impl Argon2 {
+    pub fn new(memory_cost: u32, time_cost: u32, parallelism: u32) -> Argon2 {
+        Argon2 {
+            memory_cost,
+            time_cost,
+            parallelism,
+            max_costs: (
+                memory_cost.max(MAX_MEMORY_COST),
+                time_cost.max(MAX_TIME_COST),
+                parallelism.max(MAX_PARALLELISM),
+            ),
+        }
+    }
+
+    /// Overrides the highest costs a stored hash may ask for
+    pub fn with_max_costs(mut self, memory_cost: u32, time_cost: u32, parallelism: u32) -> Argon2 {
+        self.max_costs = (memory_cost, time_cost, parallelism);
+
+        self
+    }
+
+    fn within_max_costs(&self, m: u32, t: u32, p: u32) -> bool {
+        let (max_m, max_t, max_p) = self.max_costs;
+
+        m <= max_m && t <= max_t && p <= max_p
+    }
+
+    fn hash(&self, password: &str, salt: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        self.hash_with_len(password, salt, HASH_LEN)
+    }
+
+    fn hash_with_len(
+        &self,
+        password: &str,
+        salt: &[u8],
+        len: usize,
+    ) -> Result<Vec<u8>, EncryptionError> {
+        let params = Params::new(
+            self.memory_cost,
+            self.time_cost,
+            self.parallelism,
+            Some(len),
+        )
+        .map_err(|_| EncryptionError::HashError)?;
+
+        let mut hash = vec![0u8; len];
+        ::argon2::Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), salt, &mut hash)
+            .map_err(|_| EncryptionError::HashError)?;
+
+        Ok(hash)
+    }
+}
+
+impl Default for Argon2 {
+    fn default() -> Self {
+        Self::new(
+            Params::DEFAULT_M_COST,
+            Params::DEFAULT_T_COST,
+            Params::DEFAULT_P_COST,
+        )
+    }
+}
+
+impl Encryptor for Argon2 {
+    fn encrypt(&self, password: &str) -> Result<String, EncryptionError> {
+        let rng = rand::SystemRandom::new();
+
+        let mut salt = [0u8; SALT_LEN];
+        if rng.fill(&mut salt).is_err() {
+            return Err(EncryptionError::SaltError);
+        }
+
+        let hash = self.hash(password, &salt)?;
+        let phc = PhcString::new(
+            ALGORITHM,
+            Some(VERSION),
+            vec![
+                ("m", self.memory_cost.to_string()),
+                ("t", self.time_cost.to_string()),
+                ("p", self.parallelism.to_string()),
+            ],
+            &salt,
+            &hash,
+        );
+
+        Ok(phc.to_string())
+    }
+
+    fn needs_rehash(&self, encoded_password: &str) -> bool {
+        let phc = match PhcString::parse(encoded_password) {
+            Some(phc) if phc.algorithm == ALGORITHM && phc.version == Some(VERSION) => phc,
+            _ => return true,
+        };
+
+        match (phc.param_u32("m"), phc.param_u32("t"), phc.param_u32("p")) {
+            (Some(m), Some(t), Some(p)) => {
+                m < self.memory_cost || t < self.time_cost || p < self.parallelism
+            }
+            _ => true,
+        }
+    }
+}
+
+impl Validator for Argon2 {
+    fn validate(&self, password: &str, encoded_password: &str) -> Result<bool, ValidationError> {
+        let phc = match PhcString::parse(encoded_password) {
+            Some(phc) if phc.algorithm != ALGORITHM => {
+                return Err(ValidationError::UnsupportedAlgorithm(phc.algorithm))
+            }
+            Some(phc) if phc.version == Some(VERSION) => phc,
+            _ => return Err(ValidationError::MalformedHash),
+        };
+
+        let stored = match (phc.param_u32("m"), phc.param_u32("t"), phc.param_u32("p")) {
+            (Some(m), Some(t), Some(p)) if self.within_max_costs(m, t, p) => Argon2::new(m, t, p),
+            _ => return Err(ValidationError::MalformedHash),
+        };
+
+        match stored.hash_with_len(password, &phc.salt, phc.hash.len()) {
+            Ok(hash) => Ok(verify_slices_are_equal(&hash, &phc.hash).is_ok()),
+            Err(_) => Err(ValidationError::MalformedHash),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::argon2::Argon2;
+    use argentum_encryption_business::password::{Encryptor, ValidationError, Validator};
+
+    fn stub_password() -> &'static str {
+        "123456"
+    }
+
+    fn stub_hasher() -> Argon2 {
+        Argon2::new(1024, 2, 1)
+    }
+
+    fn stub_hash() -> &'static str {
+        "$argon2id$v=19$m=1024,t=2,p=1$0IUKrVVenVUVWdh4RJo4eQ\
+        $uQaySIo8JPHPIwusQ/TFqOcOpRRqtUlkB5YAQkbTL9w"
+    }
+
+    #[test]
+    fn test_argon2_encrypt() {
+        let password = stub_password();
+        let argon2 = stub_hasher();
+        let hash = argon2.encrypt(password).expect("Can't encrypt a password");
+
+        assert!(hash.starts_with("$argon2id$v=19$m=1024,t=2,p=1$"));
+        assert!(argon2.validate(password, &hash).unwrap());
+    }
+
+    #[test]
+    fn test_argon2_validate_correct_password() {
+        let result = stub_hasher().validate(stub_password(), stub_hash());
+
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn test_argon2_validate_wrong_password() {
+        let result = stub_hasher().validate("111111", stub_hash());
+
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn test_argon2_validate_with_stored_costs() {
+        let result = Argon2::new(2048, 3, 1).validate(stub_password(), stub_hash());
+
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn test_argon2_validate_malformed_hash() {
+        let argon2 = stub_hasher();
+
+        let result = argon2.validate(stub_password(), "$argon2id$v=19$m=1024,t=2$c2FsdA$aGFzaA");
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+
+        let result = argon2.validate(stub_password(), "$argon2id$v=19$m=1,t=0,p=0$c2FsdA$aGFzaA");
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+
+        let result = argon2.validate(stub_password(), "not a hash");
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+
+        let result = argon2.validate(stub_password(), "$pbkdf2-sha512$i=1$c2FsdA$aGFzaA");
+        assert!(matches!(
+            result,
+            Err(ValidationError::UnsupportedAlgorithm(_))
+        ));
+    }
+
+    #[test]
+    fn test_argon2_validate_hash_above_max_costs() {
+        let argon2 = stub_hasher();
+
+        let result = argon2.validate(
+            stub_password(),
+            "$argon2id$v=19$m=4194304,t=1000,p=1$c2FsdA$aGFzaA",
+        );
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+
+        let result = argon2
+            .with_max_costs(1024, 1, 1)
+            .validate(stub_password(), stub_hash());
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+    }
+
+    #[test]
+    fn test_argon2_needs_rehash() {
+        assert!(!stub_hasher().needs_rehash(stub_hash()));
+        assert!(Argon2::new(2048, 2, 1).needs_rehash(stub_hash()));
+        assert!(Argon2::new(1024, 3, 1).needs_rehash(stub_hash()));
+        assert!(stub_hasher().needs_rehash("$pbkdf2-sha512$i=1000$c2FsdA$aGFzaA"));
+    }
+
+    #[test]
+    fn test_argon2_encrypt_with_invalid_costs() {
+        let argon2 = Argon2::new(1, 0, 0);
+
+        assert!(argon2.encrypt(stub_password()).is_err());
+    }
+}
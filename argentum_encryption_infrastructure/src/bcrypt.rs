@@ -0,0 +1,121 @@
+use ::bcrypt::{BcryptError, HashParts, Version};
+use argentum_encryption_business::password::{
+    EncryptionError, Encryptor, ValidationError, Validator,
+};
+use ring::rand;
+use ring::rand::SecureRandom;
+
+const SALT_LEN: usize = 16;
+
+/// Bcrypt password hashing. Hashes are kept in the modular crypt format (`$2b$12$...`).
+/// Validation accepts the `$2a$`, `$2b$` and `$2y$` variants,
+/// so hashes imported from PHP's `password_hash` can be checked as is.
+pub struct Bcrypt {
+    cost: u32,
+}
+
+impl Bcrypt {
+    pub fn new(cost: u32) -> Bcrypt {
+        Bcrypt { cost }
+    }
+}
+
+impl Default for Bcrypt {
+    fn default() -> Self {
+        Self::new(::bcrypt::DEFAULT_COST)
+    }
+}
+
+impl Encryptor for Bcrypt {
+    fn encrypt(&self, password: &str) -> Result<String, EncryptionError> {
+        let rng = rand::SystemRandom::new();
+
+        let mut salt = [0u8; SALT_LEN];
+        if rng.fill(&mut salt).is_err() {
+            return Err(EncryptionError::SaltError);
+        }
+
+        match ::bcrypt::hash_with_salt(password, self.cost, salt) {
+            Ok(parts) => Ok(parts.format_for_version(Version::TwoB)),
+            Err(_) => Err(EncryptionError::HashError),
+        }
+    }
+
+    fn needs_rehash(&self, encoded_password: &str) -> bool {
+        match encoded_password.parse::<HashParts>() {
+            Ok(parts) => parts.get_cost() < self.cost,
+            Err(_) => true,
+        }
+    }
+}
+
+impl Validator for Bcrypt {
+    fn validate(&self, password: &str, encoded_password: &str) -> Result<bool, ValidationError> {
+        match ::bcrypt::verify(password, encoded_password) {
+            Ok(valid) => Ok(valid),
+            Err(BcryptError::Io(e)) => Err(ValidationError::BackendError(e.to_string())),
+            Err(BcryptError::Rand(e)) => Err(ValidationError::BackendError(e.to_string())),
+            Err(_) => Err(ValidationError::MalformedHash),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bcrypt::Bcrypt;
+    use argentum_encryption_business::password::{Encryptor, ValidationError, Validator};
+
+    fn stub_password() -> &'static str {
+        "rasmuslerdorf"
+    }
+
+    fn stub_php_hash() -> &'static str {
+        "$2y$10$.vGA1O9wmRjrwAVXD98HNOgsNpDczlqm3Jq7KnEd1rVAGv3Fykk1a"
+    }
+
+    #[test]
+    fn test_bcrypt_encrypt() {
+        let password = stub_password();
+        let bcrypt = Bcrypt::new(4);
+        let hash = bcrypt.encrypt(password).expect("Can't encrypt a password");
+
+        assert_eq!(60, hash.len());
+        assert!(hash.starts_with("$2b$04$"));
+        assert!(bcrypt.validate(password, &hash).unwrap());
+    }
+
+    #[test]
+    fn test_bcrypt_validate_correct_password() {
+        let result = Bcrypt::default().validate(stub_password(), stub_php_hash());
+
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn test_bcrypt_validate_wrong_password() {
+        let result = Bcrypt::default().validate("111111", stub_php_hash());
+
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn test_bcrypt_validate_malformed_hash() {
+        let result = Bcrypt::default().validate(stub_password(), "not a bcrypt hash");
+
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+    }
+
+    #[test]
+    fn test_bcrypt_needs_rehash() {
+        assert!(!Bcrypt::new(10).needs_rehash(stub_php_hash()));
+        assert!(Bcrypt::new(12).needs_rehash(stub_php_hash()));
+        assert!(Bcrypt::new(10).needs_rehash("$pbkdf2-sha512$i=1000$c2FsdA$aGFzaA"));
+    }
+
+    #[test]
+    fn test_bcrypt_encrypt_with_invalid_cost() {
+        let bcrypt = Bcrypt::new(3);
+
+        assert!(bcrypt.encrypt(stub_password()).is_err());
+    }
+}
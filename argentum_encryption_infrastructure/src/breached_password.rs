@@ -0,0 +1,122 @@
+use argentum_encryption_business::breached_password::{
+    BreachCheckError, BreachedPasswordCheckerTrait,
+};
+use data_encoding::HEXUPPER;
+use ring::digest;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+const PREFIX_LEN: usize = 5;
+
+/// Looks passwords up in a local copy of the Have I Been Pwned password corpus.
+///
+/// The directory holds one file per SHA-1 prefix, named `<PREFIX>.txt` (e.g. `5BAA6.txt`),
+/// each containing sorted `<SUFFIX>:<COUNT>` lines. This is the range API format, so the
+/// files produced by the official downloader can be used as is. Padding lines with a zero
+/// count are ignored. A missing or unreadable prefix file is an error, never a pass.
+pub struct PwnedPasswordsDirectory {
+    path: PathBuf,
+    min_count: u64,
+}
+
+impl PwnedPasswordsDirectory {
+    pub fn new(path: PathBuf) -> PwnedPasswordsDirectory {
+        PwnedPasswordsDirectory::with_min_count(path, 1)
+    }
+
+    /// Passwords seen fewer than `min_count` times are not reported as breached
+    pub fn with_min_count(path: PathBuf, min_count: u64) -> PwnedPasswordsDirectory {
+        PwnedPasswordsDirectory {
+            path,
+            min_count: min_count.max(1),
+        }
+    }
+}
+
+impl BreachedPasswordCheckerTrait for PwnedPasswordsDirectory {
+    fn is_breached(&self, password: &str) -> Result<bool, BreachCheckError> {
+        let hash = digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, password.as_bytes());
+        let hash = HEXUPPER.encode(hash.as_ref());
+        let (prefix, suffix) = hash.split_at(PREFIX_LEN);
+
+        let file_path = self.path.join(format!("{}.txt", prefix));
+        let file = File::open(&file_path).map_err(|e| {
+            BreachCheckError::SourceError(format!("{}: {}", file_path.display(), e))
+        })?;
+
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| BreachCheckError::SourceError(e.to_string()))?;
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (line_suffix, count) = match line.split_once(':') {
+                Some(parts) => parts,
+                None => return Err(BreachCheckError::MalformedSource(line.to_string())),
+            };
+
+            match line_suffix.to_uppercase().as_str().cmp(suffix) {
+                Ordering::Less => continue,
+                Ordering::Greater => return Ok(false),
+                Ordering::Equal => {
+                    let count: u64 = count
+                        .parse()
+                        .map_err(|_| BreachCheckError::MalformedSource(line.to_string()))?;
+
+                    return Ok(count >= self.min_count);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::breached_password::PwnedPasswordsDirectory;
+    use argentum_encryption_business::breached_password::{
+        BreachCheckError, BreachedPasswordCheckerTrait,
+    };
+    use std::path::PathBuf;
+
+    fn fixture_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/pwned_passwords")
+    }
+
+    #[test]
+    fn test_breached_password() {
+        let checker = PwnedPasswordsDirectory::new(fixture_path());
+
+        assert!(checker.is_breached("password").unwrap());
+    }
+
+    #[test]
+    fn test_not_breached_password() {
+        let checker = PwnedPasswordsDirectory::new(fixture_path());
+
+        // Its suffix is listed as a zero count padding line
+        assert!(!checker.is_breached("Correct-Horse-7").unwrap());
+    }
+
+    #[test]
+    fn test_breached_password_below_min_count() {
+        let checker = PwnedPasswordsDirectory::with_min_count(fixture_path(), 10_000_000);
+
+        assert!(!checker.is_breached("password").unwrap());
+    }
+
+    #[test]
+    fn test_missing_prefix_file() {
+        let checker = PwnedPasswordsDirectory::new(fixture_path());
+
+        assert!(matches!(
+            checker.is_breached("P@ssw0rd"),
+            Err(BreachCheckError::SourceError(_))
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::phc;
+use argentum_encryption_business::password::{ValidationError, Validator};
+
+/// Validates an encoded password with the validator registered for its algorithm identifier
+/// (`pbkdf2-sha512`, `argon2id`, `2y`, ...), so credentials produced by older algorithms
+/// keep working after the hashing policy is changed.
+pub struct ValidatorDispatcher<'s> {
+    validators: Vec<(&'s str, &'s dyn Validator)>,
+    legacy: Option<&'s dyn Validator>,
+}
+
+impl<'s> ValidatorDispatcher<'s> {
+    pub fn new(validators: Vec<(&'s str, &'s dyn Validator)>) -> ValidatorDispatcher<'s> {
+        ValidatorDispatcher {
+            validators,
+            legacy: None,
+        }
+    }
+
+    /// Validates encoded passwords that are not PHC strings, e.g. with `LegacyPbkdf2`
+    pub fn with_legacy(mut self, validator: &'s dyn Validator) -> ValidatorDispatcher<'s> {
+        self.legacy = Some(validator);
+
+        self
+    }
+}
+
+impl<'s> Validator for ValidatorDispatcher<'s> {
+    fn validate(&self, password: &str, encoded_password: &str) -> Result<bool, ValidationError> {
+        let algorithm = match (phc::algorithm(encoded_password), self.legacy) {
+            (Some(algorithm), _) => algorithm,
+            (None, Some(legacy)) => return legacy.validate(password, encoded_password),
+            (None, None) => return Err(ValidationError::MalformedHash),
+        };
+
+        match self.validators.iter().find(|(a, _)| *a == algorithm) {
+            Some((_, validator)) => validator.validate(password, encoded_password),
+            None => Err(ValidationError::UnsupportedAlgorithm(algorithm.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::argon2::Argon2;
+    use crate::bcrypt::Bcrypt;
+    use crate::dispatcher::ValidatorDispatcher;
+    use crate::pbkdf2::{LegacyPbkdf2, Pbkdf2};
+    use argentum_encryption_business::password::{Encryptor, ValidationError, Validator};
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn test_dispatch_by_algorithm() {
+        let pbkdf2 = Pbkdf2::with_iterations(NonZeroU32::new(1000).unwrap());
+        let argon2 = Argon2::new(1024, 1, 1);
+        let bcrypt = Bcrypt::new(4);
+
+        let dispatcher = ValidatorDispatcher::new(vec![
+            ("pbkdf2-sha512", &pbkdf2),
+            ("argon2id", &argon2),
+            ("2b", &bcrypt),
+        ]);
+
+        let encryptors: Vec<&dyn Encryptor> = vec![&pbkdf2, &argon2, &bcrypt];
+        for encryptor in encryptors {
+            let hash = encryptor.encrypt("123456").unwrap();
+
+            assert!(dispatcher.validate("123456", &hash).unwrap());
+            assert!(!dispatcher.validate("111111", &hash).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_dispatch_unknown_algorithm() {
+        let pbkdf2 = Pbkdf2::new();
+        let dispatcher = ValidatorDispatcher::new(vec![("pbkdf2-sha512", &pbkdf2)]);
+
+        let result = dispatcher.validate("123456", "$unknown$c2FsdA$aGFzaA");
+        assert!(matches!(
+            result,
+            Err(ValidationError::UnsupportedAlgorithm(_))
+        ));
+
+        let result = dispatcher.validate("123456", "14BF98B8522FAF0A");
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+    }
+
+    #[test]
+    fn test_dispatch_legacy_credential() {
+        let argon2 = Argon2::new(1024, 1, 1);
+        let legacy = LegacyPbkdf2::new();
+        let dispatcher = ValidatorDispatcher::new(vec![("argon2id", &argon2)]).with_legacy(&legacy);
+
+        let legacy_hash = "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F\
+            202122232425262728292A2B2C2D2E2F303132333435363738393A3B3C3D3E3F\
+            4EB7DCC902D147EC10DC0473EFEA844E13D0F1E3F451DF0034230D679DEB2591\
+            EA5AE09ABA51EFA05AC4877BF0767EFA74F83C2D0B37F2ED918F7CA4031D7268";
+
+        assert!(dispatcher.validate("123456", legacy_hash).unwrap());
+        assert!(!dispatcher.validate("111111", legacy_hash).unwrap());
+
+        let hash = argon2.encrypt("123456").unwrap();
+        assert!(dispatcher.validate("123456", &hash).unwrap());
+    }
+}
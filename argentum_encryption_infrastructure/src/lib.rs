@@ -1 +1,6 @@
+pub mod argon2;
+pub mod bcrypt;
+pub mod breached_password;
+pub mod dispatcher;
 pub mod pbkdf2;
+pub mod phc;
@@ -1,14 +1,27 @@
-use argentum_encryption_business::password::{EncryptionError, Encryptor, Validator};
-use data_encoding::HEXUPPER;
+use crate::phc::PhcString;
+use argentum_encryption_business::password::{
+    EncryptionError, Encryptor, ValidationError, Validator,
+};
+use data_encoding::HEXUPPER_PERMISSIVE;
 use ring::rand::SecureRandom;
 use ring::{digest, pbkdf2, rand};
 use std::num::NonZeroU32;
 
-pub struct Pbkdf2 {}
+pub const ALGORITHM: &str = "pbkdf2-sha512";
+const DEFAULT_ITERATIONS: u32 = 100000;
+const LEGACY_ITERATIONS: u32 = 100000;
+
+pub struct Pbkdf2 {
+    iterations: NonZeroU32,
+}
 
 impl Pbkdf2 {
     pub fn new() -> Pbkdf2 {
-        Pbkdf2 {}
+        Pbkdf2::with_iterations(NonZeroU32::new(DEFAULT_ITERATIONS).unwrap())
+    }
+
+    pub fn with_iterations(iterations: NonZeroU32) -> Pbkdf2 {
+        Pbkdf2 { iterations }
     }
 }
 
@@ -21,8 +34,7 @@ impl Default for Pbkdf2 {
 const CREDENTIAL_LEN: usize = digest::SHA512_OUTPUT_LEN;
 
 impl Encryptor for Pbkdf2 {
-    fn encrypt(&self, password: &str) -> Result<(String, String), EncryptionError> {
-        let n_iter: NonZeroU32 = NonZeroU32::new(100000).unwrap();
+    fn encrypt(&self, password: &str) -> Result<String, EncryptionError> {
         let rng = rand::SystemRandom::new();
 
         let mut salt = [0u8; CREDENTIAL_LEN];
@@ -35,54 +47,131 @@ impl Encryptor for Pbkdf2 {
         let mut pbkdf2_hash = [0u8; CREDENTIAL_LEN];
         pbkdf2::derive(
             pbkdf2::PBKDF2_HMAC_SHA512,
-            n_iter,
+            self.iterations,
             &salt,
             password.as_bytes(),
             &mut pbkdf2_hash,
         );
 
-        let hash = HEXUPPER.encode(&pbkdf2_hash);
-        let salt_str = HEXUPPER.encode(&salt);
+        let phc = PhcString::new(
+            ALGORITHM,
+            None,
+            vec![("i", self.iterations.to_string())],
+            &salt,
+            &pbkdf2_hash,
+        );
+
+        Ok(phc.to_string())
+    }
 
-        Ok((hash, salt_str))
+    fn needs_rehash(&self, encoded_password: &str) -> bool {
+        match PhcString::parse(encoded_password) {
+            Some(phc) if phc.algorithm == ALGORITHM => match phc.param_u32("i") {
+                Some(iterations) => iterations < self.iterations.get(),
+                None => true,
+            },
+            _ => true,
+        }
     }
 }
 
 impl Validator for Pbkdf2 {
-    fn validate(&self, password: &str, salt: &str, encoded_password: &str) -> bool {
-        let n_iter: NonZeroU32 = NonZeroU32::new(100000).unwrap();
+    fn validate(&self, password: &str, encoded_password: &str) -> Result<bool, ValidationError> {
+        let phc = match PhcString::parse(encoded_password) {
+            Some(phc) if phc.algorithm == ALGORITHM => phc,
+            Some(phc) => return Err(ValidationError::UnsupportedAlgorithm(phc.algorithm)),
+            None => return Err(ValidationError::MalformedHash),
+        };
+
+        let n_iter = match phc.param_u32("i").and_then(NonZeroU32::new) {
+            Some(n_iter) => n_iter,
+            None => return Err(ValidationError::MalformedHash),
+        };
 
         let should_succeed = pbkdf2::verify(
             pbkdf2::PBKDF2_HMAC_SHA512,
             n_iter,
-            &HEXUPPER.decode(salt.as_bytes()).unwrap(),
+            &phc.salt,
             password.as_bytes(),
-            &HEXUPPER.decode(encoded_password.as_bytes()).unwrap(),
+            &phc.hash,
         );
 
         match should_succeed {
-            Ok(()) => true,
-            Err(..) => false,
+            Ok(()) => Ok(true),
+            Err(..) => Ok(false),
         }
     }
 }
 
+/// Validates credentials stored before the PHC format: the former hex `salt` column
+/// followed by the hex `password` column, PBKDF2-HMAC-SHA512 with 100000 iterations.
+/// Encryptors consider such credentials outdated, so they are rehashed on the next login.
+pub struct LegacyPbkdf2 {}
+
+impl LegacyPbkdf2 {
+    pub fn new() -> LegacyPbkdf2 {
+        LegacyPbkdf2 {}
+    }
+}
+
+impl Default for LegacyPbkdf2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Validator for LegacyPbkdf2 {
+    fn validate(&self, password: &str, encoded_password: &str) -> Result<bool, ValidationError> {
+        let decoded = match HEXUPPER_PERMISSIVE.decode(encoded_password.as_bytes()) {
+            Ok(decoded) if decoded.len() == 2 * CREDENTIAL_LEN => decoded,
+            _ => return Err(ValidationError::MalformedHash),
+        };
+        let (salt, hash) = decoded.split_at(CREDENTIAL_LEN);
+
+        let should_succeed = pbkdf2::verify(
+            pbkdf2::PBKDF2_HMAC_SHA512,
+            NonZeroU32::new(LEGACY_ITERATIONS).unwrap(),
+            salt,
+            password.as_bytes(),
+            hash,
+        );
+
+        Ok(should_succeed.is_ok())
+    }
+}
+
 #[cfg(test)]
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::needless_borrow,
+    clippy::single_match
+)]
 mod tests {
-    use crate::pbkdf2::Pbkdf2;
-    use argentum_encryption_business::password::{Encryptor, Validator};
+    use crate::argon2::Argon2;
+    use crate::pbkdf2::{LegacyPbkdf2, Pbkdf2};
+    use argentum_encryption_business::password::{
+        AsyncEncryptor, AsyncValidator, Encryptor, ValidationError, Validator,
+    };
+    use argentum_standard_business::asynchronous::Offloaded;
+    use argentum_standard_infrastructure::executor::TokioBlockingExecutor;
+    use std::num::NonZeroU32;
+    use std::sync::Arc;
 
     fn stub_password() -> &'static str {
         "123456"
     }
 
-    fn stub_hash_and_salt() -> (&'static str, &'static str) {
-        (
-            "14BF98B8522FAF0A4ED6431A2FC48168106BDA4243C64AAC641CBA32FD81C97\
-            5D7CACA60DA051DC5596DB8BA461E22F3C069DF7AEEECF80576F163CEC287F16A",
-            "14565CCF70BA588B714364A2813A1A489E62FE32FC6871F53A5B909F2D1C987\
-            91F57CA9F27A5DC94526B43FCCF0F25516BC515FAF32BFC0CEDD2B34A8222F5AA",
-        )
+    fn stub_hash() -> &'static str {
+        "$pbkdf2-sha512$i=100000\
+        $FFZcz3C6WItxQ2SigToaSJ5i/jL8aHH1OluQny0cmHkfV8qfJ6XclFJrQ/zPDyVRa8UV+vMr/Azt0rNKgiL1qg\
+        $FL+YuFIvrwpO1kMaL8SBaBBr2kJDxkqsZBy6Mv2ByXXXyspg2gUdxVltuLpGHiLzwGnfeu7s+AV28WPOwofxag"
+    }
+
+    fn stub_legacy_hash() -> &'static str {
+        "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F\
+        202122232425262728292A2B2C2D2E2F303132333435363738393A3B3C3D3E3F\
+        4EB7DCC902D147EC10DC0473EFEA844E13D0F1E3F451DF0034230D679DEB2591\
+        EA5AE09ABA51EFA05AC4877BF0767EFA74F83C2D0B37F2ED918F7CA4031D7268"
     }
 
     #[test]
@@ -94,9 +183,9 @@ mod tests {
         assert_eq!(true, result.is_ok());
 
         match result {
-            Ok((hash, salt)) => {
-                assert_eq!(128, hash.len());
-                assert_eq!(128, salt.len());
+            Ok(hash) => {
+                assert_eq!(true, hash.starts_with("$pbkdf2-sha512$i=100000$"));
+                assert_eq!(true, pbkdf2.validate(password, &hash).unwrap());
             }
             Err(_) => {}
         }
@@ -105,22 +194,90 @@ mod tests {
     #[test]
     fn test_pbkdf2_validate_correct_password() {
         let password = stub_password();
-        let (hash, salt) = stub_hash_and_salt();
 
         let pbkdf2 = Pbkdf2::new();
-        let result = pbkdf2.validate(password, salt, hash);
+        let result = pbkdf2.validate(password, stub_hash());
 
-        assert_eq!(true, result);
+        assert_eq!(true, result.unwrap());
     }
 
     #[test]
     fn test_pbkdf2_validate_wrong_password() {
         let password = "111111";
-        let (hash, salt) = stub_hash_and_salt();
 
         let pbkdf2 = Pbkdf2::new();
-        let result = pbkdf2.validate(password, salt, hash);
+        let result = pbkdf2.validate(password, stub_hash());
+
+        assert_eq!(false, result.unwrap());
+    }
+
+    #[test]
+    fn test_pbkdf2_validate_malformed_hash() {
+        let pbkdf2 = Pbkdf2::new();
+
+        let result = pbkdf2.validate(stub_password(), "$pbkdf2-sha512$i=0$c2FsdA$aGFzaA");
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+
+        let result = pbkdf2.validate(stub_password(), "$pbkdf2-sha512$i=1000$c2Fsd!$aGFzaA");
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+
+        let result = pbkdf2.validate(stub_password(), "$argon2id$v=19$m=1,t=1,p=1$c2FsdA$aGFzaA");
+        assert!(matches!(
+            result,
+            Err(ValidationError::UnsupportedAlgorithm(_))
+        ));
+    }
+
+    #[test]
+    fn test_legacy_pbkdf2_credential_validates_and_needs_rehash() {
+        let legacy = LegacyPbkdf2::new();
+
+        assert!(legacy
+            .validate(stub_password(), stub_legacy_hash())
+            .unwrap());
+        assert!(!legacy.validate("111111", stub_legacy_hash()).unwrap());
+        assert!(Pbkdf2::new().needs_rehash(stub_legacy_hash()));
+        assert!(Argon2::default().needs_rehash(stub_legacy_hash()));
+
+        // Decoding a truncated legacy hash used to panic
+        let result = legacy.validate(stub_password(), "14BF98B8522FAF0A4ED6431A2FC48168");
+        assert!(matches!(result, Err(ValidationError::MalformedHash)));
+    }
+
+    #[test]
+    fn test_pbkdf2_validate_with_stored_iterations() {
+        let weak = Pbkdf2::with_iterations(NonZeroU32::new(1000).unwrap());
+        let hash = weak.encrypt(stub_password()).unwrap();
+
+        assert!(Pbkdf2::new().validate(stub_password(), &hash).unwrap());
+    }
+
+    #[test]
+    fn test_pbkdf2_needs_rehash() {
+        let weak = Pbkdf2::with_iterations(NonZeroU32::new(1000).unwrap());
+        let hash = weak.encrypt(stub_password()).unwrap();
+
+        assert!(Pbkdf2::new().needs_rehash(&hash));
+        assert!(!weak.needs_rehash(&hash));
+        assert!(!Pbkdf2::new().needs_rehash(stub_hash()));
+        assert!(Pbkdf2::new().needs_rehash("$2y$10$abcdefghijklmnopqrstuv"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_offloaded_pbkdf2() {
+        let pbkdf2 = Offloaded::new(
+            Pbkdf2::with_iterations(NonZeroU32::new(1_000).unwrap()),
+            Arc::new(TokioBlockingExecutor::current()),
+        );
+
+        let hash = AsyncEncryptor::encrypt(&pbkdf2, "12345").await.unwrap();
 
-        assert_eq!(false, result);
+        assert!(AsyncValidator::validate(&pbkdf2, "12345", &hash)
+            .await
+            .unwrap());
+        assert!(!AsyncValidator::validate(&pbkdf2, "54321", &hash)
+            .await
+            .unwrap());
+        assert!(!AsyncEncryptor::needs_rehash(&pbkdf2, &hash));
     }
 }
@@ -0,0 +1,180 @@
+use data_encoding::BASE64_NOPAD;
+use std::fmt;
+
+/// A parsed PHC string: `$<id>[$v=<version>][$<param>=<value>(,<param>=<value>)*]$<salt>$<hash>`.
+/// Salt and hash are encoded with the standard base64 alphabet without padding.
+pub struct PhcString {
+    pub algorithm: String,
+    pub version: Option<u32>,
+    pub params: Vec<(String, String)>,
+    pub salt: Vec<u8>,
+    pub hash: Vec<u8>,
+}
+
+impl PhcString {
+    pub fn new(
+        algorithm: &str,
+        version: Option<u32>,
+        params: Vec<(&str, String)>,
+        salt: &[u8],
+        hash: &[u8],
+    ) -> PhcString {
+        PhcString {
+            algorithm: algorithm.to_string(),
+            version,
+            params: params
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+            salt: salt.to_vec(),
+            hash: hash.to_vec(),
+        }
+    }
+
+    pub fn parse(encoded: &str) -> Option<PhcString> {
+        let mut segments = encoded.strip_prefix('$')?.split('$').peekable();
+
+        let algorithm = segments.next()?.to_string();
+        if algorithm.is_empty() {
+            return None;
+        }
+
+        let version = match segments.peek() {
+            Some(segment) if segment.starts_with("v=") => {
+                let version = segment[2..].parse().ok()?;
+                segments.next();
+
+                Some(version)
+            }
+            _ => None,
+        };
+
+        let mut params = vec![];
+        if let Some(segment) = segments.peek() {
+            if segment.contains('=') {
+                for param in segment.split(',') {
+                    let (name, value) = param.split_at(param.find('=')?);
+                    params.push((name.to_string(), value[1..].to_string()));
+                }
+                segments.next();
+            }
+        }
+
+        let salt = BASE64_NOPAD.decode(segments.next()?.as_bytes()).ok()?;
+        let hash = BASE64_NOPAD.decode(segments.next()?.as_bytes()).ok()?;
+
+        if segments.next().is_some() {
+            return None;
+        }
+
+        Some(PhcString {
+            algorithm,
+            version,
+            params,
+            salt,
+            hash,
+        })
+    }
+
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn param_u32(&self, name: &str) -> Option<u32> {
+        self.param(name)?.parse().ok()
+    }
+}
+
+impl fmt::Display for PhcString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "${}", self.algorithm)?;
+
+        if let Some(version) = self.version {
+            write!(f, "$v={}", version)?;
+        }
+
+        if !self.params.is_empty() {
+            let params: Vec<String> = self
+                .params
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            write!(f, "${}", params.join(","))?;
+        }
+
+        write!(
+            f,
+            "${}${}",
+            BASE64_NOPAD.encode(&self.salt),
+            BASE64_NOPAD.encode(&self.hash)
+        )
+    }
+}
+
+/// Returns the algorithm identifier of a PHC or MCF string, e.g. `argon2id` or `2y`.
+pub fn algorithm(encoded: &str) -> Option<&str> {
+    let algorithm = encoded.strip_prefix('$')?.split('$').next()?;
+
+    if algorithm.is_empty() {
+        None
+    } else {
+        Some(algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::phc::{algorithm, PhcString};
+
+    #[test]
+    fn test_parse_phc_string() {
+        let phc = PhcString::parse("$argon2id$v=19$m=1024,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2g")
+            .expect("Can't parse a PHC string");
+
+        assert_eq!("argon2id", phc.algorithm);
+        assert_eq!(Some(19), phc.version);
+        assert_eq!(Some(1024), phc.param_u32("m"));
+        assert_eq!(Some(2), phc.param_u32("t"));
+        assert_eq!(Some(1), phc.param_u32("p"));
+        assert_eq!(b"saltsalt".to_vec(), phc.salt);
+        assert_eq!(b"hashhash".to_vec(), phc.hash);
+    }
+
+    #[test]
+    fn test_format_phc_string() {
+        let encoded = "$pbkdf2-sha512$i=1000$c2FsdHNhbHQ$aGFzaGhhc2g";
+        let phc = PhcString::new(
+            "pbkdf2-sha512",
+            None,
+            vec![("i", "1000".to_string())],
+            b"saltsalt",
+            b"hashhash",
+        );
+
+        assert_eq!(encoded, phc.to_string());
+        assert_eq!(encoded, PhcString::parse(encoded).unwrap().to_string());
+    }
+
+    #[test]
+    fn test_parse_malformed_phc_string() {
+        assert!(PhcString::parse("").is_none());
+        assert!(PhcString::parse("pbkdf2-sha512$i=1000$c2FsdA$aGFzaA").is_none());
+        assert!(PhcString::parse("$pbkdf2-sha512$i=1000$c2FsdA").is_none());
+        assert!(PhcString::parse("$pbkdf2-sha512$i=1000$!!!$aGFzaA").is_none());
+        assert!(PhcString::parse("$pbkdf2-sha512$i=1000$c2FsdA$aGFzaA$extra").is_none());
+    }
+
+    #[test]
+    fn test_algorithm() {
+        assert_eq!(
+            Some("pbkdf2-sha512"),
+            algorithm("$pbkdf2-sha512$i=1$c2FsdA$aGFzaA")
+        );
+        assert_eq!(Some("2y"), algorithm("$2y$10$abcdefghijklmnopqrstuv"));
+        assert_eq!(None, algorithm("14BF98B8522FAF0A"));
+        assert_eq!(None, algorithm("$$"));
+    }
+}
@@ -0,0 +1,278 @@
+use crate::event::{DomainEvent, EventPublisherTrait, SubscriberTrait};
+use std::cell::RefCell;
+use std::sync::{Arc, RwLock};
+
+struct Subscription<'s> {
+    event_name: Option<&'static str>,
+    subscriber: &'s dyn SubscriberTrait,
+}
+
+/// Delivers events to subscribers synchronously, in order of subscription
+pub struct EventBus<'s> {
+    subscriptions: RefCell<Vec<Subscription<'s>>>,
+}
+
+impl<'s> EventBus<'s> {
+    pub fn new() -> EventBus<'s> {
+        EventBus {
+            subscriptions: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn subscribe(&self, event_name: &'static str, subscriber: &'s dyn SubscriberTrait) {
+        self.subscriptions.borrow_mut().push(Subscription {
+            event_name: Some(event_name),
+            subscriber,
+        });
+    }
+
+    pub fn subscribe_to_all(&self, subscriber: &'s dyn SubscriberTrait) {
+        self.subscriptions.borrow_mut().push(Subscription {
+            event_name: None,
+            subscriber,
+        });
+    }
+}
+
+impl Default for EventBus<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventPublisherTrait for EventBus<'_> {
+    fn publish(&self, event: &dyn DomainEvent) {
+        // Collected first, so a subscriber may subscribe others while handling an event
+        let subscribers: Vec<&dyn SubscriberTrait> = self
+            .subscriptions
+            .borrow()
+            .iter()
+            .filter(|s| s.event_name.is_none() || s.event_name == Some(event.name()))
+            .map(|s| s.subscriber)
+            .collect();
+
+        for subscriber in subscribers {
+            subscriber.handle(event);
+        }
+    }
+}
+
+struct SharedSubscription {
+    event_name: Option<&'static str>,
+    subscriber: Arc<dyn SubscriberTrait + Send + Sync>,
+}
+
+/// Thread-safe counterpart of `EventBus` for async use cases and multi-threaded runtimes.
+/// Subscribers are owned, so the bus can be kept in an `Arc` for the lifetime of the app
+pub struct SharedEventBus {
+    subscriptions: RwLock<Vec<SharedSubscription>>,
+}
+
+impl SharedEventBus {
+    pub fn new() -> SharedEventBus {
+        SharedEventBus {
+            subscriptions: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn subscribe(
+        &self,
+        event_name: &'static str,
+        subscriber: Arc<dyn SubscriberTrait + Send + Sync>,
+    ) {
+        self.subscriptions
+            .write()
+            .unwrap()
+            .push(SharedSubscription {
+                event_name: Some(event_name),
+                subscriber,
+            });
+    }
+
+    pub fn subscribe_to_all(&self, subscriber: Arc<dyn SubscriberTrait + Send + Sync>) {
+        self.subscriptions
+            .write()
+            .unwrap()
+            .push(SharedSubscription {
+                event_name: None,
+                subscriber,
+            });
+    }
+}
+
+impl Default for SharedEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventPublisherTrait for SharedEventBus {
+    fn publish(&self, event: &dyn DomainEvent) {
+        // The lock is released before handling, subscribing from a subscriber doesn't deadlock
+        let subscribers: Vec<Arc<dyn SubscriberTrait + Send + Sync>> = self
+            .subscriptions
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|s| s.event_name.is_none() || s.event_name == Some(event.name()))
+            .map(|s| s.subscriber.clone())
+            .collect();
+
+        for subscriber in subscribers {
+            subscriber.handle(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bus::{EventBus, SharedEventBus};
+    use crate::event::{DomainEvent, EventPublisherTrait, SubscriberTrait};
+    use crate::payload::to_json;
+    use chrono::{DateTime, Utc};
+    use std::any::Any;
+    use std::cell::RefCell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    struct SomethingHappened {
+        what: String,
+        occurred_at: DateTime<Utc>,
+    }
+
+    impl DomainEvent for SomethingHappened {
+        fn name(&self) -> &'static str {
+            "test.something_happened"
+        }
+
+        fn occurred_at(&self) -> DateTime<Utc> {
+            self.occurred_at
+        }
+
+        fn payload(&self) -> String {
+            to_json(&[("what", self.what.clone())])
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    struct NothingHappened {}
+
+    impl DomainEvent for NothingHappened {
+        fn name(&self) -> &'static str {
+            "test.nothing_happened"
+        }
+
+        fn occurred_at(&self) -> DateTime<Utc> {
+            Utc::now()
+        }
+
+        fn payload(&self) -> String {
+            "{}".to_string()
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    struct RecordingSubscriber {
+        received: RefCell<Vec<String>>,
+    }
+
+    impl RecordingSubscriber {
+        fn new() -> RecordingSubscriber {
+            RecordingSubscriber {
+                received: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl SubscriberTrait for RecordingSubscriber {
+        fn handle(&self, event: &dyn DomainEvent) {
+            let record = match event.as_any().downcast_ref::<SomethingHappened>() {
+                Some(e) => e.what.clone(),
+                None => event.name().to_string(),
+            };
+
+            self.received.borrow_mut().push(record);
+        }
+    }
+
+    #[test]
+    fn test_event_bus_delivers_subscribed_events() {
+        let something = RecordingSubscriber::new();
+        let everything = RecordingSubscriber::new();
+        let bus = EventBus::new();
+        bus.subscribe("test.something_happened", &something);
+        bus.subscribe_to_all(&everything);
+
+        bus.publish(&SomethingHappened {
+            what: "it happened".to_string(),
+            occurred_at: Utc::now(),
+        });
+        bus.publish(&NothingHappened {});
+
+        assert_eq!(
+            vec!["it happened".to_string()],
+            *something.received.borrow()
+        );
+        assert_eq!(
+            vec![
+                "it happened".to_string(),
+                "test.nothing_happened".to_string()
+            ],
+            *everything.received.borrow()
+        );
+    }
+
+    #[test]
+    fn test_event_bus_without_subscribers() {
+        EventBus::new().publish(&NothingHappened {});
+    }
+
+    struct CountingSubscriber {
+        count: AtomicUsize,
+    }
+
+    impl SubscriberTrait for CountingSubscriber {
+        fn handle(&self, _event: &dyn DomainEvent) {
+            self.count.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_shared_event_bus_delivers_from_threads() {
+        let something = Arc::new(CountingSubscriber {
+            count: AtomicUsize::new(0),
+        });
+        let everything = Arc::new(CountingSubscriber {
+            count: AtomicUsize::new(0),
+        });
+        let bus = Arc::new(SharedEventBus::new());
+        bus.subscribe("test.something_happened", something.clone());
+        bus.subscribe_to_all(everything.clone());
+
+        let handles: Vec<_> = (0..4)
+            .map(|n| {
+                let bus = bus.clone();
+                thread::spawn(move || {
+                    bus.publish(&SomethingHappened {
+                        what: n.to_string(),
+                        occurred_at: Utc::now(),
+                    });
+                    bus.publish(&NothingHappened {});
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(4, something.count.load(Ordering::SeqCst));
+        assert_eq!(8, everything.count.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use std::any::Any;
+
+pub trait DomainEvent: Any {
+    /// Stable name to subscribe by, e.g. `user_account.user_registered`
+    fn name(&self) -> &'static str;
+    fn occurred_at(&self) -> DateTime<Utc>;
+    /// Event fields as a JSON object, it is what the outbox stores
+    fn payload(&self) -> String;
+    //Convert to Any for downcasting to a concrete event
+    fn as_any(&self) -> &dyn Any;
+}
+
+pub trait EventPublisherTrait {
+    fn publish(&self, event: &dyn DomainEvent);
+}
+
+pub trait SubscriberTrait {
+    fn handle(&self, event: &dyn DomainEvent);
+}
@@ -0,0 +1,4 @@
+pub mod bus;
+pub mod event;
+pub mod mock;
+pub mod payload;
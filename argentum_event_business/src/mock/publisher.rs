@@ -0,0 +1,31 @@
+use crate::event::{DomainEvent, EventPublisherTrait};
+use std::sync::RwLock;
+
+/// Remembers names of published events
+pub struct EventPublisherMock {
+    published: RwLock<Vec<&'static str>>,
+}
+
+impl EventPublisherMock {
+    pub fn new() -> EventPublisherMock {
+        EventPublisherMock {
+            published: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn published(&self) -> Vec<&'static str> {
+        self.published.read().unwrap().clone()
+    }
+}
+
+impl Default for EventPublisherMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventPublisherTrait for EventPublisherMock {
+    fn publish(&self, event: &dyn DomainEvent) {
+        self.published.write().unwrap().push(event.name());
+    }
+}
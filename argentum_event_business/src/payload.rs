@@ -0,0 +1,60 @@
+/// Builds a flat JSON object from scalar event fields.
+/// It is enough for ids, emails and names, so business crates don't need a serializer
+pub fn to_json(fields: &[(&str, String)]) -> String {
+    let mut json = String::from("{");
+
+    for (i, (key, value)) in fields.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+
+        push_string(&mut json, key);
+        json.push(':');
+        push_string(&mut json, value);
+    }
+
+    json.push('}');
+
+    json
+}
+
+fn push_string(json: &mut String, value: &str) {
+    json.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::payload::to_json;
+
+    #[test]
+    fn test_to_json() {
+        let json = to_json(&[
+            ("user_id", "42".to_string()),
+            ("email", "test@example.com".to_string()),
+        ]);
+
+        assert_eq!(r#"{"user_id":"42","email":"test@example.com"}"#, json);
+    }
+
+    #[test]
+    fn test_to_json_escapes_values() {
+        let json = to_json(&[("name", "a \"quoted\"\\\n\u{1}".to_string())]);
+
+        assert_eq!(r#"{"name":"a \"quoted\"\\\n\u0001"}"#, json);
+        assert_eq!("{}", to_json(&[]));
+    }
+}
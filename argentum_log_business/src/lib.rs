@@ -72,7 +72,7 @@ impl<'s> LoggerTrait for DefaultLogger<'s> {
     }
 }
 
-pub trait WriterTrait {
+pub trait WriterTrait: Send + Sync {
     fn write(&self, date_time: DateTime<Utc>, level: Level, msg: String);
 }
 
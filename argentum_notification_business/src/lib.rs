@@ -0,0 +1,5 @@
+pub mod mock;
+pub mod notification;
+pub mod notifier;
+pub mod queue;
+pub mod template;
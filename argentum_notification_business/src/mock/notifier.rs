@@ -0,0 +1,68 @@
+use crate::notification::Notification;
+use crate::notifier::{NotificationError, NotifierTrait};
+use argentum_standard_business::data_type::email::EmailAddress;
+use std::sync::RwLock;
+
+/// Keeps notifications in memory instead of sending them
+pub struct NotifierMock {
+    sent: RwLock<Vec<(EmailAddress, Notification)>>,
+}
+
+impl NotifierMock {
+    pub fn new() -> NotifierMock {
+        NotifierMock {
+            sent: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn sent(&self) -> Vec<(EmailAddress, Notification)> {
+        self.sent.read().unwrap().clone()
+    }
+}
+
+impl Default for NotifierMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotifierTrait for NotifierMock {
+    fn notify(
+        &self,
+        to: &EmailAddress,
+        notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        self.sent
+            .write()
+            .unwrap()
+            .push((to.clone(), notification.clone()));
+
+        Ok(())
+    }
+}
+
+pub struct NotifierMockWithBrokenDelivery {}
+
+impl NotifierMockWithBrokenDelivery {
+    pub fn new() -> NotifierMockWithBrokenDelivery {
+        NotifierMockWithBrokenDelivery {}
+    }
+}
+
+impl Default for NotifierMockWithBrokenDelivery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotifierTrait for NotifierMockWithBrokenDelivery {
+    fn notify(
+        &self,
+        _to: &EmailAddress,
+        _notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        Err(NotificationError::Delivery(
+            "Connection refused".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,15 @@
+#[derive(Clone, Debug, PartialEq)]
+pub enum Notification {
+    RestorePassword {
+        token: String,
+    },
+    /// A password reset was requested for an email without an account
+    RestorePasswordWithoutAccount,
+    EmailVerification {
+        token: String,
+    },
+    /// Something important happened to the account, e.g. the password was changed
+    SecurityAlert {
+        description: String,
+    },
+}
@@ -0,0 +1,60 @@
+use crate::notification::Notification;
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture, Offloaded};
+use argentum_standard_business::data_type::email::EmailAddress;
+
+pub trait NotifierTrait {
+    fn notify(
+        &self,
+        to: &EmailAddress,
+        notification: &Notification,
+    ) -> Result<(), NotificationError>;
+}
+
+/// Async counterpart of `NotifierTrait`. Delivery talks to remote services,
+/// implementations shouldn't block the executor threads
+pub trait AsyncNotifierTrait: Send + Sync {
+    fn notify<'a>(
+        &'a self,
+        to: &'a EmailAddress,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), NotificationError>>;
+}
+
+impl<T: NotifierTrait + Send> AsyncNotifierTrait for Blocking<T> {
+    fn notify<'a>(
+        &'a self,
+        to: &'a EmailAddress,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), NotificationError>> {
+        ready(self.inner().notify(to, notification))
+    }
+}
+
+impl<T: NotifierTrait + Send + Sync + 'static> AsyncNotifierTrait for Offloaded<T> {
+    fn notify<'a>(
+        &'a self,
+        to: &'a EmailAddress,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), NotificationError>> {
+        let to = to.clone();
+        let notification = notification.clone();
+        let delivered = self.run(move |inner| inner.notify(&to, &notification));
+
+        Box::pin(async move {
+            delivered.await.unwrap_or_else(|| {
+                Err(NotificationError::Delivery(
+                    "Delivery is interrupted".to_string(),
+                ))
+            })
+        })
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum NotificationError {
+    #[error("Wrong address: {0}")]
+    WrongAddress(String),
+
+    #[error("Can't deliver notification: {0}")]
+    Delivery(String),
+}
@@ -0,0 +1,142 @@
+use crate::notification::Notification;
+use crate::notifier::{AsyncNotifierTrait, NotificationError, NotifierTrait};
+use argentum_standard_business::asynchronous::{ready, BoxFuture};
+use argentum_standard_business::data_type::email::EmailAddress;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Only queues notifications, so `notify` returns right away and never fails.
+/// Requests answer in the same time whether something is sent or not,
+/// `deliver` hands the queue to a real notifier later, outside of the request
+pub struct QueuedNotifier {
+    queue: Mutex<VecDeque<(EmailAddress, Notification)>>,
+}
+
+impl QueuedNotifier {
+    pub fn new() -> QueuedNotifier {
+        QueuedNotifier {
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sends queued notifications in order and returns how many were delivered.
+    /// Stops at the first failure, the failed notification stays first in the queue
+    pub fn deliver(&self, notifier: &dyn NotifierTrait) -> Result<usize, NotificationError> {
+        let mut delivered = 0;
+
+        loop {
+            // The lock isn't held while sending, requests can keep queueing
+            let next = self.queue.lock().unwrap().pop_front();
+            let (to, notification) = match next {
+                Some(next) => next,
+                None => return Ok(delivered),
+            };
+
+            if let Err(e) = notifier.notify(&to, &notification) {
+                self.queue.lock().unwrap().push_front((to, notification));
+
+                return Err(e);
+            }
+
+            delivered += 1;
+        }
+    }
+}
+
+impl Default for QueuedNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotifierTrait for QueuedNotifier {
+    fn notify(
+        &self,
+        to: &EmailAddress,
+        notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        self.queue
+            .lock()
+            .unwrap()
+            .push_back((to.clone(), notification.clone()));
+
+        Ok(())
+    }
+}
+
+impl AsyncNotifierTrait for QueuedNotifier {
+    fn notify<'a>(
+        &'a self,
+        to: &'a EmailAddress,
+        notification: &'a Notification,
+    ) -> BoxFuture<'a, Result<(), NotificationError>> {
+        ready(NotifierTrait::notify(self, to, notification))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::notifier::{NotifierMock, NotifierMockWithBrokenDelivery};
+    use crate::notification::Notification;
+    use crate::notifier::NotifierTrait;
+    use crate::queue::QueuedNotifier;
+    use argentum_standard_business::data_type::email::EmailAddress;
+
+    fn stub_email() -> EmailAddress {
+        EmailAddress::new("test@mail.com".to_string()).unwrap()
+    }
+
+    fn stub_notification(token: &str) -> Notification {
+        Notification::RestorePassword {
+            token: token.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_deliver_in_order() {
+        let queue = QueuedNotifier::new();
+        queue
+            .notify(&stub_email(), &stub_notification("a"))
+            .unwrap();
+        queue
+            .notify(&stub_email(), &stub_notification("b"))
+            .unwrap();
+
+        let notifier = NotifierMock::new();
+        assert_eq!(2, queue.deliver(&notifier).unwrap());
+
+        assert!(queue.is_empty());
+        assert!(
+            notifier.sent()
+                == vec![
+                    (stub_email(), stub_notification("a")),
+                    (stub_email(), stub_notification("b")),
+                ]
+        );
+    }
+
+    #[test]
+    fn test_failed_delivery_stays_queued() {
+        let queue = QueuedNotifier::new();
+        queue
+            .notify(&stub_email(), &stub_notification("a"))
+            .unwrap();
+
+        assert!(queue
+            .deliver(&NotifierMockWithBrokenDelivery::new())
+            .is_err());
+        assert_eq!(1, queue.len());
+
+        let notifier = NotifierMock::new();
+        queue.deliver(&notifier).unwrap();
+        assert!(notifier.sent() == vec![(stub_email(), stub_notification("a"))]);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::notification::Notification;
+
+pub struct Message {
+    pub subject: String,
+    pub body: String,
+}
+
+pub trait TemplatesTrait {
+    fn render(&self, notification: &Notification) -> Message;
+}
+
+/// Plain text messages. Links are built from URL templates with a `{token}` placeholder,
+/// e.g. `https://example.com/restore-password?token={token}`
+pub struct DefaultTemplates {
+    app_name: String,
+    restore_password_url: String,
+    email_verification_url: String,
+}
+
+impl DefaultTemplates {
+    pub fn new(
+        app_name: &str,
+        restore_password_url: &str,
+        email_verification_url: &str,
+    ) -> DefaultTemplates {
+        DefaultTemplates {
+            app_name: app_name.to_string(),
+            restore_password_url: restore_password_url.to_string(),
+            email_verification_url: email_verification_url.to_string(),
+        }
+    }
+}
+
+impl TemplatesTrait for DefaultTemplates {
+    fn render(&self, notification: &Notification) -> Message {
+        match notification {
+            Notification::RestorePassword { token } => Message {
+                subject: format!("{}: restore your password", self.app_name),
+                body: format!(
+                    "Someone requested a password reset for your account.\n\
+                    Follow the link to set a new password:\n\n{}\n\n\
+                    If it was not you, just ignore this message.\n",
+                    self.restore_password_url.replace("{token}", token)
+                ),
+            },
+            Notification::RestorePasswordWithoutAccount => Message {
+                subject: format!("{}: restore your password", self.app_name),
+                body: "Someone requested a password reset for this email address,\n\
+                    but there is no account registered with it.\n\n\
+                    If it was not you, just ignore this message.\n"
+                    .to_string(),
+            },
+            Notification::EmailVerification { token } => Message {
+                subject: format!("{}: confirm your email", self.app_name),
+                body: format!(
+                    "Follow the link to confirm your email address:\n\n{}\n",
+                    self.email_verification_url.replace("{token}", token)
+                ),
+            },
+            Notification::SecurityAlert { description } => Message {
+                subject: format!("{}: security alert", self.app_name),
+                body: format!(
+                    "{}\n\nIf it was not you, restore your password right away.\n",
+                    description
+                ),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::notification::Notification;
+    use crate::template::{DefaultTemplates, TemplatesTrait};
+
+    fn stub_templates() -> DefaultTemplates {
+        DefaultTemplates::new(
+            "Argentum",
+            "https://example.com/restore?token={token}",
+            "https://example.com/verify?token={token}",
+        )
+    }
+
+    #[test]
+    fn test_restore_password_message() {
+        let message = stub_templates().render(&Notification::RestorePassword {
+            token: "abc".to_string(),
+        });
+
+        assert_eq!("Argentum: restore your password", message.subject);
+        assert!(message
+            .body
+            .contains("https://example.com/restore?token=abc"));
+    }
+
+    #[test]
+    fn test_restore_password_without_account_message() {
+        let message = stub_templates().render(&Notification::RestorePasswordWithoutAccount);
+
+        assert_eq!("Argentum: restore your password", message.subject);
+        assert!(message.body.contains("no account"));
+    }
+
+    #[test]
+    fn test_email_verification_message() {
+        let message = stub_templates().render(&Notification::EmailVerification {
+            token: "abc".to_string(),
+        });
+
+        assert!(message
+            .body
+            .contains("https://example.com/verify?token=abc"));
+    }
+
+    #[test]
+    fn test_security_alert_message() {
+        let message = stub_templates().render(&Notification::SecurityAlert {
+            description: "Your password was changed.".to_string(),
+        });
+
+        assert_eq!("Argentum: security alert", message.subject);
+        assert!(message.body.starts_with("Your password was changed."));
+    }
+}
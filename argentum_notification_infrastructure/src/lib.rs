@@ -0,0 +1,3 @@
+mod message;
+pub mod outbox;
+pub mod smtp;
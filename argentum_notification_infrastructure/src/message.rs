@@ -0,0 +1,30 @@
+use argentum_notification_business::notification::Notification;
+use argentum_notification_business::notifier::NotificationError;
+use argentum_notification_business::template::TemplatesTrait;
+use argentum_standard_business::data_type::email::EmailAddress;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::Message;
+
+pub(crate) fn parse_mailbox(address: &str) -> Result<Mailbox, NotificationError> {
+    address
+        .parse::<Mailbox>()
+        .map_err(|e| NotificationError::WrongAddress(format!("{}: {}", address, e)))
+}
+
+pub(crate) fn build_message(
+    from: &Mailbox,
+    to: &EmailAddress,
+    templates: &dyn TemplatesTrait,
+    notification: &Notification,
+) -> Result<Message, NotificationError> {
+    let rendered = templates.render(notification);
+
+    Message::builder()
+        .from(from.clone())
+        .to(parse_mailbox(&to.as_string())?)
+        .subject(rendered.subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(rendered.body)
+        .map_err(|e| NotificationError::Delivery(e.to_string()))
+}
@@ -0,0 +1,152 @@
+use crate::message::{build_message, parse_mailbox};
+use argentum_notification_business::notification::Notification;
+use argentum_notification_business::notifier::{NotificationError, NotifierTrait};
+use argentum_notification_business::template::TemplatesTrait;
+use argentum_standard_business::data_type::email::EmailAddress;
+use chrono::Utc;
+use lettre::message::Mailbox;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Writes every notification as an `.eml` file into a directory instead of sending it.
+/// Useful for local development: files can be opened by any mail client.
+/// Safe to share between threads, file names stay unique under concurrent writes
+pub struct FileOutboxNotifier {
+    directory: PathBuf,
+    from: Mailbox,
+    templates: Arc<dyn TemplatesTrait + Send + Sync>,
+    counter: AtomicU64,
+}
+
+impl FileOutboxNotifier {
+    pub fn new(
+        directory: PathBuf,
+        from: &str,
+        templates: Arc<dyn TemplatesTrait + Send + Sync>,
+    ) -> Result<FileOutboxNotifier, NotificationError> {
+        Ok(FileOutboxNotifier {
+            directory,
+            from: parse_mailbox(from)?,
+            templates,
+            counter: AtomicU64::new(0),
+        })
+    }
+}
+
+impl NotifierTrait for FileOutboxNotifier {
+    fn notify(
+        &self,
+        to: &EmailAddress,
+        notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        let message = build_message(&self.from, to, self.templates.as_ref(), notification)?;
+
+        let number = self.counter.fetch_add(1, Ordering::Relaxed) + 1;
+        let file_name = format!("{}-{}.eml", Utc::now().format("%Y%m%d%H%M%S%f"), number);
+
+        fs::create_dir_all(&self.directory)
+            .and_then(|_| fs::write(self.directory.join(file_name), message.formatted()))
+            .map_err(|e| NotificationError::Delivery(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::outbox::FileOutboxNotifier;
+    use argentum_notification_business::notification::Notification;
+    use argentum_notification_business::notifier::NotifierTrait;
+    use argentum_notification_business::template::DefaultTemplates;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn stub_templates() -> Arc<DefaultTemplates> {
+        Arc::new(DefaultTemplates::new(
+            "Argentum",
+            "https://example.com/restore?token={token}",
+            "https://example.com/verify?token={token}",
+        ))
+    }
+
+    fn stub_directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("argentum-outbox-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+
+        directory
+    }
+
+    #[test]
+    fn test_outbox_writes_messages() {
+        let directory = stub_directory("writes");
+        let notifier =
+            FileOutboxNotifier::new(directory.clone(), "no-reply@example.com", stub_templates())
+                .unwrap();
+        let to = EmailAddress::new("user@example.com".to_string()).unwrap();
+
+        for token in ["first", "second"].iter() {
+            notifier
+                .notify(
+                    &to,
+                    &Notification::EmailVerification {
+                        token: token.to_string(),
+                    },
+                )
+                .expect("Can't write a message");
+        }
+
+        let mut messages: Vec<String> = fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect();
+        messages.sort();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(2, messages.len());
+        assert!(messages.iter().all(|m| m.contains("To: user@example.com")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("https://example.com/verify?token=first")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("https://example.com/verify?token=second")));
+    }
+
+    #[test]
+    fn test_outbox_shared_between_threads() {
+        let directory = stub_directory("threads");
+        let notifier = Arc::new(
+            FileOutboxNotifier::new(directory.clone(), "no-reply@example.com", stub_templates())
+                .unwrap(),
+        );
+
+        let handles: Vec<_> = (0..4)
+            .map(|n| {
+                let notifier = notifier.clone();
+                thread::spawn(move || {
+                    let to = EmailAddress::new(format!("user-{}@example.com", n)).unwrap();
+                    notifier
+                        .notify(
+                            &to,
+                            &Notification::RestorePassword {
+                                token: n.to_string(),
+                            },
+                        )
+                        .expect("Can't write a message");
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let written = fs::read_dir(&directory).unwrap().count();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(4, written);
+    }
+}
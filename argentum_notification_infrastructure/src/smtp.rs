@@ -0,0 +1,219 @@
+use crate::message::{build_message, parse_mailbox};
+use argentum_notification_business::notification::Notification;
+use argentum_notification_business::notifier::{NotificationError, NotifierTrait};
+use argentum_notification_business::template::TemplatesTrait;
+use argentum_standard_business::data_type::email::EmailAddress;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{SmtpTransport, Transport};
+use std::sync::Arc;
+
+/// Sends notifications through an SMTP server. Owns its templates,
+/// so one notifier can be shared between threads and async tasks
+pub struct SmtpNotifier {
+    transport: SmtpTransport,
+    from: Mailbox,
+    templates: Arc<dyn TemplatesTrait + Send + Sync>,
+}
+
+impl SmtpNotifier {
+    /// `from` is a mailbox like `Argentum <no-reply@example.com>`
+    pub fn new(
+        transport: SmtpTransport,
+        from: &str,
+        templates: Arc<dyn TemplatesTrait + Send + Sync>,
+    ) -> Result<SmtpNotifier, NotificationError> {
+        Ok(SmtpNotifier {
+            transport,
+            from: parse_mailbox(from)?,
+            templates,
+        })
+    }
+
+    /// STARTTLS with authentication, for real mail servers
+    pub fn starttls(
+        host: &str,
+        port: u16,
+        username: &str,
+        password: &str,
+        from: &str,
+        templates: Arc<dyn TemplatesTrait + Send + Sync>,
+    ) -> Result<SmtpNotifier, NotificationError> {
+        let transport = SmtpTransport::starttls_relay(host)
+            .map_err(|e| NotificationError::Delivery(e.to_string()))?
+            .port(port)
+            .credentials(Credentials::new(username.to_string(), password.to_string()))
+            .build();
+
+        SmtpNotifier::new(transport, from, templates)
+    }
+
+    /// Plain connection without TLS and authentication,
+    /// for local stand-in servers like MailHog or Mailpit only
+    pub fn unencrypted(
+        host: &str,
+        port: u16,
+        from: &str,
+        templates: Arc<dyn TemplatesTrait + Send + Sync>,
+    ) -> Result<SmtpNotifier, NotificationError> {
+        let transport = SmtpTransport::builder_dangerous(host).port(port).build();
+
+        SmtpNotifier::new(transport, from, templates)
+    }
+}
+
+impl NotifierTrait for SmtpNotifier {
+    fn notify(
+        &self,
+        to: &EmailAddress,
+        notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        let message = build_message(&self.from, to, self.templates.as_ref(), notification)?;
+
+        match self.transport.send(&message) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(NotificationError::Delivery(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::smtp::SmtpNotifier;
+    use argentum_notification_business::notification::Notification;
+    use argentum_notification_business::notifier::{NotificationError, NotifierTrait};
+    use argentum_notification_business::template::DefaultTemplates;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn stub_templates() -> Arc<DefaultTemplates> {
+        Arc::new(DefaultTemplates::new(
+            "Argentum",
+            "https://example.com/restore?token={token}",
+            "https://example.com/verify?token={token}",
+        ))
+    }
+
+    /// Accepts one SMTP session and returns the received commands and data
+    fn start_smtp_server() -> (u16, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut received = String::new();
+            let mut in_data = false;
+
+            writer
+                .write_all(b"220 localhost ESMTP stand-in\r\n")
+                .unwrap();
+
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                received.push_str(&line);
+
+                let reply: &[u8] = if in_data {
+                    if line != ".\r\n" {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else if line.starts_with("EHLO") {
+                    b"250 localhost\r\n"
+                } else if line.starts_with("DATA") {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if line.starts_with("QUIT") {
+                    writer.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                } else {
+                    b"250 ok\r\n"
+                };
+
+                writer.write_all(reply).unwrap();
+            }
+
+            received
+        });
+
+        (port, handle)
+    }
+
+    #[test]
+    fn test_smtp_notifier_sends_message() {
+        let (port, server) = start_smtp_server();
+        let notifier = SmtpNotifier::unencrypted(
+            "127.0.0.1",
+            port,
+            "Argentum <no-reply@example.com>",
+            stub_templates(),
+        )
+        .unwrap();
+
+        let to = EmailAddress::new("user@example.com".to_string()).unwrap();
+        let result = notifier.notify(
+            &to,
+            &Notification::RestorePassword {
+                token: "abc".to_string(),
+            },
+        );
+        drop(notifier);
+
+        assert!(result.is_ok(), "{:?}", result);
+
+        let received = server.join().unwrap();
+        assert!(received.contains("MAIL FROM:<no-reply@example.com>"));
+        assert!(received.contains("RCPT TO:<user@example.com>"));
+        assert!(received.contains("Subject: Argentum: restore your password"));
+        assert!(received.contains("https://example.com/restore?token=abc"));
+    }
+
+    #[test]
+    fn test_smtp_notifier_with_unavailable_server() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let notifier =
+            SmtpNotifier::unencrypted("127.0.0.1", port, "no-reply@example.com", stub_templates())
+                .unwrap();
+
+        let to = EmailAddress::new("user@example.com".to_string()).unwrap();
+        let result = notifier.notify(
+            &to,
+            &Notification::SecurityAlert {
+                description: "Password changed".to_string(),
+            },
+        );
+
+        assert!(matches!(result, Err(NotificationError::Delivery(_))));
+    }
+
+    #[test]
+    fn test_smtp_notifier_with_wrong_sender() {
+        assert!(matches!(
+            SmtpNotifier::unencrypted("127.0.0.1", 25, "not an address", stub_templates()),
+            Err(NotificationError::WrongAddress(_))
+        ));
+    }
+
+    #[test]
+    fn test_smtp_notifier_is_thread_safe() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let notifier =
+            SmtpNotifier::unencrypted("127.0.0.1", 25, "no-reply@example.com", stub_templates())
+                .unwrap();
+
+        assert_send_sync(&notifier);
+    }
+}
@@ -0,0 +1,23 @@
+CREATE TABLE authenticated_users (
+    id TEXT PRIMARY KEY,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    email_verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+-- One account per address, whatever case it is typed in
+CREATE UNIQUE INDEX authenticated_users_email_idx ON authenticated_users (lower(email));
+
+CREATE TABLE anonymous_users (
+    id TEXT PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE anonymous_bindings (
+    user_id TEXT NOT NULL,
+    anonymous_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (user_id, anonymous_id)
+);
@@ -0,0 +1,27 @@
+-- Sessions belong to anonymous and authenticated users, so user_id has no foreign key
+CREATE TABLE sessions (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    token_hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    last_seen_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE UNIQUE INDEX sessions_token_hash_idx ON sessions (token_hash);
+CREATE INDEX sessions_user_id_idx ON sessions (user_id);
+
+CREATE TABLE password_credentials (
+    user_id TEXT PRIMARY KEY REFERENCES authenticated_users (id) ON DELETE CASCADE,
+    password TEXT NOT NULL
+);
+
+CREATE TABLE restore_password_tokens (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES authenticated_users (id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE UNIQUE INDEX restore_password_tokens_token_hash_idx ON restore_password_tokens (token_hash);
+CREATE INDEX restore_password_tokens_user_id_idx ON restore_password_tokens (user_id);
@@ -0,0 +1,5 @@
+pub mod connection;
+pub mod migration;
+pub mod repository;
+#[cfg(test)]
+mod test_db;
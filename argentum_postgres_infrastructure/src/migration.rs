@@ -0,0 +1,73 @@
+use crate::connection::{ConnectionError, PostgresConnection};
+
+const MIGRATIONS: &[(i32, &str)] = &[
+    (1, include_str!("../migrations/0001_user.sql")),
+    (2, include_str!("../migrations/0002_user_account.sql")),
+];
+
+/// Applies migrations which are not applied yet, each one in its own transaction.
+/// Applied versions are kept in the `schema_migrations` table
+pub fn migrate(connection: &PostgresConnection) -> Result<(), ConnectionError> {
+    let mut pooled = connection.client(None);
+    let client = pooled.inner()?;
+
+    client.batch_execute(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+        )",
+    )?;
+
+    for (version, sql) in MIGRATIONS {
+        let applied = client
+            .query_opt(
+                "SELECT version FROM schema_migrations WHERE version = $1",
+                &[version],
+            )?
+            .is_some();
+
+        if applied {
+            continue;
+        }
+
+        let mut transaction = client.transaction()?;
+        transaction.batch_execute(sql)?;
+        transaction.execute(
+            "INSERT INTO schema_migrations (version) VALUES ($1)",
+            &[version],
+        )?;
+        transaction.commit()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::migration::{migrate, MIGRATIONS};
+    use crate::test_db;
+
+    #[test]
+    fn test_migrations_are_applied_once() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+
+        // test_db has migrated the schema already
+        migrate(&db.connection).map_err(|_| "Second run should be a no-op")?;
+
+        let count: i64 = db
+            .connection
+            .client(None)
+            .inner()
+            .map_err(|_| "Can't get client")?
+            .query_one("SELECT count(*) FROM schema_migrations", &[])
+            .map_err(|_| "Can't count migrations")?
+            .get(0);
+
+        assert_eq!(MIGRATIONS.len() as i64, count);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use crate::connection::{PooledClient, PostgresConnection};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::entity::anonymous_binding::AnonymousBinding;
+use argentum_user_business::repository::anonymous_binding_repository::{
+    AnonymousBindingRepositoryError, AnonymousBindingRepositoryTrait,
+};
+
+pub struct PostgresAnonymousBindingRepository<'s> {
+    connection: &'s PostgresConnection,
+    id_factory: &'s dyn IdFactory,
+    transaction: Option<Transaction>,
+}
+
+impl<'s> PostgresAnonymousBindingRepository<'s> {
+    pub fn new(
+        connection: &'s PostgresConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> PostgresAnonymousBindingRepository<'s> {
+        PostgresAnonymousBindingRepository {
+            connection,
+            id_factory,
+            transaction: None,
+        }
+    }
+
+    fn client(&self) -> PooledClient<'_> {
+        self.connection.client(self.transaction.as_ref())
+    }
+}
+
+impl AnonymousBindingRepositoryTrait for PostgresAnonymousBindingRepository<'_> {
+    /// A user may log in from several anonymous sessions, the latest binding is returned
+    fn find_by_user_id(&self, user_id: &Id) -> Option<AnonymousBinding> {
+        let row = self
+            .client()
+            .query_opt(
+                "SELECT user_id, anonymous_id, created_at FROM anonymous_bindings
+                WHERE user_id = $1 ORDER BY created_at DESC LIMIT 1",
+                &[&user_id.to_string()],
+            )
+            .ok()??;
+
+        let user_id: String = row.get("user_id");
+        let anonymous_id: String = row.get("anonymous_id");
+
+        Some(AnonymousBinding {
+            user_id: self.id_factory.parse(&user_id).ok()?,
+            anonymous_id: self.id_factory.parse(&anonymous_id).ok()?,
+            created_at: row.get("created_at"),
+        })
+    }
+
+    fn save(&self, binding: &AnonymousBinding) -> Result<(), AnonymousBindingRepositoryError> {
+        self.client()
+            .execute(
+                "INSERT INTO anonymous_bindings (user_id, anonymous_id, created_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (user_id, anonymous_id) DO UPDATE SET created_at = EXCLUDED.created_at",
+                &[
+                    &binding.user_id.to_string(),
+                    &binding.anonymous_id.to_string(),
+                    &binding.created_at,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| AnonymousBindingRepositoryError::Save)
+    }
+
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Bound<'_, dyn AnonymousBindingRepositoryTrait + '_> {
+        Bound::Owned(Box::new(PostgresAnonymousBindingRepository {
+            connection: self.connection,
+            id_factory: self.id_factory,
+            transaction: Some(transaction.clone()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::anonymous_binding_repository::PostgresAnonymousBindingRepository;
+    use crate::repository::authenticated_user_repository::PostgresAuthenticatedUserRepository;
+    use crate::repository::password_credential_repository::PostgresPasswordCredentialRepository;
+    use crate::repository::session_repository::PostgresSessionRepository;
+    use crate::test_db;
+    use argentum_encryption_business::mock::password::{EncryptorMock, ValidatorMock};
+    use argentum_encryption_business::password::Encryptor;
+    use argentum_event_business::mock::publisher::EventPublisherMock;
+    use argentum_log_business::{DefaultLogger, Level, StdoutWriter};
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_user_account_business::entity::credential::PasswordCredential;
+    use argentum_user_account_business::mock::repository::login_attempt_repository_mock::LoginAttemptRepositoryMock;
+    use argentum_user_account_business::mock::token::{TokenGeneratorMock, TokenHasherMock};
+    use argentum_user_account_business::repository::login_throttler::{
+        LoginThrottler, ThrottlingPolicy,
+    };
+    use argentum_user_account_business::repository::password_credential_checker::PasswordCredentialChecker;
+    use argentum_user_account_business::repository::password_credential_repository::PasswordCredentialRepository;
+    use argentum_user_account_business::repository::password_credential_writer::PasswordCredentialWriter;
+    use argentum_user_account_business::use_case::user_logins_with_password::UserLoginsWithPasswordUc;
+    use argentum_user_business::conformance::anonymous_binding_repository::check;
+    use argentum_user_business::entity::anonymous_binding::AnonymousBinding;
+    use argentum_user_business::entity::user::{AnonymousUser, AuthenticatedUser};
+    use argentum_user_business::repository::anonymous_binding_repository::AnonymousBindingRepositoryTrait;
+    use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::value_object::name::Name;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_latest_binding_is_found() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let repository = PostgresAnonymousBindingRepository::new(&db.connection, &id_factory);
+
+        let user_id = id_factory.create();
+        let mut first = AnonymousBinding::new(user_id.clone(), id_factory.create());
+        first.created_at = Utc::now() - Duration::hours(1);
+        let second = AnonymousBinding::new(user_id.clone(), id_factory.create());
+
+        repository.save(&first).map_err(|_| "Can't save binding")?;
+        repository.save(&second).map_err(|_| "Can't save binding")?;
+
+        let found = repository
+            .find_by_user_id(&user_id)
+            .ok_or("Binding is not found")?;
+        assert_eq!(
+            second.anonymous_id.to_string(),
+            found.anonymous_id.to_string()
+        );
+        assert!(repository.find_by_user_id(&id_factory.create()).is_none());
+
+        Ok(())
+    }
+
+    /// A failed insert would abort the whole login transaction in Postgres
+    #[test]
+    fn test_same_anonymous_logs_in_twice() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let user_repository = PostgresAuthenticatedUserRepository::new(&db.connection, &id_factory);
+        let binding_repository =
+            PostgresAnonymousBindingRepository::new(&db.connection, &id_factory);
+        let session_repository = PostgresSessionRepository::new(&db.connection, &id_factory);
+        let credential_repository =
+            PostgresPasswordCredentialRepository::new(&db.connection, &id_factory);
+        let login_attempt_repository = LoginAttemptRepositoryMock::new();
+        let validator = ValidatorMock::new();
+        let encryptor = EncryptorMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+        let event_publisher = EventPublisherMock::new();
+
+        let name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+        let user = AuthenticatedUser::new(&id_factory.create(), name, email.clone());
+        user_repository.save(&user).map_err(|_| "Can't save user")?;
+        credential_repository.save(&PasswordCredential::new(
+            user.id.clone(),
+            encryptor.encrypt("12345").unwrap(),
+        ));
+
+        let credential_checker =
+            PasswordCredentialChecker::new(&credential_repository, &validator, &encryptor);
+        let login_throttler = LoginThrottler::new(
+            &login_attempt_repository,
+            ThrottlingPolicy::new(2, 60, 600, 5, 900),
+            ThrottlingPolicy::for_client(),
+        );
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+
+        let uc = UserLoginsWithPasswordUc::new(
+            &user_repository,
+            &binding_repository,
+            &session_repository,
+            &credential_checker,
+            &login_throttler,
+            &credential_writer,
+            &encryptor,
+            &id_factory,
+            &token_generator,
+            &token_hasher,
+            &logger,
+            3600,
+            false,
+            &event_publisher,
+            &db.connection,
+        );
+
+        let anonymous_id = id_factory.create();
+        for _ in 0..2 {
+            uc.execute(
+                AnonymousUser::new(&anonymous_id),
+                email.clone(),
+                "12345".to_string(),
+                "127.0.0.1".to_string(),
+            )
+            .map_err(|_| "Same anonymous user can't log in again")?;
+        }
+
+        let binding = binding_repository
+            .find_by_user_id(&user.id)
+            .ok_or("Binding is not found")?;
+        assert_eq!(anonymous_id.to_string(), binding.anonymous_id.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conformance() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = match test_db::connect() {
+                Some(db) => db,
+                None => return Ok(()),
+            };
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &PostgresAnonymousBindingRepository::new(&db.connection, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use crate::connection::{PooledClient, PostgresConnection};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::entity::user::AnonymousUser;
+use argentum_user_business::repository::user_repository::{
+    AnonymousUserRepositoryTrait, SavingUserError,
+};
+
+pub struct PostgresAnonymousUserRepository<'s> {
+    connection: &'s PostgresConnection,
+    id_factory: &'s dyn IdFactory,
+    transaction: Option<Transaction>,
+}
+
+impl<'s> PostgresAnonymousUserRepository<'s> {
+    pub fn new(
+        connection: &'s PostgresConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> PostgresAnonymousUserRepository<'s> {
+        PostgresAnonymousUserRepository {
+            connection,
+            id_factory,
+            transaction: None,
+        }
+    }
+
+    fn client(&self) -> PooledClient<'_> {
+        self.connection.client(self.transaction.as_ref())
+    }
+}
+
+impl AnonymousUserRepositoryTrait for PostgresAnonymousUserRepository<'_> {
+    fn find(&self, id: &Id) -> Option<AnonymousUser> {
+        let row = self
+            .client()
+            .query_opt(
+                "SELECT id, created_at FROM anonymous_users WHERE id = $1",
+                &[&id.to_string()],
+            )
+            .ok()??;
+
+        let id: String = row.get("id");
+
+        Some(AnonymousUser {
+            id: self.id_factory.parse(&id).ok()?,
+            created_at: row.get("created_at"),
+        })
+    }
+
+    fn save(&self, user: &AnonymousUser) -> Result<(), SavingUserError> {
+        self.client()
+            .execute(
+                "INSERT INTO anonymous_users (id, created_at) VALUES ($1, $2)",
+                &[&user.id.to_string(), &user.created_at],
+            )
+            .map(|_| ())
+            .map_err(|_| SavingUserError::Anonymous)
+    }
+
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Bound<'_, dyn AnonymousUserRepositoryTrait + '_> {
+        Bound::Owned(Box::new(PostgresAnonymousUserRepository {
+            connection: self.connection,
+            id_factory: self.id_factory,
+            transaction: Some(transaction.clone()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::anonymous_user_repository::PostgresAnonymousUserRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_user_business::conformance::anonymous_user_repository::check;
+    use argentum_user_business::entity::user::AnonymousUser;
+    use argentum_user_business::repository::user_repository::AnonymousUserRepositoryTrait;
+
+    #[test]
+    fn test_save_and_find_anonymous() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let repository = PostgresAnonymousUserRepository::new(&db.connection, &id_factory);
+
+        let anonymous = AnonymousUser::new(&id_factory.create());
+        repository
+            .save(&anonymous)
+            .map_err(|_| "Can't save anonymous")?;
+
+        if repository.save(&anonymous).is_ok() {
+            return Err("Anonymous should be saved once");
+        }
+
+        let found = repository
+            .find(&anonymous.id)
+            .ok_or("Anonymous is not found")?;
+        assert_eq!(anonymous.id.to_string(), found.id.to_string());
+        assert!(repository.find(&id_factory.create()).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conformance() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = match test_db::connect() {
+                Some(db) => db,
+                None => return Ok(()),
+            };
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &PostgresAnonymousUserRepository::new(&db.connection, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+}
@@ -0,0 +1,268 @@
+use crate::connection::{PooledClient, PostgresConnection};
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::entity::user::AuthenticatedUser;
+use argentum_user_business::repository::user_repository::{
+    AuthenticatedUserRepositoryTrait, SavingUserError,
+};
+use argentum_user_business::value_object::name::Name;
+use postgres::Row;
+
+const COLUMNS: &str = "id, first_name, last_name, email, email_verified_at, created_at";
+
+pub struct PostgresAuthenticatedUserRepository<'s> {
+    connection: &'s PostgresConnection,
+    id_factory: &'s dyn IdFactory,
+    transaction: Option<Transaction>,
+}
+
+impl<'s> PostgresAuthenticatedUserRepository<'s> {
+    pub fn new(
+        connection: &'s PostgresConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> PostgresAuthenticatedUserRepository<'s> {
+        PostgresAuthenticatedUserRepository {
+            connection,
+            id_factory,
+            transaction: None,
+        }
+    }
+
+    fn client(&self) -> PooledClient<'_> {
+        self.connection.client(self.transaction.as_ref())
+    }
+
+    fn user_from_row(&self, row: &Row) -> Result<AuthenticatedUser, SavingUserError> {
+        let id: String = row.get("id");
+        let name = Name::new(row.get("first_name"), row.get("last_name"))
+            .map_err(|_| SavingUserError::Authenticated)?;
+        let email =
+            EmailAddress::new(row.get("email")).map_err(|_| SavingUserError::Authenticated)?;
+
+        Ok(AuthenticatedUser {
+            id: self
+                .id_factory
+                .parse(&id)
+                .map_err(|_| SavingUserError::Authenticated)?,
+            created_at: row.get("created_at"),
+            name,
+            email,
+            email_verified_at: row.get("email_verified_at"),
+        })
+    }
+
+    fn find_one(
+        &self,
+        condition: &str,
+        value: &String,
+    ) -> Result<Option<AuthenticatedUser>, SavingUserError> {
+        let row = self
+            .client()
+            .query_opt(
+                format!(
+                    "SELECT {} FROM authenticated_users WHERE {}",
+                    COLUMNS, condition
+                )
+                .as_str(),
+                &[value],
+            )
+            .map_err(|_| SavingUserError::Authenticated)?;
+
+        match row {
+            Some(row) => Ok(Some(self.user_from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl AuthenticatedUserRepositoryTrait for PostgresAuthenticatedUserRepository<'_> {
+    fn find(&self, id: &Id) -> Result<Option<AuthenticatedUser>, SavingUserError> {
+        self.find_one("id = $1", &id.to_string())
+    }
+
+    /// Emails are compared case-insensitively, the same way the unique index does
+    fn find_by_email(
+        &self,
+        email: &EmailAddress,
+    ) -> Result<Option<AuthenticatedUser>, SavingUserError> {
+        self.find_one("lower(email) = lower($1)", &email.as_string())
+    }
+
+    fn save(&self, user: &AuthenticatedUser) -> Result<(), SavingUserError> {
+        self.client()
+            .execute(
+                "INSERT INTO authenticated_users
+                (id, first_name, last_name, email, email_verified_at, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    &user.id.to_string(),
+                    &user.name.first,
+                    &user.name.last,
+                    &user.email.as_string(),
+                    &user.email_verified_at,
+                    &user.created_at,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| SavingUserError::Authenticated)
+    }
+
+    fn update(&self, user: &AuthenticatedUser) -> Result<(), SavingUserError> {
+        let updated = self
+            .client()
+            .execute(
+                "UPDATE authenticated_users
+                SET first_name = $2, last_name = $3, email = $4, email_verified_at = $5
+                WHERE id = $1",
+                &[
+                    &user.id.to_string(),
+                    &user.name.first,
+                    &user.name.last,
+                    &user.email.as_string(),
+                    &user.email_verified_at,
+                ],
+            )
+            .map_err(|_| SavingUserError::Authenticated)?;
+
+        match updated {
+            1 => Ok(()),
+            _ => Err(SavingUserError::Authenticated),
+        }
+    }
+
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Bound<'_, dyn AuthenticatedUserRepositoryTrait + '_> {
+        Bound::Owned(Box::new(PostgresAuthenticatedUserRepository {
+            connection: self.connection,
+            id_factory: self.id_factory,
+            transaction: Some(transaction.clone()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::authenticated_user_repository::PostgresAuthenticatedUserRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_user_business::conformance::authenticated_user_repository::check;
+    use argentum_user_business::entity::user::AuthenticatedUser;
+    use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::value_object::name::Name;
+
+    fn stub_user(id_factory: &IdFactoryMock, email: &str) -> AuthenticatedUser {
+        AuthenticatedUser::new(
+            &id_factory.create(),
+            Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap(),
+            EmailAddress::new(email.to_string()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_save_and_find_user() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let repository = PostgresAuthenticatedUserRepository::new(&db.connection, &id_factory);
+
+        let user = stub_user(&id_factory, "dionne@example.com");
+        repository.save(&user).map_err(|_| "Can't save user")?;
+
+        let found = repository
+            .find(&user.id)
+            .map_err(|_| "Can't find user")?
+            .ok_or("User is not found")?;
+        assert_eq!(user.id.to_string(), found.id.to_string());
+        assert_eq!("Dionne", found.name.first);
+        assert_eq!(
+            user.created_at.timestamp_micros(),
+            found.created_at.timestamp_micros()
+        );
+
+        let email = EmailAddress::new("DIONNE@example.com".to_string()).unwrap();
+        let found = repository
+            .find_by_email(&email)
+            .map_err(|_| "Can't find user")?
+            .ok_or("User is not found by email")?;
+        assert_eq!(user.id.to_string(), found.id.to_string());
+
+        assert!(repository.find(&id_factory.create()).unwrap().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_email_is_unique() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let repository = PostgresAuthenticatedUserRepository::new(&db.connection, &id_factory);
+
+        repository
+            .save(&stub_user(&id_factory, "dionne@example.com"))
+            .map_err(|_| "Can't save user")?;
+
+        if repository
+            .save(&stub_user(&id_factory, "Dionne@Example.com"))
+            .is_ok()
+        {
+            return Err("Second user with the same email should not be saved");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_user() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let repository = PostgresAuthenticatedUserRepository::new(&db.connection, &id_factory);
+
+        let mut user = stub_user(&id_factory, "dionne@example.com");
+        repository.save(&user).map_err(|_| "Can't save user")?;
+
+        user.verify_email();
+        repository.update(&user).map_err(|_| "Can't update user")?;
+
+        let found = repository.find(&user.id).unwrap().unwrap();
+        assert!(found.is_email_verified());
+
+        if repository
+            .update(&stub_user(&id_factory, "other@example.com"))
+            .is_ok()
+        {
+            return Err("Not saved user can't be updated");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conformance() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = match test_db::connect() {
+                Some(db) => db,
+                None => return Ok(()),
+            };
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &PostgresAuthenticatedUserRepository::new(&db.connection, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+}
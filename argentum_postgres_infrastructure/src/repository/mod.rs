@@ -0,0 +1,6 @@
+pub mod anonymous_binding_repository;
+pub mod anonymous_user_repository;
+pub mod authenticated_user_repository;
+pub mod password_credential_repository;
+pub mod restore_password_token_repository;
+pub mod session_repository;
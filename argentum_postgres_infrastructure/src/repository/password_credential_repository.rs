@@ -0,0 +1,140 @@
+use crate::connection::{PooledClient, PostgresConnection};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_account_business::entity::credential::PasswordCredential;
+use argentum_user_account_business::repository::password_credential_repository::PasswordCredentialRepository;
+
+/// The trait has no way to report a failed write. Inside a transaction the failed statement
+/// aborts it, so the whole unit of work is not committed
+pub struct PostgresPasswordCredentialRepository<'s> {
+    connection: &'s PostgresConnection,
+    id_factory: &'s dyn IdFactory,
+    transaction: Option<Transaction>,
+}
+
+impl<'s> PostgresPasswordCredentialRepository<'s> {
+    pub fn new(
+        connection: &'s PostgresConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> PostgresPasswordCredentialRepository<'s> {
+        PostgresPasswordCredentialRepository {
+            connection,
+            id_factory,
+            transaction: None,
+        }
+    }
+
+    fn client(&self) -> PooledClient<'_> {
+        self.connection.client(self.transaction.as_ref())
+    }
+}
+
+impl PasswordCredentialRepository for PostgresPasswordCredentialRepository<'_> {
+    fn save(&self, cred: &PasswordCredential) {
+        let _ = self.client().execute(
+            "INSERT INTO password_credentials (user_id, password) VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET password = EXCLUDED.password",
+            &[&cred.user_id.to_string(), &cred.password],
+        );
+    }
+
+    fn find_by_user_id(&self, id: &Id) -> Option<PasswordCredential> {
+        let row = self
+            .client()
+            .query_opt(
+                "SELECT user_id, password FROM password_credentials WHERE user_id = $1",
+                &[&id.to_string()],
+            )
+            .ok()??;
+
+        let user_id: String = row.get("user_id");
+
+        Some(PasswordCredential::new(
+            self.id_factory.parse(&user_id).ok()?,
+            row.get("password"),
+        ))
+    }
+
+    fn delete(&self, cred: &PasswordCredential) {
+        let _ = self.client().execute(
+            "DELETE FROM password_credentials WHERE user_id = $1",
+            &[&cred.user_id.to_string()],
+        );
+    }
+
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Bound<'_, dyn PasswordCredentialRepository + '_> {
+        Bound::Owned(Box::new(PostgresPasswordCredentialRepository {
+            connection: self.connection,
+            id_factory: self.id_factory,
+            transaction: Some(transaction.clone()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::authenticated_user_repository::PostgresAuthenticatedUserRepository;
+    use crate::repository::password_credential_repository::PostgresPasswordCredentialRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_user_account_business::conformance::password_credential_repository::check;
+    use argentum_user_account_business::entity::credential::PasswordCredential;
+    use argentum_user_account_business::repository::password_credential_repository::PasswordCredentialRepository;
+    use argentum_user_business::entity::user::AuthenticatedUser;
+    use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::value_object::name::Name;
+
+    #[test]
+    fn test_save_replace_and_delete_credential() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let user_repository = PostgresAuthenticatedUserRepository::new(&db.connection, &id_factory);
+        let repository = PostgresPasswordCredentialRepository::new(&db.connection, &id_factory);
+
+        let user = AuthenticatedUser::new(
+            &id_factory.create(),
+            Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap(),
+            EmailAddress::new("dionne@example.com".to_string()).unwrap(),
+        );
+        user_repository.save(&user).map_err(|_| "Can't save user")?;
+
+        repository.save(&PasswordCredential::new(user.id.clone(), "old".to_string()));
+        repository.save(&PasswordCredential::new(user.id.clone(), "new".to_string()));
+
+        let cred = repository
+            .find_by_user_id(&user.id)
+            .ok_or("Credential is not found")?;
+        assert_eq!("new", cred.password);
+
+        repository.delete(&cred);
+        assert!(repository.find_by_user_id(&user.id).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conformance() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = match test_db::connect() {
+                Some(db) => db,
+                None => return Ok(()),
+            };
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &PostgresPasswordCredentialRepository::new(&db.connection, &id_factory),
+                &id_factory,
+                &|id_factory| test_db::save_user(&db.connection, id_factory),
+            )
+        })
+    }
+}
@@ -0,0 +1,200 @@
+use crate::connection::{PooledClient, PostgresConnection};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_account_business::entity::restore_password_token::RestorePasswordToken;
+use argentum_user_account_business::repository::restore_password_token_repository::{
+    RestorePasswordTokenRepositoryError, RestorePasswordTokenRepositoryTrait,
+};
+use postgres::Row;
+
+pub struct PostgresRestorePasswordTokenRepository<'s> {
+    connection: &'s PostgresConnection,
+    id_factory: &'s dyn IdFactory,
+    transaction: Option<Transaction>,
+}
+
+impl<'s> PostgresRestorePasswordTokenRepository<'s> {
+    pub fn new(
+        connection: &'s PostgresConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> PostgresRestorePasswordTokenRepository<'s> {
+        PostgresRestorePasswordTokenRepository {
+            connection,
+            id_factory,
+            transaction: None,
+        }
+    }
+
+    fn client(&self) -> PooledClient<'_> {
+        self.connection.client(self.transaction.as_ref())
+    }
+
+    fn token_from_row(&self, row: &Row) -> Option<RestorePasswordToken> {
+        let id: String = row.get("id");
+        let user_id: String = row.get("user_id");
+
+        Some(RestorePasswordToken {
+            id: self.id_factory.parse(&id).ok()?,
+            user_id: self.id_factory.parse(&user_id).ok()?,
+            token_hash: row.get("token_hash"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    fn find_one(
+        &self,
+        condition: &str,
+        value: &str,
+    ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
+        let row = self
+            .client()
+            .query_opt(
+                format!(
+                    "SELECT id, user_id, token_hash, created_at FROM restore_password_tokens
+                    WHERE {}",
+                    condition
+                )
+                .as_str(),
+                &[&value],
+            )
+            // The trait has no error for reading
+            .map_err(|_| RestorePasswordTokenRepositoryError::Save)?;
+
+        Ok(row.and_then(|row| self.token_from_row(&row)))
+    }
+}
+
+impl RestorePasswordTokenRepositoryTrait for PostgresRestorePasswordTokenRepository<'_> {
+    fn find(
+        &self,
+        id: &Id,
+    ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
+        self.find_one("id = $1", &id.to_string())
+    }
+
+    fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
+        self.find_one("token_hash = $1", token_hash)
+    }
+
+    fn save(
+        &self,
+        token: &RestorePasswordToken,
+    ) -> Result<(), RestorePasswordTokenRepositoryError> {
+        self.client()
+            .execute(
+                "INSERT INTO restore_password_tokens (id, user_id, token_hash, created_at)
+                VALUES ($1, $2, $3, $4)",
+                &[
+                    &token.id.to_string(),
+                    &token.user_id.to_string(),
+                    &token.token_hash,
+                    &token.created_at,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| RestorePasswordTokenRepositoryError::Save)
+    }
+
+    fn delete_users_tokens(&self, user_id: &Id) -> Result<(), RestorePasswordTokenRepositoryError> {
+        self.client()
+            .execute(
+                "DELETE FROM restore_password_tokens WHERE user_id = $1",
+                &[&user_id.to_string()],
+            )
+            .map(|_| ())
+            .map_err(|_| RestorePasswordTokenRepositoryError::Delete)
+    }
+
+    fn delete_if_exists(&self, id: &Id) -> Result<bool, RestorePasswordTokenRepositoryError> {
+        self.client()
+            .execute(
+                "DELETE FROM restore_password_tokens WHERE id = $1",
+                &[&id.to_string()],
+            )
+            .map(|deleted| deleted > 0)
+            .map_err(|_| RestorePasswordTokenRepositoryError::Delete)
+    }
+
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Bound<'_, dyn RestorePasswordTokenRepositoryTrait + '_> {
+        Bound::Owned(Box::new(PostgresRestorePasswordTokenRepository {
+            connection: self.connection,
+            id_factory: self.id_factory,
+            transaction: Some(transaction.clone()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::authenticated_user_repository::PostgresAuthenticatedUserRepository;
+    use crate::repository::restore_password_token_repository::PostgresRestorePasswordTokenRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_user_account_business::conformance::restore_password_token_repository::check;
+    use argentum_user_account_business::entity::restore_password_token::RestorePasswordToken;
+    use argentum_user_account_business::repository::restore_password_token_repository::RestorePasswordTokenRepositoryTrait;
+    use argentum_user_business::entity::user::AuthenticatedUser;
+    use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::value_object::name::Name;
+
+    #[test]
+    fn test_save_find_and_delete_tokens() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let user_repository = PostgresAuthenticatedUserRepository::new(&db.connection, &id_factory);
+        let repository = PostgresRestorePasswordTokenRepository::new(&db.connection, &id_factory);
+
+        let user = AuthenticatedUser::new(
+            &id_factory.create(),
+            Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap(),
+            EmailAddress::new("dionne@example.com".to_string()).unwrap(),
+        );
+        user_repository.save(&user).map_err(|_| "Can't save user")?;
+
+        let token = RestorePasswordToken::new(id_factory.create(), user.id.clone(), "hash".into());
+        repository.save(&token).map_err(|_| "Can't save token")?;
+
+        let found = repository
+            .find_by_token_hash("hash")
+            .map_err(|_| "Can't find token")?
+            .ok_or("Token is not found by hash")?;
+        assert_eq!(token.id.to_string(), found.id.to_string());
+        assert!(repository.find(&token.id).unwrap().is_some());
+
+        repository
+            .delete_users_tokens(&user.id)
+            .map_err(|_| "Can't delete tokens")?;
+        assert!(repository.find(&token.id).unwrap().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conformance() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = match test_db::connect() {
+                Some(db) => db,
+                None => return Ok(()),
+            };
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &PostgresRestorePasswordTokenRepository::new(&db.connection, &id_factory),
+                &id_factory,
+                &|id_factory| test_db::save_user(&db.connection, id_factory),
+            )
+        })
+    }
+}
@@ -0,0 +1,258 @@
+use crate::connection::{PooledClient, PostgresConnection};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_account_business::entity::session::Session;
+use argentum_user_account_business::repository::session_repository::{
+    SessionRepositoryError, SessionRepositoryTrait,
+};
+use postgres::types::ToSql;
+use postgres::Row;
+
+const COLUMNS: &str = "id, user_id, token_hash, created_at, last_seen_at, expires_at";
+
+pub struct PostgresSessionRepository<'s> {
+    connection: &'s PostgresConnection,
+    id_factory: &'s dyn IdFactory,
+    transaction: Option<Transaction>,
+}
+
+impl<'s> PostgresSessionRepository<'s> {
+    pub fn new(
+        connection: &'s PostgresConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> PostgresSessionRepository<'s> {
+        PostgresSessionRepository {
+            connection,
+            id_factory,
+            transaction: None,
+        }
+    }
+
+    fn client(&self) -> PooledClient<'_> {
+        self.connection.client(self.transaction.as_ref())
+    }
+
+    fn session_from_row(&self, row: &Row) -> Option<Session> {
+        let id: String = row.get("id");
+        let user_id: String = row.get("user_id");
+
+        Some(Session {
+            id: self.id_factory.parse(&id).ok()?,
+            user_id: self.id_factory.parse(&user_id).ok()?,
+            token_hash: row.get("token_hash"),
+            created_at: row.get("created_at"),
+            last_seen_at: row.get("last_seen_at"),
+            expires_at: row.get("expires_at"),
+        })
+    }
+
+    fn find_all(
+        &self,
+        condition: &str,
+        value: &(dyn ToSql + Sync),
+    ) -> Result<Vec<Session>, SessionRepositoryError> {
+        let rows = self
+            .client()
+            .query(
+                format!("SELECT {} FROM sessions WHERE {}", COLUMNS, condition).as_str(),
+                &[value],
+            )
+            .map_err(|_| SessionRepositoryError::Find)?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| self.session_from_row(row))
+            .collect())
+    }
+
+    fn delete_where(
+        &self,
+        condition: &str,
+        value: &(dyn ToSql + Sync),
+    ) -> Result<(), SessionRepositoryError> {
+        self.client()
+            .execute(
+                format!("DELETE FROM sessions WHERE {}", condition).as_str(),
+                &[value],
+            )
+            .map(|_| ())
+            .map_err(|_| SessionRepositoryError::Delete)
+    }
+}
+
+impl SessionRepositoryTrait for PostgresSessionRepository<'_> {
+    fn find(&self, id: &Id) -> Option<Session> {
+        self.find_all("id = $1", &id.to_string()).ok()?.pop()
+    }
+
+    /// The hash is looked up by the unique index, there is no secret to compare in constant time
+    fn find_by_token_hash(&self, token_hash: &str) -> Option<Session> {
+        self.find_all("token_hash = $1", &token_hash).ok()?.pop()
+    }
+
+    fn find_by_user_id(&self, user_id: &Id) -> Result<Vec<Session>, SessionRepositoryError> {
+        self.find_all("user_id = $1", &user_id.to_string())
+    }
+
+    fn save(&self, session: &Session) -> Result<(), SessionRepositoryError> {
+        self.client()
+            .execute(
+                "INSERT INTO sessions (id, user_id, token_hash, created_at, last_seen_at, expires_at)
+                VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    &session.id.to_string(),
+                    &session.user_id.to_string(),
+                    &session.token_hash,
+                    &session.created_at,
+                    &session.last_seen_at,
+                    &session.expires_at,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| SessionRepositoryError::Save)
+    }
+
+    fn update(&self, session: &Session) -> Result<(), SessionRepositoryError> {
+        let updated = self
+            .client()
+            .execute(
+                "UPDATE sessions SET token_hash = $2, last_seen_at = $3, expires_at = $4
+                WHERE id = $1",
+                &[
+                    &session.id.to_string(),
+                    &session.token_hash,
+                    &session.last_seen_at,
+                    &session.expires_at,
+                ],
+            )
+            .map_err(|_| SessionRepositoryError::Update)?;
+
+        match updated {
+            1 => Ok(()),
+            _ => Err(SessionRepositoryError::Update),
+        }
+    }
+
+    fn delete(&self, id: &Id) -> Result<(), SessionRepositoryError> {
+        self.delete_where("id = $1", &id.to_string())
+    }
+
+    fn delete_users_sessions(&self, user_id: &Id) -> Result<(), SessionRepositoryError> {
+        self.delete_where("user_id = $1", &user_id.to_string())
+    }
+
+    fn within(&self, transaction: &Transaction) -> Bound<'_, dyn SessionRepositoryTrait + '_> {
+        Bound::Owned(Box::new(PostgresSessionRepository {
+            connection: self.connection,
+            id_factory: self.id_factory,
+            transaction: Some(transaction.clone()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::session_repository::PostgresSessionRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_user_account_business::conformance::session_repository::check;
+    use argentum_user_account_business::entity::session::Session;
+    use argentum_user_account_business::repository::session_repository::SessionRepositoryTrait;
+
+    #[test]
+    fn test_save_find_and_update_session() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let repository = PostgresSessionRepository::new(&db.connection, &id_factory);
+
+        let mut session = Session::new(
+            id_factory.create(),
+            id_factory.create(),
+            "hash".to_string(),
+            3600,
+        );
+        repository
+            .save(&session)
+            .map_err(|_| "Can't save session")?;
+
+        let found = repository
+            .find_by_token_hash("hash")
+            .ok_or("Session is not found by token hash")?;
+        assert_eq!(session.id.to_string(), found.id.to_string());
+        assert_eq!(
+            session.expires_at.timestamp_micros(),
+            found.expires_at.timestamp_micros()
+        );
+
+        session.token_hash = "rotated".to_string();
+        repository
+            .update(&session)
+            .map_err(|_| "Can't update session")?;
+
+        assert!(repository.find_by_token_hash("hash").is_none());
+        assert!(repository.find(&session.id).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_sessions() -> Result<(), &'static str> {
+        let db = match test_db::connect() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        let id_factory = IdFactoryMock::new();
+        let repository = PostgresSessionRepository::new(&db.connection, &id_factory);
+
+        let user_id = id_factory.create();
+        let other_user_id = id_factory.create();
+        for (user_id, hash) in [(&user_id, "a"), (&user_id, "b"), (&other_user_id, "c")].iter() {
+            let session = Session::new(
+                id_factory.create(),
+                (*user_id).clone(),
+                hash.to_string(),
+                3600,
+            );
+            repository
+                .save(&session)
+                .map_err(|_| "Can't save session")?;
+        }
+
+        assert_eq!(2, repository.find_by_user_id(&user_id).unwrap().len());
+
+        let first = repository.find_by_token_hash("a").unwrap();
+        repository
+            .delete(&first.id)
+            .map_err(|_| "Can't delete session")?;
+        assert_eq!(1, repository.find_by_user_id(&user_id).unwrap().len());
+
+        repository
+            .delete_users_sessions(&user_id)
+            .map_err(|_| "Can't delete sessions")?;
+        assert!(repository.find_by_user_id(&user_id).unwrap().is_empty());
+        assert_eq!(1, repository.find_by_user_id(&other_user_id).unwrap().len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conformance() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = match test_db::connect() {
+                Some(db) => db,
+                None => return Ok(()),
+            };
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &PostgresSessionRepository::new(&db.connection, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+}
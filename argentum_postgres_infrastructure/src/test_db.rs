@@ -0,0 +1,90 @@
+use crate::connection::PostgresConnection;
+use crate::migration::migrate;
+use crate::repository::authenticated_user_repository::PostgresAuthenticatedUserRepository;
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_user_business::entity::user::AuthenticatedUser;
+use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+use argentum_user_business::value_object::name::Name;
+use postgres::{Config, NoTls};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const URL_VARIABLE: &str = "ARGENTUM_TEST_POSTGRES_URL";
+
+static SCHEMA_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A migrated schema of its own for every test, it is dropped with the value
+pub struct TestDb {
+    pub connection: PostgresConnection,
+    schema: String,
+}
+
+/// Returns None and the test passes without checks if the database is not configured.
+/// On CI the database is required, a missing one fails the test instead of hiding it
+pub fn connect() -> Option<TestDb> {
+    let url = match std::env::var(URL_VARIABLE) {
+        Ok(url) => url,
+        Err(_) if std::env::var_os("CI").is_some() => {
+            panic!(
+                "{} is not set, Postgres tests can't be skipped on CI",
+                URL_VARIABLE
+            )
+        }
+        Err(_) => {
+            eprintln!("{} is not set, Postgres test is skipped", URL_VARIABLE);
+
+            return None;
+        }
+    };
+
+    let mut config: Config = url.parse().expect("Can't parse test database url");
+    let schema = format!(
+        "test_{}_{}_{}",
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos(),
+        SCHEMA_COUNTER.fetch_add(1, Ordering::SeqCst)
+    );
+
+    config
+        .connect(NoTls)
+        .and_then(|mut client| client.batch_execute(&format!("CREATE SCHEMA {}", schema)))
+        .expect("Can't create test schema");
+
+    // Every client of the pool works in the schema
+    config.options(&format!("-c search_path={}", schema));
+    let connection = PostgresConnection::new(config).expect("Can't connect to test database");
+    migrate(&connection).expect("Can't migrate test schema");
+
+    Some(TestDb { connection, schema })
+}
+
+/// The conformance `UserSetup` of tables with a foreign key to authenticated_users
+pub fn save_user(
+    connection: &PostgresConnection,
+    id_factory: &dyn IdFactory,
+) -> Result<Id, &'static str> {
+    let id = id_factory.create();
+    let user = AuthenticatedUser::new(
+        &id,
+        Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap(),
+        EmailAddress::new(format!("user-{}@example.com", id.to_string())).unwrap(),
+    );
+
+    PostgresAuthenticatedUserRepository::new(connection, id_factory)
+        .save(&user)
+        .map_err(|_| "Can't save user")?;
+
+    Ok(id)
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        if let Ok(client) = self.connection.client(None).inner() {
+            let _ = client.batch_execute(&format!("DROP SCHEMA {} CASCADE", self.schema));
+        }
+    }
+}
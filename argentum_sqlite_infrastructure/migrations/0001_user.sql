@@ -0,0 +1,21 @@
+CREATE TABLE authenticated_users (
+    id TEXT PRIMARY KEY NOT NULL,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    -- One account per address, whatever case it is typed in
+    email TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    email_verified_at TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE anonymous_users (
+    id TEXT PRIMARY KEY NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE anonymous_bindings (
+    user_id TEXT NOT NULL,
+    anonymous_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, anonymous_id)
+);
@@ -0,0 +1,62 @@
+-- Sessions belong to anonymous and authenticated users, so user_id has no foreign key
+CREATE TABLE sessions (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    last_seen_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+
+CREATE INDEX sessions_user_id_idx ON sessions (user_id);
+
+CREATE TABLE password_credentials (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    password TEXT NOT NULL
+);
+
+CREATE TABLE restore_password_tokens (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX restore_password_tokens_user_id_idx ON restore_password_tokens (user_id);
+
+CREATE TABLE refresh_tokens (
+    id TEXT PRIMARY KEY NOT NULL,
+    session_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    used_at TEXT
+);
+
+CREATE INDEX refresh_tokens_session_id_idx ON refresh_tokens (session_id);
+
+CREATE TABLE login_attempts (
+    key TEXT PRIMARY KEY NOT NULL,
+    count INTEGER NOT NULL,
+    last_failed_at TEXT NOT NULL
+);
+
+CREATE TABLE email_verification_tokens (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    email TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX email_verification_tokens_user_id_idx ON email_verification_tokens (user_id);
+
+-- Messages are relayed in the order they were written, that is rowid order
+CREATE TABLE outbox_messages (
+    id TEXT PRIMARY KEY NOT NULL,
+    event_name TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    occurred_at TEXT NOT NULL,
+    published_at TEXT
+);
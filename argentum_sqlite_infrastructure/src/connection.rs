@@ -0,0 +1,207 @@
+use crate::migration::migrate;
+use argentum_standard_business::transaction::{
+    Transaction, TransactionError, TransactionManagerTrait,
+};
+use rusqlite::{Connection, Params, Row};
+use std::cell::Cell;
+use std::path::Path;
+use std::time::Duration;
+
+/// One connection shared by all repositories, it runs one transaction at a time, so
+/// repositories give out themselves as bound to it. The connection isn't `Sync`, each
+/// thread opens its own. The schema is migrated when the connection is opened.
+/// A failed statement doesn't abort an SQLite transaction, so failures are remembered
+/// and the commit of their transaction fails
+pub struct SqliteConnection {
+    connection: Connection,
+    transaction: Cell<Option<u64>>,
+    next_transaction: Cell<u64>,
+    failed: Cell<bool>,
+}
+
+impl SqliteConnection {
+    /// Opens the database file, it is created if it doesn't exist
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<SqliteConnection, rusqlite::Error> {
+        let connection = Connection::open(path)?;
+        // Other processes may hold the lock of the same file for a while
+        connection.busy_timeout(Duration::from_secs(5))?;
+
+        SqliteConnection::new(connection)
+    }
+
+    /// The database lives as long as the connection
+    pub fn open_in_memory() -> Result<SqliteConnection, rusqlite::Error> {
+        SqliteConnection::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> Result<SqliteConnection, rusqlite::Error> {
+        migrate(&connection)?;
+
+        Ok(SqliteConnection {
+            connection,
+            transaction: Cell::new(None),
+            next_transaction: Cell::new(1),
+            failed: Cell::new(false),
+        })
+    }
+
+    pub(crate) fn execute<P: Params>(&self, sql: &str, params: P) -> rusqlite::Result<usize> {
+        self.track(self.connection.execute(sql, params))
+    }
+
+    pub(crate) fn query_row<T, P, F>(&self, sql: &str, params: P, f: F) -> rusqlite::Result<T>
+    where
+        P: Params,
+        F: FnOnce(&Row<'_>) -> rusqlite::Result<T>,
+    {
+        self.track(self.connection.query_row(sql, params, f))
+    }
+
+    /// The connection itself, statements run on it directly are not tracked
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Nothing found is an answer, not a failure
+    fn track<R>(&self, result: rusqlite::Result<R>) -> rusqlite::Result<R> {
+        match &result {
+            Ok(_) | Err(rusqlite::Error::QueryReturnedNoRows) => {}
+            Err(_) if self.transaction.get().is_some() => self.failed.set(true),
+            Err(_) => {}
+        }
+
+        result
+    }
+}
+
+impl TransactionManagerTrait for SqliteConnection {
+    /// Takes the write lock at once, so a transaction doesn't fail half way
+    /// because another process has started writing
+    fn begin(&self) -> Result<Transaction, TransactionError> {
+        if self.transaction.get().is_some() {
+            return Err(TransactionError::Begin);
+        }
+
+        self.connection
+            .execute_batch("BEGIN IMMEDIATE")
+            .map_err(|_| TransactionError::Begin)?;
+
+        let id = self.next_transaction.get();
+        self.next_transaction.set(id + 1);
+        self.transaction.set(Some(id));
+        self.failed.set(false);
+
+        Ok(Transaction::new(id))
+    }
+
+    /// A transaction with a failed statement is rolled back and the commit fails
+    fn commit(&self, transaction: Transaction) -> Result<(), TransactionError> {
+        if self.transaction.get() != Some(transaction.id()) {
+            return Err(TransactionError::Commit);
+        }
+
+        self.transaction.set(None);
+
+        if self.failed.get() {
+            let _ = self.connection.execute_batch("ROLLBACK");
+
+            return Err(TransactionError::Commit);
+        }
+
+        if self.connection.execute_batch("COMMIT").is_err() {
+            let _ = self.connection.execute_batch("ROLLBACK");
+
+            return Err(TransactionError::Commit);
+        }
+
+        Ok(())
+    }
+
+    fn rollback(&self, transaction: Transaction) -> Result<(), TransactionError> {
+        if self.transaction.get() != Some(transaction.id()) {
+            return Err(TransactionError::Rollback);
+        }
+
+        self.transaction.set(None);
+
+        self.connection
+            .execute_batch("ROLLBACK")
+            .map_err(|_| TransactionError::Rollback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::anonymous_user_repository::SqliteAnonymousUserRepository;
+    use crate::test_db;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::transaction::{Transaction, TransactionManagerTrait};
+    use argentum_user_business::entity::user::AnonymousUser;
+    use argentum_user_business::repository::user_repository::AnonymousUserRepositoryTrait;
+
+    #[test]
+    fn test_rolled_back_writes_are_discarded() -> Result<(), &'static str> {
+        let db = test_db::in_memory();
+        let id_factory = IdFactoryMock::new();
+        let repository = SqliteAnonymousUserRepository::new(&db, &id_factory);
+
+        let committed = AnonymousUser::new(&id_factory.create());
+        let transaction = db.begin().map_err(|_| "Can't begin")?;
+        repository
+            .within(&transaction)
+            .save(&committed)
+            .map_err(|_| "Can't save")?;
+        db.commit(transaction).map_err(|_| "Can't commit")?;
+
+        let rolled_back = AnonymousUser::new(&id_factory.create());
+        let transaction = db.begin().map_err(|_| "Can't begin")?;
+        repository
+            .within(&transaction)
+            .save(&rolled_back)
+            .map_err(|_| "Can't save")?;
+        db.rollback(transaction).map_err(|_| "Can't rollback")?;
+
+        assert!(repository.find(&committed.id).is_some());
+        assert!(repository.find(&rolled_back.id).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_transactions_do_not_nest() -> Result<(), &'static str> {
+        let db = test_db::in_memory();
+
+        let transaction = db.begin().map_err(|_| "Can't begin")?;
+
+        assert!(db.begin().is_err());
+        assert!(db.commit(Transaction::new(transaction.id() + 1)).is_err());
+
+        db.commit(transaction).map_err(|_| "Can't commit")?;
+
+        assert!(db.begin().is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_keeps_data_after_reopening() -> Result<(), &'static str> {
+        let path = test_db::temp_path();
+        let id_factory = IdFactoryMock::new();
+        let user = AnonymousUser::new(&id_factory.create());
+
+        {
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't create file")?;
+            let repository = SqliteAnonymousUserRepository::new(&db, &id_factory);
+            repository.save(&user).map_err(|_| "Can't save")?;
+        }
+
+        let db = SqliteConnection::open(path.path()).map_err(|_| "Can't reopen file")?;
+        let repository = SqliteAnonymousUserRepository::new(&db, &id_factory);
+
+        assert!(repository.find(&user.id).is_some());
+
+        Ok(())
+    }
+}
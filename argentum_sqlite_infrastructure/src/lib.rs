@@ -0,0 +1,6 @@
+pub mod connection;
+mod migration;
+pub mod repository;
+#[cfg(test)]
+mod test_db;
+mod time;
@@ -0,0 +1,43 @@
+use rusqlite::Connection;
+
+const MIGRATIONS: &[&str] = &[
+    include_str!("../migrations/0001_user.sql"),
+    include_str!("../migrations/0002_user_account.sql"),
+];
+
+/// Applies migrations which are not applied yet, each one in its own transaction.
+/// The number of applied migrations is kept in `PRAGMA user_version`
+pub(crate) fn migrate(connection: &Connection) -> Result<(), rusqlite::Error> {
+    let applied: i64 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(applied as usize) {
+        let transaction = connection.unchecked_transaction()?;
+        transaction.execute_batch(sql)?;
+        transaction.pragma_update(None, "user_version", (index + 1) as i64)?;
+        transaction.commit()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::migration::{migrate, MIGRATIONS};
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_migrations_are_applied_once() -> Result<(), &'static str> {
+        let connection = Connection::open_in_memory().map_err(|_| "Can't open database")?;
+
+        migrate(&connection).map_err(|_| "Can't migrate")?;
+        migrate(&connection).map_err(|_| "Second run should be a no-op")?;
+
+        let version: i64 = connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|_| "Can't read version")?;
+
+        assert_eq!(MIGRATIONS.len() as i64, version);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use crate::connection::SqliteConnection;
+use crate::time::{format_time, parse_time};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::entity::anonymous_binding::AnonymousBinding;
+use argentum_user_business::repository::anonymous_binding_repository::{
+    AnonymousBindingRepositoryError, AnonymousBindingRepositoryTrait,
+};
+use rusqlite::{params, OptionalExtension};
+
+pub struct SqliteAnonymousBindingRepository<'s> {
+    connection: &'s SqliteConnection,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> SqliteAnonymousBindingRepository<'s> {
+    pub fn new(
+        connection: &'s SqliteConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> SqliteAnonymousBindingRepository<'s> {
+        SqliteAnonymousBindingRepository {
+            connection,
+            id_factory,
+        }
+    }
+}
+
+impl AnonymousBindingRepositoryTrait for SqliteAnonymousBindingRepository<'_> {
+    /// A user may log in from several anonymous sessions, the latest binding is returned
+    fn find_by_user_id(&self, user_id: &Id) -> Option<AnonymousBinding> {
+        let (user_id, anonymous_id, created_at): (String, String, String) = self
+            .connection
+            .query_row(
+                "SELECT user_id, anonymous_id, created_at FROM anonymous_bindings
+                WHERE user_id = ?1 ORDER BY created_at DESC LIMIT 1",
+                params![user_id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()
+            .ok()??;
+
+        Some(AnonymousBinding {
+            user_id: self.id_factory.parse(&user_id).ok()?,
+            anonymous_id: self.id_factory.parse(&anonymous_id).ok()?,
+            created_at: parse_time(&created_at)?,
+        })
+    }
+
+    fn save(&self, binding: &AnonymousBinding) -> Result<(), AnonymousBindingRepositoryError> {
+        self.connection
+            .execute(
+                "INSERT INTO anonymous_bindings (user_id, anonymous_id, created_at)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT (user_id, anonymous_id) DO UPDATE SET created_at = excluded.created_at",
+                params![
+                    binding.user_id.to_string(),
+                    binding.anonymous_id.to_string(),
+                    format_time(&binding.created_at),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| AnonymousBindingRepositoryError::Save)
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn AnonymousBindingRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::anonymous_binding_repository::SqliteAnonymousBindingRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+    use argentum_user_business::conformance::anonymous_binding_repository::check;
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &SqliteAnonymousBindingRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file_with_unique_ids() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+            let id_factory = UniqueIdFactory::new();
+
+            test(
+                &SqliteAnonymousBindingRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+}
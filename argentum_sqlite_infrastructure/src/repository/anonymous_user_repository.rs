@@ -0,0 +1,97 @@
+use crate::connection::SqliteConnection;
+use crate::time::{format_time, parse_time};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::entity::user::AnonymousUser;
+use argentum_user_business::repository::user_repository::{
+    AnonymousUserRepositoryTrait, SavingUserError,
+};
+use rusqlite::{params, OptionalExtension};
+
+pub struct SqliteAnonymousUserRepository<'s> {
+    connection: &'s SqliteConnection,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> SqliteAnonymousUserRepository<'s> {
+    pub fn new(
+        connection: &'s SqliteConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> SqliteAnonymousUserRepository<'s> {
+        SqliteAnonymousUserRepository {
+            connection,
+            id_factory,
+        }
+    }
+}
+
+impl AnonymousUserRepositoryTrait for SqliteAnonymousUserRepository<'_> {
+    fn find(&self, id: &Id) -> Option<AnonymousUser> {
+        let (id, created_at): (String, String) = self
+            .connection
+            .query_row(
+                "SELECT id, created_at FROM anonymous_users WHERE id = ?1",
+                params![id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .ok()??;
+
+        Some(AnonymousUser {
+            id: self.id_factory.parse(&id).ok()?,
+            created_at: parse_time(&created_at)?,
+        })
+    }
+
+    fn save(&self, user: &AnonymousUser) -> Result<(), SavingUserError> {
+        self.connection
+            .execute(
+                "INSERT INTO anonymous_users (id, created_at) VALUES (?1, ?2)",
+                params![user.id.to_string(), format_time(&user.created_at)],
+            )
+            .map(|_| ())
+            .map_err(|_| SavingUserError::Anonymous)
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn AnonymousUserRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::anonymous_user_repository::SqliteAnonymousUserRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+    use argentum_user_business::conformance::anonymous_user_repository::check;
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &SqliteAnonymousUserRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file_with_unique_ids() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+            let id_factory = UniqueIdFactory::new();
+
+            test(
+                &SqliteAnonymousUserRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+}
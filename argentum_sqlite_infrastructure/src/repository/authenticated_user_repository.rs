@@ -0,0 +1,207 @@
+use crate::connection::SqliteConnection;
+use crate::time::{format_time, parse_time};
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::entity::user::AuthenticatedUser;
+use argentum_user_business::repository::user_repository::{
+    AuthenticatedUserRepositoryTrait, SavingUserError,
+};
+use argentum_user_business::value_object::name::Name;
+use rusqlite::{params, OptionalExtension, Row};
+
+const COLUMNS: &str = "id, first_name, last_name, email, email_verified_at, created_at";
+
+pub struct SqliteAuthenticatedUserRepository<'s> {
+    connection: &'s SqliteConnection,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> SqliteAuthenticatedUserRepository<'s> {
+    pub fn new(
+        connection: &'s SqliteConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> SqliteAuthenticatedUserRepository<'s> {
+        SqliteAuthenticatedUserRepository {
+            connection,
+            id_factory,
+        }
+    }
+
+    fn user_from_row(&self, row: &Row) -> Option<AuthenticatedUser> {
+        let id: String = row.get(0).ok()?;
+        let email_verified_at: Option<String> = row.get(4).ok()?;
+        let created_at: String = row.get(5).ok()?;
+
+        Some(AuthenticatedUser {
+            id: self.id_factory.parse(&id).ok()?,
+            name: Name::new(row.get(1).ok()?, row.get(2).ok()?).ok()?,
+            email: EmailAddress::new(row.get(3).ok()?).ok()?,
+            email_verified_at: match email_verified_at {
+                Some(t) => Some(parse_time(&t)?),
+                None => None,
+            },
+            created_at: parse_time(&created_at)?,
+        })
+    }
+
+    fn find_one(
+        &self,
+        condition: &str,
+        value: &str,
+    ) -> Result<Option<AuthenticatedUser>, SavingUserError> {
+        let user = self
+            .connection
+            .query_row(
+                format!(
+                    "SELECT {} FROM authenticated_users WHERE {}",
+                    COLUMNS, condition
+                )
+                .as_str(),
+                params![value],
+                |row| Ok(self.user_from_row(row)),
+            )
+            .optional()
+            .map_err(|_| SavingUserError::Authenticated)?;
+
+        match user {
+            Some(Some(user)) => Ok(Some(user)),
+            Some(None) => Err(SavingUserError::Authenticated),
+            None => Ok(None),
+        }
+    }
+}
+
+impl AuthenticatedUserRepositoryTrait for SqliteAuthenticatedUserRepository<'_> {
+    fn find(&self, id: &Id) -> Result<Option<AuthenticatedUser>, SavingUserError> {
+        self.find_one("id = ?1", &id.to_string())
+    }
+
+    /// Emails are compared case-insensitively, the column is declared with `COLLATE NOCASE`
+    fn find_by_email(
+        &self,
+        email: &EmailAddress,
+    ) -> Result<Option<AuthenticatedUser>, SavingUserError> {
+        self.find_one("email = ?1", &email.as_string())
+    }
+
+    fn save(&self, user: &AuthenticatedUser) -> Result<(), SavingUserError> {
+        self.connection
+            .execute(
+                "INSERT INTO authenticated_users
+                (id, first_name, last_name, email, email_verified_at, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    user.id.to_string(),
+                    user.name.first,
+                    user.name.last,
+                    user.email.as_string(),
+                    user.email_verified_at.as_ref().map(format_time),
+                    format_time(&user.created_at),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| SavingUserError::Authenticated)
+    }
+
+    fn update(&self, user: &AuthenticatedUser) -> Result<(), SavingUserError> {
+        let updated = self
+            .connection
+            .execute(
+                "UPDATE authenticated_users
+                SET first_name = ?2, last_name = ?3, email = ?4, email_verified_at = ?5
+                WHERE id = ?1",
+                params![
+                    user.id.to_string(),
+                    user.name.first,
+                    user.name.last,
+                    user.email.as_string(),
+                    user.email_verified_at.as_ref().map(format_time),
+                ],
+            )
+            .map_err(|_| SavingUserError::Authenticated)?;
+
+        match updated {
+            1 => Ok(()),
+            _ => Err(SavingUserError::Authenticated),
+        }
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn AuthenticatedUserRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::authenticated_user_repository::SqliteAuthenticatedUserRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+    use argentum_user_business::conformance::authenticated_user_repository::check;
+    use argentum_user_business::entity::user::AuthenticatedUser;
+    use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::value_object::name::Name;
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &SqliteAuthenticatedUserRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file_with_unique_ids() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+            let id_factory = UniqueIdFactory::new();
+
+            test(
+                &SqliteAuthenticatedUserRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+
+    #[test]
+    fn test_email_is_unique_regardless_of_case() -> Result<(), &'static str> {
+        let db = test_db::in_memory();
+        let id_factory = IdFactoryMock::new();
+        let repository = SqliteAuthenticatedUserRepository::new(&db, &id_factory);
+
+        let stub_user = |email: &str| {
+            AuthenticatedUser::new(
+                &id_factory.create(),
+                Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap(),
+                EmailAddress::new(email.to_string()).unwrap(),
+            )
+        };
+
+        let user = stub_user("dionne@example.com");
+        repository.save(&user).map_err(|_| "Can't save user")?;
+
+        let email = EmailAddress::new("DIONNE@example.com".to_string()).unwrap();
+        let found = repository
+            .find_by_email(&email)
+            .map_err(|_| "Can't find user")?
+            .ok_or("User is not found by email")?;
+        assert!(*user.id == *found.id);
+
+        if repository.save(&stub_user("Dionne@Example.com")).is_ok() {
+            return Err("Second user with the same email should not be saved");
+        }
+
+        Ok(())
+    }
+}
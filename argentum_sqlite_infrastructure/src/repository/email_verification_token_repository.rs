@@ -0,0 +1,137 @@
+use crate::connection::SqliteConnection;
+use crate::time::{format_time, parse_time};
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_account_business::entity::email_verification_token::EmailVerificationToken;
+use argentum_user_account_business::repository::email_verification_token_repository::{
+    EmailVerificationTokenRepositoryError, EmailVerificationTokenRepositoryTrait,
+};
+use rusqlite::{params, OptionalExtension, Row};
+
+pub struct SqliteEmailVerificationTokenRepository<'s> {
+    connection: &'s SqliteConnection,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> SqliteEmailVerificationTokenRepository<'s> {
+    pub fn new(
+        connection: &'s SqliteConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> SqliteEmailVerificationTokenRepository<'s> {
+        SqliteEmailVerificationTokenRepository {
+            connection,
+            id_factory,
+        }
+    }
+
+    fn token_from_row(&self, row: &Row) -> Option<EmailVerificationToken> {
+        let id: String = row.get(0).ok()?;
+        let user_id: String = row.get(1).ok()?;
+        let created_at: String = row.get(4).ok()?;
+
+        Some(EmailVerificationToken {
+            id: self.id_factory.parse(&id).ok()?,
+            user_id: self.id_factory.parse(&user_id).ok()?,
+            email: EmailAddress::new(row.get(2).ok()?).ok()?,
+            token_hash: row.get(3).ok()?,
+            created_at: parse_time(&created_at)?,
+        })
+    }
+}
+
+impl EmailVerificationTokenRepositoryTrait for SqliteEmailVerificationTokenRepository<'_> {
+    fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailVerificationToken>, EmailVerificationTokenRepositoryError> {
+        let token = self
+            .connection
+            .query_row(
+                "SELECT id, user_id, email, token_hash, created_at FROM email_verification_tokens
+                WHERE token_hash = ?1",
+                params![token_hash],
+                |row| Ok(self.token_from_row(row)),
+            )
+            .optional()
+            // The trait has no error for reading
+            .map_err(|_| EmailVerificationTokenRepositoryError::Save)?;
+
+        Ok(token.flatten())
+    }
+
+    fn save(
+        &self,
+        token: &EmailVerificationToken,
+    ) -> Result<(), EmailVerificationTokenRepositoryError> {
+        self.connection
+            .execute(
+                "INSERT INTO email_verification_tokens (id, user_id, email, token_hash, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    token.id.to_string(),
+                    token.user_id.to_string(),
+                    token.email.as_string(),
+                    token.token_hash,
+                    format_time(&token.created_at),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| EmailVerificationTokenRepositoryError::Save)
+    }
+
+    fn delete_users_tokens(
+        &self,
+        user_id: &Id,
+    ) -> Result<(), EmailVerificationTokenRepositoryError> {
+        self.connection
+            .execute(
+                "DELETE FROM email_verification_tokens WHERE user_id = ?1",
+                params![user_id.to_string()],
+            )
+            .map(|_| ())
+            .map_err(|_| EmailVerificationTokenRepositoryError::Delete)
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn EmailVerificationTokenRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::email_verification_token_repository::SqliteEmailVerificationTokenRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+    use argentum_user_account_business::conformance::email_verification_token_repository::check;
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &SqliteEmailVerificationTokenRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file_with_unique_ids() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+            let id_factory = UniqueIdFactory::new();
+
+            test(
+                &SqliteEmailVerificationTokenRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use crate::connection::SqliteConnection;
+use crate::time::{format_time, parse_time};
+use argentum_user_account_business::entity::login_attempts::FailedLoginAttempts;
+use argentum_user_account_business::repository::login_attempt_repository::{
+    LoginAttemptRepositoryError, LoginAttemptRepositoryTrait,
+};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+
+pub struct SqliteLoginAttemptRepository<'s> {
+    connection: &'s SqliteConnection,
+}
+
+impl<'s> SqliteLoginAttemptRepository<'s> {
+    pub fn new(connection: &'s SqliteConnection) -> SqliteLoginAttemptRepository<'s> {
+        SqliteLoginAttemptRepository { connection }
+    }
+}
+
+impl LoginAttemptRepositoryTrait for SqliteLoginAttemptRepository<'_> {
+    fn find(&self, key: &str) -> Result<Option<FailedLoginAttempts>, LoginAttemptRepositoryError> {
+        let attempts: Option<(u32, String)> = self
+            .connection
+            .query_row(
+                "SELECT count, last_failed_at FROM login_attempts WHERE key = ?1",
+                params![key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|_| LoginAttemptRepositoryError::Find)?;
+
+        match attempts {
+            Some((count, last_failed_at)) => Ok(Some(FailedLoginAttempts {
+                key: key.to_string(),
+                count,
+                last_failed_at: parse_time(&last_failed_at)
+                    .ok_or(LoginAttemptRepositoryError::Find)?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    fn save(&self, attempts: &FailedLoginAttempts) -> Result<(), LoginAttemptRepositoryError> {
+        self.connection
+            .execute(
+                "INSERT INTO login_attempts (key, count, last_failed_at) VALUES (?1, ?2, ?3)
+                ON CONFLICT (key) DO UPDATE
+                SET count = excluded.count, last_failed_at = excluded.last_failed_at",
+                params![
+                    attempts.key,
+                    attempts.count,
+                    format_time(&attempts.last_failed_at),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| LoginAttemptRepositoryError::Save)
+    }
+
+    fn increment(
+        &self,
+        key: &str,
+        failed_at: DateTime<Utc>,
+        expired_before: DateTime<Utc>,
+    ) -> Result<FailedLoginAttempts, LoginAttemptRepositoryError> {
+        let count: u32 = self
+            .connection
+            .query_row(
+                "INSERT INTO login_attempts (key, count, last_failed_at) VALUES (?1, 1, ?2)
+                ON CONFLICT (key) DO UPDATE
+                SET count = CASE
+                        WHEN login_attempts.last_failed_at < ?3 THEN 1
+                        ELSE login_attempts.count + 1
+                    END,
+                    last_failed_at = excluded.last_failed_at
+                RETURNING count",
+                params![key, format_time(&failed_at), format_time(&expired_before)],
+                |row| row.get(0),
+            )
+            .map_err(|_| LoginAttemptRepositoryError::Save)?;
+
+        Ok(FailedLoginAttempts {
+            key: key.to_string(),
+            count,
+            last_failed_at: failed_at,
+        })
+    }
+
+    fn delete(&self, key: &str) -> Result<(), LoginAttemptRepositoryError> {
+        self.connection
+            .execute("DELETE FROM login_attempts WHERE key = ?1", params![key])
+            .map(|_| ())
+            .map_err(|_| LoginAttemptRepositoryError::Delete)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::login_attempt_repository::SqliteLoginAttemptRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_user_account_business::conformance::login_attempt_repository::check;
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+
+            test(
+                &SqliteLoginAttemptRepository::new(&db),
+                &IdFactoryMock::new(),
+            )
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+
+            test(
+                &SqliteLoginAttemptRepository::new(&db),
+                &IdFactoryMock::new(),
+            )
+        })
+    }
+}
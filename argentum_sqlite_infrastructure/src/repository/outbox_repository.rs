@@ -0,0 +1,233 @@
+use crate::connection::SqliteConnection;
+use crate::time::{format_time, parse_time};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_account_business::entity::outbox_message::OutboxMessage;
+use argentum_user_account_business::repository::outbox_repository::{
+    OutboxRepositoryError, OutboxRepositoryTrait,
+};
+use chrono::Utc;
+use rusqlite::{params, Row};
+
+/// Outbox in the database of other repositories, so messages are written in their transaction
+pub struct SqliteOutboxRepository<'s> {
+    connection: &'s SqliteConnection,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> SqliteOutboxRepository<'s> {
+    pub fn new(
+        connection: &'s SqliteConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> SqliteOutboxRepository<'s> {
+        SqliteOutboxRepository {
+            connection,
+            id_factory,
+        }
+    }
+
+    fn message_from_row(&self, row: &Row) -> Option<OutboxMessage> {
+        let id: String = row.get(0).ok()?;
+        let occurred_at: String = row.get(3).ok()?;
+        let published_at: Option<String> = row.get(4).ok()?;
+
+        Some(OutboxMessage {
+            id: self.id_factory.parse(&id).ok()?,
+            event_name: row.get(1).ok()?,
+            payload: row.get(2).ok()?,
+            occurred_at: parse_time(&occurred_at)?,
+            published_at: match published_at {
+                Some(t) => Some(parse_time(&t)?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl OutboxRepositoryTrait for SqliteOutboxRepository<'_> {
+    fn save(&self, message: &OutboxMessage) -> Result<(), OutboxRepositoryError> {
+        self.connection
+            .execute(
+                "INSERT INTO outbox_messages (id, event_name, payload, occurred_at, published_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    message.id.to_string(),
+                    message.event_name,
+                    message.payload,
+                    format_time(&message.occurred_at),
+                    message.published_at.as_ref().map(format_time),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| OutboxRepositoryError::Save)
+    }
+
+    fn find_unpublished(&self, limit: usize) -> Result<Vec<OutboxMessage>, OutboxRepositoryError> {
+        let connection = self.connection.connection();
+        let mut statement = connection
+            .prepare(
+                "SELECT id, event_name, payload, occurred_at, published_at FROM outbox_messages
+                WHERE published_at IS NULL ORDER BY rowid LIMIT ?1",
+            )
+            .map_err(|_| OutboxRepositoryError::Find)?;
+
+        let mut rows = statement
+            .query(params![limit as i64])
+            .map_err(|_| OutboxRepositoryError::Find)?;
+
+        let mut messages = Vec::new();
+
+        while let Some(row) = rows.next().map_err(|_| OutboxRepositoryError::Find)? {
+            match self.message_from_row(row) {
+                Some(message) => messages.push(message),
+                None => return Err(OutboxRepositoryError::Find),
+            }
+        }
+
+        Ok(messages)
+    }
+
+    fn mark_published(&self, id: &Id) -> Result<(), OutboxRepositoryError> {
+        let updated = self
+            .connection
+            .execute(
+                "UPDATE outbox_messages SET published_at = ?1 WHERE id = ?2",
+                params![format_time(&Utc::now()), id.to_string()],
+            )
+            .map_err(|_| OutboxRepositoryError::Update)?;
+
+        match updated {
+            1 => Ok(()),
+            _ => Err(OutboxRepositoryError::Update),
+        }
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn OutboxRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::outbox_repository::SqliteOutboxRepository;
+    use crate::repository::password_credential_repository::SqlitePasswordCredentialRepository;
+    use crate::test_db;
+    use argentum_log_business::{DefaultLogger, Level, StdoutWriter};
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::transaction::TransactionManagerTrait;
+    use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+    use argentum_user_account_business::conformance::outbox_repository::check;
+    use argentum_user_account_business::entity::credential::PasswordCredential;
+    use argentum_user_account_business::entity::outbox_message::OutboxMessage;
+    use argentum_user_account_business::event::PasswordChanged;
+    use argentum_user_account_business::repository::outbox_repository::{
+        OutboxEventPublisher, OutboxRepositoryTrait,
+    };
+    use argentum_user_account_business::repository::password_credential_repository::PasswordCredentialRepository;
+    use argentum_user_account_infrastructure::outbox::relay::{
+        OutboxRelay, OutboxSinkError, OutboxSinkTrait,
+    };
+    use std::cell::RefCell;
+
+    struct RecordingSink {
+        sent: RefCell<Vec<String>>,
+    }
+
+    impl OutboxSinkTrait for RecordingSink {
+        fn send(&self, message: &OutboxMessage) -> Result<(), OutboxSinkError> {
+            self.sent.borrow_mut().push(message.id.to_string());
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(&SqliteOutboxRepository::new(&db, &id_factory), &id_factory)
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file_with_unique_ids() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+            let id_factory = UniqueIdFactory::new();
+
+            test(&SqliteOutboxRepository::new(&db, &id_factory), &id_factory)
+        })
+    }
+
+    #[test]
+    fn test_relay_drains_sqlite_outbox() -> Result<(), &'static str> {
+        let db = test_db::in_memory();
+        let id_factory = IdFactoryMock::new();
+        let outbox = SqliteOutboxRepository::new(&db, &id_factory);
+        let sink = RecordingSink {
+            sent: RefCell::new(Vec::new()),
+        };
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+        let relay = OutboxRelay::new(&outbox, &sink, &logger, 2);
+
+        let mut ids = Vec::new();
+        for _ in 0..5 {
+            let message = OutboxMessage::new(
+                id_factory.create(),
+                &PasswordChanged::new(id_factory.create()),
+            );
+            outbox.save(&message).map_err(|_| "Can't save message")?;
+            ids.push(message.id.to_string());
+        }
+
+        assert_eq!(5, relay.drain().map_err(|_| "Can't drain outbox")?);
+        assert_eq!(ids, *sink.sent.borrow());
+        assert_eq!(0, relay.drain().map_err(|_| "Can't drain outbox")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_commit_leaves_no_outbox_message() -> Result<(), &'static str> {
+        let db = test_db::in_memory();
+        let id_factory = IdFactoryMock::new();
+        let outbox = SqliteOutboxRepository::new(&db, &id_factory);
+        let credential_repository = SqlitePasswordCredentialRepository::new(&db, &id_factory);
+        let publisher = OutboxEventPublisher::new(&outbox, &id_factory);
+
+        db.connection()
+            .execute_batch(
+                "CREATE TRIGGER broken BEFORE INSERT ON password_credentials
+                BEGIN SELECT RAISE(ABORT, 'Broken'); END",
+            )
+            .map_err(|_| "Can't break writes")?;
+
+        let user_id = id_factory.create();
+        let transaction = db.begin().map_err(|_| "Can't begin transaction")?;
+        // The credential repository doesn't return the error, the commit does
+        credential_repository
+            .within(&transaction)
+            .save(&PasswordCredential::new(
+                user_id.clone(),
+                "hash".to_string(),
+            ));
+        publisher
+            .publish(&transaction, &PasswordChanged::new(user_id))
+            .map_err(|_| "Can't publish event")?;
+
+        assert!(db.commit(transaction).is_err(), "Commit should fail");
+        assert!(outbox
+            .find_unpublished(10)
+            .map_err(|_| "Can't find messages")?
+            .is_empty());
+
+        Ok(())
+    }
+}
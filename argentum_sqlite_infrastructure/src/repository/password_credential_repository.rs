@@ -0,0 +1,129 @@
+use crate::connection::SqliteConnection;
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_account_business::entity::credential::PasswordCredential;
+use argentum_user_account_business::repository::password_credential_repository::PasswordCredentialRepository;
+use rusqlite::{params, OptionalExtension};
+
+/// The trait has no way to report a failed write. Inside a transaction the connection remembers
+/// the failed statement, so the whole unit of work is not committed
+pub struct SqlitePasswordCredentialRepository<'s> {
+    connection: &'s SqliteConnection,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> SqlitePasswordCredentialRepository<'s> {
+    pub fn new(
+        connection: &'s SqliteConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> SqlitePasswordCredentialRepository<'s> {
+        SqlitePasswordCredentialRepository {
+            connection,
+            id_factory,
+        }
+    }
+}
+
+impl PasswordCredentialRepository for SqlitePasswordCredentialRepository<'_> {
+    fn save(&self, cred: &PasswordCredential) {
+        let _ = self.connection.execute(
+            "INSERT INTO password_credentials (user_id, password) VALUES (?1, ?2)
+            ON CONFLICT (user_id) DO UPDATE SET password = excluded.password",
+            params![cred.user_id.to_string(), cred.password],
+        );
+    }
+
+    fn find_by_user_id(&self, id: &Id) -> Option<PasswordCredential> {
+        let (user_id, password): (String, String) = self
+            .connection
+            .query_row(
+                "SELECT user_id, password FROM password_credentials WHERE user_id = ?1",
+                params![id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .ok()??;
+
+        Some(PasswordCredential::new(
+            self.id_factory.parse(&user_id).ok()?,
+            password,
+        ))
+    }
+
+    fn delete(&self, cred: &PasswordCredential) {
+        let _ = self.connection.execute(
+            "DELETE FROM password_credentials WHERE user_id = ?1",
+            params![cred.user_id.to_string()],
+        );
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn PasswordCredentialRepository + '_> {
+        Bound::Shared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::password_credential_repository::SqlitePasswordCredentialRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+    use argentum_user_account_business::conformance::password_credential_repository::{
+        check, check_transactions,
+    };
+    use argentum_user_account_business::conformance::unsaved_user;
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &SqlitePasswordCredentialRepository::new(&db, &id_factory),
+                &id_factory,
+                &unsaved_user,
+            )
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file_with_unique_ids() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+            let id_factory = UniqueIdFactory::new();
+
+            test(
+                &SqlitePasswordCredentialRepository::new(&db, &id_factory),
+                &id_factory,
+                &unsaved_user,
+            )
+        })
+    }
+
+    #[test]
+    fn test_transaction_conformance_in_memory() -> Result<(), ConformanceError> {
+        check_transactions(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &SqlitePasswordCredentialRepository::new(&db, &id_factory),
+                &db,
+                &id_factory,
+                &unsaved_user,
+                &|| {
+                    db.connection()
+                        .execute_batch(
+                            "CREATE TRIGGER broken BEFORE INSERT ON password_credentials
+                            BEGIN SELECT RAISE(ABORT, 'Broken'); END",
+                        )
+                        .map_err(|_| "Can't break writes")
+                },
+            )
+        })
+    }
+}
@@ -0,0 +1,155 @@
+use crate::connection::SqliteConnection;
+use crate::time::{format_time, parse_time};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_account_business::entity::refresh_token::RefreshToken;
+use argentum_user_account_business::repository::refresh_token_repository::{
+    RefreshTokenRepositoryError, RefreshTokenRepositoryTrait,
+};
+use rusqlite::{params, OptionalExtension, Row};
+
+pub struct SqliteRefreshTokenRepository<'s> {
+    connection: &'s SqliteConnection,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> SqliteRefreshTokenRepository<'s> {
+    pub fn new(
+        connection: &'s SqliteConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> SqliteRefreshTokenRepository<'s> {
+        SqliteRefreshTokenRepository {
+            connection,
+            id_factory,
+        }
+    }
+
+    fn token_from_row(&self, row: &Row) -> Option<RefreshToken> {
+        let id: String = row.get(0).ok()?;
+        let session_id: String = row.get(1).ok()?;
+        let user_id: String = row.get(2).ok()?;
+        let created_at: String = row.get(4).ok()?;
+        let expires_at: String = row.get(5).ok()?;
+        let used_at: Option<String> = row.get(6).ok()?;
+
+        Some(RefreshToken {
+            id: self.id_factory.parse(&id).ok()?,
+            session_id: self.id_factory.parse(&session_id).ok()?,
+            user_id: self.id_factory.parse(&user_id).ok()?,
+            token_hash: row.get(3).ok()?,
+            created_at: parse_time(&created_at)?,
+            expires_at: parse_time(&expires_at)?,
+            used_at: match used_at {
+                Some(t) => Some(parse_time(&t)?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl RefreshTokenRepositoryTrait for SqliteRefreshTokenRepository<'_> {
+    /// The hash is looked up by the unique index, there is no secret to compare in constant time
+    fn find_by_token_hash(&self, token_hash: &str) -> Option<RefreshToken> {
+        self.connection
+            .query_row(
+                "SELECT id, session_id, user_id, token_hash, created_at, expires_at, used_at
+                FROM refresh_tokens WHERE token_hash = ?1",
+                params![token_hash],
+                |row| Ok(self.token_from_row(row)),
+            )
+            .optional()
+            .ok()??
+    }
+
+    fn save(&self, token: &RefreshToken) -> Result<(), RefreshTokenRepositoryError> {
+        self.connection
+            .execute(
+                "INSERT INTO refresh_tokens
+                (id, session_id, user_id, token_hash, created_at, expires_at, used_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    token.id.to_string(),
+                    token.session_id.to_string(),
+                    token.user_id.to_string(),
+                    token.token_hash,
+                    format_time(&token.created_at),
+                    format_time(&token.expires_at),
+                    token.used_at.as_ref().map(format_time),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| RefreshTokenRepositoryError::Save)
+    }
+
+    fn mark_used_if_unused(
+        &self,
+        token: &RefreshToken,
+    ) -> Result<bool, RefreshTokenRepositoryError> {
+        let used_at = token
+            .used_at
+            .as_ref()
+            .ok_or(RefreshTokenRepositoryError::Update)?;
+
+        let updated = self
+            .connection
+            .execute(
+                "UPDATE refresh_tokens SET used_at = ?2 WHERE id = ?1 AND used_at IS NULL",
+                params![token.id.to_string(), format_time(used_at)],
+            )
+            .map_err(|_| RefreshTokenRepositoryError::Update)?;
+
+        Ok(updated == 1)
+    }
+
+    fn delete_sessions_tokens(&self, session_id: &Id) -> Result<(), RefreshTokenRepositoryError> {
+        self.connection
+            .execute(
+                "DELETE FROM refresh_tokens WHERE session_id = ?1",
+                params![session_id.to_string()],
+            )
+            .map(|_| ())
+            .map_err(|_| RefreshTokenRepositoryError::Delete)
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn RefreshTokenRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::refresh_token_repository::SqliteRefreshTokenRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+    use argentum_user_account_business::conformance::refresh_token_repository::check;
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &SqliteRefreshTokenRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file_with_unique_ids() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+            let id_factory = UniqueIdFactory::new();
+
+            test(
+                &SqliteRefreshTokenRepository::new(&db, &id_factory),
+                &id_factory,
+            )
+        })
+    }
+}
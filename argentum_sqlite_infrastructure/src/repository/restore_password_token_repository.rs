@@ -0,0 +1,163 @@
+use crate::connection::SqliteConnection;
+use crate::time::{format_time, parse_time};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_account_business::entity::restore_password_token::RestorePasswordToken;
+use argentum_user_account_business::repository::restore_password_token_repository::{
+    RestorePasswordTokenRepositoryError, RestorePasswordTokenRepositoryTrait,
+};
+use rusqlite::{params, OptionalExtension, Row};
+
+pub struct SqliteRestorePasswordTokenRepository<'s> {
+    connection: &'s SqliteConnection,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> SqliteRestorePasswordTokenRepository<'s> {
+    pub fn new(
+        connection: &'s SqliteConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> SqliteRestorePasswordTokenRepository<'s> {
+        SqliteRestorePasswordTokenRepository {
+            connection,
+            id_factory,
+        }
+    }
+
+    fn token_from_row(&self, row: &Row) -> Option<RestorePasswordToken> {
+        let id: String = row.get(0).ok()?;
+        let user_id: String = row.get(1).ok()?;
+        let created_at: String = row.get(3).ok()?;
+
+        Some(RestorePasswordToken {
+            id: self.id_factory.parse(&id).ok()?,
+            user_id: self.id_factory.parse(&user_id).ok()?,
+            token_hash: row.get(2).ok()?,
+            created_at: parse_time(&created_at)?,
+        })
+    }
+
+    fn find_one(
+        &self,
+        condition: &str,
+        value: &str,
+    ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
+        let token = self
+            .connection
+            .query_row(
+                format!(
+                    "SELECT id, user_id, token_hash, created_at FROM restore_password_tokens
+                    WHERE {}",
+                    condition
+                )
+                .as_str(),
+                params![value],
+                |row| Ok(self.token_from_row(row)),
+            )
+            .optional()
+            // The trait has no error for reading
+            .map_err(|_| RestorePasswordTokenRepositoryError::Save)?;
+
+        Ok(token.flatten())
+    }
+}
+
+impl RestorePasswordTokenRepositoryTrait for SqliteRestorePasswordTokenRepository<'_> {
+    fn find(
+        &self,
+        id: &Id,
+    ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
+        self.find_one("id = ?1", &id.to_string())
+    }
+
+    fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
+        self.find_one("token_hash = ?1", token_hash)
+    }
+
+    fn save(
+        &self,
+        token: &RestorePasswordToken,
+    ) -> Result<(), RestorePasswordTokenRepositoryError> {
+        self.connection
+            .execute(
+                "INSERT INTO restore_password_tokens (id, user_id, token_hash, created_at)
+                VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    token.id.to_string(),
+                    token.user_id.to_string(),
+                    token.token_hash,
+                    format_time(&token.created_at),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| RestorePasswordTokenRepositoryError::Save)
+    }
+
+    fn delete_users_tokens(&self, user_id: &Id) -> Result<(), RestorePasswordTokenRepositoryError> {
+        self.connection
+            .execute(
+                "DELETE FROM restore_password_tokens WHERE user_id = ?1",
+                params![user_id.to_string()],
+            )
+            .map(|_| ())
+            .map_err(|_| RestorePasswordTokenRepositoryError::Delete)
+    }
+
+    fn delete_if_exists(&self, id: &Id) -> Result<bool, RestorePasswordTokenRepositoryError> {
+        self.connection
+            .execute(
+                "DELETE FROM restore_password_tokens WHERE id = ?1",
+                params![id.to_string()],
+            )
+            .map(|deleted| deleted > 0)
+            .map_err(|_| RestorePasswordTokenRepositoryError::Delete)
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn RestorePasswordTokenRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::restore_password_token_repository::SqliteRestorePasswordTokenRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+    use argentum_user_account_business::conformance::restore_password_token_repository::check;
+    use argentum_user_account_business::conformance::unsaved_user;
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(
+                &SqliteRestorePasswordTokenRepository::new(&db, &id_factory),
+                &id_factory,
+                &unsaved_user,
+            )
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file_with_unique_ids() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+            let id_factory = UniqueIdFactory::new();
+
+            test(
+                &SqliteRestorePasswordTokenRepository::new(&db, &id_factory),
+                &id_factory,
+                &unsaved_user,
+            )
+        })
+    }
+}
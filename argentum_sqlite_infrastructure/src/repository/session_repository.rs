@@ -0,0 +1,177 @@
+use crate::connection::SqliteConnection;
+use crate::time::{format_time, parse_time};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_account_business::entity::session::Session;
+use argentum_user_account_business::repository::session_repository::{
+    SessionRepositoryError, SessionRepositoryTrait,
+};
+use rusqlite::{params, Row};
+
+const COLUMNS: &str = "id, user_id, token_hash, created_at, last_seen_at, expires_at";
+
+pub struct SqliteSessionRepository<'s> {
+    connection: &'s SqliteConnection,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> SqliteSessionRepository<'s> {
+    pub fn new(
+        connection: &'s SqliteConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> SqliteSessionRepository<'s> {
+        SqliteSessionRepository {
+            connection,
+            id_factory,
+        }
+    }
+
+    fn session_from_row(&self, row: &Row) -> Option<Session> {
+        let id: String = row.get(0).ok()?;
+        let user_id: String = row.get(1).ok()?;
+        let created_at: String = row.get(3).ok()?;
+        let last_seen_at: String = row.get(4).ok()?;
+        let expires_at: String = row.get(5).ok()?;
+
+        Some(Session {
+            id: self.id_factory.parse(&id).ok()?,
+            user_id: self.id_factory.parse(&user_id).ok()?,
+            token_hash: row.get(2).ok()?,
+            created_at: parse_time(&created_at)?,
+            last_seen_at: parse_time(&last_seen_at)?,
+            expires_at: parse_time(&expires_at)?,
+        })
+    }
+
+    fn find_all(
+        &self,
+        condition: &str,
+        value: &str,
+    ) -> Result<Vec<Session>, SessionRepositoryError> {
+        let connection = self.connection.connection();
+        let mut statement = connection
+            .prepare(format!("SELECT {} FROM sessions WHERE {}", COLUMNS, condition).as_str())
+            .map_err(|_| SessionRepositoryError::Find)?;
+        let mut rows = statement
+            .query(params![value])
+            .map_err(|_| SessionRepositoryError::Find)?;
+
+        let mut sessions = Vec::new();
+
+        while let Some(row) = rows.next().map_err(|_| SessionRepositoryError::Find)? {
+            if let Some(session) = self.session_from_row(row) {
+                sessions.push(session);
+            }
+        }
+
+        Ok(sessions)
+    }
+
+    fn delete_where(&self, condition: &str, value: &str) -> Result<(), SessionRepositoryError> {
+        self.connection
+            .execute(
+                format!("DELETE FROM sessions WHERE {}", condition).as_str(),
+                params![value],
+            )
+            .map(|_| ())
+            .map_err(|_| SessionRepositoryError::Delete)
+    }
+}
+
+impl SessionRepositoryTrait for SqliteSessionRepository<'_> {
+    fn find(&self, id: &Id) -> Option<Session> {
+        self.find_all("id = ?1", &id.to_string()).ok()?.pop()
+    }
+
+    /// The hash is looked up by the unique index, there is no secret to compare in constant time
+    fn find_by_token_hash(&self, token_hash: &str) -> Option<Session> {
+        self.find_all("token_hash = ?1", token_hash).ok()?.pop()
+    }
+
+    fn find_by_user_id(&self, user_id: &Id) -> Result<Vec<Session>, SessionRepositoryError> {
+        self.find_all("user_id = ?1", &user_id.to_string())
+    }
+
+    fn save(&self, session: &Session) -> Result<(), SessionRepositoryError> {
+        self.connection
+            .execute(
+                "INSERT INTO sessions (id, user_id, token_hash, created_at, last_seen_at, expires_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    session.id.to_string(),
+                    session.user_id.to_string(),
+                    session.token_hash,
+                    format_time(&session.created_at),
+                    format_time(&session.last_seen_at),
+                    format_time(&session.expires_at),
+                ],
+            )
+            .map(|_| ())
+            .map_err(|_| SessionRepositoryError::Save)
+    }
+
+    fn update(&self, session: &Session) -> Result<(), SessionRepositoryError> {
+        let updated = self
+            .connection
+            .execute(
+                "UPDATE sessions SET token_hash = ?2, last_seen_at = ?3, expires_at = ?4
+                WHERE id = ?1",
+                params![
+                    session.id.to_string(),
+                    session.token_hash,
+                    format_time(&session.last_seen_at),
+                    format_time(&session.expires_at),
+                ],
+            )
+            .map_err(|_| SessionRepositoryError::Update)?;
+
+        match updated {
+            1 => Ok(()),
+            _ => Err(SessionRepositoryError::Update),
+        }
+    }
+
+    fn delete(&self, id: &Id) -> Result<(), SessionRepositoryError> {
+        self.delete_where("id = ?1", &id.to_string())
+    }
+
+    fn delete_users_sessions(&self, user_id: &Id) -> Result<(), SessionRepositoryError> {
+        self.delete_where("user_id = ?1", &user_id.to_string())
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn SessionRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::SqliteConnection;
+    use crate::repository::session_repository::SqliteSessionRepository;
+    use crate::test_db;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+    use argentum_user_account_business::conformance::session_repository::check;
+
+    #[test]
+    fn test_conformance_in_memory() -> Result<(), ConformanceError> {
+        check(|test| {
+            let db = test_db::in_memory();
+            let id_factory = IdFactoryMock::new();
+
+            test(&SqliteSessionRepository::new(&db, &id_factory), &id_factory)
+        })
+    }
+
+    #[test]
+    fn test_conformance_in_file_with_unique_ids() -> Result<(), ConformanceError> {
+        check(|test| {
+            let path = test_db::temp_path();
+            let db = SqliteConnection::open(path.path()).map_err(|_| "Can't open file")?;
+            let id_factory = UniqueIdFactory::new();
+
+            test(&SqliteSessionRepository::new(&db, &id_factory), &id_factory)
+        })
+    }
+}
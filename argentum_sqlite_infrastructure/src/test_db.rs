@@ -0,0 +1,32 @@
+use crate::connection::SqliteConnection;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+pub fn in_memory() -> SqliteConnection {
+    SqliteConnection::open_in_memory().expect("Can't open in-memory database")
+}
+
+/// A database file name in the temp dir, the file is removed with the value
+pub struct TempPath(PathBuf);
+
+impl TempPath {
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+pub fn temp_path() -> TempPath {
+    TempPath(std::env::temp_dir().join(format!(
+        "argentum_test_{}_{}.sqlite",
+        std::process::id(),
+        FILE_COUNTER.fetch_add(1, Ordering::SeqCst)
+    )))
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// SQLite has no time type. RFC 3339 text with nanoseconds keeps the value exact
+/// and sorts the same way as the time itself
+pub(crate) fn format_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+pub(crate) fn parse_time(time: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::time::{format_time, parse_time};
+    use chrono::Utc;
+
+    #[test]
+    fn test_time_round_trip() {
+        let now = Utc::now();
+
+        assert_eq!(Some(now), parse_time(&format_time(&now)));
+        assert_eq!(None, parse_time("yesterday"));
+    }
+}
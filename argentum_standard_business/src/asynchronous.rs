@@ -0,0 +1,226 @@
+//! Building blocks of async counterparts of repository and service traits. Async traits
+//! return boxed futures, so they stay object safe and are used as `dyn Trait` the same
+//! way synchronous ones are
+use crate::transaction::Transaction;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A future which is already resolved with `value`
+pub fn ready<'a, T: Send + 'a>(value: T) -> BoxFuture<'a, T> {
+    Box::pin(std::future::ready(value))
+}
+
+/// Serves a synchronous implementation through the async trait. Calls are made in place
+/// one at a time, so it fits in-memory storages and tests. Anything doing I/O or heavy
+/// computations should implement the async trait itself
+pub struct Blocking<T> {
+    inner: Arc<Mutex<T>>,
+    transaction: Option<Transaction>,
+}
+
+impl<T> Blocking<T> {
+    pub fn new(inner: T) -> Blocking<T> {
+        Blocking {
+            inner: Arc::new(Mutex::new(inner)),
+            transaction: None,
+        }
+    }
+
+    /// A panic in another call doesn't make the wrapped implementation unusable
+    pub fn inner(&self) -> MutexGuard<'_, T> {
+        lock(&self.inner)
+    }
+
+    /// The same implementation, repository traits call it within `transaction`
+    pub fn bind(&self, transaction: &Transaction) -> Blocking<T> {
+        Blocking {
+            inner: self.inner.clone(),
+            transaction: Some(transaction.clone()),
+        }
+    }
+
+    pub fn transaction(&self) -> Option<&Transaction> {
+        self.transaction.as_ref()
+    }
+}
+
+/// Runs blocking jobs away from the async executor, e.g. on the blocking thread pool
+/// of the runtime
+pub trait BlockingExecutorTrait: Send + Sync {
+    fn execute(&self, job: Box<dyn FnOnce() + Send>);
+}
+
+/// Serves a synchronous implementation through the async trait like `Blocking` does, but
+/// the calls are run by `executor`. It fits slow implementations: password hashing,
+/// HTTP requests, SMTP. Calls aren't serialized, so the implementation must be `Sync`
+pub struct Offloaded<T> {
+    inner: Arc<T>,
+    executor: Arc<dyn BlockingExecutorTrait>,
+}
+
+impl<T: Send + Sync + 'static> Offloaded<T> {
+    pub fn new(inner: T, executor: Arc<dyn BlockingExecutorTrait>) -> Offloaded<T> {
+        Offloaded {
+            inner: Arc::new(inner),
+            executor,
+        }
+    }
+
+    /// Cheap calls which don't block may be made in place
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Resolves with `None` if the executor drops the job without finishing it,
+    /// e.g. when the call panics or the runtime shuts down
+    pub fn run<R, C>(&self, call: C) -> BoxFuture<'static, Option<R>>
+    where
+        R: Send + 'static,
+        C: FnOnce(&T) -> R + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        let slot = Arc::new(Mutex::new(Slot {
+            value: None,
+            closed: false,
+            waker: None,
+        }));
+        let sender = Sender(Arc::clone(&slot));
+
+        self.executor
+            .execute(Box::new(move || sender.send(call(&inner))));
+
+        Box::pin(Receiver(slot))
+    }
+}
+
+struct Slot<R> {
+    value: Option<R>,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+struct Sender<R>(Arc<Mutex<Slot<R>>>);
+
+impl<R> Sender<R> {
+    fn send(self, value: R) {
+        lock(&self.0).value = Some(value);
+    }
+}
+
+/// Closes the slot whether the value is sent or the job is dropped, so the receiver
+/// never waits forever
+impl<R> Drop for Sender<R> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut slot = lock(&self.0);
+            slot.closed = true;
+            slot.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+struct Receiver<R>(Arc<Mutex<Slot<R>>>);
+
+impl<R> Future for Receiver<R> {
+    type Output = Option<R>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<R>> {
+        let mut slot = lock(&self.0);
+
+        if slot.closed {
+            return Poll::Ready(slot.value.take());
+        }
+
+        slot.waker = Some(context.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::asynchronous::{ready, Blocking, BlockingExecutorTrait, Offloaded};
+    use crate::mock::executor::{block_on, InlineExecutorMock};
+    use crate::transaction::Transaction;
+    use std::cell::RefCell;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_ready_future_resolves() {
+        assert_eq!(42, block_on(ready(42)));
+    }
+
+    #[test]
+    fn test_blocking_gives_access_to_inner() {
+        let blocking = Blocking::new(RefCell::new(1));
+
+        *blocking.inner().borrow_mut() += 1;
+
+        assert_eq!(2, *blocking.inner().borrow());
+    }
+
+    #[test]
+    fn test_bound_blocking_shares_inner() {
+        let blocking = Blocking::new(RefCell::new(1));
+        let bound = blocking.bind(&Transaction::new(7));
+
+        *bound.inner().borrow_mut() += 1;
+
+        assert_eq!(2, *blocking.inner().borrow());
+        assert_eq!(None, blocking.transaction());
+        assert_eq!(Some(&Transaction::new(7)), bound.transaction());
+    }
+
+    #[test]
+    fn test_offloaded_call_resolves() {
+        let offloaded = Offloaded::new(20, Arc::new(InlineExecutorMock::new()));
+
+        assert_eq!(Some(42), block_on(offloaded.run(|value| value + 22)));
+        assert_eq!(20, *offloaded.inner());
+    }
+
+    struct ThreadExecutor;
+
+    impl BlockingExecutorTrait for ThreadExecutor {
+        fn execute(&self, job: Box<dyn FnOnce() + Send>) {
+            thread::spawn(job);
+        }
+    }
+
+    #[test]
+    fn test_offloaded_call_resolves_from_another_thread() {
+        let offloaded = Offloaded::new(thread::current().id(), Arc::new(ThreadExecutor));
+
+        let called_from = block_on(offloaded.run(|_| thread::current().id()));
+
+        assert_ne!(Some(thread::current().id()), called_from);
+    }
+
+    struct DroppingExecutor;
+
+    impl BlockingExecutorTrait for DroppingExecutor {
+        fn execute(&self, job: Box<dyn FnOnce() + Send>) {
+            drop(job);
+        }
+    }
+
+    #[test]
+    fn test_dropped_offloaded_call_resolves_with_none() {
+        let offloaded = Offloaded::new(1, Arc::new(DroppingExecutor));
+
+        assert_eq!(None, block_on(offloaded.run(|value| value + 1)));
+    }
+}
@@ -0,0 +1,81 @@
+//! Runs the contract of a repository trait against an implementation. Business packages
+//! describe the contract as scenarios, mocks and database adapters run all of them
+use chrono::{DateTime, Utc};
+
+#[derive(thiserror::Error, Debug)]
+#[error("{scenario}: {reason}")]
+pub struct ConformanceError {
+    pub scenario: &'static str,
+    pub reason: &'static str,
+}
+
+/// Runs every scenario, the first failure is returned with the name of its scenario.
+/// A scenario gets an empty repository from `run_scenario`, which creates the repository
+/// and its storage for every scenario, so they don't see data of each other
+pub fn run<S>(
+    scenarios: &[(&'static str, S)],
+    run_scenario: impl Fn(&S) -> Result<(), &'static str>,
+) -> Result<(), ConformanceError> {
+    for (scenario, check) in scenarios {
+        run_scenario(check).map_err(|reason| ConformanceError { scenario, reason })?;
+    }
+
+    Ok(())
+}
+
+/// Fails the scenario with `reason` unless `condition` holds
+pub fn ensure(condition: bool, reason: &'static str) -> Result<(), &'static str> {
+    match condition {
+        true => Ok(()),
+        false => Err(reason),
+    }
+}
+
+/// Storages keep time to a microsecond, more precise parts may be lost
+pub fn same_time(a: &DateTime<Utc>, b: &DateTime<Utc>) -> bool {
+    a.timestamp() == b.timestamp() && a.timestamp_subsec_micros() == b.timestamp_subsec_micros()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::{ensure, run};
+    use std::cell::RefCell;
+
+    type Scenario = fn(&RefCell<u32>) -> Result<(), &'static str>;
+
+    fn starts_from_zero(counter: &RefCell<u32>) -> Result<(), &'static str> {
+        ensure(0 == *counter.borrow(), "Counter should start from zero")
+    }
+
+    fn counts_up(counter: &RefCell<u32>) -> Result<(), &'static str> {
+        *counter.borrow_mut() += 1;
+
+        ensure(1 == *counter.borrow(), "Counter should count up")
+    }
+
+    const SCENARIOS: &[(&str, Scenario)] = &[
+        ("starts_from_zero", starts_from_zero),
+        ("counts_up", counts_up),
+        ("counts_up_again", counts_up),
+    ];
+
+    #[test]
+    fn test_every_scenario_gets_fresh_repository() {
+        let result = run(SCENARIOS, |scenario| scenario(&RefCell::new(0)));
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_failed_scenario_is_named() {
+        let shared = RefCell::new(0);
+
+        let error = run(SCENARIOS, |scenario| scenario(&shared)).unwrap_err();
+
+        assert_eq!("counts_up_again", error.scenario);
+        assert_eq!(
+            "counts_up_again: Counter should count up",
+            error.to_string()
+        );
+    }
+}
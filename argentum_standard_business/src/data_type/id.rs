@@ -5,7 +5,8 @@ pub trait Printable {
     fn to_string(&self) -> String;
 }
 
-pub trait IdTrait: Any + Printable {
+/// Ids are plain values, so they are shared across threads freely
+pub trait IdTrait: Any + Printable + Send + Sync {
     fn id_eq(&self, other: &dyn Any) -> bool;
     //Convert to Any for comparison
     fn as_any(&self) -> &dyn Any;
@@ -37,8 +38,13 @@ impl Clone for Id {
 
 pub trait IdFactory {
     fn create(&self) -> Id;
+    fn parse(&self, value: &str) -> Result<Id, WrongIdError>;
 }
 
+#[derive(thiserror::Error, Debug)]
+#[error("Wrong id: {0}")]
+pub struct WrongIdError(pub String);
+
 pub struct IntId {
     value: u64,
 }
@@ -47,7 +53,7 @@ impl IdTrait for IntId {
     fn id_eq(&self, other: &dyn Any) -> bool {
         other
             .downcast_ref::<Self>()
-            .map_or(false, |id| id.value == self.value)
+            .is_some_and(|id| id.value == self.value)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1,2 +1,5 @@
+pub mod asynchronous;
+pub mod conformance;
 pub mod data_type;
 pub mod mock;
+pub mod transaction;
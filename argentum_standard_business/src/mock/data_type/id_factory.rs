@@ -1,4 +1,4 @@
-use crate::data_type::id::{Id, IdFactory, IntId};
+use crate::data_type::id::{Id, IdFactory, IntId, WrongIdError};
 use rand::Rng;
 
 pub struct IdFactoryMock {}
@@ -22,4 +22,11 @@ impl IdFactory for IdFactoryMock {
 
         Box::new(IntId::new(id))
     }
+
+    fn parse(&self, value: &str) -> Result<Id, WrongIdError> {
+        match value.parse() {
+            Ok(id) => Ok(Box::new(IntId::new(id))),
+            Err(_) => Err(WrongIdError(value.to_string())),
+        }
+    }
 }
@@ -0,0 +1,49 @@
+use crate::asynchronous::BlockingExecutorTrait;
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs a future to completion on the current thread. Business packages don't depend
+/// on a runtime, tests of async use cases run with it
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Runs blocking jobs in place, so `Offloaded` implementations may be tested without a runtime
+pub struct InlineExecutorMock {}
+
+impl InlineExecutorMock {
+    pub fn new() -> InlineExecutorMock {
+        InlineExecutorMock {}
+    }
+}
+
+impl Default for InlineExecutorMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockingExecutorTrait for InlineExecutorMock {
+    fn execute(&self, job: Box<dyn FnOnce() + Send>) {
+        job()
+    }
+}
@@ -1 +1,3 @@
 pub mod data_type;
+pub mod executor;
+pub mod transaction;
@@ -0,0 +1,87 @@
+use crate::transaction::{Transaction, TransactionError, TransactionManagerTrait};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Records calls instead of running transactions
+pub struct TransactionManagerMock {
+    journal: RwLock<Vec<&'static str>>,
+    next_id: AtomicU64,
+}
+
+impl TransactionManagerMock {
+    pub fn new() -> TransactionManagerMock {
+        TransactionManagerMock {
+            journal: RwLock::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// "begin", "commit" and "rollback" in order of calls
+    pub fn journal(&self) -> Vec<&'static str> {
+        self.journal.read().unwrap().clone()
+    }
+
+    pub fn committed(&self) -> bool {
+        self.journal.read().unwrap().last() == Some(&"commit")
+    }
+
+    pub fn rolled_back(&self) -> bool {
+        self.journal.read().unwrap().last() == Some(&"rollback")
+    }
+}
+
+impl Default for TransactionManagerMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionManagerTrait for TransactionManagerMock {
+    fn begin(&self) -> Result<Transaction, TransactionError> {
+        self.journal.write().unwrap().push("begin");
+
+        Ok(Transaction::new(
+            self.next_id.fetch_add(1, Ordering::Relaxed),
+        ))
+    }
+
+    fn commit(&self, _: Transaction) -> Result<(), TransactionError> {
+        self.journal.write().unwrap().push("commit");
+
+        Ok(())
+    }
+
+    fn rollback(&self, _: Transaction) -> Result<(), TransactionError> {
+        self.journal.write().unwrap().push("rollback");
+
+        Ok(())
+    }
+}
+
+pub struct TransactionManagerMockWithBrokenCommit {}
+
+impl TransactionManagerMockWithBrokenCommit {
+    pub fn new() -> TransactionManagerMockWithBrokenCommit {
+        TransactionManagerMockWithBrokenCommit {}
+    }
+}
+
+impl Default for TransactionManagerMockWithBrokenCommit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionManagerTrait for TransactionManagerMockWithBrokenCommit {
+    fn begin(&self) -> Result<Transaction, TransactionError> {
+        Ok(Transaction::new(1))
+    }
+
+    fn commit(&self, _: Transaction) -> Result<(), TransactionError> {
+        Err(TransactionError::Commit)
+    }
+
+    fn rollback(&self, _: Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
+}
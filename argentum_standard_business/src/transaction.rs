@@ -0,0 +1,207 @@
+use crate::asynchronous::{ready, Blocking, BoxFuture};
+use std::future::Future;
+use std::ops::Deref;
+
+/// Handle of a unit of work. Repositories write in it through their `within` method,
+/// so use cases running at the same time don't get writes of each other
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Transaction {
+    id: u64,
+}
+
+impl Transaction {
+    pub fn new(id: u64) -> Transaction {
+        Transaction { id }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+/// A repository working within a transaction. Storages keeping the state of a transaction
+/// themselves give out the same repository, others a new one bound to the transaction
+pub enum Bound<'a, T: ?Sized> {
+    Shared(&'a T),
+    Owned(Box<T>),
+}
+
+impl<T: ?Sized> Deref for Bound<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Bound::Shared(shared) => shared,
+            Bound::Owned(owned) => owned,
+        }
+    }
+}
+
+/// Begins units of work. Writes made within a transaction are applied together
+/// on `commit` or not at all
+pub trait TransactionManagerTrait {
+    fn begin(&self) -> Result<Transaction, TransactionError>;
+    fn commit(&self, transaction: Transaction) -> Result<(), TransactionError>;
+    fn rollback(&self, transaction: Transaction) -> Result<(), TransactionError>;
+}
+
+/// Async counterpart of `TransactionManagerTrait`
+pub trait AsyncTransactionManagerTrait: Send + Sync {
+    fn begin(&self) -> BoxFuture<'_, Result<Transaction, TransactionError>>;
+    fn commit(&self, transaction: Transaction) -> BoxFuture<'_, Result<(), TransactionError>>;
+    fn rollback(&self, transaction: Transaction) -> BoxFuture<'_, Result<(), TransactionError>>;
+}
+
+impl<T: TransactionManagerTrait + Send> AsyncTransactionManagerTrait for Blocking<T> {
+    fn begin(&self) -> BoxFuture<'_, Result<Transaction, TransactionError>> {
+        ready(self.inner().begin())
+    }
+
+    fn commit(&self, transaction: Transaction) -> BoxFuture<'_, Result<(), TransactionError>> {
+        ready(self.inner().commit(transaction))
+    }
+
+    fn rollback(&self, transaction: Transaction) -> BoxFuture<'_, Result<(), TransactionError>> {
+        ready(self.inner().rollback(transaction))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TransactionError {
+    #[error("Can't begin transaction")]
+    Begin,
+
+    #[error("Can't commit transaction")]
+    Commit,
+
+    #[error("Can't rollback transaction")]
+    Rollback,
+}
+
+/// Runs `work` inside a transaction: commits when it succeeds and rolls back when it fails.
+/// The error of `work` is returned even if the rollback fails too
+pub fn in_transaction<T, E, F>(manager: &dyn TransactionManagerTrait, work: F) -> Result<T, E>
+where
+    E: From<TransactionError>,
+    F: FnOnce(&Transaction) -> Result<T, E>,
+{
+    let transaction = manager.begin()?;
+
+    match work(&transaction) {
+        Ok(result) => {
+            manager.commit(transaction)?;
+
+            Ok(result)
+        }
+        Err(e) => {
+            let _ = manager.rollback(transaction);
+
+            Err(e)
+        }
+    }
+}
+
+/// Async counterpart of `in_transaction`. `work` gets its own copy of the handle
+pub async fn in_async_transaction<T, E, F, W>(
+    manager: &dyn AsyncTransactionManagerTrait,
+    work: W,
+) -> Result<T, E>
+where
+    E: From<TransactionError>,
+    F: Future<Output = Result<T, E>>,
+    W: FnOnce(Transaction) -> F,
+{
+    let transaction = manager.begin().await?;
+
+    match work(transaction.clone()).await {
+        Ok(result) => {
+            manager.commit(transaction).await?;
+
+            Ok(result)
+        }
+        Err(e) => {
+            let _ = manager.rollback(transaction).await;
+
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::asynchronous::Blocking;
+    use crate::mock::executor::block_on;
+    use crate::mock::transaction::{
+        TransactionManagerMock, TransactionManagerMockWithBrokenCommit,
+    };
+    use crate::transaction::{in_async_transaction, in_transaction, TransactionError};
+
+    #[derive(Debug)]
+    enum WorkError {
+        Failed,
+        Transaction,
+    }
+
+    impl From<TransactionError> for WorkError {
+        fn from(_: TransactionError) -> Self {
+            WorkError::Transaction
+        }
+    }
+
+    #[test]
+    fn test_successful_work_is_committed() {
+        let manager = TransactionManagerMock::new();
+
+        let result: Result<u8, WorkError> = in_transaction(&manager, |_| Ok(42));
+
+        assert_eq!(42, result.unwrap());
+        assert_eq!(vec!["begin", "commit"], manager.journal());
+    }
+
+    #[test]
+    fn test_failed_work_is_rolled_back() {
+        let manager = TransactionManagerMock::new();
+
+        let result: Result<u8, WorkError> = in_transaction(&manager, |_| Err(WorkError::Failed));
+
+        assert!(matches!(result, Err(WorkError::Failed)));
+        assert_eq!(vec!["begin", "rollback"], manager.journal());
+    }
+
+    #[test]
+    fn test_failed_commit_is_returned() {
+        let manager = TransactionManagerMockWithBrokenCommit::new();
+
+        let result: Result<u8, WorkError> = in_transaction(&manager, |_| Ok(42));
+
+        assert!(matches!(result, Err(WorkError::Transaction)));
+    }
+
+    #[test]
+    fn test_async_work_is_committed_or_rolled_back() {
+        let manager = Blocking::new(TransactionManagerMock::new());
+
+        let succeeded: Result<u8, WorkError> =
+            block_on(in_async_transaction(&manager, |_| async { Ok(42) }));
+        let failed: Result<u8, WorkError> = block_on(in_async_transaction(&manager, |_| async {
+            Err(WorkError::Failed)
+        }));
+
+        assert_eq!(42, succeeded.unwrap());
+        assert!(matches!(failed, Err(WorkError::Failed)));
+        assert_eq!(
+            vec!["begin", "commit", "begin", "rollback"],
+            manager.inner().journal()
+        );
+    }
+
+    #[test]
+    fn test_work_gets_its_own_transaction() {
+        let manager = TransactionManagerMock::new();
+
+        let first: Result<u64, WorkError> = in_transaction(&manager, |t| Ok(t.id()));
+        let second: Result<u64, WorkError> = in_transaction(&manager, |t| Ok(t.id()));
+
+        assert_ne!(first.unwrap(), second.unwrap());
+    }
+}
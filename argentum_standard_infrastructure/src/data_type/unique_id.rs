@@ -1,4 +1,4 @@
-use argentum_standard_business::data_type::id::{Id, IdFactory, IdTrait, Printable};
+use argentum_standard_business::data_type::id::{Id, IdFactory, IdTrait, Printable, WrongIdError};
 use std::any::Any;
 use uuid::Uuid;
 
@@ -11,7 +11,7 @@ impl IdTrait for UniqueId {
     fn id_eq(&self, other: &dyn Any) -> bool {
         other
             .downcast_ref::<Self>()
-            .map_or(false, |id| id.value == self.value)
+            .is_some_and(|id| id.value == self.value)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -52,6 +52,13 @@ impl IdFactory for UniqueIdFactory {
     fn create(&self) -> Id {
         Box::new(UniqueId::new(Uuid::new_v4()))
     }
+
+    fn parse(&self, value: &str) -> Result<Id, WrongIdError> {
+        match Uuid::parse_str(value) {
+            Ok(uuid) => Ok(Box::new(UniqueId::new(uuid))),
+            Err(_) => Err(WrongIdError(value.to_string())),
+        }
+    }
 }
 
 pub fn new_unique_id() -> Id {
@@ -106,4 +113,13 @@ mod tests {
 
         assert_eq!(id.to_string().len(), 36)
     }
+
+    #[test]
+    fn test_parse_unique_id() {
+        let f = UniqueIdFactory {};
+        let id = f.create();
+
+        assert!(f.parse(&id.to_string()).unwrap() == id);
+        assert!(f.parse("not-an-uuid").is_err());
+    }
 }
@@ -0,0 +1,54 @@
+use argentum_standard_business::asynchronous::BlockingExecutorTrait;
+use tokio::runtime::Handle;
+
+/// Runs blocking jobs of `Offloaded` implementations on the blocking thread pool of tokio
+pub struct TokioBlockingExecutor {
+    handle: Handle,
+}
+
+impl TokioBlockingExecutor {
+    pub fn new(handle: Handle) -> TokioBlockingExecutor {
+        TokioBlockingExecutor { handle }
+    }
+
+    /// Panics outside of a tokio runtime
+    pub fn current() -> TokioBlockingExecutor {
+        TokioBlockingExecutor::new(Handle::current())
+    }
+}
+
+impl BlockingExecutorTrait for TokioBlockingExecutor {
+    fn execute(&self, job: Box<dyn FnOnce() + Send>) {
+        self.handle.spawn_blocking(job);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::executor::TokioBlockingExecutor;
+    use argentum_standard_business::asynchronous::Offloaded;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_offloaded_call_runs_on_blocking_pool() {
+        let offloaded = Offloaded::new(
+            thread::current().id(),
+            Arc::new(TokioBlockingExecutor::current()),
+        );
+
+        let called_from = offloaded.run(|_| thread::current().id()).await;
+
+        assert!(called_from.is_some());
+        assert_ne!(Some(thread::current().id()), called_from);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_panicked_offloaded_call_resolves_with_none() {
+        let offloaded = Offloaded::new(1, Arc::new(TokioBlockingExecutor::current()));
+
+        let result: Option<i32> = offloaded.run(|_| panic!("Broken call")).await;
+
+        assert_eq!(None, result);
+    }
+}
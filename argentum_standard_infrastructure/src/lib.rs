@@ -1 +1,2 @@
 pub mod data_type;
+pub mod executor;
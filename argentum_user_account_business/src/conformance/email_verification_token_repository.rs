@@ -0,0 +1,155 @@
+use crate::entity::email_verification_token::EmailVerificationToken;
+use crate::repository::email_verification_token_repository::EmailVerificationTokenRepositoryTrait;
+use argentum_standard_business::conformance::{ensure, run, same_time, ConformanceError};
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+
+pub type Scenario =
+    fn(&dyn EmailVerificationTokenRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub type Test<'t> = &'t dyn Fn(
+    &dyn EmailVerificationTokenRepositoryTrait,
+    &dyn IdFactory,
+) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_token", saves_and_finds_token),
+    (
+        "finds_nothing_for_unknown_hash",
+        finds_nothing_for_unknown_hash,
+    ),
+    (
+        "rejects_saving_same_token_twice",
+        rejects_saving_same_token_twice,
+    ),
+    ("deletes_every_token_of_user", deletes_every_token_of_user),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+fn stub_token(
+    id_factory: &dyn IdFactory,
+    user_id: &Id,
+    token_hash: &str,
+) -> EmailVerificationToken {
+    EmailVerificationToken::new(
+        id_factory.create(),
+        user_id.clone(),
+        EmailAddress::new("dionne@example.com".to_string()).unwrap(),
+        token_hash.to_string(),
+    )
+}
+
+fn saves_and_finds_token(
+    repository: &dyn EmailVerificationTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let token = stub_token(id_factory, &id_factory.create(), "a");
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    let found = repository
+        .find_by_token_hash("a")
+        .map_err(|_| "Can't find token")?
+        .ok_or("Saved token is not found by hash")?;
+
+    ensure(*token.id == *found.id, "Id is changed")?;
+    ensure(*token.user_id == *found.user_id, "User id is changed")?;
+    ensure(token.email == found.email, "Email is changed")?;
+    ensure(
+        same_time(&token.created_at, &found.created_at),
+        "Creation time is changed",
+    )
+}
+
+fn finds_nothing_for_unknown_hash(
+    repository: &dyn EmailVerificationTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let token = stub_token(id_factory, &id_factory.create(), "a");
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    ensure(
+        repository
+            .find_by_token_hash("b")
+            .map_err(|_| "Can't find token")?
+            .is_none(),
+        "Token is found by unknown hash",
+    )
+}
+
+fn rejects_saving_same_token_twice(
+    repository: &dyn EmailVerificationTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let token = stub_token(id_factory, &id_factory.create(), "a");
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    ensure(
+        repository.save(&token).is_err(),
+        "Token id should be unique",
+    )
+}
+
+fn deletes_every_token_of_user(
+    repository: &dyn EmailVerificationTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user_id = id_factory.create();
+
+    for (user_id, hash) in [
+        (&user_id, "a"),
+        (&user_id, "b"),
+        (&id_factory.create(), "c"),
+    ]
+    .iter()
+    {
+        repository
+            .save(&stub_token(id_factory, user_id, hash))
+            .map_err(|_| "Can't save token")?;
+    }
+
+    repository
+        .delete_users_tokens(&user_id)
+        .map_err(|_| "Can't delete tokens")?;
+
+    for hash in ["a", "b"].iter() {
+        ensure(
+            repository
+                .find_by_token_hash(hash)
+                .map_err(|_| "Can't find token")?
+                .is_none(),
+            "Token of user is not deleted",
+        )?;
+    }
+
+    ensure(
+        repository
+            .find_by_token_hash("c")
+            .map_err(|_| "Can't find token")?
+            .is_some(),
+        "Token of other user is deleted",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::email_verification_token_repository::check;
+    use crate::mock::repository::email_verification_token_repository_mock::EmailVerificationTokenRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| {
+            test(
+                &EmailVerificationTokenRepositoryMock::new(),
+                &IdFactoryMock::new(),
+            )
+        })
+    }
+}
@@ -0,0 +1,200 @@
+use crate::entity::login_attempts::FailedLoginAttempts;
+use crate::repository::login_attempt_repository::LoginAttemptRepositoryTrait;
+use argentum_standard_business::conformance::{ensure, run, same_time, ConformanceError};
+use argentum_standard_business::data_type::id::IdFactory;
+use chrono::{Duration, Utc};
+
+pub type Scenario =
+    fn(&dyn LoginAttemptRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub type Test<'t> =
+    &'t dyn Fn(&dyn LoginAttemptRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_attempts", saves_and_finds_attempts),
+    (
+        "finds_nothing_for_unknown_key",
+        finds_nothing_for_unknown_key,
+    ),
+    ("replaces_attempts_of_key", replaces_attempts_of_key),
+    ("deletes_attempts", deletes_attempts),
+    ("increments_attempts", increments_attempts),
+    (
+        "increment_starts_expired_attempts_over",
+        increment_starts_expired_attempts_over,
+    ),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+fn stub_attempts(key: &str, failures: u32) -> FailedLoginAttempts {
+    let mut attempts = FailedLoginAttempts::new(key.to_string());
+
+    for _ in 0..failures {
+        attempts.register_failure();
+    }
+
+    attempts
+}
+
+fn saves_and_finds_attempts(
+    repository: &dyn LoginAttemptRepositoryTrait,
+    _: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let attempts = stub_attempts("client", 1);
+    repository
+        .save(&attempts)
+        .map_err(|_| "Can't save attempts")?;
+
+    let found = repository
+        .find("client")
+        .map_err(|_| "Can't find attempts")?
+        .ok_or("Saved attempts are not found")?;
+
+    ensure("client" == found.key, "Key is changed")?;
+    ensure(1 == found.count, "Count is changed")?;
+    ensure(
+        same_time(&attempts.last_failed_at, &found.last_failed_at),
+        "Last failure time is changed",
+    )
+}
+
+fn finds_nothing_for_unknown_key(
+    repository: &dyn LoginAttemptRepositoryTrait,
+    _: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    repository
+        .save(&stub_attempts("client", 1))
+        .map_err(|_| "Can't save attempts")?;
+
+    ensure(
+        repository
+            .find("other")
+            .map_err(|_| "Can't find attempts")?
+            .is_none(),
+        "Attempts of unknown key are found",
+    )
+}
+
+/// Attempts of a key are saved after every failure
+fn replaces_attempts_of_key(
+    repository: &dyn LoginAttemptRepositoryTrait,
+    _: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    repository
+        .save(&stub_attempts("client", 1))
+        .map_err(|_| "Can't save attempts")?;
+    repository
+        .save(&stub_attempts("client", 2))
+        .map_err(|_| "Saving should replace attempts")?;
+
+    let found = repository
+        .find("client")
+        .map_err(|_| "Can't find attempts")?
+        .ok_or("Attempts are not found")?;
+
+    ensure(2 == found.count, "Attempts are not replaced")
+}
+
+/// Every failure is counted by one statement, concurrent failures are never lost
+fn increments_attempts(
+    repository: &dyn LoginAttemptRepositoryTrait,
+    _: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let expired_before = Utc::now() - Duration::minutes(15);
+
+    let first = repository
+        .increment("client", Utc::now(), expired_before)
+        .map_err(|_| "Can't increment missing attempts")?;
+    ensure(1 == first.count, "First failure is not counted")?;
+
+    let failed_at = Utc::now();
+    let second = repository
+        .increment("client", failed_at, expired_before)
+        .map_err(|_| "Can't increment attempts")?;
+    ensure(2 == second.count, "Returned count is not incremented")?;
+
+    let found = repository
+        .find("client")
+        .map_err(|_| "Can't find attempts")?
+        .ok_or("Incremented attempts are not found")?;
+
+    ensure(2 == found.count, "Stored count is not incremented")?;
+    ensure(
+        same_time(&failed_at, &found.last_failed_at),
+        "Last failure time is not updated",
+    )
+}
+
+fn increment_starts_expired_attempts_over(
+    repository: &dyn LoginAttemptRepositoryTrait,
+    _: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let mut attempts = stub_attempts("client", 5);
+    attempts.last_failed_at = Utc::now() - Duration::minutes(20);
+    repository
+        .save(&attempts)
+        .map_err(|_| "Can't save attempts")?;
+
+    let incremented = repository
+        .increment("client", Utc::now(), Utc::now() - Duration::minutes(15))
+        .map_err(|_| "Can't increment attempts")?;
+
+    ensure(
+        1 == incremented.count,
+        "Expired attempts are not started over",
+    )
+}
+
+fn deletes_attempts(
+    repository: &dyn LoginAttemptRepositoryTrait,
+    _: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    repository
+        .save(&stub_attempts("client", 1))
+        .map_err(|_| "Can't save attempts")?;
+    repository
+        .save(&stub_attempts("other", 1))
+        .map_err(|_| "Can't save attempts")?;
+
+    repository
+        .delete("client")
+        .map_err(|_| "Can't delete attempts")?;
+
+    ensure(
+        repository
+            .find("client")
+            .map_err(|_| "Can't find attempts")?
+            .is_none(),
+        "Deleted attempts are found",
+    )?;
+    ensure(
+        repository
+            .find("other")
+            .map_err(|_| "Can't find attempts")?
+            .is_some(),
+        "Attempts of other key are deleted",
+    )?;
+    ensure(
+        repository.delete("client").is_ok(),
+        "Deleting missing attempts is not an error",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::login_attempt_repository::check;
+    use crate::mock::repository::login_attempt_repository_mock::LoginAttemptRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| test(&LoginAttemptRepositoryMock::new(), &IdFactoryMock::new()))
+    }
+}
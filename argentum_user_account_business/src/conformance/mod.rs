@@ -0,0 +1,34 @@
+//! Contracts of the user account repository traits, they run like the ones of
+//! `argentum_user_business::conformance`. Credentials and tokens belong to users, which these
+//! repositories don't save, so their scenarios take a `UserSetup` as well. Storages with
+//! a foreign key to users save one there, the others pass `unsaved_user`:
+//!
+//! ```ignore
+//! check(|test| {
+//!     let connection = PostgresConnection::connect(url).map_err(|_| "Can't connect")?;
+//!     let id_factory = UniqueIdFactory::new();
+//!
+//!     test(
+//!         &PostgresPasswordCredentialRepository::new(&connection, &id_factory),
+//!         &id_factory,
+//!         &|id_factory| save_user(&connection, id_factory),
+//!     )
+//! })
+//! ```
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+
+pub mod email_verification_token_repository;
+pub mod login_attempt_repository;
+pub mod outbox_repository;
+pub mod password_credential_repository;
+pub mod refresh_token_repository;
+pub mod restore_password_token_repository;
+pub mod session_repository;
+
+/// Gives the id of a user rows of the tested repository may belong to
+pub type UserSetup<'t> = &'t dyn Fn(&dyn IdFactory) -> Result<Id, &'static str>;
+
+/// The setup of storages without a foreign key to users, the user is never saved
+pub fn unsaved_user(id_factory: &dyn IdFactory) -> Result<Id, &'static str> {
+    Ok(id_factory.create())
+}
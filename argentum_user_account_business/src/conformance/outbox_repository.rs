@@ -0,0 +1,162 @@
+use crate::entity::outbox_message::OutboxMessage;
+use crate::event::PasswordChanged;
+use crate::repository::outbox_repository::OutboxRepositoryTrait;
+use argentum_standard_business::conformance::{ensure, run, same_time, ConformanceError};
+use argentum_standard_business::data_type::id::IdFactory;
+
+pub type Scenario = fn(&dyn OutboxRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub type Test<'t> =
+    &'t dyn Fn(&dyn OutboxRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_message", saves_and_finds_message),
+    (
+        "rejects_saving_same_message_twice",
+        rejects_saving_same_message_twice,
+    ),
+    ("finds_oldest_messages_first", finds_oldest_messages_first),
+    ("skips_published_messages", skips_published_messages),
+    (
+        "rejects_marking_unknown_message",
+        rejects_marking_unknown_message,
+    ),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+fn stub_message(id_factory: &dyn IdFactory) -> OutboxMessage {
+    OutboxMessage::new(
+        id_factory.create(),
+        &PasswordChanged::new(id_factory.create()),
+    )
+}
+
+fn save_messages(
+    repository: &dyn OutboxRepositoryTrait,
+    id_factory: &dyn IdFactory,
+    count: usize,
+) -> Result<Vec<OutboxMessage>, &'static str> {
+    let mut messages = Vec::new();
+
+    for _ in 0..count {
+        let message = stub_message(id_factory);
+        repository
+            .save(&message)
+            .map_err(|_| "Can't save message")?;
+        messages.push(message);
+    }
+
+    Ok(messages)
+}
+
+fn saves_and_finds_message(
+    repository: &dyn OutboxRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let message = stub_message(id_factory);
+    repository
+        .save(&message)
+        .map_err(|_| "Can't save message")?;
+
+    let found = repository
+        .find_unpublished(10)
+        .map_err(|_| "Can't find messages")?;
+
+    ensure(1 == found.len(), "Saved message is not found")?;
+    ensure(*message.id == *found[0].id, "Id is changed")?;
+    ensure(
+        message.event_name == found[0].event_name,
+        "Event name is changed",
+    )?;
+    ensure(message.payload == found[0].payload, "Payload is changed")?;
+    ensure(
+        same_time(&message.occurred_at, &found[0].occurred_at),
+        "Occurrence time is changed",
+    )?;
+    ensure(!found[0].is_published(), "Message should not be published")
+}
+
+fn rejects_saving_same_message_twice(
+    repository: &dyn OutboxRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let message = stub_message(id_factory);
+    repository
+        .save(&message)
+        .map_err(|_| "Can't save message")?;
+
+    ensure(
+        repository.save(&message).is_err(),
+        "Message id should be unique",
+    )
+}
+
+/// Messages are delivered in the order they were saved, a limit takes the oldest ones
+fn finds_oldest_messages_first(
+    repository: &dyn OutboxRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let messages = save_messages(repository, id_factory, 3)?;
+
+    let found = repository
+        .find_unpublished(2)
+        .map_err(|_| "Can't find messages")?;
+
+    ensure(2 == found.len(), "Limit is not respected")?;
+    ensure(
+        *messages[0].id == *found[0].id && *messages[1].id == *found[1].id,
+        "Messages are not in order of saving",
+    )
+}
+
+fn skips_published_messages(
+    repository: &dyn OutboxRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let messages = save_messages(repository, id_factory, 2)?;
+
+    repository
+        .mark_published(&messages[0].id)
+        .map_err(|_| "Can't mark message published")?;
+
+    let found = repository
+        .find_unpublished(10)
+        .map_err(|_| "Can't find messages")?;
+
+    ensure(1 == found.len(), "Published message is found")?;
+    ensure(
+        *messages[1].id == *found[0].id,
+        "Not published message is skipped",
+    )
+}
+
+fn rejects_marking_unknown_message(
+    repository: &dyn OutboxRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    save_messages(repository, id_factory, 1)?;
+
+    ensure(
+        repository.mark_published(&id_factory.create()).is_err(),
+        "Unknown message can't be marked",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::outbox_repository::check;
+    use crate::mock::repository::outbox_repository_mock::OutboxRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| test(&OutboxRepositoryMock::new(), &IdFactoryMock::new()))
+    }
+}
@@ -0,0 +1,190 @@
+use crate::conformance::UserSetup;
+use crate::entity::credential::PasswordCredential;
+use crate::repository::password_credential_repository::PasswordCredentialRepository;
+use argentum_standard_business::conformance::{ensure, run, ConformanceError};
+use argentum_standard_business::data_type::id::IdFactory;
+use argentum_standard_business::transaction::TransactionManagerTrait;
+
+pub type Scenario =
+    fn(&dyn PasswordCredentialRepository, &dyn IdFactory, UserSetup) -> Result<(), &'static str>;
+
+pub type Test<'t> = &'t dyn Fn(
+    &dyn PasswordCredentialRepository,
+    &dyn IdFactory,
+    UserSetup,
+) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_credential", saves_and_finds_credential),
+    (
+        "finds_nothing_for_unknown_user",
+        finds_nothing_for_unknown_user,
+    ),
+    ("replaces_credential_of_user", replaces_credential_of_user),
+    ("deletes_credential", deletes_credential),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+/// Makes every following write of the storage fail, e.g. with a trigger
+pub type BreakWrites<'t> = &'t dyn Fn() -> Result<(), &'static str>;
+
+pub type TransactionScenario = fn(
+    &dyn PasswordCredentialRepository,
+    &dyn TransactionManagerTrait,
+    &dyn IdFactory,
+    UserSetup,
+    BreakWrites,
+) -> Result<(), &'static str>;
+
+pub type TransactionTest<'t> = &'t dyn Fn(
+    &dyn PasswordCredentialRepository,
+    &dyn TransactionManagerTrait,
+    &dyn IdFactory,
+    UserSetup,
+    BreakWrites,
+) -> Result<(), &'static str>;
+
+pub const TRANSACTION_SCENARIOS: &[(&str, TransactionScenario)] =
+    &[("failed_write_fails_commit", failed_write_fails_commit)];
+
+/// Contract of a storage which writes in transactions of `TransactionManagerTrait`
+pub fn check_transactions<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(TransactionTest) -> Result<(), &'static str>,
+{
+    run(TRANSACTION_SCENARIOS, |scenario| factory(scenario))
+}
+
+fn saves_and_finds_credential(
+    repository: &dyn PasswordCredentialRepository,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    let user_id = user_setup(id_factory)?;
+    repository.save(&PasswordCredential::new(
+        user_id.clone(),
+        "hash".to_string(),
+    ));
+
+    let found = repository
+        .find_by_user_id(&user_id)
+        .ok_or("Saved credential is not found")?;
+
+    ensure(*user_id == *found.user_id, "User id is changed")?;
+    ensure("hash" == found.password, "Password is changed")
+}
+
+fn finds_nothing_for_unknown_user(
+    repository: &dyn PasswordCredentialRepository,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    repository.save(&PasswordCredential::new(
+        user_setup(id_factory)?,
+        "hash".to_string(),
+    ));
+
+    ensure(
+        repository.find_by_user_id(&id_factory.create()).is_none(),
+        "Credential of unknown user is found",
+    )
+}
+
+/// A user has one password, saving a new one replaces it
+fn replaces_credential_of_user(
+    repository: &dyn PasswordCredentialRepository,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    let user_id = user_setup(id_factory)?;
+    repository.save(&PasswordCredential::new(
+        user_id.clone(),
+        "first".to_string(),
+    ));
+    repository.save(&PasswordCredential::new(
+        user_id.clone(),
+        "second".to_string(),
+    ));
+
+    let found = repository
+        .find_by_user_id(&user_id)
+        .ok_or("Credential is not found")?;
+
+    ensure("second" == found.password, "Password is not replaced")
+}
+
+fn deletes_credential(
+    repository: &dyn PasswordCredentialRepository,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    let credential = PasswordCredential::new(user_setup(id_factory)?, "hash".to_string());
+    let other = PasswordCredential::new(user_setup(id_factory)?, "hash".to_string());
+    repository.save(&credential);
+    repository.save(&other);
+
+    repository.delete(&credential);
+
+    ensure(
+        repository.find_by_user_id(&credential.user_id).is_none(),
+        "Deleted credential is found",
+    )?;
+    ensure(
+        repository.find_by_user_id(&other.user_id).is_some(),
+        "Other credential is deleted",
+    )
+}
+
+/// The trait can't report a failed write, so the commit of its transaction has to fail
+fn failed_write_fails_commit(
+    repository: &dyn PasswordCredentialRepository,
+    transaction_manager: &dyn TransactionManagerTrait,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+    break_writes: BreakWrites,
+) -> Result<(), &'static str> {
+    let saved = PasswordCredential::new(user_setup(id_factory)?, "hash".to_string());
+    let failed = PasswordCredential::new(user_setup(id_factory)?, "hash".to_string());
+
+    let transaction = transaction_manager.begin().map_err(|_| "Can't begin")?;
+    let bound = repository.within(&transaction);
+    bound.save(&saved);
+    break_writes()?;
+    bound.save(&failed);
+    drop(bound);
+
+    ensure(
+        transaction_manager.commit(transaction).is_err(),
+        "Transaction with a failed write is committed",
+    )?;
+    ensure(
+        repository.find_by_user_id(&saved.user_id).is_none(),
+        "Writes of a failed transaction are kept",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::password_credential_repository::check;
+    use crate::conformance::unsaved_user;
+    use crate::mock::repository::password_credential_repository_mock::PasswordCredentialRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| {
+            test(
+                &PasswordCredentialRepositoryMock::new(),
+                &IdFactoryMock::new(),
+                &unsaved_user,
+            )
+        })
+    }
+}
@@ -0,0 +1,201 @@
+use crate::entity::refresh_token::RefreshToken;
+use crate::repository::refresh_token_repository::RefreshTokenRepositoryTrait;
+use argentum_standard_business::conformance::{ensure, run, same_time, ConformanceError};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+
+pub type Scenario =
+    fn(&dyn RefreshTokenRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub type Test<'t> =
+    &'t dyn Fn(&dyn RefreshTokenRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_token", saves_and_finds_token),
+    (
+        "finds_nothing_for_unknown_hash",
+        finds_nothing_for_unknown_hash,
+    ),
+    (
+        "rejects_saving_same_token_twice",
+        rejects_saving_same_token_twice,
+    ),
+    ("marks_token_used_once", marks_token_used_once),
+    (
+        "does_not_mark_unknown_token_used",
+        does_not_mark_unknown_token_used,
+    ),
+    ("deletes_token_family", deletes_token_family),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+fn stub_token(id_factory: &dyn IdFactory, session_id: &Id, token_hash: &str) -> RefreshToken {
+    RefreshToken::new(
+        id_factory.create(),
+        session_id.clone(),
+        id_factory.create(),
+        token_hash.to_string(),
+        3600,
+    )
+}
+
+fn saves_and_finds_token(
+    repository: &dyn RefreshTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let token = stub_token(id_factory, &id_factory.create(), "a");
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    let found = repository
+        .find_by_token_hash("a")
+        .ok_or("Saved token is not found by hash")?;
+
+    ensure(*token.id == *found.id, "Id is changed")?;
+    ensure(
+        *token.session_id == *found.session_id,
+        "Session id is changed",
+    )?;
+    ensure(*token.user_id == *found.user_id, "User id is changed")?;
+    ensure(
+        same_time(&token.created_at, &found.created_at),
+        "Creation time is changed",
+    )?;
+    ensure(
+        same_time(&token.expires_at, &found.expires_at),
+        "Expiration time is changed",
+    )?;
+    ensure(!found.is_used(), "Token should not be used")
+}
+
+fn finds_nothing_for_unknown_hash(
+    repository: &dyn RefreshTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let token = stub_token(id_factory, &id_factory.create(), "a");
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    ensure(
+        repository.find_by_token_hash("b").is_none(),
+        "Token is found by unknown hash",
+    )
+}
+
+fn rejects_saving_same_token_twice(
+    repository: &dyn RefreshTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let token = stub_token(id_factory, &id_factory.create(), "a");
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    ensure(
+        repository.save(&token).is_err(),
+        "Token id should be unique",
+    )
+}
+
+fn marks_token_used_once(
+    repository: &dyn RefreshTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let mut token = stub_token(id_factory, &id_factory.create(), "a");
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    token.mark_used();
+    ensure(
+        repository
+            .mark_used_if_unused(&token)
+            .map_err(|_| "Can't mark token used")?,
+        "Unused token is not claimed",
+    )?;
+
+    let found = repository
+        .find_by_token_hash("a")
+        .ok_or("Used token is not found")?;
+
+    ensure(
+        match (token.used_at, found.used_at) {
+            (Some(a), Some(b)) => same_time(&a, &b),
+            _ => false,
+        },
+        "Usage time is not stored",
+    )?;
+
+    let mut again = token.clone();
+    again.mark_used();
+    ensure(
+        !repository
+            .mark_used_if_unused(&again)
+            .map_err(|_| "Can't mark token used")?,
+        "Used token is claimed twice",
+    )
+}
+
+fn does_not_mark_unknown_token_used(
+    repository: &dyn RefreshTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let mut token = stub_token(id_factory, &id_factory.create(), "a");
+    token.mark_used();
+
+    ensure(
+        !repository
+            .mark_used_if_unused(&token)
+            .map_err(|_| "Can't mark token used")?,
+        "Not saved token can't be claimed",
+    )?;
+    ensure(
+        repository.find_by_token_hash("a").is_none(),
+        "Marking should not save token",
+    )
+}
+
+fn deletes_token_family(
+    repository: &dyn RefreshTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let session_id = id_factory.create();
+
+    for (session_id, hash) in [
+        (&session_id, "a"),
+        (&session_id, "b"),
+        (&id_factory.create(), "c"),
+    ]
+    .iter()
+    {
+        repository
+            .save(&stub_token(id_factory, session_id, hash))
+            .map_err(|_| "Can't save token")?;
+    }
+
+    repository
+        .delete_sessions_tokens(&session_id)
+        .map_err(|_| "Can't delete tokens")?;
+
+    ensure(
+        repository.find_by_token_hash("a").is_none()
+            && repository.find_by_token_hash("b").is_none(),
+        "Token of session is not deleted",
+    )?;
+    ensure(
+        repository.find_by_token_hash("c").is_some(),
+        "Token of other session is deleted",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::refresh_token_repository::check;
+    use crate::mock::repository::refresh_token_repository_mock::RefreshTokenRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| test(&RefreshTokenRepositoryMock::new(), &IdFactoryMock::new()))
+    }
+}
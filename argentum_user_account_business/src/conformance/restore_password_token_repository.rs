@@ -0,0 +1,231 @@
+use crate::conformance::UserSetup;
+use crate::entity::restore_password_token::RestorePasswordToken;
+use crate::repository::restore_password_token_repository::RestorePasswordTokenRepositoryTrait;
+use argentum_standard_business::conformance::{ensure, run, same_time, ConformanceError};
+use argentum_standard_business::data_type::id::IdFactory;
+
+pub type Scenario = fn(
+    &dyn RestorePasswordTokenRepositoryTrait,
+    &dyn IdFactory,
+    UserSetup,
+) -> Result<(), &'static str>;
+
+pub type Test<'t> = &'t dyn Fn(
+    &dyn RestorePasswordTokenRepositoryTrait,
+    &dyn IdFactory,
+    UserSetup,
+) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_token", saves_and_finds_token),
+    ("finds_token_by_hash", finds_token_by_hash),
+    (
+        "finds_nothing_for_unknown_token",
+        finds_nothing_for_unknown_token,
+    ),
+    (
+        "rejects_saving_same_token_twice",
+        rejects_saving_same_token_twice,
+    ),
+    ("deletes_every_token_of_user", deletes_every_token_of_user),
+    ("deletes_token_once", deletes_token_once),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+fn saves_and_finds_token(
+    repository: &dyn RestorePasswordTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    let token = RestorePasswordToken::new(
+        id_factory.create(),
+        user_setup(id_factory)?,
+        "a".to_string(),
+    );
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    let found = repository
+        .find(&token.id)
+        .map_err(|_| "Can't find token")?
+        .ok_or("Saved token is not found")?;
+
+    ensure(*token.id == *found.id, "Id is changed")?;
+    ensure(*token.user_id == *found.user_id, "User id is changed")?;
+    ensure(
+        token.token_hash == found.token_hash,
+        "Token hash is changed",
+    )?;
+    ensure(
+        same_time(&token.created_at, &found.created_at),
+        "Creation time is changed",
+    )
+}
+
+fn finds_token_by_hash(
+    repository: &dyn RestorePasswordTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    let token = RestorePasswordToken::new(
+        id_factory.create(),
+        user_setup(id_factory)?,
+        "a".to_string(),
+    );
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    let found = repository
+        .find_by_token_hash("a")
+        .map_err(|_| "Can't find token")?
+        .ok_or("Token is not found by hash")?;
+
+    ensure(*token.id == *found.id, "Other token is found")
+}
+
+fn finds_nothing_for_unknown_token(
+    repository: &dyn RestorePasswordTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    let token = RestorePasswordToken::new(
+        id_factory.create(),
+        user_setup(id_factory)?,
+        "a".to_string(),
+    );
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    ensure(
+        repository
+            .find(&id_factory.create())
+            .map_err(|_| "Can't find token")?
+            .is_none(),
+        "Unknown id is found",
+    )?;
+    ensure(
+        repository
+            .find_by_token_hash("b")
+            .map_err(|_| "Can't find token")?
+            .is_none(),
+        "Token is found by unknown hash",
+    )
+}
+
+fn rejects_saving_same_token_twice(
+    repository: &dyn RestorePasswordTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    let token = RestorePasswordToken::new(
+        id_factory.create(),
+        user_setup(id_factory)?,
+        "a".to_string(),
+    );
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    ensure(
+        repository.save(&token).is_err(),
+        "Token id should be unique",
+    )
+}
+
+fn deletes_every_token_of_user(
+    repository: &dyn RestorePasswordTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    let user_id = user_setup(id_factory)?;
+    let tokens = [
+        RestorePasswordToken::new(id_factory.create(), user_id.clone(), "a".to_string()),
+        RestorePasswordToken::new(id_factory.create(), user_id.clone(), "b".to_string()),
+    ];
+    let other = RestorePasswordToken::new(
+        id_factory.create(),
+        user_setup(id_factory)?,
+        "c".to_string(),
+    );
+
+    for token in tokens.iter().chain(std::iter::once(&other)) {
+        repository.save(token).map_err(|_| "Can't save token")?;
+    }
+
+    repository
+        .delete_users_tokens(&user_id)
+        .map_err(|_| "Can't delete tokens")?;
+
+    for token in tokens.iter() {
+        ensure(
+            repository
+                .find(&token.id)
+                .map_err(|_| "Can't find token")?
+                .is_none(),
+            "Token of user is not deleted",
+        )?;
+    }
+
+    ensure(
+        repository
+            .find(&other.id)
+            .map_err(|_| "Can't find token")?
+            .is_some(),
+        "Token of other user is deleted",
+    )
+}
+
+/// Deleting is how a token is claimed, the second attempt must see it gone
+fn deletes_token_once(
+    repository: &dyn RestorePasswordTokenRepositoryTrait,
+    id_factory: &dyn IdFactory,
+    user_setup: UserSetup,
+) -> Result<(), &'static str> {
+    let token = RestorePasswordToken::new(
+        id_factory.create(),
+        user_setup(id_factory)?,
+        "a".to_string(),
+    );
+    repository.save(&token).map_err(|_| "Can't save token")?;
+
+    ensure(
+        repository
+            .delete_if_exists(&token.id)
+            .map_err(|_| "Can't delete token")?,
+        "Existing token is not deleted",
+    )?;
+    ensure(
+        !repository
+            .delete_if_exists(&token.id)
+            .map_err(|_| "Can't delete missing token")?,
+        "Token is deleted twice",
+    )?;
+    ensure(
+        repository
+            .find(&token.id)
+            .map_err(|_| "Can't find token")?
+            .is_none(),
+        "Deleted token is found",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::restore_password_token_repository::check;
+    use crate::conformance::unsaved_user;
+    use crate::mock::repository::restore_password_token_repository_mock::RestorePasswordTokenRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| {
+            test(
+                &RestorePasswordTokenRepositoryMock::new(),
+                &IdFactoryMock::new(),
+                &unsaved_user,
+            )
+        })
+    }
+}
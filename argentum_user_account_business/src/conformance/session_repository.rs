@@ -0,0 +1,308 @@
+use crate::entity::session::Session;
+use crate::repository::session_repository::SessionRepositoryTrait;
+use argentum_standard_business::conformance::{ensure, run, same_time, ConformanceError};
+use argentum_standard_business::data_type::id::IdFactory;
+use chrono::Duration;
+
+pub type Scenario = fn(&dyn SessionRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub type Test<'t> =
+    &'t dyn Fn(&dyn SessionRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_session", saves_and_finds_session),
+    ("finds_session_by_token_hash", finds_session_by_token_hash),
+    (
+        "finds_nothing_for_unknown_session",
+        finds_nothing_for_unknown_session,
+    ),
+    (
+        "rejects_saving_same_session_twice",
+        rejects_saving_same_session_twice,
+    ),
+    ("updates_saved_session", updates_saved_session),
+    (
+        "rejects_updating_unknown_session",
+        rejects_updating_unknown_session,
+    ),
+    ("finds_sessions_of_user", finds_sessions_of_user),
+    ("deletes_session", deletes_session),
+    ("deletes_sessions_of_user", deletes_sessions_of_user),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+fn saves_and_finds_session(
+    repository: &dyn SessionRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let session = Session::new(
+        id_factory.create(),
+        id_factory.create(),
+        "a".to_string(),
+        3600,
+    );
+    repository
+        .save(&session)
+        .map_err(|_| "Can't save session")?;
+
+    let found = repository
+        .find(&session.id)
+        .ok_or("Saved session is not found")?;
+
+    ensure(*session.id == *found.id, "Id is changed")?;
+    ensure(*session.user_id == *found.user_id, "User id is changed")?;
+    ensure(
+        session.token_hash == found.token_hash,
+        "Token hash is changed",
+    )?;
+    ensure(
+        same_time(&session.created_at, &found.created_at),
+        "Creation time is changed",
+    )?;
+    ensure(
+        same_time(&session.last_seen_at, &found.last_seen_at),
+        "Last seen time is changed",
+    )?;
+    ensure(
+        same_time(&session.expires_at, &found.expires_at),
+        "Expiration time is changed",
+    )
+}
+
+fn finds_session_by_token_hash(
+    repository: &dyn SessionRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let session = Session::new(
+        id_factory.create(),
+        id_factory.create(),
+        "a".to_string(),
+        3600,
+    );
+    repository
+        .save(&session)
+        .map_err(|_| "Can't save session")?;
+
+    let found = repository
+        .find_by_token_hash("a")
+        .ok_or("Session is not found by token hash")?;
+
+    ensure(*session.id == *found.id, "Other session is found")?;
+    ensure(
+        repository.find_by_token_hash("b").is_none(),
+        "Session is found by unknown hash",
+    )
+}
+
+fn finds_nothing_for_unknown_session(
+    repository: &dyn SessionRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    ensure(
+        repository.find(&id_factory.create()).is_none(),
+        "Unknown id is found",
+    )?;
+    let found = repository
+        .find_by_user_id(&id_factory.create())
+        .map_err(|_| "Can't find sessions")?;
+
+    ensure(found.is_empty(), "Sessions of unknown user are found")
+}
+
+fn rejects_saving_same_session_twice(
+    repository: &dyn SessionRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let session = Session::new(
+        id_factory.create(),
+        id_factory.create(),
+        "a".to_string(),
+        3600,
+    );
+    repository
+        .save(&session)
+        .map_err(|_| "Can't save session")?;
+
+    ensure(
+        repository.save(&session).is_err(),
+        "Saved session should be changed with update",
+    )
+}
+
+fn updates_saved_session(
+    repository: &dyn SessionRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let mut session = Session::new(
+        id_factory.create(),
+        id_factory.create(),
+        "a".to_string(),
+        3600,
+    );
+    repository
+        .save(&session)
+        .map_err(|_| "Can't save session")?;
+
+    session.token_hash = "rotated".to_string();
+    session.last_seen_at = session.last_seen_at + Duration::seconds(10);
+    session.expires_at = session.expires_at + Duration::seconds(10);
+    repository
+        .update(&session)
+        .map_err(|_| "Can't update session")?;
+
+    let found = repository
+        .find_by_token_hash("rotated")
+        .ok_or("Session is not found by new token hash")?;
+
+    ensure(
+        same_time(&session.last_seen_at, &found.last_seen_at),
+        "Last seen time is not updated",
+    )?;
+    ensure(
+        same_time(&session.expires_at, &found.expires_at),
+        "Expiration time is not updated",
+    )?;
+    ensure(
+        repository.find_by_token_hash("a").is_none(),
+        "Session is found by previous token hash",
+    )
+}
+
+fn rejects_updating_unknown_session(
+    repository: &dyn SessionRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let session = Session::new(
+        id_factory.create(),
+        id_factory.create(),
+        "a".to_string(),
+        3600,
+    );
+
+    ensure(
+        repository.update(&session).is_err(),
+        "Not saved session can't be updated",
+    )?;
+    ensure(
+        repository.find(&session.id).is_none(),
+        "Update should not save session",
+    )
+}
+
+fn finds_sessions_of_user(
+    repository: &dyn SessionRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user_id = id_factory.create();
+
+    for (user_id, hash) in [
+        (&user_id, "a"),
+        (&user_id, "b"),
+        (&id_factory.create(), "c"),
+    ]
+    .iter()
+    {
+        let session = Session::new(
+            id_factory.create(),
+            (*user_id).clone(),
+            hash.to_string(),
+            3600,
+        );
+        repository
+            .save(&session)
+            .map_err(|_| "Can't save session")?;
+    }
+
+    let found = repository
+        .find_by_user_id(&user_id)
+        .map_err(|_| "Can't find sessions")?;
+
+    ensure(2 == found.len(), "Every session of user should be found")?;
+    ensure(
+        found.iter().all(|s| *s.user_id == *user_id),
+        "Session of other user is found",
+    )
+}
+
+fn deletes_session(
+    repository: &dyn SessionRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user_id = id_factory.create();
+    let session = Session::new(id_factory.create(), user_id.clone(), "a".to_string(), 3600);
+    let other = Session::new(id_factory.create(), user_id.clone(), "b".to_string(), 3600);
+    repository
+        .save(&session)
+        .map_err(|_| "Can't save session")?;
+    repository.save(&other).map_err(|_| "Can't save session")?;
+
+    repository
+        .delete(&session.id)
+        .map_err(|_| "Can't delete session")?;
+
+    ensure(
+        repository.find(&session.id).is_none(),
+        "Deleted session is found",
+    )?;
+    ensure(
+        repository.find(&other.id).is_some(),
+        "Other session is deleted",
+    )?;
+    ensure(
+        repository.delete(&session.id).is_ok(),
+        "Deleting a missing session is not an error",
+    )
+}
+
+fn deletes_sessions_of_user(
+    repository: &dyn SessionRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user_id = id_factory.create();
+    let other_user_id = id_factory.create();
+
+    for (user_id, hash) in [(&user_id, "a"), (&user_id, "b"), (&other_user_id, "c")].iter() {
+        let session = Session::new(
+            id_factory.create(),
+            (*user_id).clone(),
+            hash.to_string(),
+            3600,
+        );
+        repository
+            .save(&session)
+            .map_err(|_| "Can't save session")?;
+    }
+
+    repository
+        .delete_users_sessions(&user_id)
+        .map_err(|_| "Can't delete sessions")?;
+
+    let remaining = repository
+        .find_by_user_id(&user_id)
+        .map_err(|_| "Can't find sessions")?;
+    let others = repository
+        .find_by_user_id(&other_user_id)
+        .map_err(|_| "Can't find sessions")?;
+
+    ensure(remaining.is_empty(), "Sessions of user are not deleted")?;
+    ensure(1 == others.len(), "Sessions of other user are deleted")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::session_repository::check;
+    use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| test(&SessionRepositoryMock::new(), &IdFactoryMock::new()))
+    }
+}
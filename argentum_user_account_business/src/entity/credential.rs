@@ -5,10 +5,11 @@ pub trait Credential {
     fn as_any(&self) -> &dyn Any;
 }
 
+/// Password is a self-describing hash string (PHC string format),
+/// it keeps the algorithm, its parameters and the salt.
 pub struct PasswordCredential {
     pub user_id: Id,
     pub password: String,
-    pub salt: String,
 }
 
 impl Credential for PasswordCredential {
@@ -22,17 +23,12 @@ impl Clone for PasswordCredential {
         PasswordCredential {
             user_id: self.user_id.clone(),
             password: self.password.clone(),
-            salt: self.salt.clone(),
         }
     }
 }
 
 impl PasswordCredential {
-    pub fn new(user_id: Id, password: String, salt: String) -> Self {
-        PasswordCredential {
-            user_id,
-            password,
-            salt,
-        }
+    pub fn new(user_id: Id, password: String) -> Self {
+        PasswordCredential { user_id, password }
     }
 }
@@ -0,0 +1,42 @@
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_standard_business::data_type::id::Id;
+use chrono::{DateTime, Duration, Utc};
+
+/// Proves ownership of `email`, a token issued for a previous address is not accepted
+pub struct EmailVerificationToken {
+    pub id: Id,
+    pub user_id: Id,
+    pub email: EmailAddress,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EmailVerificationToken {
+    pub fn new(id: Id, user_id: Id, email: EmailAddress, token_hash: String) -> Self {
+        EmailVerificationToken {
+            id,
+            user_id,
+            email,
+            token_hash,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn is_expired(&self, ttl: u32) -> bool {
+        let expires_at = self.created_at + Duration::seconds(ttl as i64);
+
+        Utc::now().ge(&expires_at)
+    }
+}
+
+impl Clone for EmailVerificationToken {
+    fn clone(&self) -> EmailVerificationToken {
+        EmailVerificationToken {
+            id: self.id.clone(),
+            user_id: self.user_id.clone(),
+            email: self.email.clone(),
+            token_hash: self.token_hash.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
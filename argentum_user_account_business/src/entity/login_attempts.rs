@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+
+/// Consecutive failed logins for a throttling key (an account or a client)
+pub struct FailedLoginAttempts {
+    pub key: String,
+    pub count: u32,
+    pub last_failed_at: DateTime<Utc>,
+}
+
+impl FailedLoginAttempts {
+    pub fn new(key: String) -> Self {
+        FailedLoginAttempts {
+            key,
+            count: 0,
+            last_failed_at: Utc::now(),
+        }
+    }
+
+    pub fn register_failure(&mut self) {
+        self.count += 1;
+        self.last_failed_at = Utc::now();
+    }
+}
+
+impl Clone for FailedLoginAttempts {
+    fn clone(&self) -> FailedLoginAttempts {
+        FailedLoginAttempts {
+            key: self.key.clone(),
+            count: self.count,
+            last_failed_at: self.last_failed_at,
+        }
+    }
+}
@@ -1,3 +1,7 @@
 pub mod credential;
+pub mod email_verification_token;
+pub mod login_attempts;
+pub mod outbox_message;
+pub mod refresh_token;
 pub mod restore_password_token;
 pub mod session;
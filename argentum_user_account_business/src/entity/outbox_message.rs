@@ -0,0 +1,74 @@
+use argentum_event_business::event::DomainEvent;
+use argentum_standard_business::data_type::id::Id;
+use chrono::{DateTime, Utc};
+
+/// A domain event stored for delivery. The id is created once, so consumers use it
+/// to drop duplicates of an at-least-once delivery
+pub struct OutboxMessage {
+    pub id: Id,
+    pub event_name: String,
+    pub payload: String,
+    pub occurred_at: DateTime<Utc>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxMessage {
+    pub fn new(id: Id, event: &dyn DomainEvent) -> Self {
+        OutboxMessage {
+            id,
+            event_name: event.name().to_string(),
+            payload: event.payload(),
+            occurred_at: event.occurred_at(),
+            published_at: None,
+        }
+    }
+
+    pub fn is_published(&self) -> bool {
+        self.published_at.is_some()
+    }
+
+    pub fn mark_published(&mut self) {
+        self.published_at = Some(Utc::now());
+    }
+}
+
+impl Clone for OutboxMessage {
+    fn clone(&self) -> OutboxMessage {
+        OutboxMessage {
+            id: self.id.clone(),
+            event_name: self.event_name.clone(),
+            payload: self.payload.clone(),
+            occurred_at: self.occurred_at,
+            published_at: self.published_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::outbox_message::OutboxMessage;
+    use crate::event::PasswordChanged;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_new_outbox_message() {
+        let id_factory = IdFactoryMock::new();
+        let user_id = id_factory.create();
+        let event = PasswordChanged::new(user_id.clone());
+
+        let mut message = OutboxMessage::new(id_factory.create(), &event);
+
+        assert_eq!("user_account.password_changed", message.event_name);
+        assert_eq!(
+            format!(r#"{{"user_id":"{}"}}"#, user_id.to_string()),
+            message.payload
+        );
+        assert_eq!(event.occurred_at, message.occurred_at);
+        assert!(!message.is_published());
+
+        message.mark_published();
+
+        assert!(message.is_published());
+    }
+}
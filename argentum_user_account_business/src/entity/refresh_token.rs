@@ -0,0 +1,101 @@
+use argentum_standard_business::data_type::id::Id;
+use chrono::{DateTime, Duration, Utc};
+
+/// A long-lived token exchanged for a new access token. Refresh tokens of one session
+/// form a family: each of them can be used once and is replaced by the next one.
+pub struct RefreshToken {
+    pub id: Id,
+    pub session_id: Id,
+    pub user_id: Id,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+impl RefreshToken {
+    /// `ttl` is the token lifetime in seconds, it never outlives the session
+    pub fn new(id: Id, session_id: Id, user_id: Id, token_hash: String, ttl: u32) -> Self {
+        let now = Utc::now();
+
+        RefreshToken {
+            id,
+            session_id,
+            user_id,
+            token_hash,
+            created_at: now,
+            expires_at: now + Duration::seconds(ttl as i64),
+            used_at: None,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now().ge(&self.expires_at)
+    }
+
+    pub fn is_used(&self) -> bool {
+        self.used_at.is_some()
+    }
+
+    pub fn mark_used(&mut self) {
+        self.used_at = Some(Utc::now());
+    }
+}
+
+impl Clone for RefreshToken {
+    fn clone(&self) -> RefreshToken {
+        RefreshToken {
+            id: self.id.clone(),
+            session_id: self.session_id.clone(),
+            user_id: self.user_id.clone(),
+            token_hash: self.token_hash.clone(),
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            used_at: self.used_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::refresh_token::RefreshToken;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use chrono::{Duration, Utc};
+
+    fn stub_refresh_token() -> RefreshToken {
+        let id_factory = IdFactoryMock::new();
+
+        RefreshToken::new(
+            id_factory.create(),
+            id_factory.create(),
+            id_factory.create(),
+            "token-hash".to_string(),
+            3600,
+        )
+    }
+
+    #[test]
+    fn test_fresh_refresh_token() {
+        let token = stub_refresh_token();
+
+        assert!(!token.is_expired());
+        assert!(!token.is_used());
+    }
+
+    #[test]
+    fn test_expired_refresh_token() {
+        let mut token = stub_refresh_token();
+        token.expires_at = Utc::now() - Duration::seconds(1);
+
+        assert!(token.is_expired());
+    }
+
+    #[test]
+    fn test_used_refresh_token() {
+        let mut token = stub_refresh_token();
+        token.mark_used();
+
+        assert!(token.is_used());
+    }
+}
@@ -4,16 +4,16 @@ use chrono::{DateTime, Duration, Utc};
 pub struct RestorePasswordToken {
     pub id: Id,
     pub user_id: Id,
-    pub token: String,
+    pub token_hash: String,
     pub created_at: DateTime<Utc>,
 }
 
 impl RestorePasswordToken {
-    pub fn new(id: Id, user_id: Id, token: String) -> Self {
+    pub fn new(id: Id, user_id: Id, token_hash: String) -> Self {
         RestorePasswordToken {
             id,
             user_id,
-            token,
+            token_hash,
             created_at: Utc::now(),
         }
     }
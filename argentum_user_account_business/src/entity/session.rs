@@ -1,14 +1,105 @@
 use argentum_standard_business::data_type::id::Id;
+use chrono::{DateTime, Duration, Utc};
 
 pub struct Session {
     pub id: Id,
     pub user_id: Id,
-    pub token: String,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
     //device_information???
 }
 
 impl Session {
-    pub fn new(id: Id, user_id: Id, token: String) -> Self {
-        Session { id, user_id, token }
+    /// `token_hash` is a hash of the bearer token, the token itself is never stored.
+    /// `ttl` is the absolute session lifetime in seconds
+    pub fn new(id: Id, user_id: Id, token_hash: String, ttl: u32) -> Self {
+        let now = Utc::now();
+
+        Session {
+            id,
+            user_id,
+            token_hash,
+            created_at: now,
+            last_seen_at: now,
+            expires_at: now + Duration::seconds(ttl as i64),
+        }
+    }
+
+    /// A session expires when its absolute lifetime is over
+    /// or when it hasn't been used for `idle_ttl` seconds
+    pub fn is_expired(&self, idle_ttl: u32) -> bool {
+        let now = Utc::now();
+        let idle_expires_at = self.last_seen_at + Duration::seconds(idle_ttl as i64);
+
+        now.ge(&self.expires_at) || now.ge(&idle_expires_at)
+    }
+
+    pub fn touch(&mut self) {
+        self.last_seen_at = Utc::now();
+    }
+}
+
+impl Clone for Session {
+    fn clone(&self) -> Session {
+        Session {
+            id: self.id.clone(),
+            user_id: self.user_id.clone(),
+            token_hash: self.token_hash.clone(),
+            created_at: self.created_at,
+            last_seen_at: self.last_seen_at,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::session::Session;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use chrono::{Duration, Utc};
+
+    fn stub_session(ttl: u32) -> Session {
+        let id_factory = IdFactoryMock::new();
+
+        Session::new(
+            id_factory.create(),
+            id_factory.create(),
+            "token-hash".to_string(),
+            ttl,
+        )
+    }
+
+    #[test]
+    fn test_fresh_session_is_not_expired() {
+        assert!(!stub_session(3600).is_expired(600));
+    }
+
+    #[test]
+    fn test_idle_session_is_expired() {
+        let mut session = stub_session(3600);
+        session.last_seen_at = Utc::now() - Duration::seconds(601);
+
+        assert!(session.is_expired(600));
+    }
+
+    #[test]
+    fn test_session_is_expired_after_absolute_lifetime() {
+        let mut session = stub_session(3600);
+        session.expires_at = Utc::now() - Duration::seconds(1);
+        session.touch();
+
+        assert!(session.is_expired(600));
+    }
+
+    #[test]
+    fn test_touch_slides_idle_window() {
+        let mut session = stub_session(3600);
+        session.last_seen_at = Utc::now() - Duration::seconds(601);
+        session.touch();
+
+        assert!(!session.is_expired(600));
     }
 }
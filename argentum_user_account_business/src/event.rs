@@ -0,0 +1,350 @@
+use argentum_event_business::event::DomainEvent;
+use argentum_event_business::payload::to_json;
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_standard_business::data_type::id::Id;
+use chrono::{DateTime, Utc};
+use std::any::Any;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SessionRevocationReason {
+    Logout,
+    LogoutEverywhere,
+    /// An already used refresh token was presented, the token family is stolen
+    RefreshTokenReuse,
+    /// The password was reset, whoever knew the old one is logged out
+    PasswordReset,
+}
+
+impl SessionRevocationReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionRevocationReason::Logout => "logout",
+            SessionRevocationReason::LogoutEverywhere => "logout_everywhere",
+            SessionRevocationReason::RefreshTokenReuse => "refresh_token_reuse",
+            SessionRevocationReason::PasswordReset => "password_reset",
+        }
+    }
+}
+
+/// A new anonymous user is created
+pub struct AnonymousRegistered {
+    pub anonymous_id: Id,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl AnonymousRegistered {
+    pub fn new(anonymous_id: Id) -> Self {
+        AnonymousRegistered {
+            anonymous_id,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl DomainEvent for AnonymousRegistered {
+    fn name(&self) -> &'static str {
+        "user_account.anonymous_registered"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    fn payload(&self) -> String {
+        to_json(&[("anonymous_id", self.anonymous_id.to_string())])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct UserRegistered {
+    pub user_id: Id,
+    pub email: EmailAddress,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl UserRegistered {
+    pub fn new(user_id: Id, email: EmailAddress) -> Self {
+        UserRegistered {
+            user_id,
+            email,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl DomainEvent for UserRegistered {
+    fn name(&self) -> &'static str {
+        "user_account.user_registered"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    fn payload(&self) -> String {
+        to_json(&[
+            ("user_id", self.user_id.to_string()),
+            ("email", self.email.as_string()),
+        ])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct UserLoggedIn {
+    pub user_id: Id,
+    pub session_id: Id,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl UserLoggedIn {
+    pub fn new(user_id: Id, session_id: Id) -> Self {
+        UserLoggedIn {
+            user_id,
+            session_id,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl DomainEvent for UserLoggedIn {
+    fn name(&self) -> &'static str {
+        "user_account.user_logged_in"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    fn payload(&self) -> String {
+        to_json(&[
+            ("user_id", self.user_id.to_string()),
+            ("session_id", self.session_id.to_string()),
+        ])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Wrong email or password, the email may be not registered
+pub struct LoginFailed {
+    pub email: EmailAddress,
+    pub client_id: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl LoginFailed {
+    pub fn new(email: EmailAddress, client_id: String) -> Self {
+        LoginFailed {
+            email,
+            client_id,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl DomainEvent for LoginFailed {
+    fn name(&self) -> &'static str {
+        "user_account.login_failed"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    fn payload(&self) -> String {
+        to_json(&[
+            ("email", self.email.as_string()),
+            ("client_id", self.client_id.clone()),
+        ])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct AnonymousBoundToUser {
+    pub anonymous_id: Id,
+    pub user_id: Id,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl AnonymousBoundToUser {
+    pub fn new(anonymous_id: Id, user_id: Id) -> Self {
+        AnonymousBoundToUser {
+            anonymous_id,
+            user_id,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl DomainEvent for AnonymousBoundToUser {
+    fn name(&self) -> &'static str {
+        "user_account.anonymous_bound_to_user"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    fn payload(&self) -> String {
+        to_json(&[
+            ("anonymous_id", self.anonymous_id.to_string()),
+            ("user_id", self.user_id.to_string()),
+        ])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct PasswordRestoreRequested {
+    pub user_id: Id,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl PasswordRestoreRequested {
+    pub fn new(user_id: Id) -> Self {
+        PasswordRestoreRequested {
+            user_id,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl DomainEvent for PasswordRestoreRequested {
+    fn name(&self) -> &'static str {
+        "user_account.password_restore_requested"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    fn payload(&self) -> String {
+        to_json(&[("user_id", self.user_id.to_string())])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct PasswordChanged {
+    pub user_id: Id,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl PasswordChanged {
+    pub fn new(user_id: Id) -> Self {
+        PasswordChanged {
+            user_id,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl DomainEvent for PasswordChanged {
+    fn name(&self) -> &'static str {
+        "user_account.password_changed"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    fn payload(&self) -> String {
+        to_json(&[("user_id", self.user_id.to_string())])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct EmailVerified {
+    pub user_id: Id,
+    pub email: EmailAddress,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl EmailVerified {
+    pub fn new(user_id: Id, email: EmailAddress) -> Self {
+        EmailVerified {
+            user_id,
+            email,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl DomainEvent for EmailVerified {
+    fn name(&self) -> &'static str {
+        "user_account.email_verified"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    fn payload(&self) -> String {
+        to_json(&[
+            ("user_id", self.user_id.to_string()),
+            ("email", self.email.as_string()),
+        ])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct SessionRevoked {
+    pub session_id: Id,
+    pub user_id: Id,
+    pub reason: SessionRevocationReason,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl SessionRevoked {
+    pub fn new(session_id: Id, user_id: Id, reason: SessionRevocationReason) -> Self {
+        SessionRevoked {
+            session_id,
+            user_id,
+            reason,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+impl DomainEvent for SessionRevoked {
+    fn name(&self) -> &'static str {
+        "user_account.session_revoked"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    fn payload(&self) -> String {
+        to_json(&[
+            ("session_id", self.session_id.to_string()),
+            ("user_id", self.user_id.to_string()),
+            ("reason", self.reason.as_str().to_string()),
+        ])
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
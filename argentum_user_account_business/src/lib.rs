@@ -1,4 +1,7 @@
+pub mod conformance;
 pub mod entity;
+pub mod event;
 pub mod mock;
 pub mod repository;
 pub mod use_case;
+pub mod value_object;
@@ -1,18 +1,21 @@
-use std::cell::RefCell;
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 use crate::entity::session::Session;
+use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
 use crate::repository::session_repository::{SessionRepositoryError, SessionRepositoryTrait};
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::token::constant_time_eq;
 
 pub struct SessionRepositoryMockWithBrokenSave {
-    sessions: RefCell<HashMap<Id, Session>>,
+    sessions: RwLock<HashMap<Id, Session>>,
 }
 
 impl SessionRepositoryMockWithBrokenSave {
     pub fn new() -> SessionRepositoryMockWithBrokenSave {
         SessionRepositoryMockWithBrokenSave {
-            sessions: RefCell::new(HashMap::new()),
+            sessions: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -25,45 +28,106 @@ impl Default for SessionRepositoryMockWithBrokenSave {
 
 impl SessionRepositoryTrait for SessionRepositoryMockWithBrokenSave {
     fn find(&self, id: &Id) -> Option<Session> {
-        self.sessions
-            .borrow()
-            .get(id)
-            .map(|s| Session::new(s.id.clone(), s.user_id.clone(), s.token.clone()))
-    }
-
-    fn find_by_token(&self, token: String) -> Option<Session> {
-        for (_, s) in self.sessions.borrow().iter() {
-            if s.token == token {
-                return Some(Session::new(
-                    s.id.clone(),
-                    s.user_id.clone(),
-                    s.token.clone(),
-                ));
+        self.sessions.read().unwrap().get(id).cloned()
+    }
+
+    fn find_by_token_hash(&self, token_hash: &str) -> Option<Session> {
+        for (_, s) in self.sessions.read().unwrap().iter() {
+            if constant_time_eq(&s.token_hash, token_hash) {
+                return Some(s.clone());
             }
         }
 
         None
     }
 
+    fn find_by_user_id(&self, user_id: &Id) -> Result<Vec<Session>, SessionRepositoryError> {
+        Ok(self
+            .sessions
+            .read()
+            .unwrap()
+            .values()
+            .filter(|s| &s.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
     fn save(&self, _session: &Session) -> Result<(), SessionRepositoryError> {
         Err(SessionRepositoryError::Save)
     }
 
+    fn update(&self, _session: &Session) -> Result<(), SessionRepositoryError> {
+        Err(SessionRepositoryError::Update)
+    }
+
+    fn delete(&self, id: &Id) -> Result<(), SessionRepositoryError> {
+        self.sessions.write().unwrap().remove(id);
+
+        Ok(())
+    }
+
     fn delete_users_sessions(&self, user_id: &Id) -> Result<(), SessionRepositoryError> {
-        let mut id: Option<Id> = None;
+        self.sessions
+            .write()
+            .unwrap()
+            .retain(|_, s| &s.user_id != user_id);
 
-        for (k, s) in self.sessions.borrow().iter() {
-            if &s.user_id == user_id {
-                id = Some(k.clone());
+        Ok(())
+    }
 
-                break;
-            }
-        }
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn SessionRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
+
+pub struct SessionRepositoryMockWithBrokenDelete {
+    sessions: SessionRepositoryMock,
+}
 
-        if let Some(id) = id {
-            self.sessions.borrow_mut().remove(&id);
+impl SessionRepositoryMockWithBrokenDelete {
+    pub fn new() -> SessionRepositoryMockWithBrokenDelete {
+        SessionRepositoryMockWithBrokenDelete {
+            sessions: SessionRepositoryMock::new(),
         }
+    }
+}
 
-        Ok(())
+impl Default for SessionRepositoryMockWithBrokenDelete {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionRepositoryTrait for SessionRepositoryMockWithBrokenDelete {
+    fn find(&self, id: &Id) -> Option<Session> {
+        self.sessions.find(id)
+    }
+
+    fn find_by_token_hash(&self, token_hash: &str) -> Option<Session> {
+        self.sessions.find_by_token_hash(token_hash)
+    }
+
+    fn find_by_user_id(&self, user_id: &Id) -> Result<Vec<Session>, SessionRepositoryError> {
+        self.sessions.find_by_user_id(user_id)
+    }
+
+    fn save(&self, session: &Session) -> Result<(), SessionRepositoryError> {
+        self.sessions.save(session)
+    }
+
+    fn update(&self, session: &Session) -> Result<(), SessionRepositoryError> {
+        self.sessions.update(session)
+    }
+
+    fn delete(&self, _id: &Id) -> Result<(), SessionRepositoryError> {
+        Err(SessionRepositoryError::Delete)
+    }
+
+    fn delete_users_sessions(&self, _user_id: &Id) -> Result<(), SessionRepositoryError> {
+        Err(SessionRepositoryError::Delete)
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn SessionRepositoryTrait + '_> {
+        Bound::Shared(self)
     }
 }
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::entity::email_verification_token::EmailVerificationToken;
+use crate::repository::email_verification_token_repository::{
+    EmailVerificationTokenRepositoryError, EmailVerificationTokenRepositoryTrait,
+};
+use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::token::constant_time_eq;
+
+pub struct EmailVerificationTokenRepositoryMock {
+    tokens: RwLock<HashMap<Id, EmailVerificationToken>>,
+}
+
+impl EmailVerificationTokenRepositoryMock {
+    pub fn new() -> EmailVerificationTokenRepositoryMock {
+        EmailVerificationTokenRepositoryMock {
+            tokens: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for EmailVerificationTokenRepositoryMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EmailVerificationTokenRepositoryTrait for EmailVerificationTokenRepositoryMock {
+    fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailVerificationToken>, EmailVerificationTokenRepositoryError> {
+        for (_, t) in self.tokens.read().unwrap().iter() {
+            if constant_time_eq(&t.token_hash, token_hash) {
+                return Ok(Some(t.clone()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn save(
+        &self,
+        token: &EmailVerificationToken,
+    ) -> Result<(), EmailVerificationTokenRepositoryError> {
+        match self
+            .tokens
+            .write()
+            .unwrap()
+            .insert(token.id.clone(), token.clone())
+            .is_none()
+        {
+            true => Ok(()),
+            false => Err(EmailVerificationTokenRepositoryError::Save),
+        }
+    }
+
+    fn delete_users_tokens(
+        &self,
+        user_id: &Id,
+    ) -> Result<(), EmailVerificationTokenRepositoryError> {
+        self.tokens
+            .write()
+            .unwrap()
+            .retain(|_, t| &t.user_id != user_id);
+
+        Ok(())
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn EmailVerificationTokenRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::entity::login_attempts::FailedLoginAttempts;
+use crate::repository::login_attempt_repository::{
+    LoginAttemptRepositoryError, LoginAttemptRepositoryTrait,
+};
+
+pub struct LoginAttemptRepositoryMock {
+    attempts: RwLock<HashMap<String, FailedLoginAttempts>>,
+}
+
+impl LoginAttemptRepositoryMock {
+    pub fn new() -> LoginAttemptRepositoryMock {
+        LoginAttemptRepositoryMock {
+            attempts: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for LoginAttemptRepositoryMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoginAttemptRepositoryTrait for LoginAttemptRepositoryMock {
+    fn find(&self, key: &str) -> Result<Option<FailedLoginAttempts>, LoginAttemptRepositoryError> {
+        Ok(self.attempts.read().unwrap().get(key).cloned())
+    }
+
+    fn save(&self, attempts: &FailedLoginAttempts) -> Result<(), LoginAttemptRepositoryError> {
+        self.attempts
+            .write()
+            .unwrap()
+            .insert(attempts.key.clone(), attempts.clone());
+
+        Ok(())
+    }
+
+    fn increment(
+        &self,
+        key: &str,
+        failed_at: DateTime<Utc>,
+        expired_before: DateTime<Utc>,
+    ) -> Result<FailedLoginAttempts, LoginAttemptRepositoryError> {
+        let mut attempts = self.attempts.write().unwrap();
+        let counter = attempts
+            .entry(key.to_string())
+            .or_insert_with(|| FailedLoginAttempts::new(key.to_string()));
+
+        if counter.last_failed_at < expired_before {
+            counter.count = 0;
+        }
+
+        counter.count += 1;
+        counter.last_failed_at = failed_at;
+
+        Ok(counter.clone())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), LoginAttemptRepositoryError> {
+        self.attempts.write().unwrap().remove(key);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,8 @@
 pub mod broken;
+pub mod email_verification_token_repository_mock;
+pub mod login_attempt_repository_mock;
+pub mod outbox_repository_mock;
 pub mod password_credential_repository_mock;
+pub mod refresh_token_repository_mock;
 pub mod restore_password_token_repository_mock;
 pub mod session_repository_mock;
@@ -0,0 +1,71 @@
+use std::sync::RwLock;
+
+use crate::entity::outbox_message::OutboxMessage;
+use crate::repository::outbox_repository::{OutboxRepositoryError, OutboxRepositoryTrait};
+use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+
+/// In-memory outbox, messages are kept in order of saving
+pub struct OutboxRepositoryMock {
+    messages: RwLock<Vec<OutboxMessage>>,
+}
+
+impl OutboxRepositoryMock {
+    pub fn new() -> OutboxRepositoryMock {
+        OutboxRepositoryMock {
+            messages: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for OutboxRepositoryMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OutboxRepositoryTrait for OutboxRepositoryMock {
+    fn save(&self, message: &OutboxMessage) -> Result<(), OutboxRepositoryError> {
+        let mut messages = self.messages.write().unwrap();
+
+        if messages.iter().any(|m| *m.id == *message.id) {
+            return Err(OutboxRepositoryError::Save);
+        }
+
+        messages.push(message.clone());
+
+        Ok(())
+    }
+
+    fn find_unpublished(&self, limit: usize) -> Result<Vec<OutboxMessage>, OutboxRepositoryError> {
+        Ok(self
+            .messages
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|m| !m.is_published())
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    fn mark_published(&self, id: &Id) -> Result<(), OutboxRepositoryError> {
+        match self
+            .messages
+            .write()
+            .unwrap()
+            .iter_mut()
+            .find(|m| *m.id == **id)
+        {
+            Some(m) => {
+                m.mark_published();
+                Ok(())
+            }
+            None => Err(OutboxRepositoryError::Update),
+        }
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn OutboxRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
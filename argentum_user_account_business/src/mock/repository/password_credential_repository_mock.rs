@@ -1,17 +1,18 @@
 use crate::entity::credential::PasswordCredential;
 use crate::repository::password_credential_repository::PasswordCredentialRepository;
 use argentum_standard_business::data_type::id::Id;
-use std::cell::RefCell;
+use argentum_standard_business::transaction::{Bound, Transaction};
 use std::collections::HashMap;
+use std::sync::RwLock;
 /// TODO: NTS!!!!1111
 pub struct PasswordCredentialRepositoryMock {
-    credentials: RefCell<HashMap<String, PasswordCredential>>,
+    credentials: RwLock<HashMap<String, PasswordCredential>>,
 }
 
 impl PasswordCredentialRepositoryMock {
     pub fn new() -> Self {
         PasswordCredentialRepositoryMock {
-            credentials: RefCell::new(HashMap::new()),
+            credentials: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -25,20 +26,27 @@ impl Default for PasswordCredentialRepositoryMock {
 impl PasswordCredentialRepository for PasswordCredentialRepositoryMock {
     fn save(&self, cred: &PasswordCredential) {
         self.credentials
-            .borrow_mut()
+            .write()
+            .unwrap()
             .insert(cred.user_id.to_string(), cred.clone());
     }
 
     fn find_by_user_id(&self, id: &Id) -> Option<PasswordCredential> {
         self.credentials
-            .borrow()
+            .read()
+            .unwrap()
             .get(&*id.to_string())
-            .map(|c| PasswordCredential::new(c.user_id.clone(), c.password.clone(), c.salt.clone()))
+            .map(|c| PasswordCredential::new(c.user_id.clone(), c.password.clone()))
     }
 
     fn delete(&self, cred: &PasswordCredential) {
         self.credentials
-            .borrow_mut()
+            .write()
+            .unwrap()
             .remove(&cred.user_id.to_string());
     }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn PasswordCredentialRepository + '_> {
+        Bound::Shared(self)
+    }
 }
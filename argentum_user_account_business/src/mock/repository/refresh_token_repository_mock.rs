@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::entity::refresh_token::RefreshToken;
+use crate::repository::refresh_token_repository::{
+    RefreshTokenRepositoryError, RefreshTokenRepositoryTrait,
+};
+use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::token::constant_time_eq;
+
+pub struct RefreshTokenRepositoryMock {
+    tokens: RwLock<HashMap<Id, RefreshToken>>,
+}
+
+impl RefreshTokenRepositoryMock {
+    pub fn new() -> RefreshTokenRepositoryMock {
+        RefreshTokenRepositoryMock {
+            tokens: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for RefreshTokenRepositoryMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RefreshTokenRepositoryTrait for RefreshTokenRepositoryMock {
+    fn find_by_token_hash(&self, token_hash: &str) -> Option<RefreshToken> {
+        for (_, t) in self.tokens.read().unwrap().iter() {
+            if constant_time_eq(&t.token_hash, token_hash) {
+                return Some(t.clone());
+            }
+        }
+
+        None
+    }
+
+    fn save(&self, token: &RefreshToken) -> Result<(), RefreshTokenRepositoryError> {
+        match self
+            .tokens
+            .write()
+            .unwrap()
+            .insert(token.id.clone(), token.clone())
+            .is_none()
+        {
+            true => Ok(()),
+            false => Err(RefreshTokenRepositoryError::Save),
+        }
+    }
+
+    fn mark_used_if_unused(
+        &self,
+        token: &RefreshToken,
+    ) -> Result<bool, RefreshTokenRepositoryError> {
+        if token.used_at.is_none() {
+            return Err(RefreshTokenRepositoryError::Update);
+        }
+
+        let mut tokens = self.tokens.write().unwrap();
+
+        match tokens.get_mut(&token.id) {
+            Some(t) if t.is_used() => Ok(false),
+            Some(t) => {
+                t.used_at = token.used_at;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn delete_sessions_tokens(&self, session_id: &Id) -> Result<(), RefreshTokenRepositoryError> {
+        self.tokens
+            .write()
+            .unwrap()
+            .retain(|_, t| &t.session_id != session_id);
+
+        Ok(())
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn RefreshTokenRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
+}
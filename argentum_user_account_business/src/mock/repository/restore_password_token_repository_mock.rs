@@ -1,20 +1,22 @@
-use std::cell::RefCell;
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 use crate::entity::restore_password_token::RestorePasswordToken;
 use crate::repository::restore_password_token_repository::{
     RestorePasswordTokenRepositoryError, RestorePasswordTokenRepositoryTrait,
 };
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::token::constant_time_eq;
 
 pub struct RestorePasswordTokenRepositoryMock {
-    tokens: RefCell<HashMap<Id, RestorePasswordToken>>,
+    tokens: RwLock<HashMap<Id, RestorePasswordToken>>,
 }
 
 impl RestorePasswordTokenRepositoryMock {
     pub fn new() -> RestorePasswordTokenRepositoryMock {
         RestorePasswordTokenRepositoryMock {
-            tokens: RefCell::new(HashMap::new()),
+            tokens: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -30,24 +32,31 @@ impl RestorePasswordTokenRepositoryTrait for RestorePasswordTokenRepositoryMock
         &self,
         id: &Id,
     ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
-        let result =
-            self.tokens.borrow().get(id).map(|t| {
-                RestorePasswordToken::new(t.id.clone(), t.user_id.clone(), t.token.clone())
+        let result = self
+            .tokens
+            .read()
+            .unwrap()
+            .get(id)
+            .map(|t| RestorePasswordToken {
+                id: t.id.clone(),
+                user_id: t.user_id.clone(),
+                token_hash: t.token_hash.clone(),
+                created_at: t.created_at,
             });
 
         Ok(result)
     }
 
-    fn find_by_token(
+    fn find_by_token_hash(
         &self,
-        token: String,
+        token_hash: &str,
     ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
-        for (_, t) in self.tokens.borrow().iter() {
-            if t.token == token {
+        for (_, t) in self.tokens.read().unwrap().iter() {
+            if constant_time_eq(&t.token_hash, token_hash) {
                 return Ok(Some(RestorePasswordToken {
                     id: t.id.clone(),
                     user_id: t.user_id.clone(),
-                    token: t.token.clone(),
+                    token_hash: t.token_hash.clone(),
                     created_at: t.created_at,
                 }));
             }
@@ -65,13 +74,14 @@ impl RestorePasswordTokenRepositoryTrait for RestorePasswordTokenRepositoryMock
         let t = RestorePasswordToken {
             id: token.id.clone(),
             user_id: token.user_id.clone(),
-            token: token.token.clone(),
+            token_hash: token.token_hash.clone(),
             created_at: token.created_at,
         };
 
         match self
             .tokens
-            .borrow_mut()
+            .write()
+            .unwrap()
             .insert(token.id.clone(), t)
             .is_none()
         {
@@ -81,20 +91,19 @@ impl RestorePasswordTokenRepositoryTrait for RestorePasswordTokenRepositoryMock
     }
 
     fn delete_users_tokens(&self, user_id: &Id) -> Result<(), RestorePasswordTokenRepositoryError> {
-        let mut id: Option<Id> = None;
-
-        for (k, t) in self.tokens.borrow().iter() {
-            if &t.user_id == user_id {
-                id = Some(k.clone());
+        self.tokens
+            .write()
+            .unwrap()
+            .retain(|_, t| &t.user_id != user_id);
 
-                break;
-            }
-        }
+        Ok(())
+    }
 
-        if let Some(id) = id {
-            self.tokens.borrow_mut().remove(&id);
-        }
+    fn delete_if_exists(&self, id: &Id) -> Result<bool, RestorePasswordTokenRepositoryError> {
+        Ok(self.tokens.write().unwrap().remove(id).is_some())
+    }
 
-        Ok(())
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn RestorePasswordTokenRepositoryTrait + '_> {
+        Bound::Shared(self)
     }
 }
@@ -1,18 +1,20 @@
-use std::cell::RefCell;
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 use crate::entity::session::Session;
 use crate::repository::session_repository::{SessionRepositoryError, SessionRepositoryTrait};
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use argentum_user_business::token::constant_time_eq;
 
 pub struct SessionRepositoryMock {
-    sessions: RefCell<HashMap<Id, Session>>,
+    sessions: RwLock<HashMap<Id, Session>>,
 }
 
 impl SessionRepositoryMock {
     pub fn new() -> SessionRepositoryMock {
         SessionRepositoryMock {
-            sessions: RefCell::new(HashMap::new()),
+            sessions: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -25,39 +27,38 @@ impl Default for SessionRepositoryMock {
 
 impl SessionRepositoryTrait for SessionRepositoryMock {
     fn find(&self, id: &Id) -> Option<Session> {
-        self.sessions
-            .borrow()
-            .get(id)
-            .map(|s| Session::new(s.id.clone(), s.user_id.clone(), s.token.clone()))
+        self.sessions.read().unwrap().get(id).cloned()
     }
 
-    fn find_by_token(&self, token: String) -> Option<Session> {
-        for (_, s) in self.sessions.borrow().iter() {
-            if s.token == token {
-                return Some(Session::new(
-                    s.id.clone(),
-                    s.user_id.clone(),
-                    s.token.clone(),
-                ));
+    fn find_by_token_hash(&self, token_hash: &str) -> Option<Session> {
+        for (_, s) in self.sessions.read().unwrap().iter() {
+            if constant_time_eq(&s.token_hash, token_hash) {
+                return Some(s.clone());
             }
         }
 
         None
     }
 
+    fn find_by_user_id(&self, user_id: &Id) -> Result<Vec<Session>, SessionRepositoryError> {
+        Ok(self
+            .sessions
+            .read()
+            .unwrap()
+            .values()
+            .filter(|s| &s.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
     fn save(&self, session: &Session) -> Result<(), SessionRepositoryError> {
         // TODO: check if key exists
 
-        let s = Session::new(
-            session.id.clone(),
-            session.user_id.clone(),
-            session.token.clone(),
-        );
-
         match self
             .sessions
-            .borrow_mut()
-            .insert(session.id.clone(), s)
+            .write()
+            .unwrap()
+            .insert(session.id.clone(), session.clone())
             .is_none()
         {
             true => Ok(()),
@@ -65,21 +66,34 @@ impl SessionRepositoryTrait for SessionRepositoryMock {
         }
     }
 
-    fn delete_users_sessions(&self, user_id: &Id) -> Result<(), SessionRepositoryError> {
-        let mut id: Option<Id> = None;
-
-        for (k, s) in self.sessions.borrow().iter() {
-            if &s.user_id == user_id {
-                id = Some(k.clone());
+    fn update(&self, session: &Session) -> Result<(), SessionRepositoryError> {
+        let mut sessions = self.sessions.write().unwrap();
 
-                break;
+        match sessions.get_mut(&session.id) {
+            Some(s) => {
+                *s = session.clone();
+                Ok(())
             }
+            None => Err(SessionRepositoryError::Update),
         }
+    }
 
-        if let Some(id) = id {
-            self.sessions.borrow_mut().remove(&id);
-        }
+    fn delete(&self, id: &Id) -> Result<(), SessionRepositoryError> {
+        self.sessions.write().unwrap().remove(id);
 
         Ok(())
     }
+
+    fn delete_users_sessions(&self, user_id: &Id) -> Result<(), SessionRepositoryError> {
+        self.sessions
+            .write()
+            .unwrap()
+            .retain(|_, s| &s.user_id != user_id);
+
+        Ok(())
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn SessionRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
 }
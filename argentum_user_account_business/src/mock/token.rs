@@ -1,17 +1,110 @@
 use argentum_standard_business::data_type::id::Id;
-use argentum_user_business::token::GeneratorTrait;
+use argentum_user_business::token::{
+    AccessTokenClaims, GeneratorTrait, SignedTokenVerifierTrait, TokenHasherTrait,
+    TokenVerificationError,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
 
-pub struct TokenGeneratorMock {}
+/// Produces `Test token {n}` strings, every token is unique
+pub struct TokenGeneratorMock {
+    counter: AtomicU64,
+}
 
 impl TokenGeneratorMock {
     #[allow(clippy::new_without_default)]
     pub fn new() -> TokenGeneratorMock {
-        TokenGeneratorMock {}
+        TokenGeneratorMock {
+            counter: AtomicU64::new(0),
+        }
     }
 }
 
 impl GeneratorTrait for TokenGeneratorMock {
     fn generate(&self, _user_id: &Id) -> String {
-        String::from("Test token")
+        let n = self.counter.fetch_add(1, Ordering::SeqCst) + 1;
+
+        format!("Test token {}", n)
+    }
+}
+
+/// Produces `hashed:{token}` strings
+pub struct TokenHasherMock {}
+
+impl TokenHasherMock {
+    pub fn new() -> TokenHasherMock {
+        TokenHasherMock {}
+    }
+}
+
+impl Default for TokenHasherMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenHasherTrait for TokenHasherMock {
+    fn hash(&self, token: &str) -> String {
+        format!("hashed:{}", token)
+    }
+}
+
+/// Keeps claims of issued tokens in memory instead of signing them
+pub struct SignedTokenVerifierMock {
+    tokens: RwLock<HashMap<String, AccessTokenClaims>>,
+}
+
+impl SignedTokenVerifierMock {
+    pub fn new() -> SignedTokenVerifierMock {
+        SignedTokenVerifierMock {
+            tokens: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn issue(&self, user_id: &Id, session_id: &Id, expires_at: DateTime<Utc>) -> String {
+        let token = format!("signed:{}:{}", user_id.to_string(), session_id.to_string());
+        let claims = AccessTokenClaims {
+            subject: user_id.clone(),
+            session_id: Some(session_id.clone()),
+            issuer: "test".to_string(),
+            audience: "test".to_string(),
+            issued_at: Utc::now(),
+            expires_at,
+        };
+
+        self.tokens.write().unwrap().insert(token.clone(), claims);
+
+        token
+    }
+}
+
+impl Default for SignedTokenVerifierMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignedTokenVerifierTrait for SignedTokenVerifierMock {
+    fn verify(&self, token: &str) -> Result<AccessTokenClaims, TokenVerificationError> {
+        let tokens = self.tokens.read().unwrap();
+        let claims = match tokens.get(token) {
+            Some(claims) => claims,
+            None => return Err(TokenVerificationError::InvalidSignature),
+        };
+
+        if claims.expires_at <= Utc::now() {
+            return Err(TokenVerificationError::Expired);
+        }
+
+        Ok(AccessTokenClaims {
+            subject: claims.subject.clone(),
+            session_id: claims.session_id.clone(),
+            issuer: claims.issuer.clone(),
+            audience: claims.audience.clone(),
+            issued_at: claims.issued_at,
+            expires_at: claims.expires_at,
+        })
     }
 }
@@ -0,0 +1,104 @@
+use crate::entity::email_verification_token::EmailVerificationToken;
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture};
+use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use std::sync::Arc;
+
+pub trait EmailVerificationTokenRepositoryTrait {
+    fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailVerificationToken>, EmailVerificationTokenRepositoryError>;
+
+    fn save(
+        &self,
+        token: &EmailVerificationToken,
+    ) -> Result<(), EmailVerificationTokenRepositoryError>;
+
+    fn delete_users_tokens(
+        &self,
+        user_id: &Id,
+    ) -> Result<(), EmailVerificationTokenRepositoryError>;
+
+    /// The repository writing within `transaction`
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Bound<'_, dyn EmailVerificationTokenRepositoryTrait + '_>;
+}
+
+/// Async counterpart of `EmailVerificationTokenRepositoryTrait`
+pub trait AsyncEmailVerificationTokenRepositoryTrait: Send + Sync {
+    fn find_by_token_hash<'a>(
+        &'a self,
+        token_hash: &'a str,
+    ) -> BoxFuture<'a, Result<Option<EmailVerificationToken>, EmailVerificationTokenRepositoryError>>;
+    fn save<'a>(
+        &'a self,
+        token: &'a EmailVerificationToken,
+    ) -> BoxFuture<'a, Result<(), EmailVerificationTokenRepositoryError>>;
+    fn delete_users_tokens<'a>(
+        &'a self,
+        user_id: &'a Id,
+    ) -> BoxFuture<'a, Result<(), EmailVerificationTokenRepositoryError>>;
+    /// The repository writing within `transaction`
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Arc<dyn AsyncEmailVerificationTokenRepositoryTrait>;
+}
+
+impl<T: EmailVerificationTokenRepositoryTrait + Send + 'static>
+    AsyncEmailVerificationTokenRepositoryTrait for Blocking<T>
+{
+    fn find_by_token_hash<'a>(
+        &'a self,
+        token_hash: &'a str,
+    ) -> BoxFuture<'a, Result<Option<EmailVerificationToken>, EmailVerificationTokenRepositoryError>>
+    {
+        ready(call(self, |r| r.find_by_token_hash(token_hash)))
+    }
+
+    fn save<'a>(
+        &'a self,
+        token: &'a EmailVerificationToken,
+    ) -> BoxFuture<'a, Result<(), EmailVerificationTokenRepositoryError>> {
+        ready(call(self, |r| r.save(token)))
+    }
+
+    fn delete_users_tokens<'a>(
+        &'a self,
+        user_id: &'a Id,
+    ) -> BoxFuture<'a, Result<(), EmailVerificationTokenRepositoryError>> {
+        ready(call(self, |r| r.delete_users_tokens(user_id)))
+    }
+
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Arc<dyn AsyncEmailVerificationTokenRepositoryTrait> {
+        Arc::new(self.bind(transaction))
+    }
+}
+
+/// Calls the wrapped repository within the transaction `repository` is bound to
+fn call<T: EmailVerificationTokenRepositoryTrait, R>(
+    repository: &Blocking<T>,
+    call: impl FnOnce(&dyn EmailVerificationTokenRepositoryTrait) -> R,
+) -> R {
+    let inner = repository.inner();
+
+    match repository.transaction() {
+        Some(transaction) => call(&*inner.within(transaction)),
+        None => call(&*inner),
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum EmailVerificationTokenRepositoryError {
+    #[error("Can't save email verification token")]
+    Save,
+
+    #[error("Can't delete email verification tokens")]
+    Delete,
+}
@@ -0,0 +1,81 @@
+use crate::entity::login_attempts::FailedLoginAttempts;
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture};
+use chrono::{DateTime, Utc};
+
+pub trait LoginAttemptRepositoryTrait {
+    fn find(&self, key: &str) -> Result<Option<FailedLoginAttempts>, LoginAttemptRepositoryError>;
+    fn save(&self, attempts: &FailedLoginAttempts) -> Result<(), LoginAttemptRepositoryError>;
+    /// Atomically counts a failure at `failed_at` and returns the counter with it.
+    /// A counter whose last failure is before `expired_before` starts over
+    fn increment(
+        &self,
+        key: &str,
+        failed_at: DateTime<Utc>,
+        expired_before: DateTime<Utc>,
+    ) -> Result<FailedLoginAttempts, LoginAttemptRepositoryError>;
+    fn delete(&self, key: &str) -> Result<(), LoginAttemptRepositoryError>;
+}
+
+/// Async counterpart of `LoginAttemptRepositoryTrait`
+pub trait AsyncLoginAttemptRepositoryTrait: Send + Sync {
+    fn find<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<FailedLoginAttempts>, LoginAttemptRepositoryError>>;
+    fn save<'a>(
+        &'a self,
+        attempts: &'a FailedLoginAttempts,
+    ) -> BoxFuture<'a, Result<(), LoginAttemptRepositoryError>>;
+    fn increment<'a>(
+        &'a self,
+        key: &'a str,
+        failed_at: DateTime<Utc>,
+        expired_before: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<FailedLoginAttempts, LoginAttemptRepositoryError>>;
+    fn delete<'a>(&'a self, key: &'a str)
+        -> BoxFuture<'a, Result<(), LoginAttemptRepositoryError>>;
+}
+
+impl<T: LoginAttemptRepositoryTrait + Send> AsyncLoginAttemptRepositoryTrait for Blocking<T> {
+    fn find<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<FailedLoginAttempts>, LoginAttemptRepositoryError>> {
+        ready(self.inner().find(key))
+    }
+
+    fn save<'a>(
+        &'a self,
+        attempts: &'a FailedLoginAttempts,
+    ) -> BoxFuture<'a, Result<(), LoginAttemptRepositoryError>> {
+        ready(self.inner().save(attempts))
+    }
+
+    fn increment<'a>(
+        &'a self,
+        key: &'a str,
+        failed_at: DateTime<Utc>,
+        expired_before: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<FailedLoginAttempts, LoginAttemptRepositoryError>> {
+        ready(self.inner().increment(key, failed_at, expired_before))
+    }
+
+    fn delete<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<(), LoginAttemptRepositoryError>> {
+        ready(self.inner().delete(key))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LoginAttemptRepositoryError {
+    #[error("Can't get login attempts")]
+    Find,
+
+    #[error("Can't save login attempts")]
+    Save,
+
+    #[error("Can't delete login attempts")]
+    Delete,
+}
@@ -0,0 +1,422 @@
+use crate::entity::login_attempts::FailedLoginAttempts;
+use crate::repository::login_attempt_repository::{
+    AsyncLoginAttemptRepositoryTrait, LoginAttemptRepositoryError, LoginAttemptRepositoryTrait,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::cmp::{max, min};
+use std::sync::Arc;
+
+/// After `free_attempts` failures every next failure doubles the delay starting
+/// from `base_delay` up to `max_delay`. After `lockout_threshold` failures the key
+/// is locked for `lockout_duration`, then the counter starts over. A counter without failures
+/// for `decay_window` also starts over, a lockout is still served in full. All durations are
+/// in seconds
+pub struct ThrottlingPolicy {
+    pub free_attempts: u32,
+    pub base_delay: u32,
+    pub max_delay: u32,
+    pub lockout_threshold: u32,
+    pub lockout_duration: u32,
+    pub decay_window: u32,
+}
+
+impl ThrottlingPolicy {
+    pub fn new(
+        free_attempts: u32,
+        base_delay: u32,
+        max_delay: u32,
+        lockout_threshold: u32,
+        lockout_duration: u32,
+    ) -> ThrottlingPolicy {
+        ThrottlingPolicy {
+            free_attempts,
+            base_delay,
+            max_delay,
+            lockout_threshold,
+            lockout_duration,
+            decay_window: lockout_duration,
+        }
+    }
+
+    pub fn with_decay_window(mut self, decay_window: u32) -> ThrottlingPolicy {
+        self.decay_window = decay_window;
+
+        self
+    }
+
+    /// A single account is attacked with few guesses per minute
+    pub fn for_account() -> ThrottlingPolicy {
+        ThrottlingPolicy::new(3, 1, 60, 10, 15 * 60)
+    }
+
+    /// A client may legitimately share an address with many users
+    pub fn for_client() -> ThrottlingPolicy {
+        ThrottlingPolicy::new(20, 1, 60, 100, 15 * 60)
+    }
+
+    fn is_locked(&self, count: u32) -> bool {
+        count >= self.lockout_threshold
+    }
+
+    fn delay(&self, count: u32) -> u32 {
+        if self.is_locked(count) {
+            return self.lockout_duration;
+        }
+
+        if count < self.free_attempts {
+            return 0;
+        }
+
+        let exponent = min(count - self.free_attempts, 31);
+        min(
+            self.base_delay.saturating_mul(1 << exponent),
+            self.max_delay,
+        )
+    }
+
+    fn blocked_until(&self, attempts: &FailedLoginAttempts) -> DateTime<Utc> {
+        attempts.last_failed_at + Duration::seconds(self.delay(attempts.count) as i64)
+    }
+
+    fn retry_after(&self, attempts: &FailedLoginAttempts, now: DateTime<Utc>) -> u32 {
+        // Rounded up, a client retrying right after `retry_after` must succeed
+        let wait = (self.blocked_until(attempts) - now).num_milliseconds();
+
+        ((max(wait, 0) + 999) / 1000) as u32
+    }
+
+    /// Failures are not counted during a lockout, so a counter last failed before this time
+    /// has either decayed or served its lockout
+    fn expired_before(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - Duration::seconds(max(self.decay_window, self.lockout_duration) as i64)
+    }
+}
+
+/// Counts failed logins per account (email) and per client (e.g. an IP address)
+pub struct LoginThrottler<'s> {
+    repository: &'s dyn LoginAttemptRepositoryTrait,
+    account_policy: ThrottlingPolicy,
+    client_policy: ThrottlingPolicy,
+}
+
+impl<'s> LoginThrottler<'s> {
+    pub fn new(
+        repository: &'s dyn LoginAttemptRepositoryTrait,
+        account_policy: ThrottlingPolicy,
+        client_policy: ThrottlingPolicy,
+    ) -> Self {
+        LoginThrottler {
+            repository,
+            account_policy,
+            client_policy,
+        }
+    }
+
+    /// Returns the number of seconds to wait before the next attempt, 0 when it's allowed
+    pub fn retry_after(
+        &self,
+        email: &str,
+        client_id: &str,
+    ) -> Result<u32, LoginAttemptRepositoryError> {
+        let now = Utc::now();
+        let mut retry_after = 0;
+
+        for (key, policy) in self.keys(email, client_id) {
+            if let Some(attempts) = self.repository.find(&key)? {
+                retry_after = max(retry_after, policy.retry_after(&attempts, now));
+            }
+        }
+
+        Ok(retry_after)
+    }
+
+    /// Counts a failure and returns the number of seconds to wait before the next attempt.
+    /// The counters are incremented by the repository, so concurrent failures are never lost
+    pub fn register_failure(
+        &self,
+        email: &str,
+        client_id: &str,
+    ) -> Result<u32, LoginAttemptRepositoryError> {
+        let now = Utc::now();
+        let mut retry_after = 0;
+
+        for (key, policy) in self.keys(email, client_id) {
+            let attempts = self
+                .repository
+                .increment(&key, now, policy.expired_before(now))?;
+            retry_after = max(retry_after, policy.retry_after(&attempts, now));
+        }
+
+        Ok(retry_after)
+    }
+
+    /// Only the account counter is reset: an attacker must not be able to clear
+    /// the client counter by logging in to an own account
+    pub fn reset(&self, email: &str) -> Result<(), LoginAttemptRepositoryError> {
+        self.repository.delete(&account_key(email))
+    }
+
+    fn keys(&self, email: &str, client_id: &str) -> Vec<(String, &ThrottlingPolicy)> {
+        keys(email, client_id, &self.account_policy, &self.client_policy)
+    }
+}
+
+/// Async counterpart of `LoginThrottler`
+pub struct AsyncLoginThrottler {
+    repository: Arc<dyn AsyncLoginAttemptRepositoryTrait>,
+    account_policy: ThrottlingPolicy,
+    client_policy: ThrottlingPolicy,
+}
+
+impl AsyncLoginThrottler {
+    pub fn new(
+        repository: Arc<dyn AsyncLoginAttemptRepositoryTrait>,
+        account_policy: ThrottlingPolicy,
+        client_policy: ThrottlingPolicy,
+    ) -> Self {
+        AsyncLoginThrottler {
+            repository,
+            account_policy,
+            client_policy,
+        }
+    }
+
+    /// Returns the number of seconds to wait before the next attempt, 0 when it's allowed
+    pub async fn retry_after(
+        &self,
+        email: &str,
+        client_id: &str,
+    ) -> Result<u32, LoginAttemptRepositoryError> {
+        let now = Utc::now();
+        let mut retry_after = 0;
+
+        for (key, policy) in self.keys(email, client_id) {
+            if let Some(attempts) = self.repository.find(&key).await? {
+                retry_after = max(retry_after, policy.retry_after(&attempts, now));
+            }
+        }
+
+        Ok(retry_after)
+    }
+
+    /// See `LoginThrottler::register_failure`
+    pub async fn register_failure(
+        &self,
+        email: &str,
+        client_id: &str,
+    ) -> Result<u32, LoginAttemptRepositoryError> {
+        let now = Utc::now();
+        let mut retry_after = 0;
+
+        for (key, policy) in self.keys(email, client_id) {
+            let attempts = self
+                .repository
+                .increment(&key, now, policy.expired_before(now))
+                .await?;
+            retry_after = max(retry_after, policy.retry_after(&attempts, now));
+        }
+
+        Ok(retry_after)
+    }
+
+    /// Only the account counter is reset, see `LoginThrottler::reset`
+    pub async fn reset(&self, email: &str) -> Result<(), LoginAttemptRepositoryError> {
+        self.repository.delete(&account_key(email)).await
+    }
+
+    fn keys(&self, email: &str, client_id: &str) -> Vec<(String, &ThrottlingPolicy)> {
+        keys(email, client_id, &self.account_policy, &self.client_policy)
+    }
+}
+
+fn keys<'p>(
+    email: &str,
+    client_id: &str,
+    account_policy: &'p ThrottlingPolicy,
+    client_policy: &'p ThrottlingPolicy,
+) -> Vec<(String, &'p ThrottlingPolicy)> {
+    vec![
+        (account_key(email), account_policy),
+        (format!("client:{}", client_id), client_policy),
+    ]
+}
+
+fn account_key(email: &str) -> String {
+    format!("account:{}", email.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::repository::login_attempt_repository_mock::LoginAttemptRepositoryMock;
+    use crate::repository::login_attempt_repository::LoginAttemptRepositoryTrait;
+    use crate::repository::login_throttler::AsyncLoginThrottler;
+    use crate::repository::login_throttler::{LoginThrottler, ThrottlingPolicy};
+    use argentum_standard_business::asynchronous::Blocking;
+    use argentum_standard_business::mock::executor::block_on;
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+
+    const EMAIL: &str = "test@test-mail.com";
+
+    #[test]
+    fn test_policy_delay() {
+        let policy = ThrottlingPolicy::new(3, 1, 60, 10, 900);
+
+        assert_eq!(0, policy.delay(2));
+        assert_eq!(1, policy.delay(3));
+        assert_eq!(2, policy.delay(4));
+        assert_eq!(32, policy.delay(8));
+        assert_eq!(60, policy.delay(9));
+        assert_eq!(900, policy.delay(10));
+        assert_eq!(ThrottlingPolicy::new(0, 1, 60, 100, 900).delay(99), 60);
+    }
+
+    #[test]
+    fn test_backoff_and_lockout() -> Result<(), &'static str> {
+        let repository = LoginAttemptRepositoryMock::new();
+        let throttler = LoginThrottler::new(
+            &repository,
+            ThrottlingPolicy::new(2, 10, 60, 4, 900),
+            ThrottlingPolicy::for_client(),
+        );
+
+        for _ in 0..2 {
+            throttler.register_failure(EMAIL, "1.1.1.1").unwrap();
+        }
+        assert_eq!(10, throttler.retry_after(EMAIL, "2.2.2.2").unwrap());
+
+        throttler.register_failure(EMAIL, "1.1.1.1").unwrap();
+        assert_eq!(20, throttler.retry_after(EMAIL, "2.2.2.2").unwrap());
+
+        throttler.register_failure(EMAIL, "1.1.1.1").unwrap();
+        assert_eq!(900, throttler.retry_after(EMAIL, "2.2.2.2").unwrap());
+        assert_eq!(
+            0,
+            throttler
+                .retry_after("other@test-mail.com", "2.2.2.2")
+                .unwrap()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_client_is_throttled_across_accounts() {
+        let repository = LoginAttemptRepositoryMock::new();
+        let throttler = LoginThrottler::new(
+            &repository,
+            ThrottlingPolicy::for_account(),
+            ThrottlingPolicy::new(2, 5, 60, 10, 900),
+        );
+
+        throttler
+            .register_failure("a@test-mail.com", "1.1.1.1")
+            .unwrap();
+        throttler
+            .register_failure("b@test-mail.com", "1.1.1.1")
+            .unwrap();
+
+        assert_eq!(
+            5,
+            throttler.retry_after("c@test-mail.com", "1.1.1.1").unwrap()
+        );
+        assert_eq!(
+            0,
+            throttler.retry_after("c@test-mail.com", "2.2.2.2").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_counter_starts_over_after_lockout() {
+        let repository = LoginAttemptRepositoryMock::new();
+        let throttler = LoginThrottler::new(
+            &repository,
+            ThrottlingPolicy::new(2, 10, 60, 4, 900),
+            ThrottlingPolicy::for_client(),
+        );
+
+        for _ in 0..4 {
+            throttler.register_failure(EMAIL, "1.1.1.1").unwrap();
+        }
+
+        let key = "account:test@test-mail.com";
+        let mut attempts = repository.find(key).unwrap().unwrap();
+        attempts.last_failed_at = Utc::now() - Duration::seconds(901);
+        repository.save(&attempts).unwrap();
+        assert_eq!(0, throttler.retry_after(EMAIL, "1.1.1.1").unwrap());
+
+        throttler.register_failure(EMAIL, "1.1.1.1").unwrap();
+        assert_eq!(1, repository.find(key).unwrap().unwrap().count);
+    }
+
+    #[test]
+    fn test_client_counter_decays() {
+        let repository = LoginAttemptRepositoryMock::new();
+        let throttler = LoginThrottler::new(
+            &repository,
+            ThrottlingPolicy::for_account(),
+            ThrottlingPolicy::new(1, 5, 60, 10, 300).with_decay_window(600),
+        );
+
+        for _ in 0..3 {
+            throttler
+                .register_failure("a@test-mail.com", "1.1.1.1")
+                .unwrap();
+        }
+
+        let key = "client:1.1.1.1";
+        let mut attempts = repository.find(key).unwrap().unwrap();
+        attempts.last_failed_at = Utc::now() - Duration::seconds(601);
+        repository.save(&attempts).unwrap();
+
+        assert_eq!(
+            5,
+            throttler
+                .register_failure("b@test-mail.com", "1.1.1.1")
+                .unwrap()
+        );
+        assert_eq!(1, repository.find(key).unwrap().unwrap().count);
+    }
+
+    #[test]
+    fn test_reset() {
+        let repository = LoginAttemptRepositoryMock::new();
+        let throttler = LoginThrottler::new(
+            &repository,
+            ThrottlingPolicy::new(1, 10, 60, 4, 900),
+            ThrottlingPolicy::new(1, 5, 60, 4, 900),
+        );
+
+        throttler.register_failure(EMAIL, "1.1.1.1").unwrap();
+        throttler.reset("Test@Test-Mail.com").unwrap();
+
+        assert_eq!(0, throttler.retry_after(EMAIL, "2.2.2.2").unwrap());
+        assert_eq!(5, throttler.retry_after(EMAIL, "1.1.1.1").unwrap());
+    }
+
+    #[test]
+    fn test_async_throttler_backoff_and_reset() -> Result<(), &'static str> {
+        let repository = Arc::new(Blocking::new(LoginAttemptRepositoryMock::new()));
+        let throttler = AsyncLoginThrottler::new(
+            repository.clone(),
+            ThrottlingPolicy::new(2, 10, 60, 4, 900),
+            ThrottlingPolicy::for_client(),
+        );
+
+        for _ in 0..3 {
+            block_on(throttler.register_failure(EMAIL, "1.1.1.1")).unwrap();
+        }
+        assert_eq!(
+            20,
+            block_on(throttler.retry_after(EMAIL, "2.2.2.2")).unwrap()
+        );
+
+        block_on(throttler.reset(EMAIL)).unwrap();
+        assert_eq!(
+            0,
+            block_on(throttler.retry_after(EMAIL, "2.2.2.2")).unwrap()
+        );
+
+        Ok(())
+    }
+}
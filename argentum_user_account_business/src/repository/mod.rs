@@ -1,6 +1,11 @@
 pub mod credential_writer;
+pub mod email_verification_token_repository;
+pub mod login_attempt_repository;
+pub mod login_throttler;
+pub mod outbox_repository;
 pub mod password_credential_checker;
 pub mod password_credential_repository;
 pub mod password_credential_writer;
+pub mod refresh_token_repository;
 pub mod restore_password_token_repository;
 pub mod session_repository;
@@ -0,0 +1,168 @@
+use crate::entity::outbox_message::OutboxMessage;
+use argentum_event_business::event::DomainEvent;
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{Bound, Transaction};
+use std::sync::Arc;
+
+/// Keeps events until they are delivered. Implementations share the storage of
+/// user and session repositories, so events are saved in the same unit of work
+pub trait OutboxRepositoryTrait {
+    fn save(&self, message: &OutboxMessage) -> Result<(), OutboxRepositoryError>;
+    /// The oldest not published messages first
+    fn find_unpublished(&self, limit: usize) -> Result<Vec<OutboxMessage>, OutboxRepositoryError>;
+    fn mark_published(&self, id: &Id) -> Result<(), OutboxRepositoryError>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Bound<'_, dyn OutboxRepositoryTrait + '_>;
+}
+
+/// Async counterpart of `OutboxRepositoryTrait`
+pub trait AsyncOutboxRepositoryTrait: Send + Sync {
+    fn save<'a>(
+        &'a self,
+        message: &'a OutboxMessage,
+    ) -> BoxFuture<'a, Result<(), OutboxRepositoryError>>;
+    /// The oldest not published messages first
+    fn find_unpublished(
+        &self,
+        limit: usize,
+    ) -> BoxFuture<'_, Result<Vec<OutboxMessage>, OutboxRepositoryError>>;
+    fn mark_published<'a>(&'a self, id: &'a Id)
+        -> BoxFuture<'a, Result<(), OutboxRepositoryError>>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncOutboxRepositoryTrait>;
+}
+
+impl<T: OutboxRepositoryTrait + Send + 'static> AsyncOutboxRepositoryTrait for Blocking<T> {
+    fn save<'a>(
+        &'a self,
+        message: &'a OutboxMessage,
+    ) -> BoxFuture<'a, Result<(), OutboxRepositoryError>> {
+        ready(call(self, |r| r.save(message)))
+    }
+
+    fn find_unpublished(
+        &self,
+        limit: usize,
+    ) -> BoxFuture<'_, Result<Vec<OutboxMessage>, OutboxRepositoryError>> {
+        ready(call(self, |r| r.find_unpublished(limit)))
+    }
+
+    fn mark_published<'a>(
+        &'a self,
+        id: &'a Id,
+    ) -> BoxFuture<'a, Result<(), OutboxRepositoryError>> {
+        ready(call(self, |r| r.mark_published(id)))
+    }
+
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncOutboxRepositoryTrait> {
+        Arc::new(self.bind(transaction))
+    }
+}
+
+/// Calls the wrapped repository within the transaction `repository` is bound to
+fn call<T: OutboxRepositoryTrait, R>(
+    repository: &Blocking<T>,
+    call: impl FnOnce(&dyn OutboxRepositoryTrait) -> R,
+) -> R {
+    let inner = repository.inner();
+
+    match repository.transaction() {
+        Some(transaction) => call(&*inner.within(transaction)),
+        None => call(&*inner),
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OutboxRepositoryError {
+    #[error("Can't save outbox message")]
+    Save,
+
+    #[error("Can't find outbox messages")]
+    Find,
+
+    #[error("Can't update outbox message")]
+    Update,
+}
+
+/// Publishes events by writing them to the outbox, a relay delivers them later
+pub struct OutboxEventPublisher<'s> {
+    outbox_repository: &'s dyn OutboxRepositoryTrait,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> OutboxEventPublisher<'s> {
+    pub fn new(
+        outbox_repository: &'s dyn OutboxRepositoryTrait,
+        id_factory: &'s dyn IdFactory,
+    ) -> OutboxEventPublisher<'s> {
+        OutboxEventPublisher {
+            outbox_repository,
+            id_factory,
+        }
+    }
+
+    /// Call it in the transaction of the state change the event describes: the message is
+    /// written only if the change is committed. An error should fail the transaction
+    pub fn publish(
+        &self,
+        transaction: &Transaction,
+        event: &dyn DomainEvent,
+    ) -> Result<(), OutboxRepositoryError> {
+        let message = OutboxMessage::new(self.id_factory.create(), event);
+
+        self.outbox_repository.within(transaction).save(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event::{PasswordChanged, PasswordRestoreRequested};
+    use crate::mock::repository::outbox_repository_mock::OutboxRepositoryMock;
+    use crate::repository::outbox_repository::{OutboxEventPublisher, OutboxRepositoryTrait};
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::transaction::TransactionManagerMock;
+    use argentum_standard_business::transaction::TransactionManagerTrait;
+
+    #[test]
+    fn test_outbox_publisher_stores_events() -> Result<(), &'static str> {
+        let outbox_repository = OutboxRepositoryMock::new();
+        let id_factory = IdFactoryMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let publisher = OutboxEventPublisher::new(&outbox_repository, &id_factory);
+
+        let transaction = transaction_manager
+            .begin()
+            .map_err(|_| "Can't begin transaction")?;
+        publisher
+            .publish(
+                &transaction,
+                &PasswordRestoreRequested::new(id_factory.create()),
+            )
+            .map_err(|_| "Can't publish event")?;
+        publisher
+            .publish(&transaction, &PasswordChanged::new(id_factory.create()))
+            .map_err(|_| "Can't publish event")?;
+        transaction_manager
+            .commit(transaction)
+            .map_err(|_| "Can't commit transaction")?;
+
+        let messages = outbox_repository
+            .find_unpublished(10)
+            .map_err(|_| "Can't find messages")?;
+
+        assert_eq!(2, messages.len());
+        assert_eq!(
+            "user_account.password_restore_requested",
+            messages[0].event_name
+        );
+        assert_eq!("user_account.password_changed", messages[1].event_name);
+
+        if *messages[0].id == *messages[1].id {
+            return Err("Every message should get its own id");
+        }
+
+        Ok(())
+    }
+}
@@ -1,30 +1,168 @@
-use crate::repository::password_credential_repository::PasswordCredentialRepository;
-use argentum_encryption_business::password::Validator;
+use crate::repository::password_credential_repository::{
+    AsyncPasswordCredentialRepository, PasswordCredentialRepository,
+};
+use argentum_encryption_business::password::{
+    AsyncEncryptor, AsyncValidator, Encryptor, ValidationError, Validator,
+};
 use argentum_standard_business::data_type::id::Id;
+use std::sync::Arc;
+
+const DUMMY_PASSWORD: &str = "dummy password to spend the same time on unknown users";
 
 pub struct PasswordCredentialChecker<'s> {
     repository: &'s dyn PasswordCredentialRepository,
     validator: &'s dyn Validator,
+    dummy_hash: Option<String>,
 }
 
 impl<'s> PasswordCredentialChecker<'s> {
+    /// The dummy hash is produced here by the encryptor, so the first login of an unknown user
+    /// doesn't pay for hashing and can't be told apart by its timing
     pub fn new(
         repository: &'s dyn PasswordCredentialRepository,
         validator: &'s dyn Validator,
+        encryptor: &dyn Encryptor,
     ) -> Self {
         PasswordCredentialChecker {
             repository,
             validator,
+            dummy_hash: encryptor.encrypt(DUMMY_PASSWORD).ok(),
         }
     }
 
-    pub fn check(&self, user_id: Id, password: &str) -> bool {
+    /// A user without a credential is checked against the dummy hash,
+    /// so it takes as long as a wrong password does
+    pub fn check(&self, user_id: Id, password: &str) -> Result<bool, ValidationError> {
         match self.repository.find_by_user_id(&user_id) {
+            None => {
+                self.check_against_dummy(password);
+
+                Ok(false)
+            }
+            Some(cred) => self.validator.validate(password, cred.password.as_str()),
+        }
+    }
+
+    /// Validates a password against the dummy hash and ignores the result.
+    /// Called when there is no user, so a failed login takes as long as a real check does
+    pub fn check_against_dummy(&self, password: &str) {
+        if let Some(hash) = &self.dummy_hash {
+            let _ = self.validator.validate(password, hash);
+        }
+    }
+
+    /// Stored credential was produced by another algorithm or with weaker parameters
+    /// than the encryptor's policy
+    pub fn needs_rehash(&self, user_id: &Id, encryptor: &dyn Encryptor) -> bool {
+        match self.repository.find_by_user_id(user_id) {
             None => false,
+            Some(cred) => encryptor.needs_rehash(cred.password.as_str()),
+        }
+    }
+}
+
+/// Async counterpart of `PasswordCredentialChecker`
+pub struct AsyncPasswordCredentialChecker {
+    repository: Arc<dyn AsyncPasswordCredentialRepository>,
+    validator: Arc<dyn AsyncValidator>,
+    dummy_hash: Option<String>,
+}
+
+impl AsyncPasswordCredentialChecker {
+    /// See `PasswordCredentialChecker::new`. Hashing blocks, the checker is meant to be built
+    /// once at startup
+    pub fn new(
+        repository: Arc<dyn AsyncPasswordCredentialRepository>,
+        validator: Arc<dyn AsyncValidator>,
+        encryptor: &dyn Encryptor,
+    ) -> Self {
+        AsyncPasswordCredentialChecker {
+            repository,
+            validator,
+            dummy_hash: encryptor.encrypt(DUMMY_PASSWORD).ok(),
+        }
+    }
+
+    /// See `PasswordCredentialChecker::check`
+    pub async fn check(&self, user_id: Id, password: &str) -> Result<bool, ValidationError> {
+        match self.repository.find_by_user_id(&user_id).await {
+            None => {
+                self.check_against_dummy(password).await;
+
+                Ok(false)
+            }
             Some(cred) => {
                 self.validator
-                    .validate(password, &cred.salt.as_str(), &cred.password.as_str())
+                    .validate(password, cred.password.as_str())
+                    .await
             }
         }
     }
+
+    /// See `PasswordCredentialChecker::check_against_dummy`
+    pub async fn check_against_dummy(&self, password: &str) {
+        if let Some(hash) = &self.dummy_hash {
+            let _ = self.validator.validate(password, hash).await;
+        }
+    }
+
+    pub async fn needs_rehash(&self, user_id: &Id, encryptor: &dyn AsyncEncryptor) -> bool {
+        match self.repository.find_by_user_id(user_id).await {
+            None => false,
+            Some(cred) => encryptor.needs_rehash(cred.password.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::repository::password_credential_repository_mock::PasswordCredentialRepositoryMock;
+    use crate::repository::password_credential_checker::AsyncPasswordCredentialChecker;
+    use crate::repository::password_credential_checker::PasswordCredentialChecker;
+    use argentum_encryption_business::mock::password::{EncryptorMock, ValidatorMock};
+    use argentum_standard_business::asynchronous::Blocking;
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_dummy_hash_is_built_with_checker() {
+        let repository = PasswordCredentialRepositoryMock::new();
+        let validator = ValidatorMock::new();
+        let checker =
+            PasswordCredentialChecker::new(&repository, &validator, &EncryptorMock::new());
+
+        assert!(checker.dummy_hash.is_some());
+    }
+
+    #[test]
+    fn test_missing_credential_is_checked_against_dummy() {
+        let repository = PasswordCredentialRepositoryMock::new();
+        let validator = ValidatorMock::new();
+        let checker =
+            PasswordCredentialChecker::new(&repository, &validator, &EncryptorMock::new());
+        let user_id = IdFactoryMock::new().create();
+
+        let result = checker.check(user_id, "12345");
+
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn test_async_missing_credential_is_checked_against_dummy() {
+        let repository = Arc::new(Blocking::new(PasswordCredentialRepositoryMock::new()));
+        let validator = Arc::new(Blocking::new(ValidatorMock::new()));
+        let checker = AsyncPasswordCredentialChecker::new(
+            repository.clone(),
+            validator.clone(),
+            &EncryptorMock::new(),
+        );
+        let user_id = IdFactoryMock::new().create();
+
+        let result = block_on(checker.check(user_id, "12345"));
+
+        assert!(!result.unwrap());
+        assert!(checker.dummy_hash.is_some());
+    }
 }
@@ -1,5 +1,8 @@
 use crate::entity::credential::PasswordCredential;
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture};
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use std::sync::Arc;
 
 pub trait PasswordCredentialRepository {
     fn save(&self, cred: &PasswordCredential);
@@ -7,4 +10,54 @@ pub trait PasswordCredentialRepository {
     fn find_by_user_id(&self, id: &Id) -> Option<PasswordCredential>;
 
     fn delete(&self, cred: &PasswordCredential);
+
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction)
+        -> Bound<'_, dyn PasswordCredentialRepository + '_>;
+}
+
+/// Async counterpart of `PasswordCredentialRepository`
+pub trait AsyncPasswordCredentialRepository: Send + Sync {
+    fn save<'a>(&'a self, cred: &'a PasswordCredential) -> BoxFuture<'a, ()>;
+    fn find_by_user_id<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Option<PasswordCredential>>;
+    fn delete<'a>(&'a self, cred: &'a PasswordCredential) -> BoxFuture<'a, ()>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncPasswordCredentialRepository>;
+}
+
+impl<T: PasswordCredentialRepository + Send + 'static> AsyncPasswordCredentialRepository
+    for Blocking<T>
+{
+    fn save<'a>(&'a self, cred: &'a PasswordCredential) -> BoxFuture<'a, ()> {
+        call(self, |r| r.save(cred));
+
+        ready(())
+    }
+
+    fn find_by_user_id<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Option<PasswordCredential>> {
+        ready(call(self, |r| r.find_by_user_id(id)))
+    }
+
+    fn delete<'a>(&'a self, cred: &'a PasswordCredential) -> BoxFuture<'a, ()> {
+        call(self, |r| r.delete(cred));
+
+        ready(())
+    }
+
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncPasswordCredentialRepository> {
+        Arc::new(self.bind(transaction))
+    }
+}
+
+/// Calls the wrapped repository within the transaction `repository` is bound to
+fn call<T: PasswordCredentialRepository, R>(
+    repository: &Blocking<T>,
+    call: impl FnOnce(&dyn PasswordCredentialRepository) -> R,
+) -> R {
+    let inner = repository.inner();
+
+    match repository.transaction() {
+        Some(transaction) => call(&*inner.within(transaction)),
+        None => call(&*inner),
+    }
 }
@@ -1,11 +1,20 @@
 use crate::entity::credential::{Credential, PasswordCredential};
 use crate::repository::credential_writer::CredentialWriterTrait;
-use crate::repository::password_credential_repository::PasswordCredentialRepository;
+use crate::repository::password_credential_repository::{
+    AsyncPasswordCredentialRepository, PasswordCredentialRepository,
+};
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use std::sync::Arc;
 
 pub trait PasswordCredentialWriterTrait<'s>: CredentialWriterTrait {
     fn write_password_credentials(&self, cred: &PasswordCredential);
     fn delete_password_credentials_for_user(&self, user_id: &Id);
+    /// The writer writing within `transaction`
+    fn within<'a>(
+        &'a self,
+        transaction: &Transaction,
+    ) -> Box<dyn PasswordCredentialWriterTrait<'a> + 'a>;
 }
 
 impl<'s, T: PasswordCredentialWriterTrait<'s>> CredentialWriterTrait for T {
@@ -24,12 +33,14 @@ impl<'s, T: PasswordCredentialWriterTrait<'s>> CredentialWriterTrait for T {
 }
 
 pub struct PasswordCredentialWriter<'s> {
-    repository: &'s dyn PasswordCredentialRepository,
+    repository: Bound<'s, dyn PasswordCredentialRepository + 's>,
 }
 
 impl<'s> PasswordCredentialWriter<'s> {
     pub fn new(repository: &'s dyn PasswordCredentialRepository) -> Self {
-        PasswordCredentialWriter { repository }
+        PasswordCredentialWriter {
+            repository: Bound::Shared(repository),
+        }
     }
 }
 
@@ -43,4 +54,39 @@ impl<'s> PasswordCredentialWriterTrait<'s> for PasswordCredentialWriter<'s> {
             self.repository.delete(&cred);
         }
     }
+
+    fn within<'a>(
+        &'a self,
+        transaction: &Transaction,
+    ) -> Box<dyn PasswordCredentialWriterTrait<'a> + 'a> {
+        Box::new(PasswordCredentialWriter {
+            repository: self.repository.within(transaction),
+        })
+    }
+}
+
+/// Async counterpart of `PasswordCredentialWriter`
+pub struct AsyncPasswordCredentialWriter {
+    repository: Arc<dyn AsyncPasswordCredentialRepository>,
+}
+
+impl AsyncPasswordCredentialWriter {
+    pub fn new(repository: Arc<dyn AsyncPasswordCredentialRepository>) -> Self {
+        AsyncPasswordCredentialWriter { repository }
+    }
+
+    pub async fn write_password_credentials(&self, cred: &PasswordCredential) {
+        self.repository.save(cred).await;
+    }
+
+    pub async fn delete_password_credentials_for_user(&self, user_id: &Id) {
+        if let Some(cred) = self.repository.find_by_user_id(user_id).await {
+            self.repository.delete(&cred).await;
+        }
+    }
+
+    /// The writer writing within `transaction`
+    pub fn within(&self, transaction: &Transaction) -> AsyncPasswordCredentialWriter {
+        AsyncPasswordCredentialWriter::new(self.repository.within(transaction))
+    }
 }
@@ -0,0 +1,104 @@
+use crate::entity::refresh_token::RefreshToken;
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture};
+use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use std::sync::Arc;
+
+pub trait RefreshTokenRepositoryTrait {
+    fn find_by_token_hash(&self, token_hash: &str) -> Option<RefreshToken>;
+    fn save(&self, token: &RefreshToken) -> Result<(), RefreshTokenRepositoryError>;
+    /// Stores `used_at` of the token unless the stored one is used already, in one step.
+    /// Tells whether the token is claimed: of concurrent callers only one gets `true`,
+    /// a used or deleted token gives `false`
+    fn mark_used_if_unused(
+        &self,
+        token: &RefreshToken,
+    ) -> Result<bool, RefreshTokenRepositoryError>;
+    /// Deletes the whole token family of a session
+    fn delete_sessions_tokens(&self, session_id: &Id) -> Result<(), RefreshTokenRepositoryError>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Bound<'_, dyn RefreshTokenRepositoryTrait + '_>;
+}
+
+/// Async counterpart of `RefreshTokenRepositoryTrait`
+pub trait AsyncRefreshTokenRepositoryTrait: Send + Sync {
+    fn find_by_token_hash<'a>(&'a self, token_hash: &'a str)
+        -> BoxFuture<'a, Option<RefreshToken>>;
+    fn save<'a>(
+        &'a self,
+        token: &'a RefreshToken,
+    ) -> BoxFuture<'a, Result<(), RefreshTokenRepositoryError>>;
+    /// See `RefreshTokenRepositoryTrait::mark_used_if_unused`
+    fn mark_used_if_unused<'a>(
+        &'a self,
+        token: &'a RefreshToken,
+    ) -> BoxFuture<'a, Result<bool, RefreshTokenRepositoryError>>;
+    /// Deletes the whole token family of a session
+    fn delete_sessions_tokens<'a>(
+        &'a self,
+        session_id: &'a Id,
+    ) -> BoxFuture<'a, Result<(), RefreshTokenRepositoryError>>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncRefreshTokenRepositoryTrait>;
+}
+
+impl<T: RefreshTokenRepositoryTrait + Send + 'static> AsyncRefreshTokenRepositoryTrait
+    for Blocking<T>
+{
+    fn find_by_token_hash<'a>(
+        &'a self,
+        token_hash: &'a str,
+    ) -> BoxFuture<'a, Option<RefreshToken>> {
+        ready(call(self, |r| r.find_by_token_hash(token_hash)))
+    }
+
+    fn save<'a>(
+        &'a self,
+        token: &'a RefreshToken,
+    ) -> BoxFuture<'a, Result<(), RefreshTokenRepositoryError>> {
+        ready(call(self, |r| r.save(token)))
+    }
+
+    fn mark_used_if_unused<'a>(
+        &'a self,
+        token: &'a RefreshToken,
+    ) -> BoxFuture<'a, Result<bool, RefreshTokenRepositoryError>> {
+        ready(call(self, |r| r.mark_used_if_unused(token)))
+    }
+
+    fn delete_sessions_tokens<'a>(
+        &'a self,
+        session_id: &'a Id,
+    ) -> BoxFuture<'a, Result<(), RefreshTokenRepositoryError>> {
+        ready(call(self, |r| r.delete_sessions_tokens(session_id)))
+    }
+
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncRefreshTokenRepositoryTrait> {
+        Arc::new(self.bind(transaction))
+    }
+}
+
+/// Calls the wrapped repository within the transaction `repository` is bound to
+fn call<T: RefreshTokenRepositoryTrait, R>(
+    repository: &Blocking<T>,
+    call: impl FnOnce(&dyn RefreshTokenRepositoryTrait) -> R,
+) -> R {
+    let inner = repository.inner();
+
+    match repository.transaction() {
+        Some(transaction) => call(&*inner.within(transaction)),
+        None => call(&*inner),
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RefreshTokenRepositoryError {
+    #[error("Can't save refresh token")]
+    Save,
+
+    #[error("Can't update refresh token")]
+    Update,
+
+    #[error("Can't delete refresh tokens")]
+    Delete,
+}
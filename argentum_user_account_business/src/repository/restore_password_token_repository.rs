@@ -1,5 +1,8 @@
 use crate::entity::restore_password_token::RestorePasswordToken;
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture};
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use std::sync::Arc;
 
 pub trait RestorePasswordTokenRepositoryTrait {
     fn find(
@@ -7,15 +10,116 @@ pub trait RestorePasswordTokenRepositoryTrait {
         id: &Id,
     ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError>;
 
-    fn find_by_token(
+    fn find_by_token_hash(
         &self,
-        token: String,
+        token_hash: &str,
     ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError>;
 
     fn save(&self, token: &RestorePasswordToken)
         -> Result<(), RestorePasswordTokenRepositoryError>;
 
     fn delete_users_tokens(&self, user_id: &Id) -> Result<(), RestorePasswordTokenRepositoryError>;
+
+    /// Deletes the token in one step and tells whether it was there: of concurrent callers
+    /// only one gets `true`, so a token is used once
+    fn delete_if_exists(&self, id: &Id) -> Result<bool, RestorePasswordTokenRepositoryError>;
+
+    /// The repository writing within `transaction`
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Bound<'_, dyn RestorePasswordTokenRepositoryTrait + '_>;
+}
+
+/// Async counterpart of `RestorePasswordTokenRepositoryTrait`
+pub trait AsyncRestorePasswordTokenRepositoryTrait: Send + Sync {
+    fn find<'a>(
+        &'a self,
+        id: &'a Id,
+    ) -> BoxFuture<'a, Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError>>;
+    fn find_by_token_hash<'a>(
+        &'a self,
+        token_hash: &'a str,
+    ) -> BoxFuture<'a, Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError>>;
+    fn save<'a>(
+        &'a self,
+        token: &'a RestorePasswordToken,
+    ) -> BoxFuture<'a, Result<(), RestorePasswordTokenRepositoryError>>;
+    fn delete_users_tokens<'a>(
+        &'a self,
+        user_id: &'a Id,
+    ) -> BoxFuture<'a, Result<(), RestorePasswordTokenRepositoryError>>;
+    /// See `RestorePasswordTokenRepositoryTrait::delete_if_exists`
+    fn delete_if_exists<'a>(
+        &'a self,
+        id: &'a Id,
+    ) -> BoxFuture<'a, Result<bool, RestorePasswordTokenRepositoryError>>;
+    /// The repository writing within `transaction`
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Arc<dyn AsyncRestorePasswordTokenRepositoryTrait>;
+}
+
+impl<T: RestorePasswordTokenRepositoryTrait + Send + 'static>
+    AsyncRestorePasswordTokenRepositoryTrait for Blocking<T>
+{
+    fn find<'a>(
+        &'a self,
+        id: &'a Id,
+    ) -> BoxFuture<'a, Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError>>
+    {
+        ready(call(self, |r| r.find(id)))
+    }
+
+    fn find_by_token_hash<'a>(
+        &'a self,
+        token_hash: &'a str,
+    ) -> BoxFuture<'a, Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError>>
+    {
+        ready(call(self, |r| r.find_by_token_hash(token_hash)))
+    }
+
+    fn save<'a>(
+        &'a self,
+        token: &'a RestorePasswordToken,
+    ) -> BoxFuture<'a, Result<(), RestorePasswordTokenRepositoryError>> {
+        ready(call(self, |r| r.save(token)))
+    }
+
+    fn delete_users_tokens<'a>(
+        &'a self,
+        user_id: &'a Id,
+    ) -> BoxFuture<'a, Result<(), RestorePasswordTokenRepositoryError>> {
+        ready(call(self, |r| r.delete_users_tokens(user_id)))
+    }
+
+    fn delete_if_exists<'a>(
+        &'a self,
+        id: &'a Id,
+    ) -> BoxFuture<'a, Result<bool, RestorePasswordTokenRepositoryError>> {
+        ready(call(self, |r| r.delete_if_exists(id)))
+    }
+
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Arc<dyn AsyncRestorePasswordTokenRepositoryTrait> {
+        Arc::new(self.bind(transaction))
+    }
+}
+
+/// Calls the wrapped repository within the transaction `repository` is bound to
+fn call<T: RestorePasswordTokenRepositoryTrait, R>(
+    repository: &Blocking<T>,
+    call: impl FnOnce(&dyn RestorePasswordTokenRepositoryTrait) -> R,
+) -> R {
+    let inner = repository.inner();
+
+    match repository.transaction() {
+        Some(transaction) => call(&*inner.within(transaction)),
+        None => call(&*inner),
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
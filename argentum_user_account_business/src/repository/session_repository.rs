@@ -1,18 +1,116 @@
 use crate::entity::session::Session;
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture};
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use std::sync::Arc;
 
 pub trait SessionRepositoryTrait {
     fn find(&self, id: &Id) -> Option<Session>;
-    fn find_by_token(&self, token: String) -> Option<Session>;
+    fn find_by_token_hash(&self, token_hash: &str) -> Option<Session>;
+    fn find_by_user_id(&self, user_id: &Id) -> Result<Vec<Session>, SessionRepositoryError>;
     fn save(&self, session: &Session) -> Result<(), SessionRepositoryError>;
+    fn update(&self, session: &Session) -> Result<(), SessionRepositoryError>;
+    fn delete(&self, id: &Id) -> Result<(), SessionRepositoryError>;
     fn delete_users_sessions(&self, user_id: &Id) -> Result<(), SessionRepositoryError>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Bound<'_, dyn SessionRepositoryTrait + '_>;
+}
+
+/// Async counterpart of `SessionRepositoryTrait`
+pub trait AsyncSessionRepositoryTrait: Send + Sync {
+    fn find<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Option<Session>>;
+    fn find_by_token_hash<'a>(&'a self, token_hash: &'a str) -> BoxFuture<'a, Option<Session>>;
+    fn find_by_user_id<'a>(
+        &'a self,
+        user_id: &'a Id,
+    ) -> BoxFuture<'a, Result<Vec<Session>, SessionRepositoryError>>;
+    fn save<'a>(
+        &'a self,
+        session: &'a Session,
+    ) -> BoxFuture<'a, Result<(), SessionRepositoryError>>;
+    fn update<'a>(
+        &'a self,
+        session: &'a Session,
+    ) -> BoxFuture<'a, Result<(), SessionRepositoryError>>;
+    fn delete<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Result<(), SessionRepositoryError>>;
+    fn delete_users_sessions<'a>(
+        &'a self,
+        user_id: &'a Id,
+    ) -> BoxFuture<'a, Result<(), SessionRepositoryError>>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncSessionRepositoryTrait>;
+}
+
+impl<T: SessionRepositoryTrait + Send + 'static> AsyncSessionRepositoryTrait for Blocking<T> {
+    fn find<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Option<Session>> {
+        ready(call(self, |r| r.find(id)))
+    }
+
+    fn find_by_token_hash<'a>(&'a self, token_hash: &'a str) -> BoxFuture<'a, Option<Session>> {
+        ready(call(self, |r| r.find_by_token_hash(token_hash)))
+    }
+
+    fn find_by_user_id<'a>(
+        &'a self,
+        user_id: &'a Id,
+    ) -> BoxFuture<'a, Result<Vec<Session>, SessionRepositoryError>> {
+        ready(call(self, |r| r.find_by_user_id(user_id)))
+    }
+
+    fn save<'a>(
+        &'a self,
+        session: &'a Session,
+    ) -> BoxFuture<'a, Result<(), SessionRepositoryError>> {
+        ready(call(self, |r| r.save(session)))
+    }
+
+    fn update<'a>(
+        &'a self,
+        session: &'a Session,
+    ) -> BoxFuture<'a, Result<(), SessionRepositoryError>> {
+        ready(call(self, |r| r.update(session)))
+    }
+
+    fn delete<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Result<(), SessionRepositoryError>> {
+        ready(call(self, |r| r.delete(id)))
+    }
+
+    fn delete_users_sessions<'a>(
+        &'a self,
+        user_id: &'a Id,
+    ) -> BoxFuture<'a, Result<(), SessionRepositoryError>> {
+        ready(call(self, |r| r.delete_users_sessions(user_id)))
+    }
+
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncSessionRepositoryTrait> {
+        Arc::new(self.bind(transaction))
+    }
+}
+
+/// Calls the wrapped repository within the transaction `repository` is bound to
+fn call<T: SessionRepositoryTrait, R>(
+    repository: &Blocking<T>,
+    call: impl FnOnce(&dyn SessionRepositoryTrait) -> R,
+) -> R {
+    let inner = repository.inner();
+
+    match repository.transaction() {
+        Some(transaction) => call(&*inner.within(transaction)),
+        None => call(&*inner),
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum SessionRepositoryError {
+    #[error("Can't find sessions")]
+    Find,
+
     #[error("Can't save session")]
     Save,
 
+    #[error("Can't update session")]
+    Update,
+
     #[error("Can't delete session")]
     Delete,
 }
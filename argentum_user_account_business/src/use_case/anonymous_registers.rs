@@ -1,57 +1,184 @@
 use crate::entity::session::Session;
-use crate::repository::session_repository::{SessionRepositoryError, SessionRepositoryTrait};
+use crate::event::AnonymousRegistered;
+use crate::repository::session_repository::{
+    AsyncSessionRepositoryTrait, SessionRepositoryError, SessionRepositoryTrait,
+};
+use argentum_event_business::event::EventPublisherTrait;
 use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{
+    in_async_transaction, in_transaction, AsyncTransactionManagerTrait, Transaction,
+    TransactionError, TransactionManagerTrait,
+};
 use argentum_user_business::entity::user::{AnonymousUser, UserTrait};
 use argentum_user_business::repository::user_repository::{
-    AnonymousUserRepositoryTrait, SavingUserError,
+    AnonymousUserRepositoryTrait, AsyncAnonymousUserRepositoryTrait, SavingUserError,
 };
-use argentum_user_business::token::GeneratorTrait;
+use argentum_user_business::token::{GeneratorTrait, TokenHasherTrait};
+use std::sync::Arc;
 
 pub struct AnonymousRegistersUc<'s> {
     id_factory: &'s dyn IdFactory,
     user_repository: &'s dyn AnonymousUserRepositoryTrait,
     session_repository: &'s dyn SessionRepositoryTrait,
     token_generator: &'s dyn GeneratorTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
+    session_ttl: u32, //configurable session lifetime in seconds
+    event_publisher: &'s dyn EventPublisherTrait,
+    transaction_manager: &'s dyn TransactionManagerTrait,
 }
 
 impl<'s> AnonymousRegistersUc<'s> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id_factory: &'s dyn IdFactory,
         user_repository: &'s dyn AnonymousUserRepositoryTrait,
         session_repository: &'s dyn SessionRepositoryTrait,
         token_generator: &'s dyn GeneratorTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
+        session_ttl: u32,
+        event_publisher: &'s dyn EventPublisherTrait,
+        transaction_manager: &'s dyn TransactionManagerTrait,
     ) -> AnonymousRegistersUc<'s> {
         AnonymousRegistersUc {
             id_factory,
             user_repository,
             session_repository,
             token_generator,
+            token_hasher,
+            session_ttl,
+            event_publisher,
+            transaction_manager,
         }
     }
 
-    pub fn execute(&self, id: &Id) -> Result<(AnonymousUser, Session), AnonymousRegistrationError> {
-        let user = {
-            let user = AnonymousUser::new(&id);
+    /// Returns the raw session token along with the session, it is the only place to get it
+    pub fn execute(
+        &self,
+        id: &Id,
+    ) -> Result<(AnonymousUser, Session, String), AnonymousRegistrationError> {
+        let (user, session, token) = new_anonymous(
+            self.id_factory,
+            self.token_generator,
+            self.token_hasher,
+            id,
+            self.session_ttl,
+        );
 
-            let result = self.user_repository.save(&user);
+        in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<(), AnonymousRegistrationError> {
+                self.user_repository.within(transaction).save(&user)?;
+                self.session_repository.within(transaction).save(&session)?;
 
-            match result {
-                Ok(_) => user,
-                Err(e) => return Err(AnonymousRegistrationError::SavingAnonymousError(e)),
-            }
-        };
+                Ok(())
+            },
+        )?;
 
-        let session = Session::new(
-            self.id_factory.create(),
-            user.id().clone(),
-            self.token_generator.generate(&id),
-        );
+        // Published once committed, so subscribers never see a rolled back anonymous
+        self.event_publisher
+            .publish(&AnonymousRegistered::new(user.id()));
 
-        match self.session_repository.save(&session) {
-            Ok(_) => Ok((user, session)),
-            Err(e) => Err(AnonymousRegistrationError::SavingSessionError(e)),
+        Ok((user, session, token))
+    }
+}
+
+/// Async counterpart of `AnonymousRegistersUc`
+pub struct AsyncAnonymousRegistersUc {
+    id_factory: Arc<dyn IdFactory + Send + Sync>,
+    user_repository: Arc<dyn AsyncAnonymousUserRepositoryTrait>,
+    session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+    token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    session_ttl: u32, //configurable session lifetime in seconds
+    event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+    transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+}
+
+impl AsyncAnonymousRegistersUc {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id_factory: Arc<dyn IdFactory + Send + Sync>,
+        user_repository: Arc<dyn AsyncAnonymousUserRepositoryTrait>,
+        session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+        token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        session_ttl: u32,
+        event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+        transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+    ) -> AsyncAnonymousRegistersUc {
+        AsyncAnonymousRegistersUc {
+            id_factory,
+            user_repository,
+            session_repository,
+            token_generator,
+            token_hasher,
+            session_ttl,
+            event_publisher,
+            transaction_manager,
         }
     }
+
+    /// Returns the raw session token along with the session, it is the only place to get it
+    pub async fn execute(
+        &self,
+        id: &Id,
+    ) -> Result<(AnonymousUser, Session, String), AnonymousRegistrationError> {
+        let (user, session, token) = new_anonymous(
+            self.id_factory.as_ref(),
+            self.token_generator.as_ref(),
+            self.token_hasher.as_ref(),
+            id,
+            self.session_ttl,
+        );
+
+        in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.save(transaction, &user, &session)
+        })
+        .await?;
+
+        // Published once committed, so subscribers never see a rolled back anonymous
+        self.event_publisher
+            .publish(&AnonymousRegistered::new(user.id()));
+
+        Ok((user, session, token))
+    }
+
+    async fn save(
+        &self,
+        transaction: Transaction,
+        user: &AnonymousUser,
+        session: &Session,
+    ) -> Result<(), AnonymousRegistrationError> {
+        self.user_repository.within(&transaction).save(user).await?;
+        self.session_repository
+            .within(&transaction)
+            .save(session)
+            .await?;
+
+        Ok(())
+    }
+}
+
+// Shared by the sync and async use cases
+fn new_anonymous(
+    id_factory: &dyn IdFactory,
+    token_generator: &dyn GeneratorTrait,
+    token_hasher: &dyn TokenHasherTrait,
+    id: &Id,
+    session_ttl: u32,
+) -> (AnonymousUser, Session, String) {
+    let user = AnonymousUser::new(id);
+
+    let session_id = id_factory.create();
+    let token = token_generator.generate_for_session(id, &session_id);
+    let session = Session::new(
+        session_id,
+        user.id().clone(),
+        token_hasher.hash(&token),
+        session_ttl,
+    );
+
+    (user, session, token)
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -61,18 +188,29 @@ pub enum AnonymousRegistrationError {
 
     #[error("Can't save session")]
     SavingSessionError(#[from] SessionRepositoryError),
+
+    #[error(transparent)]
+    TransactionError(#[from] TransactionError),
 }
 
 #[cfg(test)]
 mod tests {
     use crate::mock::repository::broken::session_repository_mock::SessionRepositoryMockWithBrokenSave;
     use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
-    use crate::mock::token::TokenGeneratorMock;
+    use crate::mock::token::{TokenGeneratorMock, TokenHasherMock};
+    use crate::repository::session_repository::SessionRepositoryTrait;
+    use crate::use_case::anonymous_registers::AsyncAnonymousRegistersUc;
     use crate::use_case::anonymous_registers::{AnonymousRegistersUc, AnonymousRegistrationError};
+    use argentum_event_business::mock::publisher::EventPublisherMock;
+    use argentum_standard_business::asynchronous::Blocking;
     use argentum_standard_business::data_type::id::{Id, IdFactory};
     use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_standard_business::mock::transaction::TransactionManagerMock;
     use argentum_user_business::mock::repository::anonymous_user_repository_mock::AnonymousUserRepositoryMock;
     use argentum_user_business::mock::repository::broken::anonymous_user_repository_mock::AnonymousRepositoryMockWithBrokenSave;
+    use argentum_user_business::token::TokenHasherTrait;
+    use std::sync::Arc;
 
     #[test]
     fn anonymous_registers() -> Result<(), &'static str> {
@@ -80,27 +218,38 @@ mod tests {
         let session_repository = SessionRepositoryMock::new();
         let id_factory = IdFactoryMock::new();
         let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
 
         let uc = AnonymousRegistersUc::new(
             &id_factory,
             &anonymous_user_repository,
             &session_repository,
             &token_generator,
+            &token_hasher,
+            3600,
+            &event_publisher,
+            &transaction_manager,
         );
 
         let anon_id: Id = id_factory.create();
         let result = uc.execute(&anon_id);
 
         match result {
-            Ok((anonymous, s)) => {
+            Ok((anonymous, s, token)) => {
                 assert_eq!(anonymous.id.to_string(), anon_id.clone().to_string());
                 assert_eq!(s.user_id.to_string(), anon_id.clone().to_string());
+                assert_eq!(token_hasher.hash(&token), s.token_hash);
 
-                return Ok(());
-            }
-            Err(_) => {
-                return Err("User is not registered");
+                if session_repository.find_by_token_hash(&token).is_some() {
+                    return Err("Session should not be found by raw token");
+                }
+
+                Ok(())
             }
+            Err(_) => Err("User is not registered"),
         }
     }
 
@@ -110,12 +259,20 @@ mod tests {
         let session_repository = SessionRepositoryMock::new();
         let id_factory = IdFactoryMock::new();
         let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
 
         let uc = AnonymousRegistersUc::new(
             &id_factory,
             &anonymous_user_repository,
             &session_repository,
             &token_generator,
+            &token_hasher,
+            3600,
+            &event_publisher,
+            &transaction_manager,
         );
 
         let anon_id: Id = id_factory.create();
@@ -135,12 +292,20 @@ mod tests {
         let session_repository = SessionRepositoryMockWithBrokenSave::new();
         let id_factory = IdFactoryMock::new();
         let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
 
         let uc = AnonymousRegistersUc::new(
             &id_factory,
             &anonymous_user_repository,
             &session_repository,
             &token_generator,
+            &token_hasher,
+            3600,
+            &event_publisher,
+            &transaction_manager,
         );
 
         let anon_id: Id = id_factory.create();
@@ -149,9 +314,92 @@ mod tests {
         match result {
             Ok(_) => Err("Should return error"),
             Err(e) => match e {
-                AnonymousRegistrationError::SavingSessionError(_) => Ok(()),
+                AnonymousRegistrationError::SavingSessionError(_) => {
+                    assert_eq!(vec!["begin", "rollback"], transaction_manager.journal());
+
+                    Ok(())
+                }
                 _ => Err("Wrong error type"),
             },
         }
     }
+
+    #[test]
+    fn anonymous_registers_asynchronously() -> Result<(), &'static str> {
+        let anonymous_user_repository = Arc::new(Blocking::new(AnonymousUserRepositoryMock::new()));
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let id_factory = Arc::new(IdFactoryMock::new());
+        let token_generator = Arc::new(TokenGeneratorMock::new());
+        let token_hasher = Arc::new(TokenHasherMock::new());
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+
+        let uc = AsyncAnonymousRegistersUc::new(
+            id_factory.clone(),
+            anonymous_user_repository.clone(),
+            session_repository.clone(),
+            token_generator.clone(),
+            token_hasher.clone(),
+            3600,
+            event_publisher.clone(),
+            transaction_manager.clone(),
+        );
+
+        let anon_id: Id = id_factory.create();
+
+        match block_on(uc.execute(&anon_id)) {
+            Ok((anonymous, s, token)) => {
+                assert_eq!(anonymous.id.to_string(), anon_id.to_string());
+                assert_eq!(token_hasher.hash(&token), s.token_hash);
+                assert!(session_repository.inner().find(&s.id).is_some());
+                assert!(transaction_manager.inner().committed());
+
+                Ok(())
+            }
+            Err(_) => Err("User is not registered"),
+        }
+    }
+
+    #[test]
+    fn anonymous_registers_from_many_threads() -> Result<(), &'static str> {
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let id_factory = Arc::new(IdFactoryMock::new());
+        let event_publisher = Arc::new(EventPublisherMock::new());
+
+        let uc = AsyncAnonymousRegistersUc::new(
+            id_factory.clone(),
+            Arc::new(Blocking::new(AnonymousUserRepositoryMock::new())),
+            session_repository.clone(),
+            Arc::new(TokenGeneratorMock::new()),
+            Arc::new(TokenHasherMock::new()),
+            3600,
+            event_publisher.clone(),
+            Arc::new(Blocking::new(TransactionManagerMock::new())),
+        );
+
+        let anon_ids: Vec<Id> = (0..4).map(|_| id_factory.create()).collect();
+
+        // Futures are created here and polled by other threads, so both the use case
+        // and its futures must be shareable
+        let results: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = anon_ids
+                .iter()
+                .map(|id| {
+                    let future = uc.execute(id);
+                    scope.spawn(move || block_on(future))
+                })
+                .collect();
+
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        for (id, result) in anon_ids.iter().zip(results) {
+            let (_, session, _) = result.map_err(|_| "User is not registered")?;
+            assert_eq!(id.to_string(), session.user_id.to_string());
+            assert!(session_repository.inner().find(&session.id).is_some());
+        }
+        assert_eq!(4, event_publisher.published().len());
+
+        Ok(())
+    }
 }
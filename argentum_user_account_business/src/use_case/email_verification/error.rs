@@ -0,0 +1,27 @@
+use crate::repository::email_verification_token_repository::EmailVerificationTokenRepositoryError;
+use argentum_standard_business::transaction::TransactionError;
+use argentum_user_business::repository::user_repository::SavingUserError;
+
+#[derive(thiserror::Error, Debug)]
+pub enum EmailVerificationError {
+    #[error("Can't get user data. DB error")]
+    GetUserError(#[from] SavingUserError),
+
+    #[error("User is not found")]
+    UserNotFoundError,
+
+    #[error("Email is already verified")]
+    AlreadyVerified,
+
+    #[error("Token is not found")]
+    TokenNotFoundError,
+
+    #[error("Token expired")]
+    TokenExpired,
+
+    #[error("Can't save token")]
+    TokenRepositoryError(#[from] EmailVerificationTokenRepositoryError),
+
+    #[error(transparent)]
+    TransactionError(#[from] TransactionError),
+}
@@ -0,0 +1,3 @@
+pub mod error;
+pub mod user_confirms_email;
+pub mod user_requests_verification;
@@ -0,0 +1,414 @@
+use crate::entity::email_verification_token::EmailVerificationToken;
+use crate::event::EmailVerified;
+use crate::repository::email_verification_token_repository::{
+    AsyncEmailVerificationTokenRepositoryTrait, EmailVerificationTokenRepositoryTrait,
+};
+use crate::use_case::email_verification::error::EmailVerificationError;
+use argentum_event_business::event::EventPublisherTrait;
+use argentum_standard_business::transaction::{
+    in_async_transaction, in_transaction, AsyncTransactionManagerTrait, Transaction,
+    TransactionManagerTrait,
+};
+use argentum_user_business::entity::user::AuthenticatedUser;
+use argentum_user_business::repository::user_repository::{
+    AsyncAuthenticatedUserRepositoryTrait, AuthenticatedUserRepositoryTrait,
+};
+use argentum_user_business::token::TokenHasherTrait;
+use std::sync::Arc;
+
+pub struct UserConfirmsEmailUc<'s> {
+    user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
+    email_verification_token_repository: &'s dyn EmailVerificationTokenRepositoryTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
+    token_ttl: u32, //configurable ttl in seconds
+    event_publisher: &'s dyn EventPublisherTrait,
+    transaction_manager: &'s dyn TransactionManagerTrait,
+}
+
+impl<'s> UserConfirmsEmailUc<'s> {
+    pub fn new(
+        user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
+        email_verification_token_repository: &'s dyn EmailVerificationTokenRepositoryTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
+        token_ttl: u32,
+        event_publisher: &'s dyn EventPublisherTrait,
+        transaction_manager: &'s dyn TransactionManagerTrait,
+    ) -> UserConfirmsEmailUc<'s> {
+        UserConfirmsEmailUc {
+            user_repository,
+            email_verification_token_repository,
+            token_hasher,
+            token_ttl,
+            event_publisher,
+            transaction_manager,
+        }
+    }
+
+    pub fn execute(&self, token: String) -> Result<(), EmailVerificationError> {
+        let verification_token = valid_token(
+            self.email_verification_token_repository
+                .find_by_token_hash(&self.token_hasher.hash(&token))?,
+            self.token_ttl,
+        )?;
+
+        let user = verified_user(
+            self.user_repository.find(&verification_token.user_id)?,
+            &verification_token,
+        )?;
+
+        in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<(), EmailVerificationError> {
+                self.user_repository.within(transaction).update(&user)?;
+
+                // Tokens are single use
+                self.email_verification_token_repository
+                    .within(transaction)
+                    .delete_users_tokens(&user.id)?;
+
+                Ok(())
+            },
+        )?;
+
+        self.event_publisher
+            .publish(&EmailVerified::new(user.id.clone(), user.email.clone()));
+
+        Ok(())
+    }
+}
+
+/// Async counterpart of `UserConfirmsEmailUc`
+pub struct AsyncUserConfirmsEmailUc {
+    user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+    email_verification_token_repository: Arc<dyn AsyncEmailVerificationTokenRepositoryTrait>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    token_ttl: u32, //configurable ttl in seconds
+    event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+    transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+}
+
+impl AsyncUserConfirmsEmailUc {
+    pub fn new(
+        user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+        email_verification_token_repository: Arc<dyn AsyncEmailVerificationTokenRepositoryTrait>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        token_ttl: u32,
+        event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+        transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+    ) -> AsyncUserConfirmsEmailUc {
+        AsyncUserConfirmsEmailUc {
+            user_repository,
+            email_verification_token_repository,
+            token_hasher,
+            token_ttl,
+            event_publisher,
+            transaction_manager,
+        }
+    }
+
+    pub async fn execute(&self, token: String) -> Result<(), EmailVerificationError> {
+        let token_hash = self.token_hasher.hash(&token);
+        let verification_token = valid_token(
+            self.email_verification_token_repository
+                .find_by_token_hash(&token_hash)
+                .await?,
+            self.token_ttl,
+        )?;
+
+        let user = verified_user(
+            self.user_repository
+                .find(&verification_token.user_id)
+                .await?,
+            &verification_token,
+        )?;
+
+        in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.save(transaction, &user)
+        })
+        .await?;
+
+        self.event_publisher
+            .publish(&EmailVerified::new(user.id.clone(), user.email.clone()));
+
+        Ok(())
+    }
+
+    async fn save(
+        &self,
+        transaction: Transaction,
+        user: &AuthenticatedUser,
+    ) -> Result<(), EmailVerificationError> {
+        self.user_repository
+            .within(&transaction)
+            .update(user)
+            .await?;
+
+        // Tokens are single use
+        self.email_verification_token_repository
+            .within(&transaction)
+            .delete_users_tokens(&user.id)
+            .await?;
+
+        Ok(())
+    }
+}
+
+// Shared by the sync and async use cases
+fn valid_token(
+    verification_token: Option<EmailVerificationToken>,
+    token_ttl: u32,
+) -> Result<EmailVerificationToken, EmailVerificationError> {
+    let verification_token = match verification_token {
+        Some(t) => t,
+        None => return Err(EmailVerificationError::TokenNotFoundError),
+    };
+
+    if verification_token.is_expired(token_ttl) {
+        return Err(EmailVerificationError::TokenExpired);
+    }
+
+    Ok(verification_token)
+}
+
+fn verified_user(
+    user: Option<AuthenticatedUser>,
+    verification_token: &EmailVerificationToken,
+) -> Result<AuthenticatedUser, EmailVerificationError> {
+    let mut user = match user {
+        Some(user) => user,
+        None => return Err(EmailVerificationError::UserNotFoundError),
+    };
+
+    // The address was changed after the token had been sent
+    if user.email != verification_token.email {
+        return Err(EmailVerificationError::TokenNotFoundError);
+    }
+
+    user.verify_email();
+
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::email_verification_token::EmailVerificationToken;
+    use crate::mock::repository::email_verification_token_repository_mock::EmailVerificationTokenRepositoryMock;
+    use crate::mock::token::TokenHasherMock;
+    use crate::repository::email_verification_token_repository::EmailVerificationTokenRepositoryTrait;
+    use crate::use_case::email_verification::error::EmailVerificationError;
+    use crate::use_case::email_verification::user_confirms_email::AsyncUserConfirmsEmailUc;
+    use crate::use_case::email_verification::user_confirms_email::UserConfirmsEmailUc;
+    use argentum_event_business::mock::publisher::EventPublisherMock;
+    use argentum_standard_business::asynchronous::Blocking;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_standard_business::mock::transaction::TransactionManagerMock;
+    use argentum_user_business::entity::user::AuthenticatedUser;
+    use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
+    use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::token::TokenHasherTrait;
+    use argentum_user_business::value_object::name::Name;
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+
+    fn stub_user(
+        id_factory: &IdFactoryMock,
+        user_repository: &AuthenticatedUserRepositoryMock,
+    ) -> AuthenticatedUser {
+        let user_id: Id = id_factory.create();
+        let name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+        let user = AuthenticatedUser::new(&user_id, name, email);
+
+        user_repository.save(&user).unwrap();
+
+        user
+    }
+
+    fn stub_token(
+        id_factory: &IdFactoryMock,
+        token_repository: &EmailVerificationTokenRepositoryMock,
+        user: &AuthenticatedUser,
+        email: &str,
+    ) -> EmailVerificationToken {
+        let token = EmailVerificationToken::new(
+            id_factory.create(),
+            user.id.clone(),
+            EmailAddress::new(email.to_string()).unwrap(),
+            TokenHasherMock::new().hash("token"),
+        );
+
+        token_repository.save(&token).unwrap();
+
+        token
+    }
+
+    #[test]
+    fn test_user_confirms_email() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_repository = EmailVerificationTokenRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserConfirmsEmailUc::new(
+            &user_repository,
+            &token_repository,
+            &token_hasher,
+            100,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let user = stub_user(&id_factory, &user_repository);
+        stub_token(&id_factory, &token_repository, &user, "test@mail.com");
+
+        if uc.execute("token".to_string()).is_err() {
+            return Err("Email is not confirmed");
+        }
+
+        let user = user_repository.find(&user.id).unwrap().unwrap();
+        assert!(user.is_email_verified());
+
+        match uc.execute("token".to_string()) {
+            Err(EmailVerificationError::TokenNotFoundError) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Token should be single use"),
+        }
+    }
+
+    #[test]
+    fn test_user_confirms_email_with_expired_token() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_repository = EmailVerificationTokenRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserConfirmsEmailUc::new(
+            &user_repository,
+            &token_repository,
+            &token_hasher,
+            100,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let user = stub_user(&id_factory, &user_repository);
+        let mut token = stub_token(&id_factory, &token_repository, &user, "test@mail.com");
+        token.created_at = Utc::now() - Duration::seconds(101);
+        token_repository.delete_users_tokens(&user.id).unwrap();
+        token_repository.save(&token).unwrap();
+
+        match uc.execute("token".to_string()) {
+            Err(EmailVerificationError::TokenExpired) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Should return an error"),
+        }
+    }
+
+    #[test]
+    fn test_user_confirms_previous_email() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_repository = EmailVerificationTokenRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserConfirmsEmailUc::new(
+            &user_repository,
+            &token_repository,
+            &token_hasher,
+            100,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let user = stub_user(&id_factory, &user_repository);
+        stub_token(&id_factory, &token_repository, &user, "old@mail.com");
+
+        match uc.execute("token".to_string()) {
+            Err(EmailVerificationError::TokenNotFoundError) => {}
+            Err(_) => return Err("Wrong error type"),
+            Ok(_) => return Err("Should return an error"),
+        }
+
+        let user = user_repository.find(&user.id).unwrap().unwrap();
+        assert!(!user.is_email_verified());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_confirms_email_with_wrong_token() -> Result<(), &'static str> {
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_repository = EmailVerificationTokenRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserConfirmsEmailUc::new(
+            &user_repository,
+            &token_repository,
+            &token_hasher,
+            100,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        match uc.execute("wrong".to_string()) {
+            Err(EmailVerificationError::TokenNotFoundError) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Should return an error"),
+        }
+    }
+
+    #[test]
+    fn test_user_confirms_email_asynchronously() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let user_repository = Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let token_repository = Arc::new(Blocking::new(EmailVerificationTokenRepositoryMock::new()));
+        let token_hasher = Arc::new(TokenHasherMock::new());
+
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+
+        let uc = AsyncUserConfirmsEmailUc::new(
+            user_repository.clone(),
+            token_repository.clone(),
+            token_hasher.clone(),
+            100,
+            event_publisher.clone(),
+            transaction_manager.clone(),
+        );
+
+        let user = stub_user(&id_factory, &user_repository.inner());
+        stub_token(
+            &id_factory,
+            &token_repository.inner(),
+            &user,
+            "test@mail.com",
+        );
+
+        if block_on(uc.execute("token".to_string())).is_err() {
+            return Err("Email is not confirmed");
+        }
+
+        let user = user_repository.inner().find(&user.id).unwrap().unwrap();
+        assert!(user.is_email_verified());
+
+        match block_on(uc.execute("token".to_string())) {
+            Err(EmailVerificationError::TokenNotFoundError) => Ok(()),
+            _ => Err("Token should be single use"),
+        }
+    }
+}
@@ -0,0 +1,437 @@
+use crate::entity::email_verification_token::EmailVerificationToken;
+use crate::repository::email_verification_token_repository::{
+    AsyncEmailVerificationTokenRepositoryTrait, EmailVerificationTokenRepositoryTrait,
+};
+use crate::use_case::email_verification::error::EmailVerificationError;
+use argentum_log_business::LoggerTrait;
+use argentum_notification_business::notification::Notification;
+use argentum_notification_business::notifier::{
+    AsyncNotifierTrait, NotificationError, NotifierTrait,
+};
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{
+    in_async_transaction, in_transaction, AsyncTransactionManagerTrait, Transaction,
+    TransactionManagerTrait,
+};
+use argentum_user_business::entity::user::AuthenticatedUser;
+use argentum_user_business::repository::user_repository::{
+    AsyncAuthenticatedUserRepositoryTrait, AuthenticatedUserRepositoryTrait,
+};
+use argentum_user_business::token::{GeneratorTrait, TokenHasherTrait};
+use std::sync::Arc;
+
+pub struct UserRequestsVerificationUc<'s> {
+    id_factory: &'s dyn IdFactory,
+    user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
+    email_verification_token_repository: &'s dyn EmailVerificationTokenRepositoryTrait,
+    token_generator: &'s dyn GeneratorTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
+    notifier: &'s dyn NotifierTrait,
+    logger: &'s dyn LoggerTrait,
+    transaction_manager: &'s dyn TransactionManagerTrait,
+}
+
+impl<'s> UserRequestsVerificationUc<'s> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id_factory: &'s dyn IdFactory,
+        user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
+        email_verification_token_repository: &'s dyn EmailVerificationTokenRepositoryTrait,
+        token_generator: &'s dyn GeneratorTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
+        notifier: &'s dyn NotifierTrait,
+        logger: &'s dyn LoggerTrait,
+        transaction_manager: &'s dyn TransactionManagerTrait,
+    ) -> UserRequestsVerificationUc<'s> {
+        UserRequestsVerificationUc {
+            id_factory,
+            user_repository,
+            email_verification_token_repository,
+            token_generator,
+            token_hasher,
+            notifier,
+            logger,
+            transaction_manager,
+        }
+    }
+
+    /// Sends the raw token to the user's email once the stored one is committed.
+    /// Previously requested tokens stop working
+    pub fn execute(&self, user_id: &Id) -> Result<(), EmailVerificationError> {
+        let (verification_token, token) = new_verification_token(
+            self.id_factory,
+            self.token_generator,
+            self.token_hasher,
+            self.user_repository.find(user_id)?,
+        )?;
+        let user_id = &verification_token.user_id;
+
+        in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<(), EmailVerificationError> {
+                let email_verification_token_repository =
+                    self.email_verification_token_repository.within(transaction);
+
+                email_verification_token_repository.delete_users_tokens(user_id)?;
+                email_verification_token_repository.save(&verification_token)?;
+
+                Ok(())
+            },
+        )?;
+
+        log_delivery(
+            self.logger,
+            self.notifier.notify(
+                &verification_token.email,
+                &Notification::EmailVerification { token },
+            ),
+        );
+
+        Ok(())
+    }
+}
+
+/// Async counterpart of `UserRequestsVerificationUc`
+pub struct AsyncUserRequestsVerificationUc {
+    id_factory: Arc<dyn IdFactory + Send + Sync>,
+    user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+    email_verification_token_repository: Arc<dyn AsyncEmailVerificationTokenRepositoryTrait>,
+    token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    notifier: Arc<dyn AsyncNotifierTrait>,
+    logger: Arc<dyn LoggerTrait + Send + Sync>,
+    transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+}
+
+impl AsyncUserRequestsVerificationUc {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id_factory: Arc<dyn IdFactory + Send + Sync>,
+        user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+        email_verification_token_repository: Arc<dyn AsyncEmailVerificationTokenRepositoryTrait>,
+        token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        notifier: Arc<dyn AsyncNotifierTrait>,
+        logger: Arc<dyn LoggerTrait + Send + Sync>,
+        transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+    ) -> AsyncUserRequestsVerificationUc {
+        AsyncUserRequestsVerificationUc {
+            id_factory,
+            user_repository,
+            email_verification_token_repository,
+            token_generator,
+            token_hasher,
+            notifier,
+            logger,
+            transaction_manager,
+        }
+    }
+
+    /// See `UserRequestsVerificationUc::execute`
+    pub async fn execute(&self, user_id: &Id) -> Result<(), EmailVerificationError> {
+        let (verification_token, token) = new_verification_token(
+            self.id_factory.as_ref(),
+            self.token_generator.as_ref(),
+            self.token_hasher.as_ref(),
+            self.user_repository.find(user_id).await?,
+        )?;
+
+        in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.replace_token(transaction, &verification_token)
+        })
+        .await?;
+
+        let delivered = self
+            .notifier
+            .notify(
+                &verification_token.email,
+                &Notification::EmailVerification { token },
+            )
+            .await;
+        log_delivery(self.logger.as_ref(), delivered);
+
+        Ok(())
+    }
+
+    /// Earlier tokens of the user stop working
+    async fn replace_token(
+        &self,
+        transaction: Transaction,
+        verification_token: &EmailVerificationToken,
+    ) -> Result<(), EmailVerificationError> {
+        let email_verification_token_repository = self
+            .email_verification_token_repository
+            .within(&transaction);
+
+        email_verification_token_repository
+            .delete_users_tokens(&verification_token.user_id)
+            .await?;
+        email_verification_token_repository
+            .save(verification_token)
+            .await?;
+
+        Ok(())
+    }
+}
+
+// Shared by the sync and async use cases
+fn new_verification_token(
+    id_factory: &dyn IdFactory,
+    token_generator: &dyn GeneratorTrait,
+    token_hasher: &dyn TokenHasherTrait,
+    user: Option<AuthenticatedUser>,
+) -> Result<(EmailVerificationToken, String), EmailVerificationError> {
+    let user = match user {
+        Some(user) => user,
+        None => return Err(EmailVerificationError::UserNotFoundError),
+    };
+
+    if user.is_email_verified() {
+        return Err(EmailVerificationError::AlreadyVerified);
+    }
+
+    let token = token_generator.generate(&user.id);
+    let verification_token = EmailVerificationToken::new(
+        id_factory.create(),
+        user.id.clone(),
+        user.email,
+        token_hasher.hash(&token),
+    );
+
+    Ok((verification_token, token))
+}
+
+fn log_delivery(logger: &dyn LoggerTrait, delivered: Result<(), NotificationError>) {
+    // The user can ask for another token, the request itself succeeded
+    if let Err(e) = delivered {
+        logger.error(format!("Can't send email verification token: {}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::repository::email_verification_token_repository_mock::EmailVerificationTokenRepositoryMock;
+    use crate::mock::token::{TokenGeneratorMock, TokenHasherMock};
+    use crate::repository::email_verification_token_repository::EmailVerificationTokenRepositoryTrait;
+    use crate::use_case::email_verification::error::EmailVerificationError;
+    use crate::use_case::email_verification::user_requests_verification::AsyncUserRequestsVerificationUc;
+    use crate::use_case::email_verification::user_requests_verification::UserRequestsVerificationUc;
+    use argentum_log_business::{DefaultLogger, Level, StdoutWriter};
+    use argentum_notification_business::mock::notifier::{
+        NotifierMock, NotifierMockWithBrokenDelivery,
+    };
+    use argentum_notification_business::notification::Notification;
+    use argentum_standard_business::asynchronous::Blocking;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_standard_business::mock::transaction::TransactionManagerMock;
+    use argentum_user_business::entity::user::AuthenticatedUser;
+    use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
+    use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::token::TokenHasherTrait;
+    use argentum_user_business::value_object::name::Name;
+    use std::sync::Arc;
+
+    fn stub_user(id_factory: &IdFactoryMock) -> AuthenticatedUser {
+        let user_id: Id = id_factory.create();
+        let name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+
+        AuthenticatedUser::new(&user_id, name, email)
+    }
+
+    fn sent_tokens(sent: Vec<(EmailAddress, Notification)>) -> Vec<String> {
+        sent.into_iter()
+            .filter_map(|(_, notification)| match notification {
+                Notification::EmailVerification { token } => Some(token),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_user_requests_verification() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_repository = EmailVerificationTokenRepositoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserRequestsVerificationUc::new(
+            &id_factory,
+            &user_repository,
+            &token_repository,
+            &token_generator,
+            &token_hasher,
+            &notifier,
+            &logger,
+            &transaction_manager,
+        );
+
+        let user = stub_user(&id_factory);
+        user_repository.save(&user).unwrap();
+
+        if uc.execute(&user.id).is_err() {
+            return Err("Verification is not requested");
+        }
+        uc.execute(&user.id).unwrap();
+
+        let sent = notifier.sent();
+        assert!(
+            sent.iter().all(|(to, _)| *to == user.email),
+            "Wrong address"
+        );
+
+        let (first_raw, second_raw) = match &sent_tokens(sent)[..] {
+            [first, second] => (first.clone(), second.clone()),
+            _ => return Err("Tokens are not sent"),
+        };
+
+        assert!(token_repository
+            .find_by_token_hash(&token_hasher.hash(&first_raw))
+            .unwrap()
+            .is_none());
+
+        match token_repository
+            .find_by_token_hash(&token_hasher.hash(&second_raw))
+            .unwrap()
+        {
+            Some(stored) => {
+                assert!(*stored.user_id == *user.id);
+                assert!(stored.email == user.email);
+                assert_ne!(second_raw, stored.token_hash, "Raw token is stored");
+
+                Ok(())
+            }
+            None => Err("Token is not saved"),
+        }
+    }
+
+    #[test]
+    fn test_verified_user_requests_verification() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_repository = EmailVerificationTokenRepositoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserRequestsVerificationUc::new(
+            &id_factory,
+            &user_repository,
+            &token_repository,
+            &token_generator,
+            &token_hasher,
+            &notifier,
+            &logger,
+            &transaction_manager,
+        );
+
+        let mut user = stub_user(&id_factory);
+        user.verify_email();
+        user_repository.save(&user).unwrap();
+
+        match uc.execute(&user.id) {
+            Err(EmailVerificationError::AlreadyVerified) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Should return an error"),
+        }
+    }
+
+    #[test]
+    fn test_user_requests_verification_with_broken_notifier() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_repository = EmailVerificationTokenRepositoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let notifier = NotifierMockWithBrokenDelivery::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserRequestsVerificationUc::new(
+            &id_factory,
+            &user_repository,
+            &token_repository,
+            &token_generator,
+            &token_hasher,
+            &notifier,
+            &logger,
+            &transaction_manager,
+        );
+
+        let user = stub_user(&id_factory);
+        user_repository.save(&user).unwrap();
+
+        match uc.execute(&user.id) {
+            Ok(_) => {
+                assert!(transaction_manager.committed());
+
+                Ok(())
+            }
+            Err(_) => Err("Delivery errors should not be returned"),
+        }
+    }
+
+    #[test]
+    fn test_user_requests_verification_asynchronously() -> Result<(), &'static str> {
+        let id_factory = Arc::new(IdFactoryMock::new());
+        let user_repository = Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let token_repository = Arc::new(Blocking::new(EmailVerificationTokenRepositoryMock::new()));
+        let token_generator = Arc::new(TokenGeneratorMock::new());
+        let token_hasher = Arc::new(TokenHasherMock::new());
+        let notifier = Arc::new(Blocking::new(NotifierMock::new()));
+
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+
+        let uc = AsyncUserRequestsVerificationUc::new(
+            id_factory.clone(),
+            user_repository.clone(),
+            token_repository.clone(),
+            token_generator.clone(),
+            token_hasher.clone(),
+            notifier.clone(),
+            Arc::new(DefaultLogger::new(Level::Trace, &StdoutWriter {})),
+            transaction_manager.clone(),
+        );
+
+        let user = stub_user(&id_factory);
+        user_repository.inner().save(&user).unwrap();
+
+        if block_on(uc.execute(&user.id)).is_err() {
+            return Err("Verification is not requested");
+        }
+        block_on(uc.execute(&user.id)).unwrap();
+
+        let (first_raw, second_raw) = match &sent_tokens(notifier.inner().sent())[..] {
+            [first, second] => (first.clone(), second.clone()),
+            _ => return Err("Tokens are not sent"),
+        };
+
+        assert!(token_repository
+            .inner()
+            .find_by_token_hash(&token_hasher.hash(&first_raw))
+            .unwrap()
+            .is_none());
+        assert!(token_repository
+            .inner()
+            .find_by_token_hash(&token_hasher.hash(&second_raw))
+            .unwrap()
+            .is_some());
+
+        Ok(())
+    }
+}
@@ -1,5 +1,10 @@
 pub mod anonymous_registers;
+pub mod email_verification;
 pub mod restore_password;
+pub mod user_authenticates_with_signed_token;
 pub mod user_authenticates_with_token;
 pub mod user_logins_with_password;
+pub mod user_logs_out;
+pub mod user_logs_out_everywhere;
+pub mod user_refreshes_session;
 pub mod user_registers_with_password;
@@ -1,83 +1,275 @@
 use crate::entity::restore_password_token::RestorePasswordToken;
-use crate::repository::restore_password_token_repository::RestorePasswordTokenRepositoryTrait;
+use crate::event::PasswordRestoreRequested;
+use crate::repository::restore_password_token_repository::{
+    AsyncRestorePasswordTokenRepositoryTrait, RestorePasswordTokenRepositoryTrait,
+};
 use crate::use_case::restore_password::error::RestorePasswordError;
+use argentum_event_business::event::EventPublisherTrait;
+use argentum_log_business::LoggerTrait;
+use argentum_notification_business::notification::Notification;
+use argentum_notification_business::notifier::{
+    AsyncNotifierTrait, NotificationError, NotifierTrait,
+};
 use argentum_standard_business::data_type::email::EmailAddress;
-use argentum_standard_business::data_type::id::IdFactory;
-use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
-use argentum_user_business::token::GeneratorTrait;
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_standard_business::transaction::{
+    in_async_transaction, in_transaction, AsyncTransactionManagerTrait, Transaction,
+    TransactionManagerTrait,
+};
+use argentum_user_business::repository::user_repository::{
+    AsyncAuthenticatedUserRepositoryTrait, AuthenticatedUserRepositoryTrait,
+};
+use argentum_user_business::token::{GeneratorTrait, TokenHasherTrait};
+use std::sync::Arc;
 
 pub struct AnonymousRequestsRestoreToken<'s> {
     id_factory: &'s dyn IdFactory,
     user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
     restore_password_token_repository: &'s dyn RestorePasswordTokenRepositoryTrait,
     token_generator: &'s dyn GeneratorTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
+    notifier: &'s dyn NotifierTrait,
+    event_publisher: &'s dyn EventPublisherTrait,
+    logger: &'s dyn LoggerTrait,
+    transaction_manager: &'s dyn TransactionManagerTrait,
 }
 
 impl<'s> AnonymousRequestsRestoreToken<'s> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id_factory: &'s dyn IdFactory,
         user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
         restore_password_token_repository: &'s dyn RestorePasswordTokenRepositoryTrait,
         token_generator: &'s dyn GeneratorTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
+        notifier: &'s dyn NotifierTrait,
+        event_publisher: &'s dyn EventPublisherTrait,
+        logger: &'s dyn LoggerTrait,
+        transaction_manager: &'s dyn TransactionManagerTrait,
     ) -> AnonymousRequestsRestoreToken<'s> {
         AnonymousRequestsRestoreToken {
             id_factory,
             user_repository,
             restore_password_token_repository,
             token_generator,
+            token_hasher,
+            notifier,
+            event_publisher,
+            logger,
+            transaction_manager,
         }
     }
 
-    pub fn execute(
-        &self,
-        email: EmailAddress,
-    ) -> Result<RestorePasswordToken, RestorePasswordError> {
-        let result = self.user_repository.find_by_email(&email);
-        let user = match result {
-            Err(err) => return Err(RestorePasswordError::GetUserError(err)),
-            Ok(o) => match o {
-                Some(user) => user,
-                None => return Err(RestorePasswordError::UserNotFoundError),
+    /// Sends the raw token to the user after the commit, only its hash is stored.
+    /// Not registered emails go through the same token generation and transaction and
+    /// get a notification as well, so the flow doesn't reveal which emails have accounts.
+    /// The notifier should be a `QueuedNotifier`: it only queues in both cases,
+    /// so delivery time doesn't tell them apart either. Delivery errors are only logged
+    pub fn execute(&self, email: EmailAddress) -> Result<(), RestorePasswordError> {
+        let user = self.user_repository.find_by_email(&email)?;
+        let user_id = match &user {
+            Some(user) => user.id.clone(),
+            None => self.id_factory.create(),
+        };
+
+        let (restore_token, token) = new_token(
+            self.id_factory,
+            self.token_generator,
+            self.token_hasher,
+            &user_id,
+        );
+        let stored_token = user.as_ref().map(|_| &restore_token);
+
+        in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<(), RestorePasswordError> {
+                let restore_password_token_repository =
+                    self.restore_password_token_repository.within(transaction);
+
+                restore_password_token_repository.delete_users_tokens(&user_id)?;
+                if let Some(restore_token) = stored_token {
+                    restore_password_token_repository.save(restore_token)?;
+                }
+
+                Ok(())
             },
+        )?;
+
+        let notification = notification(user.is_some(), token);
+        log_delivery(self.logger, self.notifier.notify(&email, &notification));
+
+        if let Some(user) = user {
+            self.event_publisher
+                .publish(&PasswordRestoreRequested::new(user.id));
+        }
+
+        Ok(())
+    }
+}
+
+/// Async counterpart of `AnonymousRequestsRestoreToken`
+pub struct AsyncAnonymousRequestsRestoreToken {
+    id_factory: Arc<dyn IdFactory + Send + Sync>,
+    user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+    restore_password_token_repository: Arc<dyn AsyncRestorePasswordTokenRepositoryTrait>,
+    token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    notifier: Arc<dyn AsyncNotifierTrait>,
+    event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+    logger: Arc<dyn LoggerTrait + Send + Sync>,
+    transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+}
+
+impl AsyncAnonymousRequestsRestoreToken {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id_factory: Arc<dyn IdFactory + Send + Sync>,
+        user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+        restore_password_token_repository: Arc<dyn AsyncRestorePasswordTokenRepositoryTrait>,
+        token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        notifier: Arc<dyn AsyncNotifierTrait>,
+        event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+        logger: Arc<dyn LoggerTrait + Send + Sync>,
+        transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+    ) -> AsyncAnonymousRequestsRestoreToken {
+        AsyncAnonymousRequestsRestoreToken {
+            id_factory,
+            user_repository,
+            restore_password_token_repository,
+            token_generator,
+            token_hasher,
+            notifier,
+            event_publisher,
+            logger,
+            transaction_manager,
+        }
+    }
+
+    /// See `AnonymousRequestsRestoreToken::execute`
+    pub async fn execute(&self, email: EmailAddress) -> Result<(), RestorePasswordError> {
+        let user = self.user_repository.find_by_email(&email).await?;
+        let user_id = match &user {
+            Some(user) => user.id.clone(),
+            None => self.id_factory.create(),
         };
 
-        let token = self.token_generator.generate(&user.id);
-        let id = self.id_factory.create();
+        let (restore_token, token) = new_token(
+            self.id_factory.as_ref(),
+            self.token_generator.as_ref(),
+            self.token_hasher.as_ref(),
+            &user_id,
+        );
+        let stored_token = user.as_ref().map(|_| &restore_token);
 
-        let restore_token = RestorePasswordToken::new(id, user.id.clone(), token);
+        in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.replace_token(transaction, &user_id, stored_token)
+        })
+        .await?;
 
-        if let Err(e) = self
-            .restore_password_token_repository
-            .delete_users_tokens(&user.id)
-        {
-            return Err(RestorePasswordError::TokenRepositoryError(e));
+        let notification = notification(user.is_some(), token);
+        let delivered = self.notifier.notify(&email, &notification).await;
+        log_delivery(self.logger.as_ref(), delivered);
+
+        if let Some(user) = user {
+            self.event_publisher
+                .publish(&PasswordRestoreRequested::new(user.id));
         }
 
-        if let Err(e) = self.restore_password_token_repository.save(&restore_token) {
-            return Err(RestorePasswordError::TokenRepositoryError(e));
+        Ok(())
+    }
+
+    /// Nothing is saved for a not registered email, only the deletion runs
+    async fn replace_token(
+        &self,
+        transaction: Transaction,
+        user_id: &Id,
+        restore_token: Option<&RestorePasswordToken>,
+    ) -> Result<(), RestorePasswordError> {
+        let restore_password_token_repository =
+            self.restore_password_token_repository.within(&transaction);
+
+        restore_password_token_repository
+            .delete_users_tokens(user_id)
+            .await?;
+        if let Some(restore_token) = restore_token {
+            restore_password_token_repository
+                .save(restore_token)
+                .await?;
         }
 
-        //TODO: send email
+        Ok(())
+    }
+}
+
+/// The token to store and the raw one to send, shared by the sync and async use cases
+fn new_token(
+    id_factory: &dyn IdFactory,
+    token_generator: &dyn GeneratorTrait,
+    token_hasher: &dyn TokenHasherTrait,
+    user_id: &Id,
+) -> (RestorePasswordToken, String) {
+    let token = token_generator.generate(user_id);
+    let restore_token = RestorePasswordToken::new(
+        id_factory.create(),
+        user_id.clone(),
+        token_hasher.hash(&token),
+    );
+
+    (restore_token, token)
+}
+
+/// The raw token is only sent to a registered email
+fn notification(registered: bool, token: String) -> Notification {
+    if registered {
+        Notification::RestorePassword { token }
+    } else {
+        Notification::RestorePasswordWithoutAccount
+    }
+}
 
-        Ok(restore_token)
+fn log_delivery(logger: &dyn LoggerTrait, delivered: Result<(), NotificationError>) {
+    // The token is stored already, the user can ask for another one
+    if let Err(e) = delivered {
+        logger.error(format!("Can't send restore password token: {}", e));
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::mock::repository::restore_password_token_repository_mock::RestorePasswordTokenRepositoryMock;
-    use crate::mock::token::TokenGeneratorMock;
+    use crate::mock::token::{TokenGeneratorMock, TokenHasherMock};
     use crate::repository::restore_password_token_repository::RestorePasswordTokenRepositoryTrait;
-    use crate::use_case::restore_password::anonymous_requests_restore_token::{
-        AnonymousRequestsRestoreToken, RestorePasswordError,
+    use crate::use_case::restore_password::anonymous_requests_restore_token::AnonymousRequestsRestoreToken;
+    use crate::use_case::restore_password::anonymous_requests_restore_token::AsyncAnonymousRequestsRestoreToken;
+    use argentum_event_business::mock::publisher::EventPublisherMock;
+    use argentum_log_business::{DefaultLogger, Level, StdoutWriter};
+    use argentum_notification_business::mock::notifier::{
+        NotifierMock, NotifierMockWithBrokenDelivery,
     };
+    use argentum_notification_business::notification::Notification;
+    use argentum_standard_business::asynchronous::{Blocking, Offloaded};
     use argentum_standard_business::data_type::email::EmailAddress;
-    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
     use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::{block_on, InlineExecutorMock};
+    use argentum_standard_business::mock::transaction::TransactionManagerMock;
     use argentum_user_business::entity::user::AuthenticatedUser;
     use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
     use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::token::TokenHasherTrait;
     use argentum_user_business::value_object::name::Name;
+    use std::sync::Arc;
+
+    fn stub_email() -> EmailAddress {
+        EmailAddress::new("test@mail.com".to_string()).unwrap()
+    }
+
+    fn stub_user(id: &Id) -> AuthenticatedUser {
+        let user_name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+
+        AuthenticatedUser::new(id, user_name, stub_email())
+    }
 
     #[test]
     fn anonymous_requests_restore_token() -> Result<(), &'static str> {
@@ -85,36 +277,48 @@ mod tests {
         let token_repository = RestorePasswordTokenRepositoryMock::new();
         let user_repository = AuthenticatedUserRepositoryMock::new();
         let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
 
         let uc = AnonymousRequestsRestoreToken::new(
             &id_factory,
             &user_repository,
             &token_repository,
             &token_generator,
+            &token_hasher,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
         );
 
         let user_id = id_factory.create();
-        let user_name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
-        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
-
-        let user = AuthenticatedUser::new(&user_id, user_name, email.clone());
+        user_repository.save(&stub_user(&user_id)).unwrap();
 
-        user_repository.save(&user).unwrap();
-
-        let result = uc.execute(email);
-
-        if let Err(_) = result {
-            return Err("User is not registered");
+        if uc.execute(stub_email()).is_err() {
+            return Err("Token is not requested");
         }
 
-        let token = result.unwrap();
+        let sent = notifier.sent();
+        assert_eq!(1, sent.len(), "Token is not sent");
 
-        assert!(user_id.eq(&token.user_id), "Wrong user id in token");
+        let raw_token = match &sent[0] {
+            (to, Notification::RestorePassword { token }) if *to == stub_email() => token.clone(),
+            _ => return Err("Wrong notification"),
+        };
 
-        match token_repository.find_by_token(token.token).unwrap() {
+        match token_repository
+            .find_by_token_hash(&token_hasher.hash(&raw_token))
+            .unwrap()
+        {
             Some(stored_token) => {
-                assert!(stored_token.id.eq(&token.id), "Wrong token id");
                 assert!(user_id.eq(&stored_token.user_id), "Wrong user id in token");
+                assert_ne!(raw_token, stored_token.token_hash, "Raw token is stored");
 
                 Ok(())
             }
@@ -128,24 +332,133 @@ mod tests {
         let token_repository = RestorePasswordTokenRepositoryMock::new();
         let user_repository = AuthenticatedUserRepositoryMock::new();
         let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
 
         let uc = AnonymousRequestsRestoreToken::new(
             &id_factory,
             &user_repository,
             &token_repository,
             &token_generator,
+            &token_hasher,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
         );
 
-        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+        if uc.execute(stub_email()).is_err() {
+            return Err("Should not reveal that email is not registered");
+        }
+
+        assert!(
+            notifier.sent() == vec![(stub_email(), Notification::RestorePasswordWithoutAccount)],
+            "Should be notified like a registered email"
+        );
+        assert!(event_publisher.published().is_empty());
+        assert!(
+            transaction_manager.committed(),
+            "Should do the same work as for a registered email"
+        );
 
-        let result = uc.execute(email);
+        Ok(())
+    }
 
-        match result {
-            Err(e) => match e {
-                RestorePasswordError::UserNotFoundError => Ok(()),
-                _ => Err("Wrong error type"),
-            },
-            Ok(_) => Err("Should return not found error"),
+    #[test]
+    fn anonymous_requests_restore_token_with_broken_notifier() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let token_repository = RestorePasswordTokenRepositoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let notifier = NotifierMockWithBrokenDelivery::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = AnonymousRequestsRestoreToken::new(
+            &id_factory,
+            &user_repository,
+            &token_repository,
+            &token_generator,
+            &token_hasher,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
+        );
+
+        user_repository
+            .save(&stub_user(&id_factory.create()))
+            .unwrap();
+
+        if uc.execute(stub_email()).is_err() {
+            return Err("Delivery errors should not be returned");
+        }
+
+        let stored_token = token_repository
+            .find_by_token_hash(&token_hasher.hash("Test token 1"))
+            .unwrap();
+        assert!(transaction_manager.committed());
+        assert!(stored_token.is_some(), "Token should be kept");
+
+        Ok(())
+    }
+
+    #[test]
+    fn anonymous_requests_restore_token_asynchronously() -> Result<(), &'static str> {
+        let id_factory = Arc::new(IdFactoryMock::new());
+        let token_repository = Arc::new(Blocking::new(RestorePasswordTokenRepositoryMock::new()));
+        let user_repository = Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let token_hasher = Arc::new(TokenHasherMock::new());
+        let notifier = Arc::new(Offloaded::new(
+            NotifierMock::new(),
+            Arc::new(InlineExecutorMock::new()),
+        ));
+
+        let uc = AsyncAnonymousRequestsRestoreToken::new(
+            id_factory.clone(),
+            user_repository.clone(),
+            token_repository.clone(),
+            Arc::new(TokenGeneratorMock::new()),
+            token_hasher.clone(),
+            notifier.clone(),
+            Arc::new(EventPublisherMock::new()),
+            Arc::new(DefaultLogger::new(Level::Trace, &StdoutWriter {})),
+            Arc::new(Blocking::new(TransactionManagerMock::new())),
+        );
+
+        let user_id = id_factory.create();
+        user_repository.inner().save(&stub_user(&user_id)).unwrap();
+
+        if block_on(uc.execute(stub_email())).is_err() {
+            return Err("Token is not requested");
+        }
+
+        let raw_token = match &notifier.inner().sent()[..] {
+            [(_, Notification::RestorePassword { token })] => token.clone(),
+            _ => return Err("Token is not sent"),
+        };
+
+        let stored_token = token_repository
+            .inner()
+            .find_by_token_hash(&token_hasher.hash(&raw_token))
+            .unwrap();
+
+        match stored_token {
+            Some(stored_token) => {
+                assert!(user_id.eq(&stored_token.user_id), "Wrong user id in token");
+
+                Ok(())
+            }
+            None => Err("Token is not saved"),
         }
     }
 }
@@ -1,122 +1,472 @@
 use crate::entity::credential::PasswordCredential;
-use crate::repository::password_credential_writer::PasswordCredentialWriterTrait;
-use crate::repository::restore_password_token_repository::RestorePasswordTokenRepositoryTrait;
+use crate::entity::restore_password_token::RestorePasswordToken;
+use crate::entity::session::Session;
+use crate::event::{PasswordChanged, SessionRevocationReason, SessionRevoked};
+use crate::repository::password_credential_writer::{
+    AsyncPasswordCredentialWriter, PasswordCredentialWriterTrait,
+};
+use crate::repository::restore_password_token_repository::RestorePasswordTokenRepositoryError;
+use crate::repository::restore_password_token_repository::{
+    AsyncRestorePasswordTokenRepositoryTrait, RestorePasswordTokenRepositoryTrait,
+};
+use crate::repository::session_repository::{AsyncSessionRepositoryTrait, SessionRepositoryTrait};
 use crate::use_case::restore_password::error::RestorePasswordError;
-use argentum_encryption_business::password::Encryptor;
-use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+use crate::value_object::password::{PasswordPolicy, PlainPassword};
+use argentum_encryption_business::breached_password::{
+    AsyncBreachedPasswordCheckerTrait, BreachCheckError, BreachedPasswordCheckerTrait,
+};
+use argentum_encryption_business::password::{AsyncEncryptor, Encryptor};
+use argentum_event_business::event::EventPublisherTrait;
+use argentum_log_business::LoggerTrait;
+use argentum_notification_business::notification::Notification;
+use argentum_notification_business::notifier::{
+    AsyncNotifierTrait, NotificationError, NotifierTrait,
+};
+use argentum_standard_business::transaction::{
+    in_async_transaction, in_transaction, AsyncTransactionManagerTrait, Transaction,
+    TransactionManagerTrait,
+};
+use argentum_user_business::entity::user::AuthenticatedUser;
+use argentum_user_business::repository::user_repository::{
+    AsyncAuthenticatedUserRepositoryTrait, AuthenticatedUserRepositoryTrait, SavingUserError,
+};
+use argentum_user_business::token::{constant_time_eq, TokenHasherTrait};
+use std::sync::Arc;
 
 pub struct AnonymousWithTokenChangesPassword<'s> {
     user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
     restore_password_token_repository: &'s dyn RestorePasswordTokenRepositoryTrait,
+    session_repository: &'s dyn SessionRepositoryTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
     credential_writer: &'s dyn PasswordCredentialWriterTrait<'s>,
     encryptor: &'s dyn Encryptor,
+    password_policy: &'s PasswordPolicy<'s>,
+    breached_password_checker: Option<&'s dyn BreachedPasswordCheckerTrait>,
     token_ttl: u32, //configurable ttl in seconds
+    notifier: &'s dyn NotifierTrait,
+    event_publisher: &'s dyn EventPublisherTrait,
+    logger: &'s dyn LoggerTrait,
+    transaction_manager: &'s dyn TransactionManagerTrait,
 }
 
 impl<'s> AnonymousWithTokenChangesPassword<'s> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
         restore_password_token_repository: &'s dyn RestorePasswordTokenRepositoryTrait,
+        session_repository: &'s dyn SessionRepositoryTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
         encryptor: &'s dyn Encryptor,
         credential_writer: &'s dyn PasswordCredentialWriterTrait<'s>,
+        password_policy: &'s PasswordPolicy<'s>,
+        breached_password_checker: Option<&'s dyn BreachedPasswordCheckerTrait>,
         token_ttl: u32,
+        notifier: &'s dyn NotifierTrait,
+        event_publisher: &'s dyn EventPublisherTrait,
+        logger: &'s dyn LoggerTrait,
+        transaction_manager: &'s dyn TransactionManagerTrait,
     ) -> AnonymousWithTokenChangesPassword<'s> {
         AnonymousWithTokenChangesPassword {
             user_repository,
             restore_password_token_repository,
+            session_repository,
+            token_hasher,
             credential_writer,
             encryptor,
+            password_policy,
+            breached_password_checker,
             token_ttl,
+            notifier,
+            event_publisher,
+            logger,
+            transaction_manager,
         }
     }
 
     pub fn execute(&self, token: String, password: String) -> Result<(), RestorePasswordError> {
-        let restore_token = {
-            let restore_token = self.restore_password_token_repository.find_by_token(token);
+        let password = PlainPassword::new(password, self.password_policy)?;
 
-            match restore_token {
-                Err(e) => {
-                    return Err(RestorePasswordError::TokenRepositoryError(e));
-                }
-                Ok(o) => match o {
-                    Some(restore_token) => restore_token,
-                    None => return Err(RestorePasswordError::TokenNotFoundError),
-                },
-            }
-        };
-
-        if restore_token.is_expired(self.token_ttl) {
-            return Err(RestorePasswordError::TokenExpired);
+        if let Some(checker) = self.breached_password_checker {
+            reject_breached(checker.is_breached(password.as_str()))?;
         }
 
-        let result = self.user_repository.find(&restore_token.user_id);
-        let user = match result {
-            Err(err) => return Err(RestorePasswordError::GetUserError(err)),
-            Ok(o) => match o {
-                Some(user) => user,
-                None => return Err(RestorePasswordError::UserNotFoundError),
+        let token_hash = self.token_hasher.hash(&token);
+        let restore_token = valid_token(
+            self.restore_password_token_repository
+                .find_by_token_hash(&token_hash),
+            &token_hash,
+            self.token_ttl,
+        )?;
+
+        let user = found_user(self.user_repository.find(&restore_token.user_id))?;
+        let revoked = self.session_repository.find_by_user_id(&user.id)?;
+
+        // The old credential is kept if hashing of the new password fails
+        let hashed_password = self.encryptor.encrypt(password.as_str())?;
+        let cred = PasswordCredential::new(user.id.clone(), hashed_password);
+        let changed = PasswordChanged::new(user.id.clone());
+
+        in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<(), RestorePasswordError> {
+                let token_repository = self.restore_password_token_repository.within(transaction);
+                claim(token_repository.delete_if_exists(&restore_token.id))?;
+                // Restore tokens are single use
+                token_repository.delete_users_tokens(&user.id)?;
+
+                let credential_writer = self.credential_writer.within(transaction);
+                credential_writer.delete_for_user(&user.id);
+                credential_writer.write(Box::new(cred));
+
+                // Refresh tokens of a deleted session are rejected as well
+                self.session_repository
+                    .within(transaction)
+                    .delete_users_sessions(&user.id)?;
+
+                Ok(())
             },
-        };
+        )?;
 
-        self.credential_writer.delete_for_user(&user.id);
+        log_delivery(
+            self.logger,
+            self.notifier.notify(&user.email, &password_reset_alert()),
+        );
+
+        // Not before the commit, a subscriber may tell the user the password is changed
+        self.event_publisher.publish(&changed);
+        publish_revocations(self.event_publisher, revoked);
 
-        //save credentials
-        let (hashed_password, salt) = self.encryptor.encrypt(&password)?;
+        Ok(())
+    }
+}
 
-        let cred = PasswordCredential::new(user.id.clone(), hashed_password, salt);
-        self.credential_writer.write(Box::new(cred));
+/// Async counterpart of `AnonymousWithTokenChangesPassword`
+pub struct AsyncAnonymousWithTokenChangesPassword {
+    user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+    restore_password_token_repository: Arc<dyn AsyncRestorePasswordTokenRepositoryTrait>,
+    session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    credential_writer: Arc<AsyncPasswordCredentialWriter>,
+    encryptor: Arc<dyn AsyncEncryptor>,
+    password_policy: Arc<PasswordPolicy<'static>>,
+    breached_password_checker: Option<Arc<dyn AsyncBreachedPasswordCheckerTrait>>,
+    token_ttl: u32, //configurable ttl in seconds
+    notifier: Arc<dyn AsyncNotifierTrait>,
+    event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+    logger: Arc<dyn LoggerTrait + Send + Sync>,
+    transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+}
 
-        if self
-            .restore_password_token_repository
-            .delete_users_tokens(&user.id)
-            .is_err()
-        {
-            //TODO: log error
+impl AsyncAnonymousWithTokenChangesPassword {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+        restore_password_token_repository: Arc<dyn AsyncRestorePasswordTokenRepositoryTrait>,
+        session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        encryptor: Arc<dyn AsyncEncryptor>,
+        credential_writer: Arc<AsyncPasswordCredentialWriter>,
+        password_policy: Arc<PasswordPolicy<'static>>,
+        breached_password_checker: Option<Arc<dyn AsyncBreachedPasswordCheckerTrait>>,
+        token_ttl: u32,
+        notifier: Arc<dyn AsyncNotifierTrait>,
+        event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+        logger: Arc<dyn LoggerTrait + Send + Sync>,
+        transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+    ) -> AsyncAnonymousWithTokenChangesPassword {
+        AsyncAnonymousWithTokenChangesPassword {
+            user_repository,
+            restore_password_token_repository,
+            session_repository,
+            token_hasher,
+            credential_writer,
+            encryptor,
+            password_policy,
+            breached_password_checker,
+            token_ttl,
+            notifier,
+            event_publisher,
+            logger,
+            transaction_manager,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        token: String,
+        password: String,
+    ) -> Result<(), RestorePasswordError> {
+        let password = PlainPassword::new(password, &self.password_policy)?;
+
+        if let Some(checker) = &self.breached_password_checker {
+            reject_breached(checker.is_breached(password.as_str()).await)?;
         }
 
+        let token_hash = self.token_hasher.hash(&token);
+        let restore_token = valid_token(
+            self.restore_password_token_repository
+                .find_by_token_hash(&token_hash)
+                .await,
+            &token_hash,
+            self.token_ttl,
+        )?;
+
+        let user = found_user(self.user_repository.find(&restore_token.user_id).await)?;
+        let revoked = self.session_repository.find_by_user_id(&user.id).await?;
+
+        // The old credential is kept if hashing of the new password fails
+        let hashed_password = self.encryptor.encrypt(password.as_str()).await?;
+        let cred = PasswordCredential::new(user.id.clone(), hashed_password);
+        let changed = PasswordChanged::new(user.id.clone());
+
+        in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.replace_credential(transaction, &restore_token, &user, &cred)
+        })
+        .await?;
+
+        let delivered = self
+            .notifier
+            .notify(&user.email, &password_reset_alert())
+            .await;
+        log_delivery(self.logger.as_ref(), delivered);
+
+        // Not before the commit, a subscriber may tell the user the password is changed
+        self.event_publisher.publish(&changed);
+        publish_revocations(self.event_publisher.as_ref(), revoked);
+
         Ok(())
     }
+
+    async fn replace_credential(
+        &self,
+        transaction: Transaction,
+        restore_token: &RestorePasswordToken,
+        user: &AuthenticatedUser,
+        cred: &PasswordCredential,
+    ) -> Result<(), RestorePasswordError> {
+        let token_repository = self.restore_password_token_repository.within(&transaction);
+        claim(token_repository.delete_if_exists(&restore_token.id).await)?;
+        // Restore tokens are single use
+        token_repository.delete_users_tokens(&user.id).await?;
+
+        let credential_writer = self.credential_writer.within(&transaction);
+        credential_writer
+            .delete_password_credentials_for_user(&user.id)
+            .await;
+        credential_writer.write_password_credentials(cred).await;
+
+        // Refresh tokens of a deleted session are rejected as well
+        self.session_repository
+            .within(&transaction)
+            .delete_users_sessions(&user.id)
+            .await?;
+
+        Ok(())
+    }
+}
+
+// Shared by the sync and async use cases
+
+fn reject_breached(breached: Result<bool, BreachCheckError>) -> Result<(), RestorePasswordError> {
+    match breached? {
+        true => Err(RestorePasswordError::BreachedPassword),
+        false => Ok(()),
+    }
+}
+
+fn valid_token(
+    found: Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError>,
+    token_hash: &str,
+    token_ttl: u32,
+) -> Result<RestorePasswordToken, RestorePasswordError> {
+    let restore_token = match found? {
+        Some(t) if constant_time_eq(&t.token_hash, token_hash) => t,
+        _ => return Err(RestorePasswordError::TokenNotFoundError),
+    };
+
+    if restore_token.is_expired(token_ttl) {
+        return Err(RestorePasswordError::TokenExpired);
+    }
+
+    Ok(restore_token)
+}
+
+/// Of concurrent requests with the same token only the one which deleted it goes on
+fn claim(
+    deleted: Result<bool, RestorePasswordTokenRepositoryError>,
+) -> Result<(), RestorePasswordError> {
+    match deleted? {
+        true => Ok(()),
+        false => Err(RestorePasswordError::TokenNotFoundError),
+    }
+}
+
+/// Sessions are announced as revoked once the deletion is committed
+fn publish_revocations(event_publisher: &dyn EventPublisherTrait, revoked: Vec<Session>) {
+    for session in revoked {
+        event_publisher.publish(&SessionRevoked::new(
+            session.id,
+            session.user_id,
+            SessionRevocationReason::PasswordReset,
+        ));
+    }
+}
+
+/// Tells the owner in case the reset wasn't requested by them
+fn password_reset_alert() -> Notification {
+    Notification::SecurityAlert {
+        description: "The password of your account was reset with a restore password link."
+            .to_string(),
+    }
+}
+
+fn log_delivery(logger: &dyn LoggerTrait, delivered: Result<(), NotificationError>) {
+    // The password is changed already, the alert isn't worth failing the request
+    if let Err(e) = delivered {
+        logger.error(format!("Can't send password reset alert: {}", e));
+    }
+}
+
+fn found_user(
+    found: Result<Option<AuthenticatedUser>, SavingUserError>,
+) -> Result<AuthenticatedUser, RestorePasswordError> {
+    match found? {
+        Some(user) => Ok(user),
+        None => Err(RestorePasswordError::UserNotFoundError),
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::entity::credential::PasswordCredential;
     use crate::entity::restore_password_token::RestorePasswordToken;
+    use crate::entity::session::Session;
     use crate::mock::repository::password_credential_repository_mock::PasswordCredentialRepositoryMock;
     use crate::mock::repository::restore_password_token_repository_mock::RestorePasswordTokenRepositoryMock;
-    use crate::mock::token::TokenGeneratorMock;
+    use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
+    use crate::mock::token::{TokenGeneratorMock, TokenHasherMock};
     use crate::repository::password_credential_repository::PasswordCredentialRepository;
+    use crate::repository::password_credential_writer::AsyncPasswordCredentialWriter;
     use crate::repository::password_credential_writer::PasswordCredentialWriter;
-    use crate::repository::restore_password_token_repository::RestorePasswordTokenRepositoryTrait;
+    use crate::repository::restore_password_token_repository::{
+        RestorePasswordTokenRepositoryError, RestorePasswordTokenRepositoryTrait,
+    };
+    use crate::repository::session_repository::SessionRepositoryTrait;
     use crate::use_case::restore_password::anonymous_with_token_changes_password::AnonymousWithTokenChangesPassword;
+    use crate::use_case::restore_password::anonymous_with_token_changes_password::AsyncAnonymousWithTokenChangesPassword;
     use crate::use_case::restore_password::error::RestorePasswordError;
-    use argentum_encryption_business::mock::password::EncryptorMock;
+    use crate::value_object::password::PasswordPolicy;
+    use argentum_encryption_business::mock::breached_password::BreachedPasswordCheckerMock;
+    use argentum_encryption_business::mock::password::{
+        EncryptorMock, EncryptorMockWithBrokenHashing,
+    };
+    use argentum_event_business::mock::publisher::EventPublisherMock;
+    use argentum_log_business::{DefaultLogger, Level, StdoutWriter};
+    use argentum_notification_business::mock::notifier::NotifierMock;
+    use argentum_notification_business::notification::Notification;
+    use argentum_notification_business::queue::QueuedNotifier;
+    use argentum_standard_business::asynchronous::Blocking;
     use argentum_standard_business::data_type::email::EmailAddress;
-    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
     use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_standard_business::mock::transaction::TransactionManagerMock;
+    use argentum_standard_business::transaction::{Bound, Transaction};
     use argentum_user_business::entity::user::AuthenticatedUser;
     use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
     use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
-    use argentum_user_business::token::GeneratorTrait;
+    use argentum_user_business::token::{GeneratorTrait, TokenHasherTrait};
     use argentum_user_business::value_object::name::Name;
+    use std::sync::Arc;
     use std::thread;
     use std::time::Duration;
 
+    /// Another request with the same token claims it right after it is read
+    struct RacingRestorePasswordTokenRepository {
+        inner: RestorePasswordTokenRepositoryMock,
+    }
+
+    impl RestorePasswordTokenRepositoryTrait for RacingRestorePasswordTokenRepository {
+        fn find(
+            &self,
+            id: &Id,
+        ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
+            self.inner.find(id)
+        }
+
+        fn find_by_token_hash(
+            &self,
+            token_hash: &str,
+        ) -> Result<Option<RestorePasswordToken>, RestorePasswordTokenRepositoryError> {
+            let found = self.inner.find_by_token_hash(token_hash)?;
+
+            if let Some(token) = &found {
+                self.inner.delete_if_exists(&token.id)?;
+            }
+
+            Ok(found)
+        }
+
+        fn save(
+            &self,
+            token: &RestorePasswordToken,
+        ) -> Result<(), RestorePasswordTokenRepositoryError> {
+            self.inner.save(token)
+        }
+
+        fn delete_users_tokens(
+            &self,
+            user_id: &Id,
+        ) -> Result<(), RestorePasswordTokenRepositoryError> {
+            self.inner.delete_users_tokens(user_id)
+        }
+
+        fn delete_if_exists(&self, id: &Id) -> Result<bool, RestorePasswordTokenRepositoryError> {
+            self.inner.delete_if_exists(id)
+        }
+
+        fn within(
+            &self,
+            _: &Transaction,
+        ) -> Bound<'_, dyn RestorePasswordTokenRepositoryTrait + '_> {
+            Bound::Shared(self)
+        }
+    }
+
     #[test]
     fn anonymous_changes_password_with_token() -> Result<(), &'static str> {
         let id_factory = IdFactoryMock::new();
         let token_repository = RestorePasswordTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
         let user_repository = AuthenticatedUserRepositoryMock::new();
         let token_generator: &dyn GeneratorTrait = &TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
         let credential_repository = PasswordCredentialRepositoryMock::new();
         let credential_writer = PasswordCredentialWriter::new(&credential_repository);
         let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
 
         let uc = AnonymousWithTokenChangesPassword::new(
             &user_repository,
             &token_repository,
+            &session_repository,
+            &token_hasher,
             &encryptor,
             &credential_writer,
+            &password_policy,
+            None,
             100,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
         );
 
         let user_id = id_factory.create();
@@ -129,40 +479,200 @@ mod tests {
 
         let token = token_generator.generate(&user.id);
         let token_id = id_factory.create();
-        let restore_token = RestorePasswordToken::new(token_id, user.id.clone(), token.clone());
+        let restore_token =
+            RestorePasswordToken::new(token_id, user.id.clone(), token_hasher.hash(&token));
 
         token_repository.save(&restore_token).unwrap();
 
-        let password = "234".to_string();
+        let password = "Correct-Horse-7".to_string();
         let result = uc.execute(token, password);
 
-        if let Err(_) = result {
+        if result.is_err() {
             return Err("Password is not changed");
         }
 
-        if let None = credential_repository.find_by_user_id(&user_id) {
+        if credential_repository.find_by_user_id(&user_id).is_none() {
             return Err("Can't find new password");
         }
 
+        match &notifier.sent()[..] {
+            [(to, Notification::SecurityAlert { .. })] if *to == email => Ok(()),
+            _ => Err("User is not alerted"),
+        }
+    }
+
+    #[test]
+    fn anonymous_changes_password_and_revokes_sessions() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let token_repository = RestorePasswordTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_generator: &dyn GeneratorTrait = &TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = AnonymousWithTokenChangesPassword::new(
+            &user_repository,
+            &token_repository,
+            &session_repository,
+            &token_hasher,
+            &encryptor,
+            &credential_writer,
+            &password_policy,
+            None,
+            100,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
+        );
+
+        let user_id = id_factory.create();
+        let user_name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+        let user = AuthenticatedUser::new(&user_id, user_name, email);
+        user_repository.save(&user).unwrap();
+
+        let session = Session::new(id_factory.create(), user_id.clone(), "hash".into(), 3600);
+        session_repository.save(&session).unwrap();
+
+        let token = token_generator.generate(&user.id);
+        let restore_token = RestorePasswordToken::new(
+            id_factory.create(),
+            user.id.clone(),
+            token_hasher.hash(&token),
+        );
+        token_repository.save(&restore_token).unwrap();
+
+        if uc.execute(token, "Correct-Horse-7".to_string()).is_err() {
+            return Err("Password is not changed");
+        }
+
+        if session_repository.find(&session.id).is_some() {
+            return Err("Session is not revoked");
+        }
+
+        assert_eq!(
+            vec![
+                "user_account.password_changed",
+                "user_account.session_revoked"
+            ],
+            event_publisher.published()
+        );
+
         Ok(())
     }
 
+    #[test]
+    fn anonymous_changes_password_with_claimed_token() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let token_repository = RacingRestorePasswordTokenRepository {
+            inner: RestorePasswordTokenRepositoryMock::new(),
+        };
+        let session_repository = SessionRepositoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_generator: &dyn GeneratorTrait = &TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = AnonymousWithTokenChangesPassword::new(
+            &user_repository,
+            &token_repository,
+            &session_repository,
+            &token_hasher,
+            &encryptor,
+            &credential_writer,
+            &password_policy,
+            None,
+            100,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
+        );
+
+        let user_id = id_factory.create();
+        let user_name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+        let user = AuthenticatedUser::new(&user_id, user_name, email);
+        user_repository.save(&user).unwrap();
+
+        let token = token_generator.generate(&user.id);
+        let restore_token = RestorePasswordToken::new(
+            id_factory.create(),
+            user.id.clone(),
+            token_hasher.hash(&token),
+        );
+        token_repository.save(&restore_token).unwrap();
+
+        match uc.execute(token, "Correct-Horse-7".to_string()) {
+            Ok(_) => Err("Claimed token should be rejected"),
+            Err(RestorePasswordError::TokenNotFoundError) => {
+                assert!(transaction_manager.rolled_back());
+                assert!(credential_repository.find_by_user_id(&user_id).is_none());
+                assert!(event_publisher.published().is_empty());
+                assert!(notifier.sent().is_empty(), "Nothing should be sent");
+
+                Ok(())
+            }
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
     #[test]
     fn anonymous_changes_password_with_expired_token() -> Result<(), &'static str> {
         let id_factory = IdFactoryMock::new();
         let token_repository = RestorePasswordTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
         let user_repository = AuthenticatedUserRepositoryMock::new();
         let token_generator: &dyn GeneratorTrait = &TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
         let credential_repository = PasswordCredentialRepositoryMock::new();
         let credential_writer = PasswordCredentialWriter::new(&credential_repository);
         let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
 
         let uc = AnonymousWithTokenChangesPassword::new(
             &user_repository,
             &token_repository,
+            &session_repository,
+            &token_hasher,
             &encryptor,
             &credential_writer,
+            &password_policy,
+            None,
             1,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
         );
 
         let user_id = id_factory.create();
@@ -175,13 +685,14 @@ mod tests {
 
         let token = token_generator.generate(&user.id);
         let token_id = id_factory.create();
-        let restore_token = RestorePasswordToken::new(token_id, user.id.clone(), token.clone());
+        let restore_token =
+            RestorePasswordToken::new(token_id, user.id.clone(), token_hasher.hash(&token));
 
         token_repository.save(&restore_token).unwrap();
 
         thread::sleep(Duration::from_secs(1));
 
-        let password = "234".to_string();
+        let password = "Correct-Horse-7".to_string();
         let result = uc.execute(token, password);
 
         match result {
@@ -192,4 +703,279 @@ mod tests {
             _ => Err("Should return 'Token Expired' error."),
         }
     }
+
+    #[test]
+    fn anonymous_changes_password_to_weak_one() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let token_repository = RestorePasswordTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_generator: &dyn GeneratorTrait = &TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = AnonymousWithTokenChangesPassword::new(
+            &user_repository,
+            &token_repository,
+            &session_repository,
+            &token_hasher,
+            &encryptor,
+            &credential_writer,
+            &password_policy,
+            None,
+            100,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
+        );
+
+        let user_id = id_factory.create();
+        let user_name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+
+        let user = AuthenticatedUser::new(&user_id, user_name, email.clone());
+
+        user_repository.save(&user).unwrap();
+
+        let token = token_generator.generate(&user.id);
+        let token_id = id_factory.create();
+        let restore_token =
+            RestorePasswordToken::new(token_id, user.id.clone(), token_hasher.hash(&token));
+
+        token_repository.save(&restore_token).unwrap();
+
+        let result = uc.execute(token, "password".to_string());
+
+        match result {
+            Err(RestorePasswordError::WeakPassword(e)) => {
+                assert!(e.violations.len() > 1);
+
+                if credential_repository.find_by_user_id(&user_id).is_some() {
+                    return Err("Password should not be changed");
+                }
+
+                Ok(())
+            }
+            _ => Err("Should return 'Weak Password' error"),
+        }
+    }
+
+    #[test]
+    fn anonymous_changes_password_to_breached_one() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let token_repository = RestorePasswordTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_generator: &dyn GeneratorTrait = &TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+        let breached_password_checker = BreachedPasswordCheckerMock::new(vec!["Correct-Horse-7"]);
+
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = AnonymousWithTokenChangesPassword::new(
+            &user_repository,
+            &token_repository,
+            &session_repository,
+            &token_hasher,
+            &encryptor,
+            &credential_writer,
+            &password_policy,
+            Some(&breached_password_checker),
+            100,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
+        );
+
+        let user_id = id_factory.create();
+        let user_name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+
+        let user = AuthenticatedUser::new(&user_id, user_name, email.clone());
+
+        user_repository.save(&user).unwrap();
+
+        let token = token_generator.generate(&user.id);
+        let token_id = id_factory.create();
+        let restore_token =
+            RestorePasswordToken::new(token_id, user.id.clone(), token_hasher.hash(&token));
+
+        token_repository.save(&restore_token).unwrap();
+
+        let result = uc.execute(token, "Correct-Horse-7".to_string());
+
+        match result {
+            Err(RestorePasswordError::BreachedPassword) => {
+                if credential_repository.find_by_user_id(&user_id).is_some() {
+                    return Err("Password should not be changed");
+                }
+
+                Ok(())
+            }
+            _ => Err("Should return 'Breached Password' error"),
+        }
+    }
+
+    #[test]
+    fn anonymous_keeps_old_password_when_hashing_fails() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let token_repository = RestorePasswordTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let token_generator: &dyn GeneratorTrait = &TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMockWithBrokenHashing::new();
+        let password_policy = PasswordPolicy::default();
+        let notifier = NotifierMock::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = AnonymousWithTokenChangesPassword::new(
+            &user_repository,
+            &token_repository,
+            &session_repository,
+            &token_hasher,
+            &encryptor,
+            &credential_writer,
+            &password_policy,
+            None,
+            100,
+            &notifier,
+            &event_publisher,
+            &logger,
+            &transaction_manager,
+        );
+
+        let user_id = id_factory.create();
+        let user_name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+        let user = AuthenticatedUser::new(&user_id, user_name, email);
+        user_repository.save(&user).unwrap();
+        credential_repository.save(&PasswordCredential::new(
+            user_id.clone(),
+            "old-hash".to_string(),
+        ));
+
+        let token = token_generator.generate(&user.id);
+        let restore_token = RestorePasswordToken::new(
+            id_factory.create(),
+            user.id.clone(),
+            token_hasher.hash(&token),
+        );
+        token_repository.save(&restore_token).unwrap();
+
+        match uc.execute(token, "Correct-Horse-7".to_string()) {
+            Err(RestorePasswordError::PasswordEncryptionError(_)) => {}
+            _ => return Err("Encryption error is expected"),
+        }
+
+        match credential_repository.find_by_user_id(&user_id) {
+            Some(cred) if cred.password == "old-hash" => {}
+            _ => return Err("Old password should be kept"),
+        }
+
+        assert!(transaction_manager.journal().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn anonymous_changes_password_with_token_asynchronously() -> Result<(), &'static str> {
+        let id_factory = IdFactoryMock::new();
+        let token_repository = Arc::new(Blocking::new(RestorePasswordTokenRepositoryMock::new()));
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let user_repository = Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let token_hasher = Arc::new(TokenHasherMock::new());
+        let credential_repository =
+            Arc::new(Blocking::new(PasswordCredentialRepositoryMock::new()));
+        let credential_writer = Arc::new(AsyncPasswordCredentialWriter::new(
+            credential_repository.clone(),
+        ));
+        let encryptor = Arc::new(Blocking::new(EncryptorMock::new()));
+        let password_policy = Arc::new(PasswordPolicy::default());
+
+        let notifier = Arc::new(QueuedNotifier::new());
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+
+        let uc = AsyncAnonymousWithTokenChangesPassword::new(
+            user_repository.clone(),
+            token_repository.clone(),
+            session_repository.clone(),
+            token_hasher.clone(),
+            encryptor.clone(),
+            credential_writer.clone(),
+            password_policy.clone(),
+            None,
+            100,
+            notifier.clone(),
+            event_publisher.clone(),
+            Arc::new(DefaultLogger::new(Level::Trace, &StdoutWriter {})),
+            transaction_manager.clone(),
+        );
+
+        let user_id = id_factory.create();
+        let user_name = Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap();
+        let email = EmailAddress::new("test@mail.com".to_string()).unwrap();
+
+        user_repository
+            .inner()
+            .save(&AuthenticatedUser::new(&user_id, user_name, email))
+            .unwrap();
+        credential_repository
+            .inner()
+            .save(&PasswordCredential::new(user_id.clone(), "old".to_string()));
+
+        let token = TokenGeneratorMock::new().generate(&user_id);
+        token_repository
+            .inner()
+            .save(&RestorePasswordToken::new(
+                id_factory.create(),
+                user_id.clone(),
+                token_hasher.hash(&token),
+            ))
+            .unwrap();
+
+        if block_on(uc.execute(token.clone(), "Correct-Horse-7".to_string())).is_err() {
+            return Err("Password is not changed");
+        }
+
+        let stored = credential_repository
+            .inner()
+            .find_by_user_id(&user_id)
+            .unwrap();
+        assert_ne!("old", stored.password);
+        assert_eq!(1, notifier.len(), "Alert is not queued");
+
+        match block_on(uc.execute(token, "Correct-Horse-8".to_string())) {
+            Err(RestorePasswordError::TokenNotFoundError) => Ok(()),
+            _ => Err("Token should be single use"),
+        }
+    }
 }
@@ -1,5 +1,9 @@
 use crate::repository::restore_password_token_repository::RestorePasswordTokenRepositoryError;
+use crate::repository::session_repository::SessionRepositoryError;
+use crate::value_object::password::WeakPasswordError;
+use argentum_encryption_business::breached_password::BreachCheckError;
 use argentum_encryption_business::password::EncryptionError;
+use argentum_standard_business::transaction::TransactionError;
 use argentum_user_business::repository::user_repository::SavingUserError;
 
 #[derive(thiserror::Error, Debug)]
@@ -19,6 +23,21 @@ pub enum RestorePasswordError {
     #[error("Can't save token")]
     TokenRepositoryError(#[from] RestorePasswordTokenRepositoryError),
 
+    #[error("Can't get or delete sessions")]
+    SessionStorageError(#[from] SessionRepositoryError),
+
     #[error("Can't encrypt new password")]
     PasswordEncryptionError(#[from] EncryptionError),
+
+    #[error(transparent)]
+    WeakPassword(#[from] WeakPasswordError),
+
+    #[error("Password is found in a data breach")]
+    BreachedPassword,
+
+    #[error("Can't check password against data breaches")]
+    BreachCheckError(#[from] BreachCheckError),
+
+    #[error(transparent)]
+    TransactionError(#[from] TransactionError),
 }
@@ -0,0 +1,401 @@
+use crate::entity::session::Session;
+use crate::repository::session_repository::{AsyncSessionRepositoryTrait, SessionRepositoryTrait};
+use crate::use_case::user_authenticates_with_token::{anonymous_user, AuthenticationError};
+use argentum_standard_business::data_type::id::Id;
+use argentum_user_business::entity::user::User;
+use argentum_user_business::entity::user::User::Authenticated;
+use argentum_user_business::repository::user_repository::{
+    AnonymousUserRepositoryTrait, AsyncAnonymousUserRepositoryTrait,
+    AsyncAuthenticatedUserRepositoryTrait, AuthenticatedUserRepositoryTrait,
+};
+use argentum_user_business::token::{
+    AccessTokenClaims, SignedTokenVerifierTrait, TokenVerificationError,
+};
+use std::sync::Arc;
+
+/// Authenticates with a signed access token. The signature and the claims are checked offline,
+/// the session repository is used only to find out whether the session is revoked or expired.
+pub struct UserAuthenticatesWithSignedTokenUc<'s> {
+    user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
+    anon_repository: &'s dyn AnonymousUserRepositoryTrait,
+    session_repository: &'s dyn SessionRepositoryTrait,
+    token_verifier: &'s dyn SignedTokenVerifierTrait,
+    session_idle_ttl: u32, //configurable idle timeout in seconds
+}
+
+impl<'s> UserAuthenticatesWithSignedTokenUc<'s> {
+    pub fn new(
+        user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
+        anon_repository: &'s dyn AnonymousUserRepositoryTrait,
+        session_repository: &'s dyn SessionRepositoryTrait,
+        token_verifier: &'s dyn SignedTokenVerifierTrait,
+        session_idle_ttl: u32,
+    ) -> UserAuthenticatesWithSignedTokenUc<'s> {
+        UserAuthenticatesWithSignedTokenUc {
+            user_repository,
+            anon_repository,
+            session_repository,
+            token_verifier,
+            session_idle_ttl,
+        }
+    }
+
+    pub fn execute(&self, token: String) -> Result<User, AuthenticationError> {
+        let (claims, session_id) = verify(self.token_verifier, &token)?;
+
+        check_session(
+            self.session_repository.find(&session_id),
+            &claims,
+            self.session_idle_ttl,
+        )?;
+
+        match self.user_repository.find(&claims.subject)? {
+            Some(u) => Ok(Authenticated(u)),
+            None => anonymous_user(self.anon_repository.find(&claims.subject)),
+        }
+    }
+}
+
+/// Async counterpart of `UserAuthenticatesWithSignedTokenUc`
+pub struct AsyncUserAuthenticatesWithSignedTokenUc {
+    user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+    anon_repository: Arc<dyn AsyncAnonymousUserRepositoryTrait>,
+    session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+    token_verifier: Arc<dyn SignedTokenVerifierTrait + Send + Sync>,
+    session_idle_ttl: u32, //configurable idle timeout in seconds
+}
+
+impl AsyncUserAuthenticatesWithSignedTokenUc {
+    pub fn new(
+        user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+        anon_repository: Arc<dyn AsyncAnonymousUserRepositoryTrait>,
+        session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+        token_verifier: Arc<dyn SignedTokenVerifierTrait + Send + Sync>,
+        session_idle_ttl: u32,
+    ) -> AsyncUserAuthenticatesWithSignedTokenUc {
+        AsyncUserAuthenticatesWithSignedTokenUc {
+            user_repository,
+            anon_repository,
+            session_repository,
+            token_verifier,
+            session_idle_ttl,
+        }
+    }
+
+    pub async fn execute(&self, token: String) -> Result<User, AuthenticationError> {
+        let (claims, session_id) = verify(self.token_verifier.as_ref(), &token)?;
+
+        check_session(
+            self.session_repository.find(&session_id).await,
+            &claims,
+            self.session_idle_ttl,
+        )?;
+
+        match self.user_repository.find(&claims.subject).await? {
+            Some(u) => Ok(Authenticated(u)),
+            None => anonymous_user(self.anon_repository.find(&claims.subject).await),
+        }
+    }
+}
+
+fn verify(
+    verifier: &dyn SignedTokenVerifierTrait,
+    token: &str,
+) -> Result<(AccessTokenClaims, Id), AuthenticationError> {
+    let claims = match verifier.verify(token) {
+        Ok(claims) => claims,
+        Err(TokenVerificationError::Expired) => return Err(AuthenticationError::SessionExpired),
+        Err(e) => return Err(AuthenticationError::InvalidSignedToken(e)),
+    };
+
+    match claims.session_id.clone() {
+        Some(id) => Ok((claims, id)),
+        None => Err(AuthenticationError::InvalidSignedToken(
+            TokenVerificationError::InvalidClaims("Session id is missing".to_string()),
+        )),
+    }
+}
+
+/// A deleted or expired session revokes its signed tokens before they expire
+fn check_session(
+    found: Option<Session>,
+    claims: &AccessTokenClaims,
+    session_idle_ttl: u32,
+) -> Result<(), AuthenticationError> {
+    match found {
+        Some(session) if *session.user_id != *claims.subject => {
+            Err(AuthenticationError::SessionRevoked)
+        }
+        Some(session) if session.is_expired(session_idle_ttl) => {
+            Err(AuthenticationError::SessionExpired)
+        }
+        Some(_) => Ok(()),
+        None => Err(AuthenticationError::SessionRevoked),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::session::Session;
+    use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
+    use crate::mock::token::SignedTokenVerifierMock;
+    use crate::repository::session_repository::SessionRepositoryTrait;
+    use crate::use_case::user_authenticates_with_signed_token::AsyncUserAuthenticatesWithSignedTokenUc;
+    use crate::use_case::user_authenticates_with_signed_token::UserAuthenticatesWithSignedTokenUc;
+    use crate::use_case::user_authenticates_with_token::AuthenticationError;
+    use argentum_standard_business::asynchronous::Blocking;
+    use argentum_standard_business::data_type::email::EmailAddress;
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_user_business::entity::user::AuthenticatedUser;
+    use argentum_user_business::entity::user::User::Authenticated;
+    use argentum_user_business::mock::repository::anonymous_user_repository_mock::AnonymousUserRepositoryMock;
+    use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
+    use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::value_object::name::Name;
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+
+    fn prefill(
+        user_repository: &dyn AuthenticatedUserRepositoryTrait,
+        session_repository: &dyn SessionRepositoryTrait,
+    ) -> (Id, Id) {
+        let id_factory = IdFactoryMock::new();
+        let user_id: Id = id_factory.create();
+        let session_id: Id = id_factory.create();
+
+        let user = AuthenticatedUser::new(
+            &user_id,
+            Name::new(String::from("aaaa"), String::from("bbbb")).unwrap(),
+            EmailAddress::new(format!("user{}@a.com", user_id.to_string())).unwrap(),
+        );
+        let session = Session::new(session_id.clone(), user_id.clone(), "hash".into(), 3600);
+
+        user_repository.save(&user).expect("Can't save a user");
+        session_repository
+            .save(&session)
+            .expect("Can't save a session");
+
+        (user_id, session_id)
+    }
+
+    #[test]
+    fn test_authenticates_with_signed_token() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let verifier = SignedTokenVerifierMock::new();
+        let (user_id, session_id) = prefill(&authenticated_user_repository, &session_repository);
+
+        let uc = UserAuthenticatesWithSignedTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &verifier,
+            3600,
+        );
+
+        let token = verifier.issue(&user_id, &session_id, Utc::now() + Duration::minutes(5));
+
+        match uc.execute(token) {
+            Ok(Authenticated(u)) => {
+                assert_eq!(u.id.to_string(), user_id.to_string());
+                Ok(())
+            }
+            Ok(_) => Err("Return's anonymous user, not authenticated"),
+            Err(_) => Err("User is not authenticated"),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_with_expired_signed_token() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let verifier = SignedTokenVerifierMock::new();
+        let (user_id, session_id) = prefill(&authenticated_user_repository, &session_repository);
+
+        let uc = UserAuthenticatesWithSignedTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &verifier,
+            3600,
+        );
+
+        let token = verifier.issue(&user_id, &session_id, Utc::now() - Duration::minutes(5));
+
+        match uc.execute(token) {
+            Ok(_) => Err("Should return an error"),
+            Err(AuthenticationError::SessionExpired) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_with_forged_signed_token() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let verifier = SignedTokenVerifierMock::new();
+        prefill(&authenticated_user_repository, &session_repository);
+
+        let uc = UserAuthenticatesWithSignedTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &verifier,
+            3600,
+        );
+
+        match uc.execute("forged".to_string()) {
+            Ok(_) => Err("Should return an error"),
+            Err(AuthenticationError::InvalidSignedToken(_)) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_with_signed_token_of_revoked_session() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let verifier = SignedTokenVerifierMock::new();
+        let (user_id, session_id) = prefill(&authenticated_user_repository, &session_repository);
+
+        let uc = UserAuthenticatesWithSignedTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &verifier,
+            3600,
+        );
+
+        let token = verifier.issue(&user_id, &session_id, Utc::now() + Duration::minutes(5));
+        session_repository.delete(&session_id).unwrap();
+
+        match uc.execute(token) {
+            Ok(_) => Err("Should return an error"),
+            Err(AuthenticationError::SessionRevoked) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_with_signed_token_of_expired_session() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let verifier = SignedTokenVerifierMock::new();
+        let (user_id, session_id) = prefill(&authenticated_user_repository, &session_repository);
+
+        let mut session = session_repository.find(&session_id).unwrap();
+        session.expires_at = Utc::now() - Duration::seconds(1);
+        session_repository.delete(&session_id).unwrap();
+        session_repository.save(&session).unwrap();
+
+        let uc = UserAuthenticatesWithSignedTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &verifier,
+            3600,
+        );
+
+        let token = verifier.issue(&user_id, &session_id, Utc::now() + Duration::minutes(5));
+
+        match uc.execute(token) {
+            Ok(_) => Err("Should return an error"),
+            Err(AuthenticationError::SessionExpired) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_with_signed_token_of_idle_session() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let verifier = SignedTokenVerifierMock::new();
+        let (user_id, session_id) = prefill(&authenticated_user_repository, &session_repository);
+
+        let uc = UserAuthenticatesWithSignedTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &verifier,
+            0,
+        );
+
+        let token = verifier.issue(&user_id, &session_id, Utc::now() + Duration::minutes(5));
+
+        match uc.execute(token) {
+            Ok(_) => Err("Should return an error"),
+            Err(AuthenticationError::SessionExpired) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_with_signed_token_of_another_users_session() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let verifier = SignedTokenVerifierMock::new();
+        let (_, session_id) = prefill(&authenticated_user_repository, &session_repository);
+        let (user_id, _) = prefill(&authenticated_user_repository, &session_repository);
+
+        let uc = UserAuthenticatesWithSignedTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &verifier,
+            3600,
+        );
+
+        let token = verifier.issue(&user_id, &session_id, Utc::now() + Duration::minutes(5));
+
+        match uc.execute(token) {
+            Ok(_) => Err("Should return an error"),
+            Err(AuthenticationError::SessionRevoked) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_with_signed_token_asynchronously() -> Result<(), &'static str> {
+        let anonymous_user_repository = Arc::new(Blocking::new(AnonymousUserRepositoryMock::new()));
+        let authenticated_user_repository =
+            Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let verifier = Arc::new(SignedTokenVerifierMock::new());
+        let (user_id, session_id) = prefill(
+            &*authenticated_user_repository.inner(),
+            &*session_repository.inner(),
+        );
+
+        let uc = AsyncUserAuthenticatesWithSignedTokenUc::new(
+            authenticated_user_repository.clone(),
+            anonymous_user_repository.clone(),
+            session_repository.clone(),
+            verifier.clone(),
+            3600,
+        );
+
+        let token = verifier.issue(&user_id, &session_id, Utc::now() + Duration::minutes(5));
+
+        match block_on(uc.execute(token.clone())) {
+            Ok(Authenticated(u)) => assert_eq!(u.id.to_string(), user_id.to_string()),
+            Ok(_) => return Err("Return's anonymous user, not authenticated"),
+            Err(_) => return Err("User is not authenticated"),
+        }
+
+        session_repository.inner().delete(&session_id).unwrap();
+
+        match block_on(uc.execute(token)) {
+            Err(AuthenticationError::SessionRevoked) => Ok(()),
+            _ => Err("Revoked session should be rejected"),
+        }
+    }
+}
@@ -1,14 +1,22 @@
-use crate::repository::session_repository::SessionRepositoryTrait;
-use argentum_user_business::entity::user::User;
+use crate::entity::session::Session;
+use crate::repository::session_repository::{
+    AsyncSessionRepositoryTrait, SessionRepositoryError, SessionRepositoryTrait,
+};
 use argentum_user_business::entity::user::User::{Anonymous, Authenticated};
+use argentum_user_business::entity::user::{AnonymousUser, User};
 use argentum_user_business::repository::user_repository::{
-    AnonymousUserRepositoryTrait, AuthenticatedUserRepositoryTrait, SavingUserError,
+    AnonymousUserRepositoryTrait, AsyncAnonymousUserRepositoryTrait,
+    AsyncAuthenticatedUserRepositoryTrait, AuthenticatedUserRepositoryTrait, SavingUserError,
 };
+use argentum_user_business::token::{constant_time_eq, TokenHasherTrait, TokenVerificationError};
+use std::sync::Arc;
 
 pub struct UserAuthenticatesWithTokenUc<'s> {
     user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
     anon_repository: &'s dyn AnonymousUserRepositoryTrait,
     session_repository: &'s dyn SessionRepositoryTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
+    session_idle_ttl: u32, //configurable idle timeout in seconds
 }
 
 impl<'s> UserAuthenticatesWithTokenUc<'s> {
@@ -16,37 +24,108 @@ impl<'s> UserAuthenticatesWithTokenUc<'s> {
         user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
         anon_repository: &'s dyn AnonymousUserRepositoryTrait,
         session_repository: &'s dyn SessionRepositoryTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
+        session_idle_ttl: u32,
     ) -> UserAuthenticatesWithTokenUc<'s> {
         UserAuthenticatesWithTokenUc {
             user_repository,
             anon_repository,
             session_repository,
+            token_hasher,
+            session_idle_ttl,
         }
     }
 
     pub fn execute(&self, token: String) -> Result<User, AuthenticationError> {
-        let session_result = self.session_repository.find_by_token(token);
-
-        let session = match session_result {
-            Some(s) => s,
-            None => return Err(AuthenticationError::WrongToken),
-        };
-
-        let user = self.user_repository.find(&session.user_id);
-
-        match user {
-            Err(e) => Err(AuthenticationError::UserRepositoryError(e)),
-            Ok(o) => match o {
-                Some(u) => Ok(Authenticated(u)),
-                None => match self.anon_repository.find(&session.user_id) {
-                    Some(a) => Ok(Anonymous(a)),
-                    None => Err(AuthenticationError::UserNotFound),
-                },
-            },
+        let token_hash = self.token_hasher.hash(&token);
+        let session = touched_session(
+            self.session_repository.find_by_token_hash(&token_hash),
+            &token_hash,
+            self.session_idle_ttl,
+        )?;
+        self.session_repository.update(&session)?;
+
+        match self.user_repository.find(&session.user_id)? {
+            Some(u) => Ok(Authenticated(u)),
+            None => anonymous_user(self.anon_repository.find(&session.user_id)),
+        }
+    }
+}
+
+/// Async counterpart of `UserAuthenticatesWithTokenUc`
+pub struct AsyncUserAuthenticatesWithTokenUc {
+    user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+    anon_repository: Arc<dyn AsyncAnonymousUserRepositoryTrait>,
+    session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    session_idle_ttl: u32, //configurable idle timeout in seconds
+}
+
+impl AsyncUserAuthenticatesWithTokenUc {
+    pub fn new(
+        user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+        anon_repository: Arc<dyn AsyncAnonymousUserRepositoryTrait>,
+        session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        session_idle_ttl: u32,
+    ) -> AsyncUserAuthenticatesWithTokenUc {
+        AsyncUserAuthenticatesWithTokenUc {
+            user_repository,
+            anon_repository,
+            session_repository,
+            token_hasher,
+            session_idle_ttl,
+        }
+    }
+
+    pub async fn execute(&self, token: String) -> Result<User, AuthenticationError> {
+        let token_hash = self.token_hasher.hash(&token);
+        let session = touched_session(
+            self.session_repository
+                .find_by_token_hash(&token_hash)
+                .await,
+            &token_hash,
+            self.session_idle_ttl,
+        )?;
+        self.session_repository.update(&session).await?;
+
+        match self.user_repository.find(&session.user_id).await? {
+            Some(u) => Ok(Authenticated(u)),
+            None => anonymous_user(self.anon_repository.find(&session.user_id).await),
         }
     }
 }
 
+// Shared by the sync and async use cases
+
+/// The session behind the token with its idle window moved, it still has to be stored
+fn touched_session(
+    found: Option<Session>,
+    token_hash: &str,
+    session_idle_ttl: u32,
+) -> Result<Session, AuthenticationError> {
+    let mut session = match found {
+        Some(s) if constant_time_eq(&s.token_hash, token_hash) => s,
+        _ => return Err(AuthenticationError::WrongToken),
+    };
+
+    if session.is_expired(session_idle_ttl) {
+        return Err(AuthenticationError::SessionExpired);
+    }
+
+    session.touch();
+
+    Ok(session)
+}
+
+/// Sessions of anonymous users are authenticated too
+pub(super) fn anonymous_user(found: Option<AnonymousUser>) -> Result<User, AuthenticationError> {
+    match found {
+        Some(a) => Ok(Anonymous(a)),
+        None => Err(AuthenticationError::UserNotFound),
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum AuthenticationError {
     #[error("User not found")]
@@ -55,17 +134,34 @@ pub enum AuthenticationError {
     #[error("Wrong token")]
     WrongToken,
 
+    #[error("Session expired")]
+    SessionExpired,
+
+    #[error("Session is revoked")]
+    SessionRevoked,
+
+    #[error("Invalid signed token")]
+    InvalidSignedToken(#[source] TokenVerificationError),
+
+    #[error("Can't update session")]
+    SessionRepositoryError(#[from] SessionRepositoryError),
+
     #[error("User repository error")]
     UserRepositoryError(#[from] SavingUserError),
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::use_case::user_authenticates_with_token::AsyncUserAuthenticatesWithTokenUc;
+    use argentum_standard_business::asynchronous::Blocking;
     use argentum_standard_business::data_type::email::EmailAddress;
     use argentum_standard_business::data_type::id::{Id, IdFactory};
+    use argentum_standard_business::mock::executor::block_on;
+    use std::sync::Arc;
 
     use crate::entity::session::Session;
     use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
+    use crate::mock::token::TokenHasherMock;
     use crate::repository::session_repository::SessionRepositoryTrait;
     use crate::use_case::user_authenticates_with_token::AuthenticationError;
     use crate::use_case::user_authenticates_with_token::UserAuthenticatesWithTokenUc;
@@ -75,13 +171,16 @@ mod tests {
     use argentum_user_business::mock::repository::anonymous_user_repository_mock::AnonymousUserRepositoryMock;
     use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
     use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::token::TokenHasherTrait;
     use argentum_user_business::value_object::name::Name;
+    use chrono::{Duration, Utc};
 
     #[test]
     fn test_authenticates_with_token() -> Result<(), &'static str> {
         let anonymous_user_repository = AnonymousUserRepositoryMock::new();
         let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
         let session_repository = SessionRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
         let id_factory = IdFactoryMock::new();
 
         //Data
@@ -93,7 +192,7 @@ mod tests {
             Name::new(String::from("aaaa"), String::from("bbbb")).unwrap(),
             EmailAddress::new(String::from("aa@a.com")).unwrap(),
         );
-        let session = Session::new(session_id, user_id.clone(), token.clone());
+        let session = Session::new(session_id, user_id.clone(), token_hasher.hash(&token), 3600);
 
         //Prefilling
         authenticated_user_repository
@@ -108,6 +207,8 @@ mod tests {
             &authenticated_user_repository,
             &anonymous_user_repository,
             &session_repository,
+            &token_hasher,
+            600,
         );
 
         let result = uc.execute(token.clone());
@@ -117,15 +218,11 @@ mod tests {
                 Authenticated(u) => {
                     assert_eq!(u.id.to_string(), user_id.clone().to_string());
 
-                    return Ok(());
-                }
-                Anonymous(_) => {
-                    return Err("Return's anonymous user, not authenticated");
+                    Ok(())
                 }
+                Anonymous(_) => Err("Return's anonymous user, not authenticated"),
             },
-            Err(_) => {
-                return Err("User is not authenticated");
-            }
+            Err(_) => Err("User is not authenticated"),
         }
     }
 
@@ -135,6 +232,7 @@ mod tests {
         let anonymous_user_repository = AnonymousUserRepositoryMock::new();
         let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
         let session_repository = SessionRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
         let id_factory = IdFactoryMock::new();
 
         //Data
@@ -146,7 +244,7 @@ mod tests {
             Name::new(String::from("aaaa"), String::from("bbbb")).unwrap(),
             EmailAddress::new(String::from("aa@a.com")).unwrap(),
         );
-        let session = Session::new(session_id, user_id.clone(), token.clone());
+        let session = Session::new(session_id, user_id.clone(), token_hasher.hash(&token), 3600);
 
         //Prefilling
         authenticated_user_repository
@@ -161,19 +259,17 @@ mod tests {
             &authenticated_user_repository,
             &anonymous_user_repository,
             &session_repository,
+            &token_hasher,
+            600,
         );
 
         let result = uc.execute(String::from("wrong-test-token"));
 
         match result {
-            Ok(_) => {
-                return Err("Shpould return error, not an user");
-            }
+            Ok(_) => Err("Shpould return error, not an user"),
             Err(e) => match e {
-                AuthenticationError::WrongToken => return Ok(()),
-                _ => {
-                    return Err("Invalid response status");
-                }
+                AuthenticationError::WrongToken => Ok(()),
+                _ => Err("Invalid response status"),
             },
         }
     }
@@ -184,13 +280,14 @@ mod tests {
         let anonymous_user_repository = AnonymousUserRepositoryMock::new();
         let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
         let session_repository = SessionRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
         let id_factory = IdFactoryMock::new();
 
         //Data
         let user_id: Id = id_factory.create();
         let session_id = id_factory.create();
         let token = String::from("test-token");
-        let session = Session::new(session_id, user_id.clone(), token.clone());
+        let session = Session::new(session_id, user_id.clone(), token_hasher.hash(&token), 3600);
 
         //Prefilling
         session_repository
@@ -202,20 +299,197 @@ mod tests {
             &authenticated_user_repository,
             &anonymous_user_repository,
             &session_repository,
+            &token_hasher,
+            600,
         );
 
         let result = uc.execute(token.clone());
 
         match result {
-            Ok(_) => {
-                return Err("Shpould return error, not an user");
-            }
+            Ok(_) => Err("Shpould return error, not an user"),
             Err(e) => match e {
-                AuthenticationError::UserNotFound => return Ok(()),
-                _ => {
-                    return Err("Invalid response status");
-                }
+                AuthenticationError::UserNotFound => Ok(()),
+                _ => Err("Invalid response status"),
             },
         }
     }
+
+    #[test]
+    fn test_authenticates_with_idle_session() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let id_factory = IdFactoryMock::new();
+
+        //Data
+        let user_id: Id = id_factory.create();
+        let session_id = id_factory.create();
+        let token = String::from("test-token");
+        let mut session =
+            Session::new(session_id, user_id.clone(), token_hasher.hash(&token), 3600);
+        session.last_seen_at = Utc::now() - Duration::seconds(601);
+
+        //Prefilling
+        session_repository
+            .save(&session)
+            .expect("Can't save a session");
+
+        //Test
+        let uc = UserAuthenticatesWithTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &token_hasher,
+            600,
+        );
+
+        match uc.execute(token) {
+            Ok(_) => Err("Should return error, not an user"),
+            Err(AuthenticationError::SessionExpired) => Ok(()),
+            Err(_) => Err("Invalid response status"),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_with_session_after_its_lifetime() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let id_factory = IdFactoryMock::new();
+
+        //Data
+        let user_id: Id = id_factory.create();
+        let session_id = id_factory.create();
+        let token = String::from("test-token");
+        let mut session =
+            Session::new(session_id, user_id.clone(), token_hasher.hash(&token), 3600);
+        session.expires_at = Utc::now() - Duration::seconds(1);
+
+        //Prefilling
+        session_repository
+            .save(&session)
+            .expect("Can't save a session");
+
+        //Test
+        let uc = UserAuthenticatesWithTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &token_hasher,
+            600,
+        );
+
+        match uc.execute(token) {
+            Ok(_) => Err("Should return error, not an user"),
+            Err(AuthenticationError::SessionExpired) => Ok(()),
+            Err(_) => Err("Invalid response status"),
+        }
+    }
+
+    #[test]
+    fn test_authentication_slides_idle_window() -> Result<(), &'static str> {
+        let anonymous_user_repository = AnonymousUserRepositoryMock::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let id_factory = IdFactoryMock::new();
+
+        //Data
+        let user_id: Id = id_factory.create();
+        let session_id = id_factory.create();
+        let token = String::from("test-token");
+        let authenticated_user = AuthenticatedUser::new(
+            &user_id,
+            Name::new(String::from("aaaa"), String::from("bbbb")).unwrap(),
+            EmailAddress::new(String::from("aa@a.com")).unwrap(),
+        );
+        let mut session =
+            Session::new(session_id.clone(), user_id, token_hasher.hash(&token), 3600);
+        session.last_seen_at = Utc::now() - Duration::seconds(500);
+
+        //Prefilling
+        authenticated_user_repository
+            .save(&authenticated_user)
+            .expect("Can't save a user");
+        session_repository
+            .save(&session)
+            .expect("Can't save a session");
+
+        //Test
+        let uc = UserAuthenticatesWithTokenUc::new(
+            &authenticated_user_repository,
+            &anonymous_user_repository,
+            &session_repository,
+            &token_hasher,
+            600,
+        );
+
+        if uc.execute(token).is_err() {
+            return Err("User is not authenticated");
+        }
+
+        match session_repository.find(&session_id) {
+            Some(s) if s.last_seen_at > session.last_seen_at => Ok(()),
+            Some(_) => Err("Idle window is not moved"),
+            None => Err("Session is not found"),
+        }
+    }
+
+    #[test]
+    fn test_authenticates_with_token_asynchronously() -> Result<(), &'static str> {
+        let anonymous_user_repository = Arc::new(Blocking::new(AnonymousUserRepositoryMock::new()));
+        let authenticated_user_repository =
+            Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let token_hasher = Arc::new(TokenHasherMock::new());
+        let id_factory = IdFactoryMock::new();
+
+        let user_id: Id = id_factory.create();
+        let token = String::from("test-token");
+        let authenticated_user = AuthenticatedUser::new(
+            &user_id,
+            Name::new(String::from("aaaa"), String::from("bbbb")).unwrap(),
+            EmailAddress::new(String::from("aa@a.com")).unwrap(),
+        );
+        let session = Session::new(
+            id_factory.create(),
+            user_id.clone(),
+            token_hasher.hash(&token),
+            3600,
+        );
+
+        authenticated_user_repository
+            .inner()
+            .save(&authenticated_user)
+            .expect("Can't save a user");
+        session_repository
+            .inner()
+            .save(&session)
+            .expect("Can't save a session");
+
+        let uc = AsyncUserAuthenticatesWithTokenUc::new(
+            authenticated_user_repository.clone(),
+            anonymous_user_repository.clone(),
+            session_repository.clone(),
+            token_hasher.clone(),
+            600,
+        );
+
+        match block_on(uc.execute("wrong-token".to_string())) {
+            Err(AuthenticationError::WrongToken) => {}
+            _ => return Err("Wrong token should be rejected"),
+        }
+
+        match block_on(uc.execute(token)) {
+            Ok(Authenticated(u)) => {
+                assert_eq!(u.id.to_string(), user_id.to_string());
+
+                Ok(())
+            }
+            Ok(Anonymous(_)) => Err("Return's anonymous user, not authenticated"),
+            Err(_) => Err("User is not authenticated"),
+        }
+    }
 }
@@ -1,137 +1,572 @@
+use crate::entity::credential::PasswordCredential;
 use crate::entity::session::Session;
-use crate::repository::password_credential_checker::PasswordCredentialChecker;
-use crate::repository::session_repository::SessionRepositoryTrait;
+use crate::event::{AnonymousBoundToUser, LoginFailed, UserLoggedIn};
+use crate::repository::login_attempt_repository::LoginAttemptRepositoryError;
+use crate::repository::login_throttler::{AsyncLoginThrottler, LoginThrottler};
+use crate::repository::password_credential_checker::{
+    AsyncPasswordCredentialChecker, PasswordCredentialChecker,
+};
+use crate::repository::password_credential_writer::{
+    AsyncPasswordCredentialWriter, PasswordCredentialWriterTrait,
+};
+use crate::repository::session_repository::{
+    AsyncSessionRepositoryTrait, SessionRepositoryError, SessionRepositoryTrait,
+};
+use argentum_encryption_business::password::{
+    AsyncEncryptor, EncryptionError, Encryptor, ValidationError,
+};
+use argentum_event_business::event::EventPublisherTrait;
 use argentum_log_business::LoggerTrait;
 use argentum_standard_business::data_type::email::EmailAddress;
 use argentum_standard_business::data_type::id::IdFactory;
+use argentum_standard_business::transaction::{
+    in_async_transaction, in_transaction, AsyncTransactionManagerTrait, Transaction,
+    TransactionError, TransactionManagerTrait,
+};
 use argentum_user_business::entity::anonymous_binding::AnonymousBinding;
-use argentum_user_business::entity::user::{AnonymousUser, UserTrait};
-use argentum_user_business::repository::anonymous_binding_repository::AnonymousBindingRepositoryTrait;
+use argentum_user_business::entity::user::{AnonymousUser, AuthenticatedUser, UserTrait};
+use argentum_user_business::repository::anonymous_binding_repository::{
+    AnonymousBindingRepositoryError, AnonymousBindingRepositoryTrait,
+    AsyncAnonymousBindingRepositoryTrait,
+};
 use argentum_user_business::repository::user_repository::{
-    AuthenticatedUserRepositoryTrait, SavingUserError,
+    AsyncAuthenticatedUserRepositoryTrait, AuthenticatedUserRepositoryTrait, SavingUserError,
 };
-use argentum_user_business::token::GeneratorTrait;
+use argentum_user_business::token::{GeneratorTrait, TokenHasherTrait};
+use std::sync::Arc;
 
 pub struct UserLoginsWithPasswordUc<'s> {
     user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
     anonymous_binding_repository: &'s dyn AnonymousBindingRepositoryTrait,
     session_repository: &'s dyn SessionRepositoryTrait,
     credential_checker: &'s PasswordCredentialChecker<'s>,
+    login_throttler: &'s LoginThrottler<'s>,
+    credential_writer: &'s dyn PasswordCredentialWriterTrait<'s>,
+    encryptor: &'s dyn Encryptor,
     id_factory: &'s dyn IdFactory,
     token_generator: &'s dyn GeneratorTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
     logger: &'s dyn LoggerTrait,
+    session_ttl: u32, //configurable session lifetime in seconds
+    require_verified_email: bool,
+    event_publisher: &'s dyn EventPublisherTrait,
+    transaction_manager: &'s dyn TransactionManagerTrait,
 }
 
 impl<'s> UserLoginsWithPasswordUc<'s> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
         anonymous_binding_repository: &'s dyn AnonymousBindingRepositoryTrait,
         session_repository: &'s dyn SessionRepositoryTrait,
         credential_checker: &'s PasswordCredentialChecker<'s>,
+        login_throttler: &'s LoginThrottler<'s>,
+        credential_writer: &'s dyn PasswordCredentialWriterTrait<'s>,
+        encryptor: &'s dyn Encryptor,
         id_factory: &'s dyn IdFactory,
         token_generator: &'s dyn GeneratorTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
         logger: &'s dyn LoggerTrait,
+        session_ttl: u32,
+        require_verified_email: bool,
+        event_publisher: &'s dyn EventPublisherTrait,
+        transaction_manager: &'s dyn TransactionManagerTrait,
     ) -> UserLoginsWithPasswordUc<'s> {
         UserLoginsWithPasswordUc {
             user_repository,
             anonymous_binding_repository,
             session_repository,
             credential_checker,
+            login_throttler,
+            credential_writer,
+            encryptor,
             id_factory,
             token_generator,
+            token_hasher,
             logger,
+            session_ttl,
+            require_verified_email,
+            event_publisher,
+            transaction_manager,
         }
     }
 
+    /// Returns the raw session token along with the session, it is the only place to get it.
+    /// `client_id` identifies the caller for throttling, e.g. its IP address
     pub fn execute(
         &self,
         anonymous: AnonymousUser,
         email: EmailAddress,
         password: String,
-    ) -> Result<Session, LoginError> {
+        client_id: String,
+    ) -> Result<(Session, String), LoginError> {
+        throttle(
+            self.login_throttler
+                .retry_after(&email.as_string(), &client_id)?,
+        )?;
+
         let result = self.user_repository.find_by_email(&email);
 
         let user = match result {
             Ok(o) => match o {
                 Some(u) => u,
-                None => return Err(LoginError::WrongEmailOrPassword),
+                None => {
+                    self.credential_checker.check_against_dummy(&password);
+
+                    return self.fail(&email, &client_id);
+                }
             },
             Err(e) => return Err(LoginError::GetUserError(e)),
         };
 
-        let ok = self.credential_checker.check(user.id(), &password);
+        match self.credential_checker.check(user.id(), &password) {
+            Ok(true) => {}
+            Ok(false) => return self.fail(&email, &client_id),
+            Err(e) => return Err(credential_error(self.logger, e)),
+        }
+
+        if let Err(e) = self.login_throttler.reset(&email.as_string()) {
+            self.logger
+                .warning(format!("Failed login attempts are not reset: {}", e));
+        }
 
-        if !ok {
-            return Err(LoginError::WrongEmailOrPassword);
+        // Checked after the password, so it doesn't tell anything to a guesser
+        if self.require_verified_email && !user.is_email_verified() {
+            return Err(LoginError::EmailNotVerified);
         }
 
-        let session = Session::new(
-            self.id_factory.create(),
-            user.id().clone(),
-            self.token_generator.generate(&user.id),
+        let rehashed_credential = match self
+            .credential_checker
+            .needs_rehash(&user.id, self.encryptor)
+        {
+            true => rehashed(self.logger, &user, self.encryptor.encrypt(&password)),
+            false => None,
+        };
+
+        let (session, token) = new_session(
+            self.id_factory,
+            self.token_generator,
+            self.token_hasher,
+            &user,
+            self.session_ttl,
         );
 
-        let result = match self.session_repository.save(&session) {
-            Ok(_) => Result::Ok(session),
-            Err(_) => Err(LoginError::SaveSession),
+        in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<(), LoginError> {
+                let session_repository = self.session_repository.within(transaction);
+
+                if session_repository.save(&session).is_err() {
+                    return Err(LoginError::SaveSession);
+                }
+
+                // A failed write fails the commit along with the login
+                if let Some(cred) = &rehashed_credential {
+                    self.credential_writer
+                        .within(transaction)
+                        .write_password_credentials(cred);
+                }
+
+                session_repository
+                    .delete_users_sessions(&anonymous.id)
+                    .map_err(LoginError::DeleteAnonymousSession)?;
+
+                let binding = AnonymousBinding::new(user.id(), anonymous.id());
+                self.anonymous_binding_repository
+                    .within(transaction)
+                    .save(&binding)?;
+
+                Ok(())
+            },
+        )?;
+
+        publish_login(self.event_publisher, &user, &anonymous, &session);
+
+        Ok((session, token))
+    }
+
+    fn fail(&self, email: &EmailAddress, client_id: &str) -> Result<(Session, String), LoginError> {
+        self.event_publisher
+            .publish(&LoginFailed::new(email.clone(), client_id.to_string()));
+
+        throttle(
+            self.login_throttler
+                .register_failure(&email.as_string(), client_id)?,
+        )?;
+
+        Err(LoginError::WrongEmailOrPassword)
+    }
+}
+
+/// Async counterpart of `UserLoginsWithPasswordUc`
+pub struct AsyncUserLoginsWithPasswordUc {
+    user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+    anonymous_binding_repository: Arc<dyn AsyncAnonymousBindingRepositoryTrait>,
+    session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+    credential_checker: Arc<AsyncPasswordCredentialChecker>,
+    login_throttler: Arc<AsyncLoginThrottler>,
+    credential_writer: Arc<AsyncPasswordCredentialWriter>,
+    encryptor: Arc<dyn AsyncEncryptor>,
+    id_factory: Arc<dyn IdFactory + Send + Sync>,
+    token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    logger: Arc<dyn LoggerTrait + Send + Sync>,
+    session_ttl: u32, //configurable session lifetime in seconds
+    require_verified_email: bool,
+    event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+    transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+}
+
+impl AsyncUserLoginsWithPasswordUc {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+        anonymous_binding_repository: Arc<dyn AsyncAnonymousBindingRepositoryTrait>,
+        session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+        credential_checker: Arc<AsyncPasswordCredentialChecker>,
+        login_throttler: Arc<AsyncLoginThrottler>,
+        credential_writer: Arc<AsyncPasswordCredentialWriter>,
+        encryptor: Arc<dyn AsyncEncryptor>,
+        id_factory: Arc<dyn IdFactory + Send + Sync>,
+        token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        logger: Arc<dyn LoggerTrait + Send + Sync>,
+        session_ttl: u32,
+        require_verified_email: bool,
+        event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+        transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+    ) -> AsyncUserLoginsWithPasswordUc {
+        AsyncUserLoginsWithPasswordUc {
+            user_repository,
+            anonymous_binding_repository,
+            session_repository,
+            credential_checker,
+            login_throttler,
+            credential_writer,
+            encryptor,
+            id_factory,
+            token_generator,
+            token_hasher,
+            logger,
+            session_ttl,
+            require_verified_email,
+            event_publisher,
+            transaction_manager,
+        }
+    }
+
+    /// See `UserLoginsWithPasswordUc::execute`
+    pub async fn execute(
+        &self,
+        anonymous: AnonymousUser,
+        email: EmailAddress,
+        password: String,
+        client_id: String,
+    ) -> Result<(Session, String), LoginError> {
+        throttle(
+            self.login_throttler
+                .retry_after(&email.as_string(), &client_id)
+                .await?,
+        )?;
+
+        let user = match self.user_repository.find_by_email(&email).await {
+            Ok(Some(u)) => u,
+            Ok(None) => {
+                self.credential_checker.check_against_dummy(&password).await;
+
+                return self.fail(&email, &client_id).await;
+            }
+            Err(e) => return Err(LoginError::GetUserError(e)),
         };
 
-        match self
-            .session_repository
-            .delete_users_sessions(&anonymous.id())
+        match self.credential_checker.check(user.id(), &password).await {
+            Ok(true) => {}
+            Ok(false) => return self.fail(&email, &client_id).await,
+            Err(e) => return Err(credential_error(self.logger.as_ref(), e)),
+        }
+
+        if let Err(e) = self.login_throttler.reset(&email.as_string()).await {
+            self.logger
+                .warning(format!("Failed login attempts are not reset: {}", e));
+        }
+
+        // Checked after the password, so it doesn't tell anything to a guesser
+        if self.require_verified_email && !user.is_email_verified() {
+            return Err(LoginError::EmailNotVerified);
+        }
+
+        let rehashed_credential = match self
+            .credential_checker
+            .needs_rehash(&user.id, self.encryptor.as_ref())
+            .await
         {
-            Ok(_) => self.logger.info("Anonymous session deleted".to_string()),
-            Err(_) => self
-                .logger
-                .warning("Anonymous session is not deleted".to_string()),
+            true => {
+                let hashed = self.encryptor.encrypt(&password).await;
+
+                rehashed(self.logger.as_ref(), &user, hashed)
+            }
+            false => None,
         };
 
+        let (session, token) = new_session(
+            self.id_factory.as_ref(),
+            self.token_generator.as_ref(),
+            self.token_hasher.as_ref(),
+            &user,
+            self.session_ttl,
+        );
+
+        in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.save_session(
+                transaction,
+                &user,
+                &anonymous,
+                &session,
+                rehashed_credential.as_ref(),
+            )
+        })
+        .await?;
+
+        publish_login(self.event_publisher.as_ref(), &user, &anonymous, &session);
+
+        Ok((session, token))
+    }
+
+    /// Replaces the anonymous session with the session of the user and binds the anonymous to it
+    async fn save_session(
+        &self,
+        transaction: Transaction,
+        user: &AuthenticatedUser,
+        anonymous: &AnonymousUser,
+        session: &Session,
+        rehashed_credential: Option<&PasswordCredential>,
+    ) -> Result<(), LoginError> {
+        let session_repository = self.session_repository.within(&transaction);
+
+        if session_repository.save(session).await.is_err() {
+            return Err(LoginError::SaveSession);
+        }
+
+        // A failed write fails the commit along with the login
+        if let Some(cred) = rehashed_credential {
+            self.credential_writer
+                .within(&transaction)
+                .write_password_credentials(cred)
+                .await;
+        }
+
+        session_repository
+            .delete_users_sessions(&anonymous.id)
+            .await
+            .map_err(LoginError::DeleteAnonymousSession)?;
+
         let binding = AnonymousBinding::new(user.id(), anonymous.id());
-        match self.anonymous_binding_repository.save(&binding) {
-            Ok(_) => self.logger.info("Anonymous binding saved".to_string()),
-            Err(_) => self
-                .logger
-                .warning("Anonymous binding is not saved".to_string()),
+        self.anonymous_binding_repository
+            .within(&transaction)
+            .save(&binding)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fail(
+        &self,
+        email: &EmailAddress,
+        client_id: &str,
+    ) -> Result<(Session, String), LoginError> {
+        self.event_publisher
+            .publish(&LoginFailed::new(email.clone(), client_id.to_string()));
+
+        throttle(
+            self.login_throttler
+                .register_failure(&email.as_string(), client_id)
+                .await?,
+        )?;
+
+        Err(LoginError::WrongEmailOrPassword)
+    }
+}
+
+// The decisions below are shared by the sync and async use cases, only the way
+// the dependencies are called differs
+
+fn throttle(retry_after: u32) -> Result<(), LoginError> {
+    match retry_after {
+        0 => Ok(()),
+        _ => Err(LoginError::TooManyAttempts { retry_after }),
+    }
+}
+
+fn credential_error(logger: &dyn LoggerTrait, e: ValidationError) -> LoginError {
+    logger.error(format!("Can't validate password credential: {}", e));
+
+    match e {
+        ValidationError::BackendError(_) => LoginError::CredentialBackendError(e),
+        _ => LoginError::MalformedCredential(e),
+    }
+}
+
+/// The credential to store in place of an outdated one, a failed rehash doesn't fail the login
+fn rehashed(
+    logger: &dyn LoggerTrait,
+    user: &AuthenticatedUser,
+    hashed_password: Result<String, EncryptionError>,
+) -> Option<PasswordCredential> {
+    match hashed_password {
+        Ok(hashed_password) => {
+            logger.info("Password credential rehashed".to_string());
+
+            Some(PasswordCredential::new(user.id(), hashed_password))
         }
+        Err(_) => {
+            logger.warning("Password credential is not rehashed".to_string());
 
-        result
+            None
+        }
     }
 }
 
+fn new_session(
+    id_factory: &dyn IdFactory,
+    token_generator: &dyn GeneratorTrait,
+    token_hasher: &dyn TokenHasherTrait,
+    user: &AuthenticatedUser,
+    session_ttl: u32,
+) -> (Session, String) {
+    let session_id = id_factory.create();
+    let token = token_generator.generate_for_session(&user.id, &session_id);
+    let session = Session::new(
+        session_id,
+        user.id().clone(),
+        token_hasher.hash(&token),
+        session_ttl,
+    );
+
+    (session, token)
+}
+
+/// Runs after commit, so subscribers never hear of a session that is not stored
+fn publish_login(
+    event_publisher: &dyn EventPublisherTrait,
+    user: &AuthenticatedUser,
+    anonymous: &AnonymousUser,
+    session: &Session,
+) {
+    event_publisher.publish(&UserLoggedIn::new(user.id(), session.id.clone()));
+    event_publisher.publish(&AnonymousBoundToUser::new(anonymous.id(), user.id()));
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum LoginError {
     #[error("Can't save session")]
     SaveSession,
 
+    #[error("Can't delete anonymous session")]
+    DeleteAnonymousSession(#[source] SessionRepositoryError),
+
+    #[error("Can't bind anonymous user")]
+    BindingError(#[from] AnonymousBindingRepositoryError),
+
     #[error("Can't get an user. DB error")]
     GetUserError(#[from] SavingUserError),
 
     #[error("Wrong email or password")]
     WrongEmailOrPassword,
+
+    #[error("Stored password credential is malformed")]
+    MalformedCredential(#[source] ValidationError),
+
+    #[error("Can't validate password credential")]
+    CredentialBackendError(#[source] ValidationError),
+
+    #[error("Email is not verified")]
+    EmailNotVerified,
+
+    #[error("Too many login attempts, retry after {retry_after} seconds")]
+    TooManyAttempts { retry_after: u32 },
+
+    #[error("Can't count login attempts")]
+    ThrottlingError(#[from] LoginAttemptRepositoryError),
+
+    #[error(transparent)]
+    TransactionError(#[from] TransactionError),
 }
 
 #[cfg(test)]
 mod test {
     use crate::entity::credential::PasswordCredential;
+    use crate::entity::session::Session;
+    use crate::mock::repository::login_attempt_repository_mock::LoginAttemptRepositoryMock;
     use crate::mock::repository::password_credential_repository_mock::PasswordCredentialRepositoryMock;
     use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
-    use crate::mock::token::TokenGeneratorMock;
+    use crate::mock::token::{TokenGeneratorMock, TokenHasherMock};
     use crate::repository::credential_writer::CredentialWriterTrait;
-    use crate::repository::password_credential_checker::PasswordCredentialChecker;
-    use crate::repository::password_credential_writer::PasswordCredentialWriter;
-    use crate::use_case::user_logins_with_password::UserLoginsWithPasswordUc;
-    use argentum_encryption_business::mock::password::{EncryptorMock, ValidatorMock};
-    use argentum_encryption_business::password::Encryptor;
+    use crate::repository::login_throttler::{
+        AsyncLoginThrottler, LoginThrottler, ThrottlingPolicy,
+    };
+    use crate::repository::password_credential_checker::{
+        AsyncPasswordCredentialChecker, PasswordCredentialChecker,
+    };
+    use crate::repository::password_credential_repository::PasswordCredentialRepository;
+    use crate::repository::password_credential_writer::{
+        AsyncPasswordCredentialWriter, PasswordCredentialWriter,
+    };
+    use crate::use_case::user_logins_with_password::{
+        AsyncUserLoginsWithPasswordUc, LoginError, UserLoginsWithPasswordUc,
+    };
+    use argentum_encryption_business::mock::password::{
+        EncryptorMock, ValidatorMock, ValidatorMockWithBrokenBackend,
+    };
+    use argentum_encryption_business::password::{Encryptor, Validator};
+    use argentum_event_business::mock::publisher::EventPublisherMock;
     use argentum_log_business::{DefaultLogger, Level, StdoutWriter};
+    use argentum_standard_business::asynchronous::Blocking;
     use argentum_standard_business::data_type::email::EmailAddress;
     use argentum_standard_business::data_type::id::{Id, IdFactory};
     use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_standard_business::mock::transaction::{
+        TransactionManagerMock, TransactionManagerMockWithBrokenCommit,
+    };
+    use argentum_standard_business::transaction::{Bound, Transaction};
+    use argentum_user_business::entity::anonymous_binding::AnonymousBinding;
     use argentum_user_business::entity::user::{AnonymousUser, AuthenticatedUser};
     use argentum_user_business::mock::repository::anonymous_binding_repository_mock::AnonymousBindingRepositoryMock;
     use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
-    use argentum_user_business::repository::anonymous_binding_repository::AnonymousBindingRepositoryTrait;
+    use argentum_user_business::repository::anonymous_binding_repository::{
+        AnonymousBindingRepositoryError, AnonymousBindingRepositoryTrait,
+    };
     use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
+    use argentum_user_business::token::TokenHasherTrait;
     use argentum_user_business::value_object::name::Name;
+    use std::sync::Arc;
+
+    const CLIENT_ID: &str = "127.0.0.1";
+
+    fn stub_login_throttler(repository: &LoginAttemptRepositoryMock) -> LoginThrottler<'_> {
+        LoginThrottler::new(
+            repository,
+            ThrottlingPolicy::new(2, 60, 600, 5, 900),
+            ThrottlingPolicy::for_client(),
+        )
+    }
+
+    /// Fails every save, as a storage which is down
+    struct BrokenAnonymousBindingRepository {}
+
+    impl AnonymousBindingRepositoryTrait for BrokenAnonymousBindingRepository {
+        fn find_by_user_id(&self, _: &Id) -> Option<AnonymousBinding> {
+            None
+        }
+
+        fn save(&self, _: &AnonymousBinding) -> Result<(), AnonymousBindingRepositoryError> {
+            Err(AnonymousBindingRepositoryError::Save)
+        }
+
+        fn within(&self, _: &Transaction) -> Bound<'_, dyn AnonymousBindingRepositoryTrait + '_> {
+            Bound::Shared(self)
+        }
+    }
 
     #[test]
     fn test_user_logins_with_passwodr() -> Result<(), &'static str> {
@@ -140,22 +575,38 @@ mod test {
         let session_repository = SessionRepositoryMock::new();
         let credential_repository = PasswordCredentialRepositoryMock::new();
         let validator = ValidatorMock::new();
-        let credential_checker = PasswordCredentialChecker::new(&credential_repository, &validator);
+        let encryptor = EncryptorMock::new();
+        let credential_checker =
+            PasswordCredentialChecker::new(&credential_repository, &validator, &encryptor);
+        let login_attempt_repository = LoginAttemptRepositoryMock::new();
+        let login_throttler = stub_login_throttler(&login_attempt_repository);
         let id_factory = IdFactoryMock::new();
         let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
         let credential_writer = PasswordCredentialWriter::new(&credential_repository);
 
         let log_writer = StdoutWriter::new();
         let logger = DefaultLogger::new(Level::Trace, &log_writer);
 
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
         let uc = UserLoginsWithPasswordUc::new(
             &user_repository,
             &anonymous_binding_repository,
             &session_repository,
             &credential_checker,
+            &login_throttler,
+            &credential_writer,
+            &encryptor,
             &id_factory,
             &token_generator,
+            &token_hasher,
             &logger,
+            3600,
+            false,
+            &event_publisher,
+            &transaction_manager,
         );
 
         let id_factory = IdFactoryMock::new();
@@ -165,9 +616,8 @@ mod test {
         let email = EmailAddress::new(String::from("test@test-mail.com")).unwrap();
         let password = String::from("12345");
         let user = AuthenticatedUser::new(&user_id, name, email.clone());
-        let encryptor = EncryptorMock::new();
-        let (hashed_password, salt) = encryptor.encrypt(&password).unwrap();
-        let cred = PasswordCredential::new(user_id.clone(), hashed_password, salt);
+        let hashed_password = encryptor.encrypt(&password).unwrap();
+        let cred = PasswordCredential::new(user_id.clone(), hashed_password);
 
         user_repository.save(&user).expect("Can't save a user");
         credential_writer.write(Box::new(cred));
@@ -175,24 +625,600 @@ mod test {
         let anonymous_id: Id = id_factory.create();
         let anonymous = AnonymousUser::new(&anonymous_id);
 
-        let result = uc.execute(anonymous, email, password);
+        let result = uc.execute(anonymous, email, password, CLIENT_ID.to_string());
 
         match result {
-            Ok(s) => {
+            Ok((s, token)) => {
                 assert_eq!(s.user_id.to_string(), user_id.to_string());
+                assert_eq!(token_hasher.hash(&token), s.token_hash);
 
                 let binding = anonymous_binding_repository
                     .find_by_user_id(&user_id)
                     .unwrap();
                 assert_eq!(binding.anonymous_id.to_string(), anonymous_id.to_string());
 
-                return Ok(());
+                assert_eq!(
+                    event_publisher.published(),
+                    vec![
+                        "user_account.user_logged_in",
+                        "user_account.anonymous_bound_to_user"
+                    ]
+                );
+
+                Ok(())
             }
-            Err(_) => {
-                return Err("User can't login");
+            Err(_) => Err("User can't login"),
+        }
+    }
+
+    #[test]
+    fn test_user_logged_in_is_not_published_when_commit_fails() -> Result<(), &'static str> {
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let anonymous_binding_repository = AnonymousBindingRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let validator = ValidatorMock::new();
+        let encryptor = EncryptorMock::new();
+        let credential_checker =
+            PasswordCredentialChecker::new(&credential_repository, &validator, &encryptor);
+        let login_attempt_repository = LoginAttemptRepositoryMock::new();
+        let login_throttler = stub_login_throttler(&login_attempt_repository);
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMockWithBrokenCommit::new();
+
+        let uc = UserLoginsWithPasswordUc::new(
+            &user_repository,
+            &anonymous_binding_repository,
+            &session_repository,
+            &credential_checker,
+            &login_throttler,
+            &credential_writer,
+            &encryptor,
+            &id_factory,
+            &token_generator,
+            &token_hasher,
+            &logger,
+            3600,
+            false,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let user_id: Id = id_factory.create();
+        let name = Name::new(String::from("Some"), String::from("Name")).unwrap();
+        let email = EmailAddress::new(String::from("test@test-mail.com")).unwrap();
+        let password = String::from("12345");
+        let user = AuthenticatedUser::new(&user_id, name, email.clone());
+        let cred = PasswordCredential::new(user_id, encryptor.encrypt(&password).unwrap());
+
+        user_repository.save(&user).expect("Can't save a user");
+        credential_writer.write(Box::new(cred));
+
+        let anonymous = AnonymousUser::new(&id_factory.create());
+
+        match uc.execute(anonymous, email, password, CLIENT_ID.to_string()) {
+            Ok(_) => Err("Should return an error"),
+            Err(LoginError::TransactionError(_)) => {
+                assert!(event_publisher.published().is_empty());
+
+                Ok(())
+            }
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_user_login_fails_when_anonymous_is_not_bound() -> Result<(), &'static str> {
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let anonymous_binding_repository = BrokenAnonymousBindingRepository {};
+        let session_repository = SessionRepositoryMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let validator = ValidatorMock::new();
+        let encryptor = EncryptorMock::new();
+        let credential_checker =
+            PasswordCredentialChecker::new(&credential_repository, &validator, &encryptor);
+        let login_attempt_repository = LoginAttemptRepositoryMock::new();
+        let login_throttler = stub_login_throttler(&login_attempt_repository);
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserLoginsWithPasswordUc::new(
+            &user_repository,
+            &anonymous_binding_repository,
+            &session_repository,
+            &credential_checker,
+            &login_throttler,
+            &credential_writer,
+            &encryptor,
+            &id_factory,
+            &token_generator,
+            &token_hasher,
+            &logger,
+            3600,
+            false,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let user_id: Id = id_factory.create();
+        let name = Name::new(String::from("Some"), String::from("Name")).unwrap();
+        let email = EmailAddress::new(String::from("test@test-mail.com")).unwrap();
+        let password = String::from("12345");
+        let user = AuthenticatedUser::new(&user_id, name, email.clone());
+        let cred = PasswordCredential::new(user_id, encryptor.encrypt(&password).unwrap());
+
+        user_repository.save(&user).expect("Can't save a user");
+        credential_writer.write(Box::new(cred));
+
+        let anonymous = AnonymousUser::new(&id_factory.create());
+
+        match uc.execute(anonymous, email, password, CLIENT_ID.to_string()) {
+            Ok(_) => Err("Should return an error"),
+            Err(LoginError::BindingError(_)) => {
+                assert!(transaction_manager.rolled_back());
+                assert!(event_publisher.published().is_empty());
+
+                Ok(())
             }
+            Err(_) => Err("Wrong error type"),
         }
     }
 
-    //TODO: negative test
+    #[test]
+    fn test_user_logins_with_outdated_password_hash() -> Result<(), &'static str> {
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let anonymous_binding_repository = AnonymousBindingRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let validator = ValidatorMock::new();
+        let encryptor = EncryptorMock::with_version(2);
+        let credential_checker =
+            PasswordCredentialChecker::new(&credential_repository, &validator, &encryptor);
+        let login_attempt_repository = LoginAttemptRepositoryMock::new();
+        let login_throttler = stub_login_throttler(&login_attempt_repository);
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserLoginsWithPasswordUc::new(
+            &user_repository,
+            &anonymous_binding_repository,
+            &session_repository,
+            &credential_checker,
+            &login_throttler,
+            &credential_writer,
+            &encryptor,
+            &id_factory,
+            &token_generator,
+            &token_hasher,
+            &logger,
+            3600,
+            false,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let user_id: Id = id_factory.create();
+        let name = Name::new(String::from("Some"), String::from("Name")).unwrap();
+        let email = EmailAddress::new(String::from("test@test-mail.com")).unwrap();
+        let password = String::from("12345");
+        let user = AuthenticatedUser::new(&user_id, name, email.clone());
+        let outdated_hash = EncryptorMock::with_version(1).encrypt(&password).unwrap();
+        let cred = PasswordCredential::new(user_id.clone(), outdated_hash.clone());
+
+        user_repository.save(&user).expect("Can't save a user");
+        credential_writer.write(Box::new(cred));
+
+        let anonymous = AnonymousUser::new(&id_factory.create());
+
+        if uc
+            .execute(anonymous, email, password, CLIENT_ID.to_string())
+            .is_err()
+        {
+            return Err("User can't login");
+        }
+
+        let stored = credential_repository.find_by_user_id(&user_id).unwrap();
+        assert_ne!(outdated_hash, stored.password, "Credential is not rehashed");
+        assert!(!encryptor.needs_rehash(&stored.password));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_logins_with_malformed_password_credential() -> Result<(), &'static str> {
+        let validator = ValidatorMock::new();
+
+        match login_with_stored_hash(&validator, "corrupted row") {
+            Err(LoginError::MalformedCredential(_)) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Should return an error"),
+        }
+    }
+
+    #[test]
+    fn test_user_logins_with_broken_validator() -> Result<(), &'static str> {
+        let validator = ValidatorMockWithBrokenBackend::new();
+
+        match login_with_stored_hash(&validator, "$mock$v=1$12345") {
+            Err(LoginError::CredentialBackendError(_)) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Should return an error"),
+        }
+    }
+
+    #[test]
+    fn test_user_logins_with_wrong_password() -> Result<(), &'static str> {
+        let validator = ValidatorMock::new();
+
+        match login_with_stored_hash(&validator, "$mock$v=1$54321") {
+            Err(LoginError::WrongEmailOrPassword) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Should return an error"),
+        }
+    }
+
+    fn login_with_stored_hash(
+        validator: &dyn Validator,
+        stored_hash: &str,
+    ) -> Result<(Session, String), LoginError> {
+        login_with_stored_hash_and_verification(validator, stored_hash, false, false)
+    }
+
+    fn login_with_stored_hash_and_verification(
+        validator: &dyn Validator,
+        stored_hash: &str,
+        require_verified_email: bool,
+        email_verified: bool,
+    ) -> Result<(Session, String), LoginError> {
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let anonymous_binding_repository = AnonymousBindingRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let encryptor = EncryptorMock::new();
+        let credential_checker =
+            PasswordCredentialChecker::new(&credential_repository, validator, &encryptor);
+        let login_attempt_repository = LoginAttemptRepositoryMock::new();
+        let login_throttler = stub_login_throttler(&login_attempt_repository);
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserLoginsWithPasswordUc::new(
+            &user_repository,
+            &anonymous_binding_repository,
+            &session_repository,
+            &credential_checker,
+            &login_throttler,
+            &credential_writer,
+            &encryptor,
+            &id_factory,
+            &token_generator,
+            &token_hasher,
+            &logger,
+            3600,
+            require_verified_email,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let user_id: Id = id_factory.create();
+        let name = Name::new(String::from("Some"), String::from("Name")).unwrap();
+        let email = EmailAddress::new(String::from("test@test-mail.com")).unwrap();
+        let mut user = AuthenticatedUser::new(&user_id, name, email.clone());
+        if email_verified {
+            user.verify_email();
+        }
+        let cred = PasswordCredential::new(user_id, stored_hash.to_string());
+
+        user_repository.save(&user).expect("Can't save a user");
+        credential_writer.write(Box::new(cred));
+
+        let anonymous = AnonymousUser::new(&id_factory.create());
+
+        uc.execute(
+            anonymous,
+            email,
+            String::from("12345"),
+            CLIENT_ID.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_user_logins_after_too_many_attempts() -> Result<(), &'static str> {
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let anonymous_binding_repository = AnonymousBindingRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let validator = ValidatorMock::new();
+        let encryptor = EncryptorMock::new();
+        let credential_checker =
+            PasswordCredentialChecker::new(&credential_repository, &validator, &encryptor);
+        let login_attempt_repository = LoginAttemptRepositoryMock::new();
+        let login_throttler = stub_login_throttler(&login_attempt_repository);
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserLoginsWithPasswordUc::new(
+            &user_repository,
+            &anonymous_binding_repository,
+            &session_repository,
+            &credential_checker,
+            &login_throttler,
+            &credential_writer,
+            &encryptor,
+            &id_factory,
+            &token_generator,
+            &token_hasher,
+            &logger,
+            3600,
+            false,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let user_id: Id = id_factory.create();
+        let name = Name::new(String::from("Some"), String::from("Name")).unwrap();
+        let email = EmailAddress::new(String::from("test@test-mail.com")).unwrap();
+        let user = AuthenticatedUser::new(&user_id, name, email.clone());
+        let cred = PasswordCredential::new(user_id, encryptor.encrypt("12345").unwrap());
+
+        user_repository.save(&user).expect("Can't save a user");
+        credential_writer.write(Box::new(cred));
+
+        let login = |password: &str| {
+            uc.execute(
+                AnonymousUser::new(&id_factory.create()),
+                email.clone(),
+                password.to_string(),
+                CLIENT_ID.to_string(),
+            )
+        };
+
+        if !matches!(login("54321"), Err(LoginError::WrongEmailOrPassword)) {
+            return Err("Wrong password should be rejected");
+        }
+
+        if !matches!(
+            login("54321"),
+            Err(LoginError::TooManyAttempts { retry_after: 60 })
+        ) {
+            return Err("Failure over the free attempts should be throttled");
+        }
+
+        match login("12345") {
+            Err(LoginError::TooManyAttempts { retry_after: 60 }) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Login should be throttled"),
+        }
+    }
+
+    #[test]
+    fn test_successful_login_resets_failed_attempts() -> Result<(), &'static str> {
+        let validator = ValidatorMock::new();
+        let user_repository = AuthenticatedUserRepositoryMock::new();
+        let anonymous_binding_repository = AnonymousBindingRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let encryptor = EncryptorMock::new();
+        let credential_checker =
+            PasswordCredentialChecker::new(&credential_repository, &validator, &encryptor);
+        let login_attempt_repository = LoginAttemptRepositoryMock::new();
+        let login_throttler = stub_login_throttler(&login_attempt_repository);
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserLoginsWithPasswordUc::new(
+            &user_repository,
+            &anonymous_binding_repository,
+            &session_repository,
+            &credential_checker,
+            &login_throttler,
+            &credential_writer,
+            &encryptor,
+            &id_factory,
+            &token_generator,
+            &token_hasher,
+            &logger,
+            3600,
+            false,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let user_id: Id = id_factory.create();
+        let name = Name::new(String::from("Some"), String::from("Name")).unwrap();
+        let email = EmailAddress::new(String::from("test@test-mail.com")).unwrap();
+        let user = AuthenticatedUser::new(&user_id, name, email.clone());
+        let cred = PasswordCredential::new(user_id, encryptor.encrypt("12345").unwrap());
+
+        user_repository.save(&user).expect("Can't save a user");
+        credential_writer.write(Box::new(cred));
+
+        let login = |password: &str| {
+            uc.execute(
+                AnonymousUser::new(&id_factory.create()),
+                email.clone(),
+                password.to_string(),
+                CLIENT_ID.to_string(),
+            )
+        };
+
+        assert!(login("54321").is_err());
+        assert!(login("12345").is_ok());
+        assert!(login("54321").is_err());
+
+        match login("12345") {
+            Ok(_) => Ok(()),
+            Err(_) => Err("Failed attempts are not reset"),
+        }
+    }
+
+    #[test]
+    fn test_unverified_user_logins() -> Result<(), &'static str> {
+        let validator = ValidatorMock::new();
+
+        match login_with_stored_hash_and_verification(&validator, "$mock$v=1$12345", true, false) {
+            Err(LoginError::EmailNotVerified) => {}
+            Err(_) => return Err("Wrong error type"),
+            Ok(_) => return Err("Unverified user should not login"),
+        }
+
+        match login_with_stored_hash_and_verification(&validator, "$mock$v=1$12345", true, true) {
+            Ok(_) => Ok(()),
+            Err(_) => Err("Verified user can't login"),
+        }
+    }
+
+    #[test]
+    fn test_user_logins_with_password_asynchronously() -> Result<(), &'static str> {
+        let user_repository = Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let anonymous_binding_repository =
+            Arc::new(Blocking::new(AnonymousBindingRepositoryMock::new()));
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let credential_repository =
+            Arc::new(Blocking::new(PasswordCredentialRepositoryMock::new()));
+        let validator = Arc::new(Blocking::new(ValidatorMock::new()));
+        let credential_checker = Arc::new(AsyncPasswordCredentialChecker::new(
+            credential_repository.clone(),
+            validator.clone(),
+            &EncryptorMock::new(),
+        ));
+        let login_attempt_repository = Arc::new(Blocking::new(LoginAttemptRepositoryMock::new()));
+        let login_throttler = Arc::new(AsyncLoginThrottler::new(
+            login_attempt_repository.clone(),
+            ThrottlingPolicy::new(2, 60, 600, 5, 900),
+            ThrottlingPolicy::for_client(),
+        ));
+        let id_factory = Arc::new(IdFactoryMock::new());
+        let token_generator = Arc::new(TokenGeneratorMock::new());
+        let token_hasher = Arc::new(TokenHasherMock::new());
+        let credential_writer = Arc::new(AsyncPasswordCredentialWriter::new(
+            credential_repository.clone(),
+        ));
+        let encryptor = Arc::new(Blocking::new(EncryptorMock::new()));
+
+        let logger = Arc::new(DefaultLogger::new(Level::Trace, &StdoutWriter {}));
+
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+
+        let uc = AsyncUserLoginsWithPasswordUc::new(
+            user_repository.clone(),
+            anonymous_binding_repository.clone(),
+            session_repository.clone(),
+            credential_checker.clone(),
+            login_throttler.clone(),
+            credential_writer.clone(),
+            encryptor.clone(),
+            id_factory.clone(),
+            token_generator.clone(),
+            token_hasher.clone(),
+            logger.clone(),
+            3600,
+            false,
+            event_publisher.clone(),
+            transaction_manager.clone(),
+        );
+
+        let user_id: Id = id_factory.create();
+        let name = Name::new(String::from("Some"), String::from("Name")).unwrap();
+        let email = EmailAddress::new(String::from("test@test-mail.com")).unwrap();
+        let password = String::from("12345");
+        let user = AuthenticatedUser::new(&user_id, name, email.clone());
+        let hashed_password = EncryptorMock::new().encrypt(&password).unwrap();
+
+        user_repository
+            .inner()
+            .save(&user)
+            .expect("Can't save a user");
+        credential_repository
+            .inner()
+            .save(&PasswordCredential::new(user_id.clone(), hashed_password));
+
+        let wrong_password = block_on(uc.execute(
+            AnonymousUser::new(&id_factory.create()),
+            email.clone(),
+            String::from("54321"),
+            CLIENT_ID.to_string(),
+        ));
+        assert!(matches!(
+            wrong_password,
+            Err(LoginError::WrongEmailOrPassword)
+        ));
+
+        let anonymous_id: Id = id_factory.create();
+        let result = block_on(uc.execute(
+            AnonymousUser::new(&anonymous_id),
+            email,
+            password,
+            CLIENT_ID.to_string(),
+        ));
+
+        match result {
+            Ok((s, token)) => {
+                assert_eq!(s.user_id.to_string(), user_id.to_string());
+                assert_eq!(token_hasher.hash(&token), s.token_hash);
+
+                let binding = anonymous_binding_repository
+                    .inner()
+                    .find_by_user_id(&user_id)
+                    .unwrap();
+                assert_eq!(binding.anonymous_id.to_string(), anonymous_id.to_string());
+                assert_eq!(
+                    vec!["begin", "commit"],
+                    transaction_manager.inner().journal()
+                );
+
+                Ok(())
+            }
+            Err(_) => Err("User can't login"),
+        }
+    }
 }
@@ -0,0 +1,241 @@
+use crate::entity::session::Session;
+use crate::event::{SessionRevocationReason, SessionRevoked};
+use crate::repository::session_repository::{
+    AsyncSessionRepositoryTrait, SessionRepositoryError, SessionRepositoryTrait,
+};
+use argentum_event_business::event::EventPublisherTrait;
+use argentum_user_business::token::TokenHasherTrait;
+use std::sync::Arc;
+
+pub struct UserLogsOutUc<'s> {
+    session_repository: &'s dyn SessionRepositoryTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
+    event_publisher: &'s dyn EventPublisherTrait,
+}
+
+impl<'s> UserLogsOutUc<'s> {
+    pub fn new(
+        session_repository: &'s dyn SessionRepositoryTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
+        event_publisher: &'s dyn EventPublisherTrait,
+    ) -> UserLogsOutUc<'s> {
+        UserLogsOutUc {
+            session_repository,
+            token_hasher,
+            event_publisher,
+        }
+    }
+
+    pub fn execute(&self, token: String) -> Result<(), LogoutError> {
+        let session = found_session(
+            self.session_repository
+                .find_by_token_hash(&self.token_hasher.hash(&token)),
+        )?;
+
+        self.session_repository.delete(&session.id)?;
+
+        self.event_publisher.publish(&revocation(session));
+
+        Ok(())
+    }
+}
+
+/// Async counterpart of `UserLogsOutUc`
+pub struct AsyncUserLogsOutUc {
+    session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+}
+
+impl AsyncUserLogsOutUc {
+    pub fn new(
+        session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+    ) -> AsyncUserLogsOutUc {
+        AsyncUserLogsOutUc {
+            session_repository,
+            token_hasher,
+            event_publisher,
+        }
+    }
+
+    pub async fn execute(&self, token: String) -> Result<(), LogoutError> {
+        let token_hash = self.token_hasher.hash(&token);
+        let session = found_session(
+            self.session_repository
+                .find_by_token_hash(&token_hash)
+                .await,
+        )?;
+
+        self.session_repository.delete(&session.id).await?;
+
+        self.event_publisher.publish(&revocation(session));
+
+        Ok(())
+    }
+}
+
+// Shared by the sync and async use cases
+fn found_session(session: Option<Session>) -> Result<Session, LogoutError> {
+    session.ok_or(LogoutError::WrongToken)
+}
+
+fn revocation(session: Session) -> SessionRevoked {
+    SessionRevoked::new(session.id, session.user_id, SessionRevocationReason::Logout)
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LogoutError {
+    #[error("Wrong token")]
+    WrongToken,
+
+    #[error("Can't delete session")]
+    DeleteSessionError(#[from] SessionRepositoryError),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::session::Session;
+    use crate::mock::repository::broken::session_repository_mock::SessionRepositoryMockWithBrokenDelete;
+    use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
+    use crate::mock::token::TokenHasherMock;
+    use crate::repository::session_repository::SessionRepositoryTrait;
+    use crate::use_case::user_logs_out::AsyncUserLogsOutUc;
+    use crate::use_case::user_logs_out::{LogoutError, UserLogsOutUc};
+    use argentum_event_business::mock::publisher::EventPublisherMock;
+    use argentum_standard_business::asynchronous::Blocking;
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_user_business::token::TokenHasherTrait;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_user_logs_out() -> Result<(), &'static str> {
+        let session_repository = SessionRepositoryMock::new();
+        let id_factory = IdFactoryMock::new();
+
+        let user_id: Id = id_factory.create();
+        let session = Session::new(
+            id_factory.create(),
+            user_id.clone(),
+            TokenHasherMock::new().hash("token"),
+            3600,
+        );
+        let other_session = Session::new(
+            id_factory.create(),
+            user_id,
+            TokenHasherMock::new().hash("other"),
+            3600,
+        );
+
+        session_repository
+            .save(&session)
+            .expect("Can't save a session");
+        session_repository
+            .save(&other_session)
+            .expect("Can't save a session");
+
+        let token_hasher = TokenHasherMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let uc = UserLogsOutUc::new(&session_repository, &token_hasher, &event_publisher);
+
+        if uc.execute("token".to_string()).is_err() {
+            return Err("User is not logged out");
+        }
+
+        if session_repository.find(&session.id).is_some() {
+            return Err("Session is not deleted");
+        }
+
+        if session_repository.find(&other_session.id).is_none() {
+            return Err("Other session should stay");
+        }
+
+        assert_eq!(
+            event_publisher.published(),
+            vec!["user_account.session_revoked"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_logs_out_with_wrong_token() -> Result<(), &'static str> {
+        let session_repository = SessionRepositoryMock::new();
+        let token_hasher = TokenHasherMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let uc = UserLogsOutUc::new(&session_repository, &token_hasher, &event_publisher);
+
+        match uc.execute("wrong-token".to_string()) {
+            Ok(_) => Err("Should return an error"),
+            Err(LogoutError::WrongToken) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_user_logs_out_with_broken_session_repository() -> Result<(), &'static str> {
+        let session_repository = SessionRepositoryMockWithBrokenDelete::new();
+        let id_factory = IdFactoryMock::new();
+
+        let session = Session::new(
+            id_factory.create(),
+            id_factory.create(),
+            TokenHasherMock::new().hash("token"),
+            3600,
+        );
+        session_repository
+            .save(&session)
+            .expect("Can't save a session");
+
+        let token_hasher = TokenHasherMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let uc = UserLogsOutUc::new(&session_repository, &token_hasher, &event_publisher);
+
+        match uc.execute("token".to_string()) {
+            Ok(_) => Err("Should return an error"),
+            Err(LogoutError::DeleteSessionError(_)) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_user_logs_out_asynchronously() -> Result<(), &'static str> {
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let id_factory = IdFactoryMock::new();
+        let session = Session::new(
+            id_factory.create(),
+            id_factory.create(),
+            TokenHasherMock::new().hash("token"),
+            3600,
+        );
+
+        session_repository
+            .inner()
+            .save(&session)
+            .expect("Can't save a session");
+
+        let token_hasher = Arc::new(TokenHasherMock::new());
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let uc = AsyncUserLogsOutUc::new(
+            session_repository.clone(),
+            token_hasher.clone(),
+            event_publisher.clone(),
+        );
+
+        if block_on(uc.execute("token".to_string())).is_err() {
+            return Err("User is not logged out");
+        }
+
+        if session_repository.inner().find(&session.id).is_some() {
+            return Err("Session is not deleted");
+        }
+
+        match block_on(uc.execute("token".to_string())) {
+            Err(LogoutError::WrongToken) => Ok(()),
+            _ => Err("Session can't be logged out twice"),
+        }
+    }
+}
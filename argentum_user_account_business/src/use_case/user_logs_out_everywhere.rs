@@ -0,0 +1,402 @@
+use crate::entity::session::Session;
+use crate::event::{SessionRevocationReason, SessionRevoked};
+use crate::repository::session_repository::{
+    AsyncSessionRepositoryTrait, SessionRepositoryError, SessionRepositoryTrait,
+};
+use argentum_event_business::event::EventPublisherTrait;
+use argentum_standard_business::transaction::{
+    in_async_transaction, in_transaction, AsyncTransactionManagerTrait, Transaction,
+    TransactionError, TransactionManagerTrait,
+};
+use argentum_user_business::token::TokenHasherTrait;
+use std::sync::Arc;
+
+pub struct UserLogsOutEverywhereUc<'s> {
+    session_repository: &'s dyn SessionRepositoryTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
+    event_publisher: &'s dyn EventPublisherTrait,
+    transaction_manager: &'s dyn TransactionManagerTrait,
+}
+
+impl<'s> UserLogsOutEverywhereUc<'s> {
+    pub fn new(
+        session_repository: &'s dyn SessionRepositoryTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
+        event_publisher: &'s dyn EventPublisherTrait,
+        transaction_manager: &'s dyn TransactionManagerTrait,
+    ) -> UserLogsOutEverywhereUc<'s> {
+        UserLogsOutEverywhereUc {
+            session_repository,
+            token_hasher,
+            event_publisher,
+            transaction_manager,
+        }
+    }
+
+    /// Revokes all sessions of the token owner. The session behind the token itself
+    /// is revoked too unless `keep_current` is set.
+    pub fn execute(&self, token: String, keep_current: bool) -> Result<(), LogoutEverywhereError> {
+        let current = match self
+            .session_repository
+            .find_by_token_hash(&self.token_hasher.hash(&token))
+        {
+            Some(s) => s,
+            None => return Err(LogoutEverywhereError::WrongToken),
+        };
+
+        let revoked = revoked_sessions(
+            self.session_repository.find_by_user_id(&current.user_id)?,
+            &current,
+            keep_current,
+        );
+
+        in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<(), LogoutEverywhereError> {
+                let session_repository = self.session_repository.within(transaction);
+
+                if keep_current {
+                    for session in &revoked {
+                        session_repository.delete(&session.id)?;
+                    }
+                } else {
+                    session_repository.delete_users_sessions(&current.user_id)?;
+                }
+
+                Ok(())
+            },
+        )?;
+
+        publish_revocations(self.event_publisher, revoked);
+
+        Ok(())
+    }
+}
+
+/// Async counterpart of `UserLogsOutEverywhereUc`
+pub struct AsyncUserLogsOutEverywhereUc {
+    session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+    transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+}
+
+impl AsyncUserLogsOutEverywhereUc {
+    pub fn new(
+        session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+        transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+    ) -> AsyncUserLogsOutEverywhereUc {
+        AsyncUserLogsOutEverywhereUc {
+            session_repository,
+            token_hasher,
+            event_publisher,
+            transaction_manager,
+        }
+    }
+
+    /// See `UserLogsOutEverywhereUc::execute`
+    pub async fn execute(
+        &self,
+        token: String,
+        keep_current: bool,
+    ) -> Result<(), LogoutEverywhereError> {
+        let token_hash = self.token_hasher.hash(&token);
+        let current = match self
+            .session_repository
+            .find_by_token_hash(&token_hash)
+            .await
+        {
+            Some(s) => s,
+            None => return Err(LogoutEverywhereError::WrongToken),
+        };
+
+        let revoked = revoked_sessions(
+            self.session_repository
+                .find_by_user_id(&current.user_id)
+                .await?,
+            &current,
+            keep_current,
+        );
+
+        in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.delete(transaction, &current, &revoked, keep_current)
+        })
+        .await?;
+
+        publish_revocations(self.event_publisher.as_ref(), revoked);
+
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        transaction: Transaction,
+        current: &Session,
+        revoked: &[Session],
+        keep_current: bool,
+    ) -> Result<(), LogoutEverywhereError> {
+        let session_repository = self.session_repository.within(&transaction);
+
+        if keep_current {
+            for session in revoked {
+                session_repository.delete(&session.id).await?;
+            }
+        } else {
+            session_repository
+                .delete_users_sessions(&current.user_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+// Shared by the sync and async use cases
+fn revoked_sessions(sessions: Vec<Session>, current: &Session, keep_current: bool) -> Vec<Session> {
+    sessions
+        .into_iter()
+        .filter(|session| !keep_current || *session.id != *current.id)
+        .collect()
+}
+
+/// Sessions are announced as revoked once the deletion is committed
+fn publish_revocations(event_publisher: &dyn EventPublisherTrait, revoked: Vec<Session>) {
+    for session in revoked {
+        event_publisher.publish(&SessionRevoked::new(
+            session.id,
+            session.user_id,
+            SessionRevocationReason::LogoutEverywhere,
+        ));
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LogoutEverywhereError {
+    #[error("Wrong token")]
+    WrongToken,
+
+    #[error("Can't get or delete sessions")]
+    SessionStorageError(#[from] SessionRepositoryError),
+
+    #[error(transparent)]
+    TransactionError(#[from] TransactionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::session::Session;
+    use crate::mock::repository::broken::session_repository_mock::SessionRepositoryMockWithBrokenDelete;
+    use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
+    use crate::mock::token::TokenHasherMock;
+    use crate::repository::session_repository::SessionRepositoryTrait;
+    use crate::use_case::user_logs_out_everywhere::AsyncUserLogsOutEverywhereUc;
+    use crate::use_case::user_logs_out_everywhere::{
+        LogoutEverywhereError, UserLogsOutEverywhereUc,
+    };
+    use argentum_event_business::mock::publisher::EventPublisherMock;
+    use argentum_standard_business::asynchronous::Blocking;
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_standard_business::mock::transaction::TransactionManagerMock;
+    use argentum_user_business::token::TokenHasherTrait;
+    use std::sync::Arc;
+
+    fn prefill(session_repository: &dyn SessionRepositoryTrait) -> (Id, Id) {
+        let id_factory = IdFactoryMock::new();
+        let user_id: Id = id_factory.create();
+        let another_user_id: Id = id_factory.create();
+
+        for token in ["token", "phone", "laptop"] {
+            let session = Session::new(
+                id_factory.create(),
+                user_id.clone(),
+                TokenHasherMock::new().hash(token),
+                3600,
+            );
+            session_repository
+                .save(&session)
+                .expect("Can't save a session");
+        }
+
+        let another = Session::new(
+            id_factory.create(),
+            another_user_id.clone(),
+            "another".into(),
+            3600,
+        );
+        session_repository
+            .save(&another)
+            .expect("Can't save a session");
+
+        (user_id, another_user_id)
+    }
+
+    #[test]
+    fn test_user_logs_out_everywhere() -> Result<(), &'static str> {
+        let session_repository = SessionRepositoryMock::new();
+        let (user_id, another_user_id) = prefill(&session_repository);
+
+        let token_hasher = TokenHasherMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let uc = UserLogsOutEverywhereUc::new(
+            &session_repository,
+            &token_hasher,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        if uc.execute("token".to_string(), false).is_err() {
+            return Err("User is not logged out");
+        }
+
+        if !session_repository
+            .find_by_user_id(&user_id)
+            .unwrap()
+            .is_empty()
+        {
+            return Err("Sessions are not deleted");
+        }
+
+        if session_repository
+            .find_by_user_id(&another_user_id)
+            .unwrap()
+            .len()
+            != 1
+        {
+            return Err("Sessions of another user should stay");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_logs_out_everywhere_except_current_session() -> Result<(), &'static str> {
+        let session_repository = SessionRepositoryMock::new();
+        let (user_id, another_user_id) = prefill(&session_repository);
+
+        let token_hasher = TokenHasherMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let uc = UserLogsOutEverywhereUc::new(
+            &session_repository,
+            &token_hasher,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        if uc.execute("token".to_string(), true).is_err() {
+            return Err("User is not logged out");
+        }
+
+        let sessions = session_repository.find_by_user_id(&user_id).unwrap();
+        if sessions.len() != 1 || sessions[0].token_hash != TokenHasherMock::new().hash("token") {
+            return Err("Only the current session should stay");
+        }
+
+        if session_repository
+            .find_by_user_id(&another_user_id)
+            .unwrap()
+            .len()
+            != 1
+        {
+            return Err("Sessions of another user should stay");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_logs_out_everywhere_with_wrong_token() -> Result<(), &'static str> {
+        let session_repository = SessionRepositoryMock::new();
+        prefill(&session_repository);
+
+        let token_hasher = TokenHasherMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let uc = UserLogsOutEverywhereUc::new(
+            &session_repository,
+            &token_hasher,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        match uc.execute("wrong-token".to_string(), false) {
+            Ok(_) => Err("Should return an error"),
+            Err(LogoutEverywhereError::WrongToken) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_user_logs_out_everywhere_with_broken_session_repository() -> Result<(), &'static str> {
+        let session_repository = SessionRepositoryMockWithBrokenDelete::new();
+        prefill(&session_repository);
+
+        let token_hasher = TokenHasherMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let uc = UserLogsOutEverywhereUc::new(
+            &session_repository,
+            &token_hasher,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        for keep_current in [false, true] {
+            match uc.execute("token".to_string(), keep_current) {
+                Ok(_) => return Err("Should return an error"),
+                Err(LogoutEverywhereError::SessionStorageError(_)) => {}
+                Err(_) => return Err("Wrong error type"),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_logs_out_everywhere_asynchronously() -> Result<(), &'static str> {
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let (user_id, another_user_id) = prefill(&*session_repository.inner());
+
+        let token_hasher = Arc::new(TokenHasherMock::new());
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+        let uc = AsyncUserLogsOutEverywhereUc::new(
+            session_repository.clone(),
+            token_hasher.clone(),
+            event_publisher.clone(),
+            transaction_manager.clone(),
+        );
+
+        if block_on(uc.execute("token".to_string(), true)).is_err() {
+            return Err("User is not logged out");
+        }
+
+        let remaining = session_repository
+            .inner()
+            .find_by_user_id(&user_id)
+            .unwrap();
+        if remaining.len() != 1 || remaining[0].token_hash != token_hasher.hash("token") {
+            return Err("Only the current session should stay");
+        }
+
+        if session_repository
+            .inner()
+            .find_by_user_id(&another_user_id)
+            .unwrap()
+            .len()
+            != 1
+        {
+            return Err("Sessions of another user should stay");
+        }
+
+        assert_eq!(2, event_publisher.published().len());
+        assert!(transaction_manager.inner().committed());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,821 @@
+use crate::entity::refresh_token::RefreshToken;
+use crate::entity::session::Session;
+use crate::event::{SessionRevocationReason, SessionRevoked};
+use crate::repository::refresh_token_repository::{
+    AsyncRefreshTokenRepositoryTrait, RefreshTokenRepositoryError, RefreshTokenRepositoryTrait,
+};
+use crate::repository::session_repository::{
+    AsyncSessionRepositoryTrait, SessionRepositoryError, SessionRepositoryTrait,
+};
+use argentum_event_business::event::EventPublisherTrait;
+use argentum_standard_business::data_type::id::IdFactory;
+use argentum_standard_business::transaction::{
+    in_async_transaction, in_transaction, AsyncTransactionManagerTrait, Transaction,
+    TransactionError, TransactionManagerTrait,
+};
+use argentum_user_business::token::{GeneratorTrait, TokenHasherTrait};
+use chrono::Utc;
+use std::cmp::min;
+use std::sync::Arc;
+
+pub struct UserRefreshesSessionUc<'s> {
+    refresh_token_repository: &'s dyn RefreshTokenRepositoryTrait,
+    session_repository: &'s dyn SessionRepositoryTrait,
+    id_factory: &'s dyn IdFactory,
+    access_token_generator: &'s dyn GeneratorTrait,
+    refresh_token_generator: &'s dyn GeneratorTrait,
+    token_hasher: &'s dyn TokenHasherTrait,
+    refresh_token_ttl: u32, //refresh token lifetime in seconds
+    event_publisher: &'s dyn EventPublisherTrait,
+    transaction_manager: &'s dyn TransactionManagerTrait,
+}
+
+pub struct RefreshedSession {
+    pub session: Session,
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+impl<'s> UserRefreshesSessionUc<'s> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        refresh_token_repository: &'s dyn RefreshTokenRepositoryTrait,
+        session_repository: &'s dyn SessionRepositoryTrait,
+        id_factory: &'s dyn IdFactory,
+        access_token_generator: &'s dyn GeneratorTrait,
+        refresh_token_generator: &'s dyn GeneratorTrait,
+        token_hasher: &'s dyn TokenHasherTrait,
+        refresh_token_ttl: u32,
+        event_publisher: &'s dyn EventPublisherTrait,
+        transaction_manager: &'s dyn TransactionManagerTrait,
+    ) -> UserRefreshesSessionUc<'s> {
+        UserRefreshesSessionUc {
+            refresh_token_repository,
+            session_repository,
+            id_factory,
+            access_token_generator,
+            refresh_token_generator,
+            token_hasher,
+            refresh_token_ttl,
+            event_publisher,
+            transaction_manager,
+        }
+    }
+
+    /// Starts a token family for a freshly created session, e.g. right after login.
+    /// Returns the raw refresh token, only its hash is stored
+    pub fn issue(&self, session: &Session) -> Result<String, RefreshError> {
+        self.issue_with(self.refresh_token_repository, session)
+    }
+
+    fn issue_with(
+        &self,
+        refresh_token_repository: &dyn RefreshTokenRepositoryTrait,
+        session: &Session,
+    ) -> Result<String, RefreshError> {
+        let (refresh_token, token) = new_refresh_token(
+            self.id_factory,
+            self.refresh_token_generator,
+            self.token_hasher,
+            self.refresh_token_ttl,
+            session,
+        );
+
+        refresh_token_repository.save(&refresh_token)?;
+
+        Ok(token)
+    }
+
+    /// Exchanges a refresh token for a new access token and a new refresh token.
+    /// Presenting an already used refresh token revokes the session with its whole token family
+    pub fn execute(&self, refresh_token: String) -> Result<RefreshedSession, RefreshError> {
+        let mut stored = match self
+            .refresh_token_repository
+            .find_by_token_hash(&self.token_hasher.hash(&refresh_token))
+        {
+            Some(t) => t,
+            None => return Err(RefreshError::WrongToken),
+        };
+
+        if stored.is_used() {
+            self.revoke_family(&stored)?;
+
+            return Err(RefreshError::TokenReused);
+        }
+
+        if stored.is_expired() {
+            return Err(RefreshError::TokenExpired);
+        }
+
+        let mut session = match self.session_repository.find(&stored.session_id) {
+            Some(s) if *s.user_id == *stored.user_id => s,
+            _ => {
+                self.refresh_token_repository
+                    .delete_sessions_tokens(&stored.session_id)?;
+
+                return Err(RefreshError::SessionRevoked);
+            }
+        };
+
+        if Utc::now().ge(&session.expires_at) {
+            return Err(RefreshError::SessionExpired);
+        }
+
+        stored.mark_used();
+
+        let access_token =
+            renew_access_token(self.access_token_generator, self.token_hasher, &mut session);
+
+        let rotated = in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<String, RefreshError> {
+                let refresh_token_repository = self.refresh_token_repository.within(transaction);
+
+                claim(refresh_token_repository.mark_used_if_unused(&stored))?;
+                self.session_repository
+                    .within(transaction)
+                    .update(&session)?;
+
+                self.issue_with(&*refresh_token_repository, &session)
+            },
+        );
+
+        let refresh_token = match rotated {
+            Err(RefreshError::TokenReused) => {
+                self.revoke_family(&stored)?;
+
+                return Err(RefreshError::TokenReused);
+            }
+            rotated => rotated?,
+        };
+
+        Ok(RefreshedSession {
+            session,
+            access_token,
+            refresh_token,
+        })
+    }
+
+    fn revoke_family(&self, reused: &RefreshToken) -> Result<(), RefreshError> {
+        in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<(), RefreshError> {
+                self.refresh_token_repository
+                    .within(transaction)
+                    .delete_sessions_tokens(&reused.session_id)?;
+                self.session_repository
+                    .within(transaction)
+                    .delete(&reused.session_id)?;
+
+                Ok(())
+            },
+        )?;
+
+        self.event_publisher.publish(&reuse_revocation(reused));
+
+        Ok(())
+    }
+}
+
+/// Async counterpart of `UserRefreshesSessionUc`
+pub struct AsyncUserRefreshesSessionUc {
+    refresh_token_repository: Arc<dyn AsyncRefreshTokenRepositoryTrait>,
+    session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+    id_factory: Arc<dyn IdFactory + Send + Sync>,
+    access_token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+    refresh_token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+    token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+    refresh_token_ttl: u32, //refresh token lifetime in seconds
+    event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+    transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+}
+
+impl AsyncUserRefreshesSessionUc {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        refresh_token_repository: Arc<dyn AsyncRefreshTokenRepositoryTrait>,
+        session_repository: Arc<dyn AsyncSessionRepositoryTrait>,
+        id_factory: Arc<dyn IdFactory + Send + Sync>,
+        access_token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+        refresh_token_generator: Arc<dyn GeneratorTrait + Send + Sync>,
+        token_hasher: Arc<dyn TokenHasherTrait + Send + Sync>,
+        refresh_token_ttl: u32,
+        event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+        transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+    ) -> AsyncUserRefreshesSessionUc {
+        AsyncUserRefreshesSessionUc {
+            refresh_token_repository,
+            session_repository,
+            id_factory,
+            access_token_generator,
+            refresh_token_generator,
+            token_hasher,
+            refresh_token_ttl,
+            event_publisher,
+            transaction_manager,
+        }
+    }
+
+    /// See `UserRefreshesSessionUc::issue`
+    pub async fn issue(&self, session: &Session) -> Result<String, RefreshError> {
+        self.issue_with(self.refresh_token_repository.as_ref(), session)
+            .await
+    }
+
+    async fn issue_with(
+        &self,
+        refresh_token_repository: &dyn AsyncRefreshTokenRepositoryTrait,
+        session: &Session,
+    ) -> Result<String, RefreshError> {
+        let (refresh_token, token) = new_refresh_token(
+            self.id_factory.as_ref(),
+            self.refresh_token_generator.as_ref(),
+            self.token_hasher.as_ref(),
+            self.refresh_token_ttl,
+            session,
+        );
+
+        refresh_token_repository.save(&refresh_token).await?;
+
+        Ok(token)
+    }
+
+    /// See `UserRefreshesSessionUc::execute`
+    pub async fn execute(&self, refresh_token: String) -> Result<RefreshedSession, RefreshError> {
+        let token_hash = self.token_hasher.hash(&refresh_token);
+        let mut stored = match self
+            .refresh_token_repository
+            .find_by_token_hash(&token_hash)
+            .await
+        {
+            Some(t) => t,
+            None => return Err(RefreshError::WrongToken),
+        };
+
+        if stored.is_used() {
+            self.revoke_family(&stored).await?;
+
+            return Err(RefreshError::TokenReused);
+        }
+
+        if stored.is_expired() {
+            return Err(RefreshError::TokenExpired);
+        }
+
+        let mut session = match self.session_repository.find(&stored.session_id).await {
+            Some(s) if *s.user_id == *stored.user_id => s,
+            _ => {
+                self.refresh_token_repository
+                    .delete_sessions_tokens(&stored.session_id)
+                    .await?;
+
+                return Err(RefreshError::SessionRevoked);
+            }
+        };
+
+        if Utc::now().ge(&session.expires_at) {
+            return Err(RefreshError::SessionExpired);
+        }
+
+        stored.mark_used();
+
+        let access_token = renew_access_token(
+            self.access_token_generator.as_ref(),
+            self.token_hasher.as_ref(),
+            &mut session,
+        );
+
+        let rotated = in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.rotate(transaction, &stored, &session)
+        })
+        .await;
+
+        let refresh_token = match rotated {
+            Err(RefreshError::TokenReused) => {
+                self.revoke_family(&stored).await?;
+
+                return Err(RefreshError::TokenReused);
+            }
+            rotated => rotated?,
+        };
+
+        Ok(RefreshedSession {
+            session,
+            access_token,
+            refresh_token,
+        })
+    }
+
+    /// Stores the used token and the touched session, the family gets a new token
+    async fn rotate(
+        &self,
+        transaction: Transaction,
+        used: &RefreshToken,
+        session: &Session,
+    ) -> Result<String, RefreshError> {
+        let refresh_token_repository = self.refresh_token_repository.within(&transaction);
+
+        claim(refresh_token_repository.mark_used_if_unused(used).await)?;
+        self.session_repository
+            .within(&transaction)
+            .update(session)
+            .await?;
+
+        self.issue_with(refresh_token_repository.as_ref(), session)
+            .await
+    }
+
+    async fn revoke_family(&self, reused: &RefreshToken) -> Result<(), RefreshError> {
+        in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.delete_family(transaction, reused)
+        })
+        .await?;
+
+        self.event_publisher.publish(&reuse_revocation(reused));
+
+        Ok(())
+    }
+
+    async fn delete_family(
+        &self,
+        transaction: Transaction,
+        reused: &RefreshToken,
+    ) -> Result<(), RefreshError> {
+        self.refresh_token_repository
+            .within(&transaction)
+            .delete_sessions_tokens(&reused.session_id)
+            .await?;
+        self.session_repository
+            .within(&transaction)
+            .delete(&reused.session_id)
+            .await?;
+
+        Ok(())
+    }
+}
+
+// Shared by the sync and async use cases
+
+/// The token is claimed in the same statement it is checked, so a request losing the race
+/// to a concurrent one with the same token is a reuse as well
+fn claim(claimed: Result<bool, RefreshTokenRepositoryError>) -> Result<(), RefreshError> {
+    match claimed? {
+        true => Ok(()),
+        false => Err(RefreshError::TokenReused),
+    }
+}
+
+/// Published after the family is deleted for good
+fn reuse_revocation(reused: &RefreshToken) -> SessionRevoked {
+    SessionRevoked::new(
+        reused.session_id.clone(),
+        reused.user_id.clone(),
+        SessionRevocationReason::RefreshTokenReuse,
+    )
+}
+
+/// A refresh token never outlives its session
+fn new_refresh_token(
+    id_factory: &dyn IdFactory,
+    refresh_token_generator: &dyn GeneratorTrait,
+    token_hasher: &dyn TokenHasherTrait,
+    refresh_token_ttl: u32,
+    session: &Session,
+) -> (RefreshToken, String) {
+    let remaining = (session.expires_at - Utc::now()).num_seconds().max(0) as u32;
+    let token = refresh_token_generator.generate(&session.user_id);
+    let refresh_token = RefreshToken::new(
+        id_factory.create(),
+        session.id.clone(),
+        session.user_id.clone(),
+        token_hasher.hash(&token),
+        min(refresh_token_ttl, remaining),
+    );
+
+    (refresh_token, token)
+}
+
+fn renew_access_token(
+    access_token_generator: &dyn GeneratorTrait,
+    token_hasher: &dyn TokenHasherTrait,
+    session: &mut Session,
+) -> String {
+    let access_token = access_token_generator.generate_for_session(&session.user_id, &session.id);
+    session.token_hash = token_hasher.hash(&access_token);
+    session.touch();
+
+    access_token
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RefreshError {
+    #[error("Wrong refresh token")]
+    WrongToken,
+
+    #[error("Refresh token is expired")]
+    TokenExpired,
+
+    #[error("Refresh token is already used, the session is revoked")]
+    TokenReused,
+
+    #[error("Session is revoked")]
+    SessionRevoked,
+
+    #[error("Session is expired")]
+    SessionExpired,
+
+    #[error("Can't store refresh token")]
+    RefreshTokenRepositoryError(#[from] RefreshTokenRepositoryError),
+
+    #[error("Can't store session")]
+    SessionRepositoryError(#[from] SessionRepositoryError),
+
+    #[error(transparent)]
+    TransactionError(#[from] TransactionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::refresh_token::RefreshToken;
+    use crate::entity::session::Session;
+    use crate::mock::repository::refresh_token_repository_mock::RefreshTokenRepositoryMock;
+    use crate::mock::repository::session_repository_mock::SessionRepositoryMock;
+    use crate::mock::token::{TokenGeneratorMock, TokenHasherMock};
+    use crate::repository::refresh_token_repository::{
+        RefreshTokenRepositoryError, RefreshTokenRepositoryTrait,
+    };
+    use crate::repository::session_repository::SessionRepositoryTrait;
+    use crate::use_case::user_refreshes_session::AsyncUserRefreshesSessionUc;
+    use crate::use_case::user_refreshes_session::{RefreshError, UserRefreshesSessionUc};
+    use argentum_event_business::mock::publisher::EventPublisherMock;
+    use argentum_standard_business::asynchronous::Blocking;
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_standard_business::mock::transaction::TransactionManagerMock;
+    use argentum_standard_business::transaction::{Bound, Transaction};
+    use argentum_user_business::token::TokenHasherTrait;
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+
+    fn stub_session(session_repository: &SessionRepositoryMock) -> Session {
+        let id_factory = IdFactoryMock::new();
+        let session = Session::new(
+            id_factory.create(),
+            id_factory.create(),
+            TokenHasherMock::new().hash("access"),
+            3600,
+        );
+
+        session_repository
+            .save(&session)
+            .expect("Can't save a session");
+
+        session
+    }
+
+    /// Another request claims every token right after it is read
+    struct RacingRefreshTokenRepository {
+        inner: RefreshTokenRepositoryMock,
+    }
+
+    impl RefreshTokenRepositoryTrait for RacingRefreshTokenRepository {
+        fn find_by_token_hash(&self, token_hash: &str) -> Option<RefreshToken> {
+            let found = self.inner.find_by_token_hash(token_hash)?;
+
+            let mut claimed = found.clone();
+            claimed.mark_used();
+            self.inner.mark_used_if_unused(&claimed).ok()?;
+
+            Some(found)
+        }
+
+        fn save(&self, token: &RefreshToken) -> Result<(), RefreshTokenRepositoryError> {
+            self.inner.save(token)
+        }
+
+        fn mark_used_if_unused(
+            &self,
+            token: &RefreshToken,
+        ) -> Result<bool, RefreshTokenRepositoryError> {
+            self.inner.mark_used_if_unused(token)
+        }
+
+        fn delete_sessions_tokens(
+            &self,
+            session_id: &Id,
+        ) -> Result<(), RefreshTokenRepositoryError> {
+            self.inner.delete_sessions_tokens(session_id)
+        }
+
+        fn within(&self, _: &Transaction) -> Bound<'_, dyn RefreshTokenRepositoryTrait + '_> {
+            Bound::Shared(self)
+        }
+    }
+
+    #[test]
+    fn test_user_refreshes_session() -> Result<(), &'static str> {
+        let refresh_token_repository = RefreshTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let id_factory = IdFactoryMock::new();
+        let access_token_generator = TokenGeneratorMock::new();
+        let refresh_token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserRefreshesSessionUc::new(
+            &refresh_token_repository,
+            &session_repository,
+            &id_factory,
+            &access_token_generator,
+            &refresh_token_generator,
+            &token_hasher,
+            86400,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let session = stub_session(&session_repository);
+        let first = uc.issue(&session).expect("Can't issue a refresh token");
+
+        let refreshed = match uc.execute(first.clone()) {
+            Ok(r) => r,
+            Err(_) => return Err("Session is not refreshed"),
+        };
+
+        assert!(*refreshed.session.id == *session.id);
+        assert_ne!(first, refreshed.refresh_token);
+        assert_eq!(
+            token_hasher.hash(&refreshed.access_token),
+            session_repository.find(&session.id).unwrap().token_hash
+        );
+
+        let new_token = refresh_token_repository
+            .find_by_token_hash(&token_hasher.hash(&refreshed.refresh_token))
+            .unwrap();
+        assert!(!new_token.is_used());
+        assert!(new_token.expires_at <= session.expires_at);
+
+        if uc.execute(refreshed.refresh_token).is_err() {
+            return Err("Rotated token should be accepted");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reused_refresh_token_revokes_family() -> Result<(), &'static str> {
+        let refresh_token_repository = RefreshTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let id_factory = IdFactoryMock::new();
+        let access_token_generator = TokenGeneratorMock::new();
+        let refresh_token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserRefreshesSessionUc::new(
+            &refresh_token_repository,
+            &session_repository,
+            &id_factory,
+            &access_token_generator,
+            &refresh_token_generator,
+            &token_hasher,
+            86400,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let session = stub_session(&session_repository);
+        let stolen = uc.issue(&session).expect("Can't issue a refresh token");
+        let refreshed = uc.execute(stolen.clone()).expect("Can't refresh a session");
+
+        match uc.execute(stolen) {
+            Err(RefreshError::TokenReused) => {}
+            Err(_) => return Err("Wrong error type"),
+            Ok(_) => return Err("Should return an error"),
+        }
+
+        if session_repository.find(&session.id).is_some() {
+            return Err("Session is not revoked");
+        }
+
+        match uc.execute(refreshed.refresh_token) {
+            Err(RefreshError::WrongToken) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Token family is not revoked"),
+        }
+    }
+
+    #[test]
+    fn test_refresh_losing_race_revokes_family() -> Result<(), &'static str> {
+        let refresh_token_repository = RacingRefreshTokenRepository {
+            inner: RefreshTokenRepositoryMock::new(),
+        };
+        let session_repository = SessionRepositoryMock::new();
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserRefreshesSessionUc::new(
+            &refresh_token_repository,
+            &session_repository,
+            &id_factory,
+            &token_generator,
+            &token_generator,
+            &token_hasher,
+            86400,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let session = stub_session(&session_repository);
+        let token = uc.issue(&session).expect("Can't issue a refresh token");
+
+        match uc.execute(token) {
+            Err(RefreshError::TokenReused) => {}
+            Err(_) => return Err("Wrong error type"),
+            Ok(_) => return Err("Should return an error"),
+        }
+
+        assert_eq!(
+            vec!["begin", "rollback", "begin", "commit"],
+            transaction_manager.journal()
+        );
+
+        if session_repository.find(&session.id).is_some() {
+            return Err("Session is not revoked");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_refresh_token() -> Result<(), &'static str> {
+        let refresh_token_repository = RefreshTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserRefreshesSessionUc::new(
+            &refresh_token_repository,
+            &session_repository,
+            &id_factory,
+            &token_generator,
+            &token_generator,
+            &token_hasher,
+            86400,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let session = stub_session(&session_repository);
+        let token = uc.issue(&session).expect("Can't issue a refresh token");
+
+        let mut stored = refresh_token_repository
+            .find_by_token_hash(&token_hasher.hash(&token))
+            .unwrap();
+        stored.expires_at = Utc::now() - Duration::seconds(1);
+        refresh_token_repository
+            .delete_sessions_tokens(&stored.session_id)
+            .unwrap();
+        refresh_token_repository.save(&stored).unwrap();
+
+        match uc.execute(token) {
+            Err(RefreshError::TokenExpired) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Should return an error"),
+        }
+    }
+
+    #[test]
+    fn test_refresh_after_logout() -> Result<(), &'static str> {
+        let refresh_token_repository = RefreshTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserRefreshesSessionUc::new(
+            &refresh_token_repository,
+            &session_repository,
+            &id_factory,
+            &token_generator,
+            &token_generator,
+            &token_hasher,
+            86400,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        let session = stub_session(&session_repository);
+        let token = uc.issue(&session).expect("Can't issue a refresh token");
+        session_repository.delete(&session.id).unwrap();
+
+        match uc.execute(token) {
+            Err(RefreshError::SessionRevoked) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Should return an error"),
+        }
+    }
+
+    #[test]
+    fn test_refresh_with_wrong_token() -> Result<(), &'static str> {
+        let refresh_token_repository = RefreshTokenRepositoryMock::new();
+        let session_repository = SessionRepositoryMock::new();
+        let id_factory = IdFactoryMock::new();
+        let token_generator = TokenGeneratorMock::new();
+        let token_hasher = TokenHasherMock::new();
+
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+
+        let uc = UserRefreshesSessionUc::new(
+            &refresh_token_repository,
+            &session_repository,
+            &id_factory,
+            &token_generator,
+            &token_generator,
+            &token_hasher,
+            86400,
+            &event_publisher,
+            &transaction_manager,
+        );
+
+        match uc.execute("wrong-token".to_string()) {
+            Err(RefreshError::WrongToken) => Ok(()),
+            Err(_) => Err("Wrong error type"),
+            Ok(_) => Err("Should return an error"),
+        }
+    }
+
+    #[test]
+    fn test_user_refreshes_session_asynchronously() -> Result<(), &'static str> {
+        let refresh_token_repository = Arc::new(Blocking::new(RefreshTokenRepositoryMock::new()));
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let id_factory = Arc::new(IdFactoryMock::new());
+        let access_token_generator = Arc::new(TokenGeneratorMock::new());
+        let refresh_token_generator = Arc::new(TokenGeneratorMock::new());
+        let token_hasher = Arc::new(TokenHasherMock::new());
+
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+
+        let uc = AsyncUserRefreshesSessionUc::new(
+            refresh_token_repository.clone(),
+            session_repository.clone(),
+            id_factory.clone(),
+            access_token_generator.clone(),
+            refresh_token_generator.clone(),
+            token_hasher.clone(),
+            86400,
+            event_publisher.clone(),
+            transaction_manager.clone(),
+        );
+
+        let session = stub_session(&session_repository.inner());
+        let first = block_on(uc.issue(&session)).expect("Can't issue a refresh token");
+
+        let refreshed = match block_on(uc.execute(first.clone())) {
+            Ok(r) => r,
+            Err(_) => return Err("Session is not refreshed"),
+        };
+
+        assert!(*refreshed.session.id == *session.id);
+        assert_eq!(
+            token_hasher.hash(&refreshed.access_token),
+            session_repository
+                .inner()
+                .find(&session.id)
+                .unwrap()
+                .token_hash
+        );
+
+        match block_on(uc.execute(first)) {
+            Err(RefreshError::TokenReused) => {}
+            _ => return Err("Reused token should be rejected"),
+        }
+
+        if session_repository.inner().find(&session.id).is_some() {
+            return Err("Session should be revoked");
+        }
+
+        match block_on(uc.execute(refreshed.refresh_token)) {
+            Err(RefreshError::WrongToken) => Ok(()),
+            _ => Err("Token family should be revoked"),
+        }
+    }
+}
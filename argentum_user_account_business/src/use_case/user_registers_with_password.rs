@@ -1,21 +1,38 @@
 use crate::entity::credential::PasswordCredential;
-use crate::repository::password_credential_writer::PasswordCredentialWriterTrait;
+use crate::event::UserRegistered;
+use crate::repository::password_credential_writer::{
+    AsyncPasswordCredentialWriter, PasswordCredentialWriterTrait,
+};
+use crate::value_object::password::{PasswordPolicy, PlainPassword, WeakPasswordError};
+use argentum_standard_business::transaction::{
+    in_async_transaction, in_transaction, AsyncTransactionManagerTrait, Transaction,
+    TransactionError, TransactionManagerTrait,
+};
 
-use argentum_encryption_business::password::{EncryptionError, Encryptor};
+use argentum_encryption_business::breached_password::{
+    AsyncBreachedPasswordCheckerTrait, BreachCheckError, BreachedPasswordCheckerTrait,
+};
+use argentum_encryption_business::password::{AsyncEncryptor, EncryptionError, Encryptor};
+use argentum_event_business::event::EventPublisherTrait;
 use argentum_standard_business::data_type::email::EmailAddress;
 use argentum_user_business::entity::user::AuthenticatedUser;
 use argentum_user_business::repository::user_repository::{
-    AuthenticatedUserRepositoryTrait, SavingUserError,
+    AsyncAuthenticatedUserRepositoryTrait, AuthenticatedUserRepositoryTrait, SavingUserError,
 };
 use argentum_user_business::value_object::name::Name;
 
 use argentum_standard_business::data_type::id::Id;
+use std::sync::Arc;
 
 pub struct UserRegistersWithPasswordUc<'s> {
     user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
     credential_writer: &'s dyn PasswordCredentialWriterTrait<'s>,
     // encryptor: &'s dyn Encryptor<'s>
     encryptor: &'s dyn Encryptor,
+    password_policy: &'s PasswordPolicy<'s>,
+    breached_password_checker: Option<&'s dyn BreachedPasswordCheckerTrait>,
+    event_publisher: &'s dyn EventPublisherTrait,
+    transaction_manager: &'s dyn TransactionManagerTrait,
 }
 
 impl<'s> UserRegistersWithPasswordUc<'s> {
@@ -23,11 +40,19 @@ impl<'s> UserRegistersWithPasswordUc<'s> {
         user_repository: &'s dyn AuthenticatedUserRepositoryTrait,
         credential_writer: &'s dyn PasswordCredentialWriterTrait<'s>,
         encryptor: &'s dyn Encryptor,
+        password_policy: &'s PasswordPolicy<'s>,
+        breached_password_checker: Option<&'s dyn BreachedPasswordCheckerTrait>,
+        event_publisher: &'s dyn EventPublisherTrait,
+        transaction_manager: &'s dyn TransactionManagerTrait,
     ) -> UserRegistersWithPasswordUc<'s> {
         UserRegistersWithPasswordUc {
             user_repository,
             credential_writer,
             encryptor,
+            password_policy,
+            breached_password_checker,
+            event_publisher,
+            transaction_manager,
         }
     }
 
@@ -38,26 +63,147 @@ impl<'s> UserRegistersWithPasswordUc<'s> {
         email: EmailAddress,
         password: String,
     ) -> Result<AuthenticatedUser, RegistrationError> {
-        //save user
-        let user = {
-            //it is `temporary mutability` pattern
-            let user = AuthenticatedUser::new(&id, name, email);
-            let result = self.user_repository.save(&user);
-
-            match result {
-                Ok(_) => user,
-                Err(e) => return Err(RegistrationError::SavingError(e)),
-            }
-        };
+        let password = PlainPassword::new(password, self.password_policy)?;
+
+        if let Some(checker) = self.breached_password_checker {
+            reject_breached(checker.is_breached(password.as_str()))?;
+        }
+
+        // Hashing may fail, so it is done before anything is written
+        let hashed_password = self.encryptor.encrypt(password.as_str())?;
+
+        let (user, cred) = new_user(id, name, email, hashed_password);
+
+        in_transaction(
+            self.transaction_manager,
+            |transaction| -> Result<(), RegistrationError> {
+                let user_repository = self.user_repository.within(transaction);
+                reject_taken(user_repository.find_by_email(&user.email))?;
+                user_repository.save(&user)?;
+                self.credential_writer
+                    .within(transaction)
+                    .write(Box::new(cred));
+
+                Ok(())
+            },
+        )?;
+
+        // Published once committed, so subscribers never see a rolled back user
+        self.event_publisher
+            .publish(&UserRegistered::new(user.id.clone(), user.email.clone()));
+
+        Ok(user)
+    }
+}
+
+/// Async counterpart of `UserRegistersWithPasswordUc`
+pub struct AsyncUserRegistersWithPasswordUc {
+    user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+    credential_writer: Arc<AsyncPasswordCredentialWriter>,
+    encryptor: Arc<dyn AsyncEncryptor>,
+    password_policy: Arc<PasswordPolicy<'static>>,
+    breached_password_checker: Option<Arc<dyn AsyncBreachedPasswordCheckerTrait>>,
+    event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+    transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+}
+
+impl AsyncUserRegistersWithPasswordUc {
+    pub fn new(
+        user_repository: Arc<dyn AsyncAuthenticatedUserRepositoryTrait>,
+        credential_writer: Arc<AsyncPasswordCredentialWriter>,
+        encryptor: Arc<dyn AsyncEncryptor>,
+        password_policy: Arc<PasswordPolicy<'static>>,
+        breached_password_checker: Option<Arc<dyn AsyncBreachedPasswordCheckerTrait>>,
+        event_publisher: Arc<dyn EventPublisherTrait + Send + Sync>,
+        transaction_manager: Arc<dyn AsyncTransactionManagerTrait>,
+    ) -> AsyncUserRegistersWithPasswordUc {
+        AsyncUserRegistersWithPasswordUc {
+            user_repository,
+            credential_writer,
+            encryptor,
+            password_policy,
+            breached_password_checker,
+            event_publisher,
+            transaction_manager,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        id: Id,
+        name: Name,
+        email: EmailAddress,
+        password: String,
+    ) -> Result<AuthenticatedUser, RegistrationError> {
+        let password = PlainPassword::new(password, &self.password_policy)?;
+
+        if let Some(checker) = &self.breached_password_checker {
+            reject_breached(checker.is_breached(password.as_str()).await)?;
+        }
+
+        // Hashing may fail, so it is done before anything is written
+        let hashed_password = self.encryptor.encrypt(password.as_str()).await?;
 
-        //save credentials
-        let (hashed_password, salt) = self.encryptor.encrypt(&password)?;
+        let (user, cred) = new_user(id, name, email, hashed_password);
 
-        let cred = PasswordCredential::new(id.clone(), hashed_password, salt);
-        self.credential_writer.write(Box::new(cred));
+        in_async_transaction(self.transaction_manager.as_ref(), |transaction| {
+            self.save(transaction, &user, &cred)
+        })
+        .await?;
+
+        // Published once committed, so subscribers never see a rolled back user
+        self.event_publisher
+            .publish(&UserRegistered::new(user.id.clone(), user.email.clone()));
 
         Ok(user)
     }
+
+    async fn save(
+        &self,
+        transaction: Transaction,
+        user: &AuthenticatedUser,
+        cred: &PasswordCredential,
+    ) -> Result<(), RegistrationError> {
+        let user_repository = self.user_repository.within(&transaction);
+        reject_taken(user_repository.find_by_email(&user.email).await)?;
+        user_repository.save(user).await?;
+        self.credential_writer
+            .within(&transaction)
+            .write_password_credentials(cred)
+            .await;
+
+        Ok(())
+    }
+}
+
+// Shared by the sync and async use cases
+
+fn reject_breached(breached: Result<bool, BreachCheckError>) -> Result<(), RegistrationError> {
+    match breached? {
+        true => Err(RegistrationError::BreachedPassword),
+        false => Ok(()),
+    }
+}
+
+fn reject_taken(
+    found: Result<Option<AuthenticatedUser>, SavingUserError>,
+) -> Result<(), RegistrationError> {
+    match found? {
+        Some(_) => Err(RegistrationError::EmailTaken),
+        None => Ok(()),
+    }
+}
+
+fn new_user(
+    id: Id,
+    name: Name,
+    email: EmailAddress,
+    hashed_password: String,
+) -> (AuthenticatedUser, PasswordCredential) {
+    let user = AuthenticatedUser::new(&id, name, email);
+    let cred = PasswordCredential::new(id, hashed_password);
+
+    (user, cred)
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -65,53 +211,131 @@ pub enum RegistrationError {
     #[error("Can't encrypt password")]
     EncryptionError(#[from] EncryptionError),
 
+    #[error("Email is already registered")]
+    EmailTaken,
+
     #[error("Can't save user")]
     SavingError(#[from] SavingUserError),
+
+    #[error(transparent)]
+    WeakPassword(#[from] WeakPasswordError),
+
+    #[error("Password is found in a data breach")]
+    BreachedPassword,
+
+    #[error("Can't check password against data breaches")]
+    BreachCheckError(#[from] BreachCheckError),
+
+    #[error(transparent)]
+    TransactionError(#[from] TransactionError),
 }
 
 #[cfg(test)]
 mod test {
     use crate::mock::repository::password_credential_repository_mock::PasswordCredentialRepositoryMock;
-    use crate::repository::password_credential_writer::PasswordCredentialWriter;
+    use crate::repository::password_credential_repository::PasswordCredentialRepository;
+    use crate::repository::password_credential_writer::{
+        AsyncPasswordCredentialWriter, PasswordCredentialWriter,
+    };
     use crate::use_case::user_registers_with_password::{
-        RegistrationError, UserRegistersWithPasswordUc,
+        AsyncUserRegistersWithPasswordUc, RegistrationError, UserRegistersWithPasswordUc,
+    };
+    use crate::value_object::password::PasswordPolicy;
+    use argentum_encryption_business::mock::breached_password::{
+        BreachedPasswordCheckerMock, BreachedPasswordCheckerMockWithBrokenSource,
+    };
+    use argentum_encryption_business::mock::password::{
+        EncryptorMock, EncryptorMockWithBrokenHashing,
     };
-    use argentum_encryption_business::mock::password::EncryptorMock;
+    use argentum_event_business::mock::publisher::EventPublisherMock;
+    use argentum_standard_business::asynchronous::Blocking;
     use argentum_standard_business::data_type::email::EmailAddress;
     use argentum_standard_business::data_type::id::{Id, IdFactory};
     use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::mock::executor::block_on;
+    use argentum_standard_business::mock::transaction::{
+        TransactionManagerMock, TransactionManagerMockWithBrokenCommit,
+    };
     use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
     use argentum_user_business::mock::repository::broken::authenticated_user_repository_mock::AuthenticatedUserRepositoryMockWihBrokenSave;
+    use argentum_user_business::repository::user_repository::AuthenticatedUserRepositoryTrait;
     use argentum_user_business::value_object::name::Name;
+    use std::sync::Arc;
 
     #[test]
     fn test_user_registers_with_password() -> Result<(), &'static str> {
         let credential_repository = PasswordCredentialRepositoryMock::new();
         let credential_writer = PasswordCredentialWriter::new(&credential_repository);
         let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
         let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
         let uc = UserRegistersWithPasswordUc::new(
             &authenticated_user_repository,
             &credential_writer,
             &encryptor,
+            &password_policy,
+            None,
+            &event_publisher,
+            &transaction_manager,
         );
         let id_factory = IdFactoryMock::new();
 
         let id: Id = id_factory.create();
         let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
         let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
-        let password = String::from("123");
+        let password = String::from("Correct-Horse-7");
         let result = uc.execute(id.clone(), name, email, password);
 
         match result {
             Ok(u) => {
                 assert_eq!(u.id.to_string(), id.clone().to_string());
+                assert_eq!(vec!["begin", "commit"], transaction_manager.journal());
+                assert_eq!(
+                    vec!["user_account.user_registered"],
+                    event_publisher.published()
+                );
 
-                return Ok(());
+                Ok(())
             }
-            Err(_) => {
-                return Err("Can't register an user");
+            Err(_) => Err("Can't register an user"),
+        }
+    }
+
+    #[test]
+    fn test_user_registered_is_not_published_when_commit_fails() -> Result<(), &'static str> {
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMockWithBrokenCommit::new();
+        let uc = UserRegistersWithPasswordUc::new(
+            &authenticated_user_repository,
+            &credential_writer,
+            &encryptor,
+            &password_policy,
+            None,
+            &event_publisher,
+            &transaction_manager,
+        );
+        let id_factory = IdFactoryMock::new();
+
+        let id: Id = id_factory.create();
+        let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
+        let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
+        let result = uc.execute(id, name, email, String::from("Correct-Horse-7"));
+
+        match result {
+            Ok(_) => Err("Should return an error"),
+            Err(RegistrationError::TransactionError(_)) => {
+                assert!(event_publisher.published().is_empty());
+
+                Ok(())
             }
+            Err(_) => Err("Wrong Error"),
         }
     }
 
@@ -120,11 +344,18 @@ mod test {
         let credential_repository = PasswordCredentialRepositoryMock::new();
         let credential_writer = PasswordCredentialWriter::new(&credential_repository);
         let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
         let authenticated_user_repository = AuthenticatedUserRepositoryMockWihBrokenSave::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
         let uc = UserRegistersWithPasswordUc::new(
             &authenticated_user_repository,
             &credential_writer,
             &encryptor,
+            &password_policy,
+            None,
+            &event_publisher,
+            &transaction_manager,
         );
 
         let id_factory = IdFactoryMock::new();
@@ -132,7 +363,7 @@ mod test {
         let id: Id = id_factory.create();
         let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
         let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
-        let password = String::from("123");
+        let password = String::from("Correct-Horse-7");
         let result = uc.execute(id.clone(), name, email, password);
 
         match result {
@@ -142,9 +373,345 @@ mod test {
                 Err("Should return an error")
             }
             Err(e) => match e {
-                RegistrationError::SavingError(_) => Ok(()),
+                RegistrationError::SavingError(_) => {
+                    assert!(transaction_manager.rolled_back());
+
+                    Ok(())
+                }
                 _ => Err("Wrong Error"),
             },
         }
     }
+
+    #[test]
+    fn test_user_registers_with_taken_email() -> Result<(), &'static str> {
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let uc = UserRegistersWithPasswordUc::new(
+            &authenticated_user_repository,
+            &credential_writer,
+            &encryptor,
+            &password_policy,
+            None,
+            &event_publisher,
+            &transaction_manager,
+        );
+        let id_factory = IdFactoryMock::new();
+
+        let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
+        let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
+        uc.execute(
+            id_factory.create(),
+            name.clone(),
+            email.clone(),
+            String::from("Correct-Horse-7"),
+        )
+        .expect("Can't register an user");
+
+        let id: Id = id_factory.create();
+        let result = uc.execute(id.clone(), name, email, String::from("Correct-Horse-8"));
+
+        match result {
+            Ok(_) => Err("Should return an error"),
+            Err(RegistrationError::EmailTaken) => {
+                assert!(credential_repository.find_by_user_id(&id).is_none());
+                assert!(transaction_manager.rolled_back());
+                assert_eq!(1, event_publisher.published().len());
+
+                Ok(())
+            }
+            Err(_) => Err("Wrong Error"),
+        }
+    }
+
+    #[test]
+    fn test_user_registers_with_weak_password() -> Result<(), &'static str> {
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let uc = UserRegistersWithPasswordUc::new(
+            &authenticated_user_repository,
+            &credential_writer,
+            &encryptor,
+            &password_policy,
+            None,
+            &event_publisher,
+            &transaction_manager,
+        );
+        let id_factory = IdFactoryMock::new();
+
+        let id: Id = id_factory.create();
+        let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
+        let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
+        let password = String::from("111111");
+        let result = uc.execute(id.clone(), name, email, password);
+
+        match result {
+            Ok(_) => Err("Should return an error"),
+            Err(RegistrationError::WeakPassword(e)) => {
+                assert_eq!(4, e.violations.len());
+
+                if authenticated_user_repository.find(&id).unwrap().is_some() {
+                    return Err("User should not be saved");
+                }
+
+                Ok(())
+            }
+            Err(_) => Err("Wrong Error"),
+        }
+    }
+
+    #[test]
+    fn test_user_registers_with_breached_password() -> Result<(), &'static str> {
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+        let breached_password_checker = BreachedPasswordCheckerMock::new(vec!["Correct-Horse-7"]);
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let uc = UserRegistersWithPasswordUc::new(
+            &authenticated_user_repository,
+            &credential_writer,
+            &encryptor,
+            &password_policy,
+            Some(&breached_password_checker),
+            &event_publisher,
+            &transaction_manager,
+        );
+        let id_factory = IdFactoryMock::new();
+
+        let id: Id = id_factory.create();
+        let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
+        let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
+        let password = String::from("Correct-Horse-7");
+        let result = uc.execute(id.clone(), name, email, password);
+
+        match result {
+            Ok(_) => Err("Should return an error"),
+            Err(RegistrationError::BreachedPassword) => {
+                if authenticated_user_repository.find(&id).unwrap().is_some() {
+                    return Err("User should not be saved");
+                }
+
+                Ok(())
+            }
+            Err(_) => Err("Wrong Error"),
+        }
+    }
+
+    #[test]
+    fn test_user_registers_with_broken_breached_password_checker() -> Result<(), &'static str> {
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMock::new();
+        let password_policy = PasswordPolicy::default();
+        let breached_password_checker = BreachedPasswordCheckerMockWithBrokenSource::new();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let uc = UserRegistersWithPasswordUc::new(
+            &authenticated_user_repository,
+            &credential_writer,
+            &encryptor,
+            &password_policy,
+            Some(&breached_password_checker),
+            &event_publisher,
+            &transaction_manager,
+        );
+        let id_factory = IdFactoryMock::new();
+
+        let id: Id = id_factory.create();
+        let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
+        let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
+        let password = String::from("Correct-Horse-7");
+        let result = uc.execute(id, name, email, password);
+
+        match result {
+            Ok(_) => Err("Should return an error"),
+            Err(RegistrationError::BreachCheckError(_)) => Ok(()),
+            Err(_) => Err("Wrong Error"),
+        }
+    }
+
+    #[test]
+    fn test_user_is_not_saved_when_password_hashing_fails() -> Result<(), &'static str> {
+        let credential_repository = PasswordCredentialRepositoryMock::new();
+        let credential_writer = PasswordCredentialWriter::new(&credential_repository);
+        let encryptor = EncryptorMockWithBrokenHashing::new();
+        let password_policy = PasswordPolicy::default();
+        let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
+        let event_publisher = EventPublisherMock::new();
+        let transaction_manager = TransactionManagerMock::new();
+        let uc = UserRegistersWithPasswordUc::new(
+            &authenticated_user_repository,
+            &credential_writer,
+            &encryptor,
+            &password_policy,
+            None,
+            &event_publisher,
+            &transaction_manager,
+        );
+        let id_factory = IdFactoryMock::new();
+
+        let id: Id = id_factory.create();
+        let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
+        let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
+        let result = uc.execute(id.clone(), name, email, String::from("Correct-Horse-7"));
+
+        match result {
+            Ok(_) => Err("Should return an error"),
+            Err(RegistrationError::EncryptionError(_)) => {
+                if authenticated_user_repository.find(&id).unwrap().is_some() {
+                    return Err("User should not be saved");
+                }
+
+                assert!(event_publisher.published().is_empty());
+
+                Ok(())
+            }
+            Err(_) => Err("Wrong Error"),
+        }
+    }
+
+    #[test]
+    fn test_user_registers_with_password_asynchronously() -> Result<(), &'static str> {
+        let credential_repository =
+            Arc::new(Blocking::new(PasswordCredentialRepositoryMock::new()));
+        let credential_writer = Arc::new(AsyncPasswordCredentialWriter::new(
+            credential_repository.clone(),
+        ));
+        let encryptor = Arc::new(Blocking::new(EncryptorMock::new()));
+        let password_policy = Arc::new(PasswordPolicy::default());
+        let authenticated_user_repository =
+            Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+        let uc = AsyncUserRegistersWithPasswordUc::new(
+            authenticated_user_repository.clone(),
+            credential_writer.clone(),
+            encryptor.clone(),
+            password_policy.clone(),
+            None,
+            event_publisher.clone(),
+            transaction_manager.clone(),
+        );
+
+        let id: Id = IdFactoryMock::new().create();
+        let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
+        let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
+        let result = block_on(uc.execute(id.clone(), name, email, "Correct-Horse-7".into()));
+
+        match result {
+            Ok(u) => {
+                assert_eq!(u.id.to_string(), id.to_string());
+                assert!(credential_repository.inner().find_by_user_id(&id).is_some());
+                assert_eq!(
+                    vec!["begin", "commit"],
+                    transaction_manager.inner().journal()
+                );
+
+                Ok(())
+            }
+            Err(_) => Err("Can't register an user"),
+        }
+    }
+
+    #[test]
+    fn test_user_registers_with_password_asynchronously_with_broken_user_repository(
+    ) -> Result<(), &'static str> {
+        let credential_repository =
+            Arc::new(Blocking::new(PasswordCredentialRepositoryMock::new()));
+        let credential_writer = Arc::new(AsyncPasswordCredentialWriter::new(
+            credential_repository.clone(),
+        ));
+        let encryptor = Arc::new(Blocking::new(EncryptorMock::new()));
+        let password_policy = Arc::new(PasswordPolicy::default());
+        let authenticated_user_repository = Arc::new(Blocking::new(
+            AuthenticatedUserRepositoryMockWihBrokenSave::new(),
+        ));
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+        let uc = AsyncUserRegistersWithPasswordUc::new(
+            authenticated_user_repository.clone(),
+            credential_writer.clone(),
+            encryptor.clone(),
+            password_policy.clone(),
+            None,
+            event_publisher.clone(),
+            transaction_manager.clone(),
+        );
+
+        let id: Id = IdFactoryMock::new().create();
+        let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
+        let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
+
+        match block_on(uc.execute(id, name, email, "Correct-Horse-7".into())) {
+            Ok(_) => Err("Should return an error"),
+            Err(RegistrationError::SavingError(_)) => {
+                assert!(transaction_manager.inner().rolled_back());
+                assert!(event_publisher.published().is_empty());
+
+                Ok(())
+            }
+            Err(_) => Err("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_user_registers_with_password_asynchronously_with_taken_email(
+    ) -> Result<(), &'static str> {
+        let credential_repository =
+            Arc::new(Blocking::new(PasswordCredentialRepositoryMock::new()));
+        let credential_writer = Arc::new(AsyncPasswordCredentialWriter::new(
+            credential_repository.clone(),
+        ));
+        let encryptor = Arc::new(Blocking::new(EncryptorMock::new()));
+        let password_policy = Arc::new(PasswordPolicy::default());
+        let authenticated_user_repository =
+            Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+        let uc = AsyncUserRegistersWithPasswordUc::new(
+            authenticated_user_repository.clone(),
+            credential_writer.clone(),
+            encryptor.clone(),
+            password_policy.clone(),
+            None,
+            event_publisher.clone(),
+            transaction_manager.clone(),
+        );
+
+        let id_factory = IdFactoryMock::new();
+        let name = Name::new(String::from("John"), String::from("Cooper")).unwrap();
+        let email = EmailAddress::new(String::from("demo@test.com")).unwrap();
+        block_on(uc.execute(
+            id_factory.create(),
+            name.clone(),
+            email.clone(),
+            "Correct-Horse-7".into(),
+        ))
+        .expect("Can't register an user");
+
+        match block_on(uc.execute(id_factory.create(), name, email, "Correct-Horse-7".into())) {
+            Ok(_) => Err("Should return an error"),
+            Err(RegistrationError::EmailTaken) => {
+                assert!(transaction_manager.inner().rolled_back());
+                assert_eq!(1, event_publisher.published().len());
+
+                Ok(())
+            }
+            Err(_) => Err("Wrong error type"),
+        }
+    }
 }
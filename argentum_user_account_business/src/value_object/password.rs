@@ -0,0 +1,341 @@
+use std::fmt;
+
+const DEFAULT_MIN_LENGTH: usize = 10;
+const DEFAULT_MAX_LENGTH: usize = 128;
+
+/// Passwords that must never be accepted, however well they match the other rules.
+pub trait DenyListTrait: Send + Sync {
+    fn contains(&self, password: &str) -> bool;
+}
+
+/// A small built-in list of the most common leaked passwords. Comparison is case-insensitive.
+pub struct CommonPasswordsDenyList {}
+
+impl CommonPasswordsDenyList {
+    pub fn new() -> CommonPasswordsDenyList {
+        CommonPasswordsDenyList {}
+    }
+}
+
+impl Default for CommonPasswordsDenyList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DenyListTrait for CommonPasswordsDenyList {
+    fn contains(&self, password: &str) -> bool {
+        let password = password.to_lowercase();
+
+        COMMON_PASSWORDS.iter().any(|p| *p == password)
+    }
+}
+
+static COMMON_PASSWORDS: &[&str] = &[
+    "000000",
+    "111111",
+    "112233",
+    "121212",
+    "123123",
+    "123321",
+    "1234",
+    "12345",
+    "123456",
+    "1234567",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "1q2w3e4r",
+    "1q2w3e4r5t",
+    "654321",
+    "666666",
+    "696969",
+    "7777777",
+    "987654321",
+    "aa123456",
+    "abc123",
+    "admin",
+    "admin123",
+    "baseball",
+    "dragon",
+    "football",
+    "iloveyou",
+    "letmein",
+    "master",
+    "monkey",
+    "password",
+    "password1",
+    "password123",
+    "passw0rd",
+    "princess",
+    "qwerty",
+    "qwerty123",
+    "qwertyuiop",
+    "shadow",
+    "sunshine",
+    "superman",
+    "trustno1",
+    "welcome",
+    "welcome1",
+];
+
+static COMMON_PASSWORDS_DENY_LIST: CommonPasswordsDenyList = CommonPasswordsDenyList {};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterClass {
+    Lowercase,
+    Uppercase,
+    Digit,
+    Symbol,
+}
+
+impl CharacterClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CharacterClass::Lowercase => c.is_lowercase(),
+            CharacterClass::Uppercase => c.is_uppercase(),
+            CharacterClass::Digit => c.is_numeric(),
+            CharacterClass::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+        }
+    }
+}
+
+impl fmt::Display for CharacterClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CharacterClass::Lowercase => write!(f, "a lowercase letter"),
+            CharacterClass::Uppercase => write!(f, "an uppercase letter"),
+            CharacterClass::Digit => write!(f, "a digit"),
+            CharacterClass::Symbol => write!(f, "a symbol"),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum PasswordRuleViolation {
+    #[error("should be at least {0} characters long")]
+    TooShort(usize),
+
+    #[error("should be at most {0} characters long")]
+    TooLong(usize),
+
+    #[error("should contain {0}")]
+    MissingCharacterClass(CharacterClass),
+
+    #[error("is too common")]
+    Common,
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Password is too weak: {}", join_violations(.violations))]
+pub struct WeakPasswordError {
+    pub violations: Vec<PasswordRuleViolation>,
+}
+
+fn join_violations(violations: &[PasswordRuleViolation]) -> String {
+    violations
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Rules a new password has to satisfy. Lengths are counted in characters.
+/// The maximum length keeps huge inputs away from the password hasher.
+pub struct PasswordPolicy<'s> {
+    min_length: usize,
+    max_length: usize,
+    required_classes: Vec<CharacterClass>,
+    deny_list: &'s dyn DenyListTrait,
+}
+
+impl<'s> PasswordPolicy<'s> {
+    pub fn new(
+        min_length: usize,
+        max_length: usize,
+        required_classes: Vec<CharacterClass>,
+        deny_list: &'s dyn DenyListTrait,
+    ) -> PasswordPolicy<'s> {
+        PasswordPolicy {
+            min_length,
+            max_length,
+            required_classes,
+            deny_list,
+        }
+    }
+
+    pub fn check(&self, password: &str) -> Result<(), WeakPasswordError> {
+        let mut violations = vec![];
+
+        // Don't walk through a huge input char by char
+        let length = if password.len() > self.max_length * 4 {
+            password.len()
+        } else {
+            password.chars().count()
+        };
+
+        if length < self.min_length {
+            violations.push(PasswordRuleViolation::TooShort(self.min_length));
+        }
+
+        if length > self.max_length {
+            violations.push(PasswordRuleViolation::TooLong(self.max_length));
+        }
+
+        for class in self.required_classes.iter() {
+            if !password.chars().any(|c| class.matches(c)) {
+                violations.push(PasswordRuleViolation::MissingCharacterClass(*class));
+            }
+        }
+
+        if self.deny_list.contains(password) {
+            violations.push(PasswordRuleViolation::Common);
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(WeakPasswordError { violations })
+        }
+    }
+}
+
+impl Default for PasswordPolicy<'_> {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_MIN_LENGTH,
+            DEFAULT_MAX_LENGTH,
+            vec![
+                CharacterClass::Lowercase,
+                CharacterClass::Uppercase,
+                CharacterClass::Digit,
+            ],
+            &COMMON_PASSWORDS_DENY_LIST,
+        )
+    }
+}
+
+/// A password which has passed the policy check and can be hashed
+pub struct PlainPassword {
+    value: String,
+}
+
+impl PlainPassword {
+    pub fn new(value: String, policy: &PasswordPolicy) -> Result<PlainPassword, WeakPasswordError> {
+        policy.check(&value)?;
+
+        Ok(PlainPassword { value })
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.value.as_str()
+    }
+}
+
+impl fmt::Debug for PlainPassword {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PlainPassword(***)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value_object::password::{
+        CharacterClass, CommonPasswordsDenyList, DenyListTrait, PasswordPolicy,
+        PasswordRuleViolation, PlainPassword,
+    };
+
+    struct CompanyNameDenyList {}
+
+    impl DenyListTrait for CompanyNameDenyList {
+        fn contains(&self, password: &str) -> bool {
+            password.to_lowercase().contains("argentum")
+        }
+    }
+
+    #[test]
+    fn test_strong_password() -> Result<(), &'static str> {
+        let policy = PasswordPolicy::default();
+
+        match PlainPassword::new("Correct-Horse-7".to_string(), &policy) {
+            Ok(password) => {
+                assert_eq!("Correct-Horse-7", password.as_str());
+                Ok(())
+            }
+            Err(_) => Err("Strong password is rejected"),
+        }
+    }
+
+    #[test]
+    fn test_weak_password_lists_all_violations() -> Result<(), &'static str> {
+        let policy = PasswordPolicy::default();
+
+        match PlainPassword::new("111111".to_string(), &policy) {
+            Ok(_) => Err("Weak password is accepted"),
+            Err(e) => {
+                assert_eq!(
+                    vec![
+                        PasswordRuleViolation::TooShort(10),
+                        PasswordRuleViolation::MissingCharacterClass(CharacterClass::Lowercase),
+                        PasswordRuleViolation::MissingCharacterClass(CharacterClass::Uppercase),
+                        PasswordRuleViolation::Common,
+                    ],
+                    e.violations
+                );
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_too_long_password() -> Result<(), &'static str> {
+        let policy = PasswordPolicy::default();
+        let password = "Aa1".repeat(1024 * 1024);
+
+        match PlainPassword::new(password, &policy) {
+            Ok(_) => Err("Too long password is accepted"),
+            Err(e) => {
+                assert_eq!(vec![PasswordRuleViolation::TooLong(128)], e.violations);
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_too_long_password_lists_other_violations() -> Result<(), &'static str> {
+        let policy = PasswordPolicy::default();
+        let password = "a".repeat(200);
+
+        match PlainPassword::new(password, &policy) {
+            Ok(_) => Err("Too long password is accepted"),
+            Err(e) => {
+                assert_eq!(
+                    vec![
+                        PasswordRuleViolation::TooLong(128),
+                        PasswordRuleViolation::MissingCharacterClass(CharacterClass::Uppercase),
+                        PasswordRuleViolation::MissingCharacterClass(CharacterClass::Digit),
+                    ],
+                    e.violations
+                );
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_common_password_is_rejected() {
+        let policy = PasswordPolicy::new(0, 128, vec![], &CommonPasswordsDenyList {});
+
+        assert!(policy.check("Password1").is_err());
+        assert!(policy.check("not-so-common").is_ok());
+    }
+
+    #[test]
+    fn test_custom_deny_list() {
+        let deny_list = CompanyNameDenyList {};
+        let policy = PasswordPolicy::new(8, 64, vec![CharacterClass::Symbol], &deny_list);
+
+        assert!(policy.check("Argentum#2024").is_err());
+        assert!(policy.check("Rusty#Silver").is_ok());
+    }
+}
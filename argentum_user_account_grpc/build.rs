@@ -0,0 +1,8 @@
+// protoc is vendored, so the build doesn't depend on one installed in the system
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+
+    tonic_build::compile_protos("proto/user_account.proto")?;
+
+    Ok(())
+}
@@ -0,0 +1,8 @@
+pub mod proto {
+    tonic::include_proto!("argentum.user_account.v1");
+}
+
+pub mod server;
+pub mod status;
+#[cfg(test)]
+mod test_server;
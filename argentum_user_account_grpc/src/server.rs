@@ -0,0 +1,406 @@
+use crate::proto::authenticate_response;
+use crate::proto::user_account_service_server::UserAccountService;
+use crate::proto::{
+    AnonymousUser, AuthenticateRequest, AuthenticateResponse, AuthenticatedUser,
+    ChangePasswordWithTokenRequest, ChangePasswordWithTokenResponse, LoginRequest,
+    RegisterAnonymousRequest, RegisterWithPasswordRequest, RegisterWithPasswordResponse,
+    RequestPasswordRestoreRequest, RequestPasswordRestoreResponse, Session,
+};
+use crate::status::IntoStatus;
+
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_standard_business::data_type::id::IdFactory;
+use argentum_user_account_business::entity::session;
+use argentum_user_account_business::use_case::anonymous_registers::AsyncAnonymousRegistersUc;
+use argentum_user_account_business::use_case::restore_password::anonymous_requests_restore_token::AsyncAnonymousRequestsRestoreToken;
+use argentum_user_account_business::use_case::restore_password::anonymous_with_token_changes_password::AsyncAnonymousWithTokenChangesPassword;
+use argentum_user_account_business::use_case::user_authenticates_with_token::AsyncUserAuthenticatesWithTokenUc;
+use argentum_user_account_business::use_case::user_logins_with_password::AsyncUserLoginsWithPasswordUc;
+use argentum_user_account_business::use_case::user_registers_with_password::AsyncUserRegistersWithPasswordUc;
+use argentum_user_business::entity::user::User;
+use argentum_user_business::value_object::name::Name;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+/// Adapts the async use cases to `UserAccountService`. Wrap it into
+/// `UserAccountServiceServer` to serve it
+pub struct UserAccountGrpcService {
+    id_factory: Arc<dyn IdFactory + Send + Sync>,
+    anonymous_registers: AsyncAnonymousRegistersUc,
+    user_registers_with_password: AsyncUserRegistersWithPasswordUc,
+    user_logins_with_password: AsyncUserLoginsWithPasswordUc,
+    user_authenticates_with_token: AsyncUserAuthenticatesWithTokenUc,
+    anonymous_requests_restore_token: AsyncAnonymousRequestsRestoreToken,
+    anonymous_with_token_changes_password: AsyncAnonymousWithTokenChangesPassword,
+}
+
+impl UserAccountGrpcService {
+    pub fn new(
+        id_factory: Arc<dyn IdFactory + Send + Sync>,
+        anonymous_registers: AsyncAnonymousRegistersUc,
+        user_registers_with_password: AsyncUserRegistersWithPasswordUc,
+        user_logins_with_password: AsyncUserLoginsWithPasswordUc,
+        user_authenticates_with_token: AsyncUserAuthenticatesWithTokenUc,
+        anonymous_requests_restore_token: AsyncAnonymousRequestsRestoreToken,
+        anonymous_with_token_changes_password: AsyncAnonymousWithTokenChangesPassword,
+    ) -> UserAccountGrpcService {
+        UserAccountGrpcService {
+            id_factory,
+            anonymous_registers,
+            user_registers_with_password,
+            user_logins_with_password,
+            user_authenticates_with_token,
+            anonymous_requests_restore_token,
+            anonymous_with_token_changes_password,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl UserAccountService for UserAccountGrpcService {
+    async fn register_anonymous(
+        &self,
+        _request: Request<RegisterAnonymousRequest>,
+    ) -> Result<Response<Session>, Status> {
+        let id = self.id_factory.create();
+        let (_, session, token) = self
+            .anonymous_registers
+            .execute(&id)
+            .await
+            .map_err(IntoStatus::into_status)?;
+
+        Ok(Response::new(to_session(&session, token)))
+    }
+
+    async fn register_with_password(
+        &self,
+        request: Request<RegisterWithPasswordRequest>,
+    ) -> Result<Response<RegisterWithPasswordResponse>, Status> {
+        let request = request.into_inner();
+        let name =
+            Name::new(request.first_name, request.last_name).map_err(IntoStatus::into_status)?;
+        let email = EmailAddress::new(request.email).map_err(IntoStatus::into_status)?;
+
+        let user = self
+            .user_registers_with_password
+            .execute(self.id_factory.create(), name, email, request.password)
+            .await
+            .map_err(IntoStatus::into_status)?;
+
+        Ok(Response::new(RegisterWithPasswordResponse {
+            id: user.id.to_string(),
+        }))
+    }
+
+    /// The peer address is the client id of the login throttling
+    async fn login(&self, request: Request<LoginRequest>) -> Result<Response<Session>, Status> {
+        let client_id = match request.remote_addr() {
+            Some(address) => address.ip().to_string(),
+            None => "unknown".to_string(),
+        };
+        let request = request.into_inner();
+
+        let anonymous = match self
+            .user_authenticates_with_token
+            .execute(request.anonymous_token)
+            .await
+            .map_err(IntoStatus::into_status)?
+        {
+            User::Anonymous(anonymous) => anonymous,
+            User::Authenticated(_) => {
+                return Err(Status::failed_precondition("User is already logged in"))
+            }
+        };
+        let email = EmailAddress::new(request.email).map_err(IntoStatus::into_status)?;
+
+        let (session, token) = self
+            .user_logins_with_password
+            .execute(anonymous, email, request.password, client_id)
+            .await
+            .map_err(IntoStatus::into_status)?;
+
+        Ok(Response::new(to_session(&session, token)))
+    }
+
+    async fn authenticate(
+        &self,
+        request: Request<AuthenticateRequest>,
+    ) -> Result<Response<AuthenticateResponse>, Status> {
+        let user = self
+            .user_authenticates_with_token
+            .execute(request.into_inner().token)
+            .await
+            .map_err(IntoStatus::into_status)?;
+
+        Ok(Response::new(AuthenticateResponse {
+            user: Some(to_user(user)),
+        }))
+    }
+
+    async fn request_password_restore(
+        &self,
+        request: Request<RequestPasswordRestoreRequest>,
+    ) -> Result<Response<RequestPasswordRestoreResponse>, Status> {
+        let email =
+            EmailAddress::new(request.into_inner().email).map_err(IntoStatus::into_status)?;
+
+        self.anonymous_requests_restore_token
+            .execute(email)
+            .await
+            .map_err(IntoStatus::into_status)?;
+
+        Ok(Response::new(RequestPasswordRestoreResponse {}))
+    }
+
+    async fn change_password_with_token(
+        &self,
+        request: Request<ChangePasswordWithTokenRequest>,
+    ) -> Result<Response<ChangePasswordWithTokenResponse>, Status> {
+        let request = request.into_inner();
+
+        self.anonymous_with_token_changes_password
+            .execute(request.token, request.password)
+            .await
+            .map_err(IntoStatus::into_status)?;
+
+        Ok(Response::new(ChangePasswordWithTokenResponse {}))
+    }
+}
+
+fn to_session(session: &session::Session, token: String) -> Session {
+    Session {
+        user_id: session.user_id.to_string(),
+        token,
+        expires_at: session.expires_at.timestamp(),
+    }
+}
+
+fn to_user(user: User) -> authenticate_response::User {
+    match user {
+        User::Anonymous(anonymous) => authenticate_response::User::Anonymous(AnonymousUser {
+            id: anonymous.id.to_string(),
+        }),
+        User::Authenticated(user) => {
+            authenticate_response::User::Authenticated(AuthenticatedUser {
+                id: user.id.to_string(),
+                email_verified: user.is_email_verified(),
+                first_name: user.name.first,
+                last_name: user.name.last,
+                email: user.email.as_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::proto::authenticate_response::User;
+    use crate::proto::{
+        AuthenticateRequest, ChangePasswordWithTokenRequest, LoginRequest,
+        RegisterAnonymousRequest, RegisterWithPasswordRequest, RequestPasswordRestoreRequest,
+        Session,
+    };
+    use crate::status::RETRY_AFTER;
+    use crate::test_server::{TestServer, FREE_LOGIN_ATTEMPTS};
+    use tonic::{Code, Status};
+
+    const EMAIL: &str = "sarah-connor@example.com";
+    const PASSWORD: &str = "Judgment-Day-1997";
+
+    async fn register_anonymous(server: &TestServer) -> Session {
+        server
+            .client
+            .clone()
+            .register_anonymous(RegisterAnonymousRequest {})
+            .await
+            .expect("Anonymous is not registered")
+            .into_inner()
+    }
+
+    async fn register(server: &TestServer, email: &str, password: &str) -> Result<String, Status> {
+        let response = server
+            .client
+            .clone()
+            .register_with_password(RegisterWithPasswordRequest {
+                first_name: "Sarah".to_string(),
+                last_name: "Connor".to_string(),
+                email: email.to_string(),
+                password: password.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner().id)
+    }
+
+    async fn login(server: &TestServer, email: &str, password: &str) -> Result<Session, Status> {
+        let anonymous = register_anonymous(server).await;
+
+        let response = server
+            .client
+            .clone()
+            .login(LoginRequest {
+                anonymous_token: anonymous.token,
+                email: email.to_string(),
+                password: password.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner())
+    }
+
+    async fn authenticate(server: &TestServer, token: &str) -> Result<User, Status> {
+        let response = server
+            .client
+            .clone()
+            .authenticate(AuthenticateRequest {
+                token: token.to_string(),
+            })
+            .await?;
+
+        response
+            .into_inner()
+            .user
+            .ok_or_else(|| Status::internal("No user"))
+    }
+
+    async fn change_password(
+        server: &TestServer,
+        token: &str,
+        password: &str,
+    ) -> Result<(), Status> {
+        server
+            .client
+            .clone()
+            .change_password_with_token(ChangePasswordWithTokenRequest {
+                token: token.to_string(),
+                password: password.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_registers_and_authenticates() {
+        let server = TestServer::start().await;
+        let session = register_anonymous(&server).await;
+
+        match authenticate(&server, &session.token).await {
+            Ok(User::Anonymous(anonymous)) => assert_eq!(session.user_id, anonymous.id),
+            _ => panic!("Anonymous is not authenticated"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_user_registers_and_logs_in() {
+        let server = TestServer::start().await;
+
+        let id = register(&server, EMAIL, PASSWORD)
+            .await
+            .expect("User is not registered");
+        let session = login(&server, EMAIL, PASSWORD)
+            .await
+            .expect("User is not logged in");
+        assert_eq!(id, session.user_id);
+
+        match authenticate(&server, &session.token).await {
+            Ok(User::Authenticated(user)) => {
+                assert_eq!(id, user.id);
+                assert_eq!(EMAIL, user.email);
+                assert!(!user.email_verified);
+            }
+            _ => panic!("User is not authenticated"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registration_statuses() {
+        let server = TestServer::start().await;
+        register(&server, EMAIL, PASSWORD).await.unwrap();
+
+        let code = |result: Result<String, Status>| result.unwrap_err().code();
+
+        assert_eq!(
+            Code::AlreadyExists,
+            code(register(&server, EMAIL, PASSWORD).await)
+        );
+        assert_eq!(
+            Code::InvalidArgument,
+            code(register(&server, "kyle-reese@example.com", "short").await)
+        );
+        assert_eq!(
+            Code::InvalidArgument,
+            code(register(&server, "not an email", PASSWORD).await)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_statuses() {
+        let server = TestServer::start().await;
+        register(&server, EMAIL, PASSWORD).await.unwrap();
+
+        let wrong_token = server
+            .client
+            .clone()
+            .login(LoginRequest {
+                anonymous_token: "wrong".to_string(),
+                email: EMAIL.to_string(),
+                password: PASSWORD.to_string(),
+            })
+            .await;
+        assert_eq!(Code::Unauthenticated, wrong_token.unwrap_err().code());
+
+        for _ in 1..FREE_LOGIN_ATTEMPTS {
+            let wrong_password = login(&server, EMAIL, "Wrong-Password-1").await;
+            assert_eq!(Code::Unauthenticated, wrong_password.unwrap_err().code());
+        }
+
+        // The failure over the free attempts is throttled right away
+        let last_wrong_password = login(&server, EMAIL, "Wrong-Password-1").await;
+        assert_eq!(
+            Code::ResourceExhausted,
+            last_wrong_password.unwrap_err().code()
+        );
+
+        let throttled = login(&server, EMAIL, PASSWORD).await.unwrap_err();
+        assert_eq!(Code::ResourceExhausted, throttled.code());
+        assert!(throttled.metadata().get(RETRY_AFTER).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_user_restores_password() {
+        let server = TestServer::start().await;
+        register(&server, EMAIL, PASSWORD).await.unwrap();
+        let new_password = "Skynet-Falls-2029";
+
+        server
+            .client
+            .clone()
+            .request_password_restore(RequestPasswordRestoreRequest {
+                email: EMAIL.to_string(),
+            })
+            .await
+            .expect("Restore is not requested");
+        let token = server.sent_restore_token().expect("Token is not sent");
+
+        assert_eq!(
+            Code::InvalidArgument,
+            change_password(&server, &token, "weak")
+                .await
+                .unwrap_err()
+                .code()
+        );
+        change_password(&server, &token, new_password)
+            .await
+            .expect("Password is not changed");
+        assert_eq!(
+            Code::NotFound,
+            change_password(&server, &token, new_password)
+                .await
+                .unwrap_err()
+                .code()
+        );
+
+        assert!(login(&server, EMAIL, new_password).await.is_ok());
+    }
+}
@@ -0,0 +1,118 @@
+//! Errors of use cases as gRPC statuses
+use argentum_standard_business::data_type::email::WrongEmailError;
+use argentum_user_account_business::use_case::anonymous_registers::AnonymousRegistrationError;
+use argentum_user_account_business::use_case::restore_password::error::RestorePasswordError;
+use argentum_user_account_business::use_case::user_authenticates_with_token::AuthenticationError;
+use argentum_user_account_business::use_case::user_logins_with_password::LoginError;
+use argentum_user_account_business::use_case::user_registers_with_password::RegistrationError;
+use argentum_user_business::value_object::name::WrongNameError;
+use tonic::metadata::MetadataValue;
+use tonic::{Code, Status};
+
+/// Seconds to wait before the next login attempt
+pub const RETRY_AFTER: &str = "retry-after";
+
+pub trait IntoStatus {
+    fn into_status(self) -> Status;
+}
+
+impl IntoStatus for WrongEmailError {
+    fn into_status(self) -> Status {
+        Status::invalid_argument(self.to_string())
+    }
+}
+
+impl IntoStatus for WrongNameError {
+    fn into_status(self) -> Status {
+        Status::invalid_argument(self.to_string())
+    }
+}
+
+impl IntoStatus for AnonymousRegistrationError {
+    fn into_status(self) -> Status {
+        Status::internal(self.to_string())
+    }
+}
+
+impl IntoStatus for RegistrationError {
+    fn into_status(self) -> Status {
+        let code = match self {
+            RegistrationError::WeakPassword(_) | RegistrationError::BreachedPassword => {
+                Code::InvalidArgument
+            }
+            RegistrationError::EmailTaken => Code::AlreadyExists,
+            RegistrationError::BreachCheckError(_) => Code::Unavailable,
+            // Only the use case knows the email is taken, a failed insert is a server error
+            RegistrationError::SavingError(_)
+            | RegistrationError::EncryptionError(_)
+            | RegistrationError::TransactionError(_) => Code::Internal,
+        };
+
+        Status::new(code, self.to_string())
+    }
+}
+
+impl IntoStatus for LoginError {
+    fn into_status(self) -> Status {
+        let code = match self {
+            LoginError::WrongEmailOrPassword => Code::Unauthenticated,
+            LoginError::EmailNotVerified => Code::PermissionDenied,
+            LoginError::TooManyAttempts { retry_after } => {
+                let mut status = Status::resource_exhausted(self.to_string());
+                status
+                    .metadata_mut()
+                    .insert(RETRY_AFTER, MetadataValue::from(retry_after));
+
+                return status;
+            }
+            LoginError::CredentialBackendError(_) => Code::Unavailable,
+            LoginError::SaveSession
+            | LoginError::DeleteAnonymousSession(_)
+            | LoginError::BindingError(_)
+            | LoginError::GetUserError(_)
+            | LoginError::MalformedCredential(_)
+            | LoginError::ThrottlingError(_)
+            | LoginError::TransactionError(_) => Code::Internal,
+        };
+
+        Status::new(code, self.to_string())
+    }
+}
+
+impl IntoStatus for AuthenticationError {
+    fn into_status(self) -> Status {
+        let code = match self {
+            AuthenticationError::UserNotFound
+            | AuthenticationError::WrongToken
+            | AuthenticationError::SessionExpired
+            | AuthenticationError::SessionRevoked
+            | AuthenticationError::InvalidSignedToken(_) => Code::Unauthenticated,
+            AuthenticationError::SessionRepositoryError(_)
+            | AuthenticationError::UserRepositoryError(_) => Code::Internal,
+        };
+
+        Status::new(code, self.to_string())
+    }
+}
+
+impl IntoStatus for RestorePasswordError {
+    fn into_status(self) -> Status {
+        let code = match self {
+            RestorePasswordError::WeakPassword(_) | RestorePasswordError::BreachedPassword => {
+                Code::InvalidArgument
+            }
+            RestorePasswordError::UserNotFoundError | RestorePasswordError::TokenNotFoundError => {
+                Code::NotFound
+            }
+            RestorePasswordError::TokenExpired => Code::FailedPrecondition,
+            RestorePasswordError::BreachCheckError(_) => Code::Unavailable,
+            RestorePasswordError::GetUserError(_)
+            | RestorePasswordError::TokenRepositoryError(_)
+            | RestorePasswordError::SessionStorageError(_)
+            | RestorePasswordError::PasswordEncryptionError(_)
+            | RestorePasswordError::TransactionError(_) => Code::Internal,
+        };
+
+        Status::new(code, self.to_string())
+    }
+}
@@ -0,0 +1,187 @@
+use crate::proto::user_account_service_client::UserAccountServiceClient;
+use crate::proto::user_account_service_server::UserAccountServiceServer;
+use crate::server::UserAccountGrpcService;
+
+use argentum_encryption_business::mock::password::{EncryptorMock, ValidatorMock};
+use argentum_event_business::mock::publisher::EventPublisherMock;
+use argentum_log_business::{DefaultLogger, Level, StdoutWriter};
+use argentum_notification_business::mock::notifier::NotifierMock;
+use argentum_notification_business::notification::Notification;
+use argentum_notification_business::queue::QueuedNotifier;
+use argentum_standard_business::asynchronous::Blocking;
+use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+use argentum_standard_business::mock::transaction::TransactionManagerMock;
+use argentum_user_account_business::mock::repository::login_attempt_repository_mock::LoginAttemptRepositoryMock;
+use argentum_user_account_business::mock::repository::password_credential_repository_mock::PasswordCredentialRepositoryMock;
+use argentum_user_account_business::mock::repository::restore_password_token_repository_mock::RestorePasswordTokenRepositoryMock;
+use argentum_user_account_business::mock::repository::session_repository_mock::SessionRepositoryMock;
+use argentum_user_account_business::mock::token::{TokenGeneratorMock, TokenHasherMock};
+use argentum_user_account_business::repository::login_throttler::{
+    AsyncLoginThrottler, ThrottlingPolicy,
+};
+use argentum_user_account_business::repository::password_credential_checker::AsyncPasswordCredentialChecker;
+use argentum_user_account_business::repository::password_credential_writer::AsyncPasswordCredentialWriter;
+use argentum_user_account_business::use_case::anonymous_registers::AsyncAnonymousRegistersUc;
+use argentum_user_account_business::use_case::restore_password::anonymous_requests_restore_token::AsyncAnonymousRequestsRestoreToken;
+use argentum_user_account_business::use_case::restore_password::anonymous_with_token_changes_password::AsyncAnonymousWithTokenChangesPassword;
+use argentum_user_account_business::use_case::user_authenticates_with_token::AsyncUserAuthenticatesWithTokenUc;
+use argentum_user_account_business::use_case::user_logins_with_password::AsyncUserLoginsWithPasswordUc;
+use argentum_user_account_business::use_case::user_registers_with_password::AsyncUserRegistersWithPasswordUc;
+use argentum_user_account_business::value_object::password::PasswordPolicy;
+use argentum_user_business::mock::repository::anonymous_binding_repository_mock::AnonymousBindingRepositoryMock;
+use argentum_user_business::mock::repository::anonymous_user_repository_mock::AnonymousUserRepositoryMock;
+use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
+use std::sync::Arc;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server};
+
+/// Failed logins of an account after which it has to wait
+pub const FREE_LOGIN_ATTEMPTS: u32 = 2;
+
+/// The service over in-memory repositories, served on a random loopback port
+pub struct TestServer {
+    notifier: Arc<QueuedNotifier>,
+    mailbox: NotifierMock,
+    pub client: UserAccountServiceClient<Channel>,
+}
+
+impl TestServer {
+    pub async fn start() -> TestServer {
+        let id_factory = Arc::new(IdFactoryMock::new());
+        let anonymous_user_repository = Arc::new(Blocking::new(AnonymousUserRepositoryMock::new()));
+        let user_repository = Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let credential_repository =
+            Arc::new(Blocking::new(PasswordCredentialRepositoryMock::new()));
+        let restore_token_repository =
+            Arc::new(Blocking::new(RestorePasswordTokenRepositoryMock::new()));
+        let token_generator = Arc::new(TokenGeneratorMock::new());
+        let token_hasher = Arc::new(TokenHasherMock::new());
+        let encryptor = Arc::new(Blocking::new(EncryptorMock::new()));
+        let password_policy = Arc::new(PasswordPolicy::default());
+        let notifier = Arc::new(QueuedNotifier::new());
+        let event_publisher = Arc::new(EventPublisherMock::new());
+        let transaction_manager = Arc::new(Blocking::new(TransactionManagerMock::new()));
+        let credential_writer = Arc::new(AsyncPasswordCredentialWriter::new(
+            credential_repository.clone(),
+        ));
+
+        let service = UserAccountGrpcService::new(
+            id_factory.clone(),
+            AsyncAnonymousRegistersUc::new(
+                id_factory.clone(),
+                anonymous_user_repository.clone(),
+                session_repository.clone(),
+                token_generator.clone(),
+                token_hasher.clone(),
+                3600,
+                event_publisher.clone(),
+                transaction_manager.clone(),
+            ),
+            AsyncUserRegistersWithPasswordUc::new(
+                user_repository.clone(),
+                credential_writer.clone(),
+                encryptor.clone(),
+                password_policy.clone(),
+                None,
+                event_publisher.clone(),
+                transaction_manager.clone(),
+            ),
+            AsyncUserLoginsWithPasswordUc::new(
+                user_repository.clone(),
+                Arc::new(Blocking::new(AnonymousBindingRepositoryMock::new())),
+                session_repository.clone(),
+                Arc::new(AsyncPasswordCredentialChecker::new(
+                    credential_repository,
+                    Arc::new(Blocking::new(ValidatorMock::new())),
+                    &EncryptorMock::new(),
+                )),
+                Arc::new(AsyncLoginThrottler::new(
+                    Arc::new(Blocking::new(LoginAttemptRepositoryMock::new())),
+                    ThrottlingPolicy::new(FREE_LOGIN_ATTEMPTS, 60, 600, 10, 900),
+                    ThrottlingPolicy::for_client(),
+                )),
+                credential_writer.clone(),
+                encryptor.clone(),
+                id_factory.clone(),
+                token_generator.clone(),
+                token_hasher.clone(),
+                Arc::new(DefaultLogger::new(Level::Warning, &StdoutWriter {})),
+                3600,
+                false,
+                event_publisher.clone(),
+                transaction_manager.clone(),
+            ),
+            AsyncUserAuthenticatesWithTokenUc::new(
+                user_repository.clone(),
+                anonymous_user_repository,
+                session_repository.clone(),
+                token_hasher.clone(),
+                3600,
+            ),
+            AsyncAnonymousRequestsRestoreToken::new(
+                id_factory,
+                user_repository.clone(),
+                restore_token_repository.clone(),
+                token_generator,
+                token_hasher.clone(),
+                notifier.clone(),
+                event_publisher.clone(),
+                Arc::new(DefaultLogger::new(Level::Warning, &StdoutWriter {})),
+                transaction_manager.clone(),
+            ),
+            AsyncAnonymousWithTokenChangesPassword::new(
+                user_repository,
+                restore_token_repository,
+                session_repository,
+                token_hasher,
+                encryptor,
+                credential_writer,
+                password_policy,
+                None,
+                3600,
+                notifier.clone(),
+                event_publisher,
+                Arc::new(DefaultLogger::new(Level::Warning, &StdoutWriter {})),
+                transaction_manager,
+            ),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Can't bind a port");
+        let address = listener.local_addr().expect("Can't get an address");
+
+        tokio::spawn(
+            Server::builder()
+                .add_service(UserAccountServiceServer::new(service))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        let client = UserAccountServiceClient::connect(format!("http://{}", address))
+            .await
+            .expect("Can't connect to the server");
+
+        TestServer {
+            notifier,
+            mailbox: NotifierMock::new(),
+            client,
+        }
+    }
+
+    /// The last restore password token sent to users, delivers the queued ones first
+    pub fn sent_restore_token(&self) -> Option<String> {
+        self.notifier
+            .deliver(&self.mailbox)
+            .expect("Can't deliver notifications");
+
+        self.mailbox
+            .sent()
+            .into_iter()
+            .rev()
+            .find_map(|(_, notification)| match notification {
+                Notification::RestorePassword { token } => Some(token),
+                _ => None,
+            })
+    }
+}
@@ -0,0 +1,343 @@
+use argentum_standard_business::data_type::id::{Id, IdFactory};
+use argentum_user_business::token::{
+    AccessTokenClaims, GeneratorTrait, SignedTokenVerifierTrait, TokenVerificationError,
+};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::Ed25519KeyPair;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sid: Option<String>,
+    iss: String,
+    aud: String,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum JwtKeyError {
+    #[error("Invalid Ed25519 key")]
+    InvalidEd25519Key,
+}
+
+/// Issues JWT access tokens signed with HS256 or Ed25519 (EdDSA).
+/// Tokens carry the user id as `sub` and, when bound to a session, the session id as `sid`.
+pub struct JwtTokenGenerator {
+    key: EncodingKey,
+    algorithm: Algorithm,
+    issuer: String,
+    audience: String,
+    ttl: u32, //token lifetime in seconds
+}
+
+impl JwtTokenGenerator {
+    pub fn hs256(secret: &[u8], issuer: &str, audience: &str, ttl: u32) -> JwtTokenGenerator {
+        JwtTokenGenerator {
+            key: EncodingKey::from_secret(secret),
+            algorithm: Algorithm::HS256,
+            issuer: issuer.to_string(),
+            audience: audience.to_string(),
+            ttl,
+        }
+    }
+
+    /// `private_key` is a PKCS#8 document in DER
+    pub fn ed25519(
+        private_key: &[u8],
+        issuer: &str,
+        audience: &str,
+        ttl: u32,
+    ) -> Result<JwtTokenGenerator, JwtKeyError> {
+        if Ed25519KeyPair::from_pkcs8_maybe_unchecked(private_key).is_err() {
+            return Err(JwtKeyError::InvalidEd25519Key);
+        }
+
+        Ok(JwtTokenGenerator {
+            key: EncodingKey::from_ed_der(private_key),
+            algorithm: Algorithm::EdDSA,
+            issuer: issuer.to_string(),
+            audience: audience.to_string(),
+            ttl,
+        })
+    }
+
+    fn sign(&self, user_id: &Id, session_id: Option<&Id>, expires_at: DateTime<Utc>) -> String {
+        let claims = Claims {
+            sub: user_id.to_string(),
+            sid: session_id.map(|id| id.to_string()),
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            iat: Utc::now().timestamp(),
+            exp: expires_at.timestamp(),
+        };
+
+        // Keys are checked on construction, so signing can't fail
+        jsonwebtoken::encode(&Header::new(self.algorithm), &claims, &self.key)
+            .expect("Can't sign an access token")
+    }
+
+    fn expires_at(&self) -> DateTime<Utc> {
+        Utc::now() + Duration::seconds(self.ttl as i64)
+    }
+}
+
+impl GeneratorTrait for JwtTokenGenerator {
+    fn generate(&self, user_id: &Id) -> String {
+        self.sign(user_id, None, self.expires_at())
+    }
+
+    fn generate_for_session(&self, user_id: &Id, session_id: &Id) -> String {
+        self.sign(user_id, Some(session_id), self.expires_at())
+    }
+}
+
+/// Verifies tokens issued by `JwtTokenGenerator` without any storage lookups
+pub struct JwtTokenVerifier<'s> {
+    key: DecodingKey,
+    validation: Validation,
+    id_factory: &'s dyn IdFactory,
+}
+
+impl<'s> JwtTokenVerifier<'s> {
+    pub fn hs256(
+        secret: &[u8],
+        issuer: &str,
+        audience: &str,
+        id_factory: &'s dyn IdFactory,
+    ) -> JwtTokenVerifier<'s> {
+        JwtTokenVerifier::with_key(
+            DecodingKey::from_secret(secret),
+            Algorithm::HS256,
+            issuer,
+            audience,
+            id_factory,
+        )
+    }
+
+    /// `public_key` is the raw 32 bytes Ed25519 public key
+    pub fn ed25519(
+        public_key: &[u8],
+        issuer: &str,
+        audience: &str,
+        id_factory: &'s dyn IdFactory,
+    ) -> JwtTokenVerifier<'s> {
+        JwtTokenVerifier::with_key(
+            DecodingKey::from_ed_der(public_key),
+            Algorithm::EdDSA,
+            issuer,
+            audience,
+            id_factory,
+        )
+    }
+
+    fn with_key(
+        key: DecodingKey,
+        algorithm: Algorithm,
+        issuer: &str,
+        audience: &str,
+        id_factory: &'s dyn IdFactory,
+    ) -> JwtTokenVerifier<'s> {
+        let mut validation = Validation::new(algorithm);
+        validation.set_issuer(&[issuer]);
+        validation.set_audience(&[audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        JwtTokenVerifier {
+            key,
+            validation,
+            id_factory,
+        }
+    }
+
+    fn timestamp(&self, value: i64) -> Result<DateTime<Utc>, TokenVerificationError> {
+        match Utc.timestamp_opt(value, 0).single() {
+            Some(time) => Ok(time),
+            None => Err(TokenVerificationError::InvalidClaims(format!(
+                "Wrong timestamp {}",
+                value
+            ))),
+        }
+    }
+
+    fn id(&self, value: &str) -> Result<Id, TokenVerificationError> {
+        self.id_factory
+            .parse(value)
+            .map_err(|e| TokenVerificationError::InvalidClaims(e.to_string()))
+    }
+}
+
+impl SignedTokenVerifierTrait for JwtTokenVerifier<'_> {
+    fn verify(&self, token: &str) -> Result<AccessTokenClaims, TokenVerificationError> {
+        let data = match jsonwebtoken::decode::<Claims>(token, &self.key, &self.validation) {
+            Ok(data) => data,
+            Err(e) => {
+                return Err(match e.kind() {
+                    ErrorKind::ExpiredSignature => TokenVerificationError::Expired,
+                    ErrorKind::InvalidSignature | ErrorKind::InvalidAlgorithm => {
+                        TokenVerificationError::InvalidSignature
+                    }
+                    ErrorKind::InvalidIssuer
+                    | ErrorKind::InvalidAudience
+                    | ErrorKind::InvalidSubject
+                    | ErrorKind::ImmatureSignature
+                    | ErrorKind::MissingRequiredClaim(_) => {
+                        TokenVerificationError::InvalidClaims(e.to_string())
+                    }
+                    _ => TokenVerificationError::Malformed,
+                })
+            }
+        };
+        let claims = data.claims;
+
+        Ok(AccessTokenClaims {
+            subject: self.id(&claims.sub)?,
+            session_id: match claims.sid {
+                Some(sid) => Some(self.id(&sid)?),
+                None => None,
+            },
+            issuer: claims.iss,
+            audience: claims.aud,
+            issued_at: self.timestamp(claims.iat)?,
+            expires_at: self.timestamp(claims.exp)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jwt::{JwtTokenGenerator, JwtTokenVerifier};
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_user_business::token::{
+        GeneratorTrait, SignedTokenVerifierTrait, TokenVerificationError,
+    };
+    use chrono::{Duration, Utc};
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    const SECRET: &[u8] = b"a very secret key for the tests";
+    const ISSUER: &str = "argentum";
+    const AUDIENCE: &str = "api-gateway";
+
+    fn ed25519_keys() -> (Vec<u8>, Vec<u8>) {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+
+        (
+            pkcs8.as_ref().to_vec(),
+            key_pair.public_key().as_ref().to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_hs256_token() {
+        let id_factory = IdFactoryMock::new();
+        let user_id: Id = id_factory.create();
+        let session_id: Id = id_factory.create();
+
+        let generator = JwtTokenGenerator::hs256(SECRET, ISSUER, AUDIENCE, 300);
+        let verifier = JwtTokenVerifier::hs256(SECRET, ISSUER, AUDIENCE, &id_factory);
+
+        let token = generator.generate_for_session(&user_id, &session_id);
+        let claims = verifier.verify(&token).expect("Can't verify a token");
+
+        assert!(claims.subject == user_id);
+        assert!(claims.session_id.unwrap() == session_id);
+        assert_eq!(ISSUER, claims.issuer);
+        assert_eq!(AUDIENCE, claims.audience);
+        assert!(claims.expires_at > Utc::now() + Duration::seconds(290));
+    }
+
+    #[test]
+    fn test_ed25519_token() {
+        let id_factory = IdFactoryMock::new();
+        let user_id: Id = id_factory.create();
+        let (private_key, public_key) = ed25519_keys();
+
+        let generator = JwtTokenGenerator::ed25519(&private_key, ISSUER, AUDIENCE, 300).unwrap();
+        let verifier = JwtTokenVerifier::ed25519(&public_key, ISSUER, AUDIENCE, &id_factory);
+
+        let token = generator.generate(&user_id);
+        let claims = verifier.verify(&token).expect("Can't verify a token");
+
+        assert!(claims.subject == user_id);
+        assert!(claims.session_id.is_none());
+    }
+
+    #[test]
+    fn test_invalid_ed25519_key() {
+        assert!(JwtTokenGenerator::ed25519(b"not a key", ISSUER, AUDIENCE, 300).is_err());
+    }
+
+    #[test]
+    fn test_token_signed_with_another_key() {
+        let id_factory = IdFactoryMock::new();
+        let (_, public_key) = ed25519_keys();
+        let (another_private_key, _) = ed25519_keys();
+
+        let token = JwtTokenGenerator::ed25519(&another_private_key, ISSUER, AUDIENCE, 300)
+            .unwrap()
+            .generate(&id_factory.create());
+
+        let verifier = JwtTokenVerifier::ed25519(&public_key, ISSUER, AUDIENCE, &id_factory);
+        assert!(matches!(
+            verifier.verify(&token),
+            Err(TokenVerificationError::InvalidSignature)
+        ));
+
+        let token =
+            JwtTokenGenerator::hs256(SECRET, ISSUER, AUDIENCE, 300).generate(&id_factory.create());
+        assert!(matches!(
+            verifier.verify(&token),
+            Err(TokenVerificationError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_token_for_another_audience() {
+        let id_factory = IdFactoryMock::new();
+        let token = JwtTokenGenerator::hs256(SECRET, ISSUER, "another-service", 300)
+            .generate(&id_factory.create());
+
+        let verifier = JwtTokenVerifier::hs256(SECRET, ISSUER, AUDIENCE, &id_factory);
+
+        assert!(matches!(
+            verifier.verify(&token),
+            Err(TokenVerificationError::InvalidClaims(_))
+        ));
+    }
+
+    #[test]
+    fn test_expired_token() {
+        let id_factory = IdFactoryMock::new();
+        let generator = JwtTokenGenerator::hs256(SECRET, ISSUER, AUDIENCE, 300);
+        let token = generator.sign(
+            &id_factory.create(),
+            None,
+            Utc::now() - Duration::minutes(5),
+        );
+
+        let verifier = JwtTokenVerifier::hs256(SECRET, ISSUER, AUDIENCE, &id_factory);
+
+        assert!(matches!(
+            verifier.verify(&token),
+            Err(TokenVerificationError::Expired)
+        ));
+    }
+
+    #[test]
+    fn test_malformed_token() {
+        let id_factory = IdFactoryMock::new();
+        let verifier = JwtTokenVerifier::hs256(SECRET, ISSUER, AUDIENCE, &id_factory);
+
+        assert!(matches!(
+            verifier.verify("not.a.token"),
+            Err(TokenVerificationError::Malformed)
+        ));
+    }
+}
@@ -1 +1,3 @@
+pub mod jwt;
+pub mod outbox;
 pub mod token;
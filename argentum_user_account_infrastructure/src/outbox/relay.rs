@@ -0,0 +1,290 @@
+use argentum_log_business::LoggerTrait;
+use argentum_standard_business::data_type::id::Id;
+use argentum_user_account_business::entity::outbox_message::OutboxMessage;
+use argentum_user_account_business::repository::outbox_repository::{
+    OutboxRepositoryError, OutboxRepositoryTrait,
+};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::thread;
+use std::time::Duration;
+
+/// Where the relay delivers outbox messages: a broker, a webhook, another process.
+/// A message is sent again if the relay stops before marking it published
+pub trait OutboxSinkTrait {
+    fn send(&self, message: &OutboxMessage) -> Result<(), OutboxSinkError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Can't send outbox message: {0}")]
+pub struct OutboxSinkError(pub String);
+
+#[derive(thiserror::Error, Debug)]
+pub enum RelayError {
+    #[error(transparent)]
+    Outbox(#[from] OutboxRepositoryError),
+
+    #[error(transparent)]
+    Sink(#[from] OutboxSinkError),
+}
+
+/// Drains the outbox to a sink in order of saving. Delivery is at-least-once:
+/// a message is marked published only after the sink accepted it
+pub struct OutboxRelay<'s> {
+    outbox_repository: &'s dyn OutboxRepositoryTrait,
+    sink: &'s dyn OutboxSinkTrait,
+    logger: &'s dyn LoggerTrait,
+    batch_size: usize,
+}
+
+impl<'s> OutboxRelay<'s> {
+    pub fn new(
+        outbox_repository: &'s dyn OutboxRepositoryTrait,
+        sink: &'s dyn OutboxSinkTrait,
+        logger: &'s dyn LoggerTrait,
+        batch_size: usize,
+    ) -> OutboxRelay<'s> {
+        OutboxRelay {
+            outbox_repository,
+            sink,
+            logger,
+            batch_size,
+        }
+    }
+
+    /// Returns the number of delivered messages. Stops at the first failed one,
+    /// so the order of messages is kept
+    pub fn relay_batch(&self) -> Result<usize, RelayError> {
+        let messages = self.outbox_repository.find_unpublished(self.batch_size)?;
+
+        for message in messages.iter() {
+            self.sink.send(message)?;
+            self.outbox_repository.mark_published(&message.id)?;
+        }
+
+        Ok(messages.len())
+    }
+
+    pub fn drain(&self) -> Result<usize, RelayError> {
+        let mut relayed = 0;
+
+        loop {
+            let count = self.relay_batch()?;
+            relayed += count;
+
+            if count < self.batch_size {
+                return Ok(relayed);
+            }
+        }
+    }
+
+    /// Worker loop, drains the outbox every `poll_interval` until `should_stop` returns true.
+    /// Failures are logged and retried on the next round
+    pub fn run(&self, poll_interval: Duration, should_stop: &dyn Fn() -> bool) {
+        while !should_stop() {
+            if let Err(e) = self.drain() {
+                self.logger.warning(format!("Outbox relay failed: {}", e));
+            }
+
+            thread::sleep(poll_interval);
+        }
+    }
+}
+
+/// Drops messages already sent through it, for sinks which can't deduplicate by themselves.
+/// Ids are kept in memory, so it only covers redelivery within one process
+pub struct DedupingSink<'s> {
+    sink: &'s dyn OutboxSinkTrait,
+    delivered: RefCell<HashSet<Id>>,
+}
+
+impl<'s> DedupingSink<'s> {
+    pub fn new(sink: &'s dyn OutboxSinkTrait) -> DedupingSink<'s> {
+        DedupingSink {
+            sink,
+            delivered: RefCell::new(HashSet::new()),
+        }
+    }
+}
+
+impl OutboxSinkTrait for DedupingSink<'_> {
+    fn send(&self, message: &OutboxMessage) -> Result<(), OutboxSinkError> {
+        if self.delivered.borrow().contains(&message.id) {
+            return Ok(());
+        }
+
+        self.sink.send(message)?;
+        self.delivered.borrow_mut().insert(message.id.clone());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::outbox::relay::{
+        DedupingSink, OutboxRelay, OutboxSinkError, OutboxSinkTrait, RelayError,
+    };
+    use argentum_log_business::{DefaultLogger, Level, StdoutWriter};
+    use argentum_standard_business::data_type::id::{Id, IdFactory};
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_standard_business::transaction::{Bound, Transaction};
+    use argentum_user_account_business::entity::outbox_message::OutboxMessage;
+    use argentum_user_account_business::event::{PasswordChanged, PasswordRestoreRequested};
+    use argentum_user_account_business::mock::repository::outbox_repository_mock::OutboxRepositoryMock;
+    use argentum_user_account_business::repository::outbox_repository::{
+        OutboxRepositoryError, OutboxRepositoryTrait,
+    };
+    use std::cell::{Cell, RefCell};
+
+    struct RecordingSink {
+        sent: RefCell<Vec<String>>,
+        broken: Cell<bool>,
+    }
+
+    impl RecordingSink {
+        fn new() -> RecordingSink {
+            RecordingSink {
+                sent: RefCell::new(Vec::new()),
+                broken: Cell::new(false),
+            }
+        }
+    }
+
+    impl OutboxSinkTrait for RecordingSink {
+        fn send(&self, message: &OutboxMessage) -> Result<(), OutboxSinkError> {
+            if self.broken.get() {
+                return Err(OutboxSinkError("Broker is down".to_string()));
+            }
+
+            self.sent.borrow_mut().push(message.event_name.clone());
+
+            Ok(())
+        }
+    }
+
+    /// Loses the first "published" mark, like a relay that crashed right after sending
+    struct OutboxRepositoryWithLostMark {
+        outbox: OutboxRepositoryMock,
+        lost: Cell<bool>,
+    }
+
+    impl OutboxRepositoryTrait for OutboxRepositoryWithLostMark {
+        fn save(&self, message: &OutboxMessage) -> Result<(), OutboxRepositoryError> {
+            self.outbox.save(message)
+        }
+
+        fn find_unpublished(
+            &self,
+            limit: usize,
+        ) -> Result<Vec<OutboxMessage>, OutboxRepositoryError> {
+            self.outbox.find_unpublished(limit)
+        }
+
+        fn mark_published(&self, id: &Id) -> Result<(), OutboxRepositoryError> {
+            if !self.lost.replace(true) {
+                return Err(OutboxRepositoryError::Update);
+            }
+
+            self.outbox.mark_published(id)
+        }
+
+        fn within(&self, _: &Transaction) -> Bound<'_, dyn OutboxRepositoryTrait + '_> {
+            Bound::Shared(self)
+        }
+    }
+
+    fn save_events(outbox: &dyn OutboxRepositoryTrait) {
+        let id_factory = IdFactoryMock::new();
+
+        outbox
+            .save(&OutboxMessage::new(
+                id_factory.create(),
+                &PasswordRestoreRequested::new(id_factory.create()),
+            ))
+            .unwrap();
+        outbox
+            .save(&OutboxMessage::new(
+                id_factory.create(),
+                &PasswordChanged::new(id_factory.create()),
+            ))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_relay_drains_outbox_in_order() -> Result<(), &'static str> {
+        let outbox = OutboxRepositoryMock::new();
+        let sink = RecordingSink::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+        let relay = OutboxRelay::new(&outbox, &sink, &logger, 1);
+
+        save_events(&outbox);
+
+        let relayed = relay.drain().map_err(|_| "Can't drain outbox")?;
+
+        assert_eq!(2, relayed);
+        assert_eq!(
+            vec![
+                "user_account.password_restore_requested",
+                "user_account.password_changed"
+            ],
+            *sink.sent.borrow()
+        );
+        assert!(outbox.find_unpublished(10).unwrap().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_relay_keeps_messages_when_sink_fails() -> Result<(), &'static str> {
+        let outbox = OutboxRepositoryMock::new();
+        let sink = RecordingSink::new();
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+        let relay = OutboxRelay::new(&outbox, &sink, &logger, 10);
+
+        save_events(&outbox);
+        sink.broken.set(true);
+
+        match relay.drain() {
+            Err(RelayError::Sink(_)) => {}
+            _ => return Err("Sink error is expected"),
+        }
+
+        assert_eq!(2, outbox.find_unpublished(10).unwrap().len());
+
+        sink.broken.set(false);
+
+        assert_eq!(2, relay.drain().map_err(|_| "Can't drain outbox")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deduping_sink_drops_redelivered_message() -> Result<(), &'static str> {
+        let outbox = OutboxRepositoryWithLostMark {
+            outbox: OutboxRepositoryMock::new(),
+            lost: Cell::new(false),
+        };
+        let sink = RecordingSink::new();
+        let deduping_sink = DedupingSink::new(&sink);
+        let log_writer = StdoutWriter::new();
+        let logger = DefaultLogger::new(Level::Trace, &log_writer);
+        let relay = OutboxRelay::new(&outbox, &deduping_sink, &logger, 10);
+
+        save_events(&outbox);
+
+        if relay.drain().is_ok() {
+            return Err("The lost mark should fail the first drain");
+        }
+
+        relay.drain().map_err(|_| "Can't drain outbox")?;
+
+        // The first message is relayed twice, but reaches the sink once
+        assert_eq!(2, sink.sent.borrow().len());
+        assert!(outbox.find_unpublished(10).unwrap().is_empty());
+
+        Ok(())
+    }
+}
@@ -1,9 +1,17 @@
 use argentum_standard_business::data_type::id::Id;
-use argentum_user_business::token::GeneratorTrait;
+use argentum_user_business::token::{GeneratorTrait, TokenHasherTrait};
+use data_encoding::{Encoding, Specification, BASE64URL_NOPAD, HEXLOWER};
 use rand::Rng;
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
 
+const MIN_ENTROPY_BITS: usize = 128;
+const CROCKFORD_BASE32_SYMBOLS: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+#[deprecated(note = "token length and entropy vary, use SecureTokenGenerator")]
 pub struct StringTokenGenerator {}
 
+#[allow(deprecated)]
 impl StringTokenGenerator {
     #[allow(clippy::new_without_default)]
     pub fn new() -> StringTokenGenerator {
@@ -11,6 +19,7 @@ impl StringTokenGenerator {
     }
 }
 
+#[allow(deprecated)]
 impl GeneratorTrait for StringTokenGenerator {
     fn generate(&self, _user_id: &Id) -> String {
         const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
@@ -29,3 +38,255 @@ impl GeneratorTrait for StringTokenGenerator {
         token
     }
 }
+
+pub enum TokenEncoding {
+    Base64Url,
+    Hex,
+    CrockfordBase32,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TokenGeneratorError {
+    #[error("Token entropy must be at least {MIN_ENTROPY_BITS} bits, {0} given")]
+    WeakEntropy(usize),
+
+    #[error("Token entropy must be a multiple of 8 bits, {0} given")]
+    NotByteAligned(usize),
+}
+
+/// Generates tokens from a fixed amount of OS CSPRNG output.
+/// A token looks like `{prefix}{random part}{checksum}`: the prefix (e.g. `arg_live_`)
+/// and the CRC32 checksum of the random part let secret scanners spot leaked tokens
+/// and reject mistyped ones without a storage lookup.
+pub struct SecureTokenGenerator {
+    entropy_bytes: usize,
+    encoding: Encoding,
+    prefix: String,
+    checksum: bool,
+    rng: SystemRandom,
+}
+
+impl SecureTokenGenerator {
+    pub fn new(
+        entropy_bits: usize,
+        encoding: TokenEncoding,
+    ) -> Result<SecureTokenGenerator, TokenGeneratorError> {
+        if entropy_bits < MIN_ENTROPY_BITS {
+            return Err(TokenGeneratorError::WeakEntropy(entropy_bits));
+        }
+
+        // `usize::is_multiple_of` needs Rust 1.87
+        #[allow(clippy::manual_is_multiple_of)]
+        if entropy_bits % 8 != 0 {
+            return Err(TokenGeneratorError::NotByteAligned(entropy_bits));
+        }
+
+        let encoding = match encoding {
+            TokenEncoding::Base64Url => BASE64URL_NOPAD,
+            TokenEncoding::Hex => HEXLOWER,
+            TokenEncoding::CrockfordBase32 => {
+                let mut spec = Specification::new();
+                spec.symbols.push_str(CROCKFORD_BASE32_SYMBOLS);
+                spec.encoding().unwrap()
+            }
+        };
+
+        Ok(SecureTokenGenerator {
+            entropy_bytes: entropy_bits / 8,
+            encoding,
+            prefix: String::new(),
+            checksum: false,
+            rng: SystemRandom::new(),
+        })
+    }
+
+    pub fn with_prefix(mut self, prefix: &str) -> SecureTokenGenerator {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    pub fn with_checksum(mut self) -> SecureTokenGenerator {
+        self.checksum = true;
+        self
+    }
+
+    /// Checks the prefix and the checksum of a token, the token itself can still be unknown
+    pub fn is_well_formed(&self, token: &str) -> bool {
+        let body = match token.strip_prefix(self.prefix.as_str()) {
+            Some(body) => body,
+            None => return false,
+        };
+
+        let payload_len = self.encoding.encode_len(self.entropy_bytes);
+        let checksum_len = match self.checksum {
+            true => self.encoding.encode_len(4),
+            false => 0,
+        };
+
+        if body.len() != payload_len + checksum_len || !body.is_char_boundary(payload_len) {
+            return false;
+        }
+
+        let (payload, checksum) = body.split_at(payload_len);
+        if self.encoding.decode(payload.as_bytes()).is_err() {
+            return false;
+        }
+
+        !self.checksum || checksum == self.checksum_of(payload)
+    }
+
+    fn checksum_of(&self, payload: &str) -> String {
+        self.encoding
+            .encode(&crc32(payload.as_bytes()).to_be_bytes())
+    }
+}
+
+impl GeneratorTrait for SecureTokenGenerator {
+    fn generate(&self, _user_id: &Id) -> String {
+        let mut bytes = vec![0u8; self.entropy_bytes];
+        // There is no safe fallback when the OS CSPRNG is unavailable
+        self.rng
+            .fill(&mut bytes)
+            .expect("Can't get random bytes from the OS");
+
+        let payload = self.encoding.encode(&bytes);
+        let checksum = match self.checksum {
+            true => self.checksum_of(&payload),
+            false => String::new(),
+        };
+
+        format!("{}{}{}", self.prefix, payload, checksum)
+    }
+}
+
+/// CRC-32 (IEEE 802.3), the same checksum GitHub tokens use
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+/// HMAC-SHA256 of a token with a server-side secret key, hex encoded.
+/// Without the key stored hashes can't be matched against guessed tokens.
+pub struct HmacTokenHasher {
+    key: hmac::Key,
+}
+
+impl HmacTokenHasher {
+    pub fn new(secret: &[u8]) -> HmacTokenHasher {
+        HmacTokenHasher {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret),
+        }
+    }
+}
+
+impl TokenHasherTrait for HmacTokenHasher {
+    fn hash(&self, token: &str) -> String {
+        HEXLOWER.encode(hmac::sign(&self.key, token.as_bytes()).as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::token::{crc32, HmacTokenHasher, SecureTokenGenerator, TokenEncoding};
+    use argentum_standard_business::data_type::id::IdFactory;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+    use argentum_user_business::token::{GeneratorTrait, TokenHasherTrait};
+
+    fn generate(generator: &SecureTokenGenerator) -> String {
+        generator.generate(&IdFactoryMock::new().create())
+    }
+
+    #[test]
+    fn test_secure_token_generator_base64url() {
+        let generator = SecureTokenGenerator::new(256, TokenEncoding::Base64Url).unwrap();
+        let token = generate(&generator);
+
+        assert_eq!(43, token.len());
+        assert!(token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_ne!(token, generate(&generator));
+    }
+
+    #[test]
+    fn test_secure_token_generator_hex() {
+        let generator = SecureTokenGenerator::new(128, TokenEncoding::Hex).unwrap();
+        let token = generate(&generator);
+
+        assert_eq!(32, token.len());
+        assert!(token
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)));
+    }
+
+    #[test]
+    fn test_secure_token_generator_crockford_base32() {
+        let generator = SecureTokenGenerator::new(160, TokenEncoding::CrockfordBase32).unwrap();
+        let token = generate(&generator);
+
+        assert_eq!(32, token.len());
+        assert!(token
+            .chars()
+            .all(|c| "0123456789ABCDEFGHJKMNPQRSTVWXYZ".contains(c)));
+    }
+
+    #[test]
+    fn test_secure_token_generator_with_prefix_and_checksum() {
+        let generator = SecureTokenGenerator::new(256, TokenEncoding::Base64Url)
+            .unwrap()
+            .with_prefix("arg_live_")
+            .with_checksum();
+        let token = generate(&generator);
+
+        assert!(token.starts_with("arg_live_"));
+        assert_eq!(9 + 43 + 6, token.len());
+        assert!(generator.is_well_formed(&token));
+
+        let mut mistyped = token.clone();
+        let last = mistyped.pop().unwrap();
+        mistyped.push(if last == 'A' { 'B' } else { 'A' });
+        assert!(!generator.is_well_formed(&mistyped));
+        assert!(!generator.is_well_formed(&token.replace("arg_live_", "arg_test_")));
+    }
+
+    #[test]
+    fn test_secure_token_generator_rejects_weak_entropy() {
+        assert!(SecureTokenGenerator::new(64, TokenEncoding::Hex).is_err());
+        assert!(SecureTokenGenerator::new(130, TokenEncoding::Hex).is_err());
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+    }
+
+    #[test]
+    fn test_hmac_token_hasher() {
+        // RFC 4231, test case 2
+        let hasher = HmacTokenHasher::new(b"Jefe");
+
+        assert_eq!(
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            hasher.hash("what do ya want for nothing?")
+        );
+    }
+
+    #[test]
+    fn test_hmac_token_hasher_depends_on_key() {
+        let token = "some-token";
+
+        assert_ne!(
+            HmacTokenHasher::new(b"first key").hash(token),
+            HmacTokenHasher::new(b"second key").hash(token)
+        );
+    }
+}
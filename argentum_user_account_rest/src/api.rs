@@ -0,0 +1,73 @@
+use crate::handler;
+
+use argentum_standard_business::data_type::id::IdFactory;
+use argentum_user_account_business::use_case::anonymous_registers::AsyncAnonymousRegistersUc;
+use argentum_user_account_business::use_case::restore_password::anonymous_requests_restore_token::AsyncAnonymousRequestsRestoreToken;
+use argentum_user_account_business::use_case::restore_password::anonymous_with_token_changes_password::AsyncAnonymousWithTokenChangesPassword;
+use argentum_user_account_business::use_case::user_authenticates_with_token::AsyncUserAuthenticatesWithTokenUc;
+use argentum_user_account_business::use_case::user_logins_with_password::AsyncUserLoginsWithPasswordUc;
+use argentum_user_account_business::use_case::user_logs_out::AsyncUserLogsOutUc;
+use argentum_user_account_business::use_case::user_registers_with_password::AsyncUserRegistersWithPasswordUc;
+use axum::routing::{get, post};
+use axum::Router;
+use std::sync::Arc;
+
+/// Use cases served by the API, one instance is shared by all requests
+pub struct UserAccountApi {
+    pub(crate) id_factory: Arc<dyn IdFactory + Send + Sync>,
+    pub(crate) anonymous_registers: AsyncAnonymousRegistersUc,
+    pub(crate) user_registers_with_password: AsyncUserRegistersWithPasswordUc,
+    pub(crate) user_logins_with_password: AsyncUserLoginsWithPasswordUc,
+    pub(crate) user_authenticates_with_token: AsyncUserAuthenticatesWithTokenUc,
+    pub(crate) user_logs_out: AsyncUserLogsOutUc,
+    pub(crate) anonymous_requests_restore_token: AsyncAnonymousRequestsRestoreToken,
+    pub(crate) anonymous_with_token_changes_password: AsyncAnonymousWithTokenChangesPassword,
+}
+
+impl UserAccountApi {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id_factory: Arc<dyn IdFactory + Send + Sync>,
+        anonymous_registers: AsyncAnonymousRegistersUc,
+        user_registers_with_password: AsyncUserRegistersWithPasswordUc,
+        user_logins_with_password: AsyncUserLoginsWithPasswordUc,
+        user_authenticates_with_token: AsyncUserAuthenticatesWithTokenUc,
+        user_logs_out: AsyncUserLogsOutUc,
+        anonymous_requests_restore_token: AsyncAnonymousRequestsRestoreToken,
+        anonymous_with_token_changes_password: AsyncAnonymousWithTokenChangesPassword,
+    ) -> UserAccountApi {
+        UserAccountApi {
+            id_factory,
+            anonymous_registers,
+            user_registers_with_password,
+            user_logins_with_password,
+            user_authenticates_with_token,
+            user_logs_out,
+            anonymous_requests_restore_token,
+            anonymous_with_token_changes_password,
+        }
+    }
+}
+
+/// Routes of the API. The login throttling counts failures per peer address, so the router
+/// should be served with `into_make_service_with_connect_info::<SocketAddr>()`. Without it
+/// all clients share one counter
+pub fn router(api: Arc<UserAccountApi>) -> Router {
+    Router::new()
+        .route("/anonymous", post(handler::register_anonymous))
+        .route("/users", post(handler::register_with_password))
+        .route("/sessions", post(handler::login))
+        .route(
+            "/sessions/current",
+            get(handler::authenticate).delete(handler::logout),
+        )
+        .route(
+            "/restore-password/tokens",
+            post(handler::request_restore_token),
+        )
+        .route(
+            "/restore-password",
+            post(handler::change_password_with_token),
+        )
+        .with_state(api)
+}
@@ -0,0 +1,85 @@
+//! Bodies of requests and responses
+use argentum_user_account_business::entity::session::Session;
+use argentum_user_business::entity::user::User;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegisterWithPasswordRequest {
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RestoreTokenRequest {
+    pub email: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChangePasswordRequest {
+    pub token: String,
+    pub password: String,
+}
+
+/// A new session. The token is sent as `Authorization: Bearer {token}` with later requests
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SessionResponse {
+    pub user_id: String,
+    pub token: String,
+    /// RFC 3339
+    pub expires_at: String,
+}
+
+impl SessionResponse {
+    pub fn new(session: &Session, token: String) -> SessionResponse {
+        SessionResponse {
+            user_id: session.user_id.to_string(),
+            token,
+            expires_at: session.expires_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegisteredUserResponse {
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UserResponse {
+    Anonymous {
+        id: String,
+    },
+    Authenticated {
+        id: String,
+        first_name: String,
+        last_name: String,
+        email: String,
+        email_verified: bool,
+    },
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        match user {
+            User::Anonymous(anonymous) => UserResponse::Anonymous {
+                id: anonymous.id.to_string(),
+            },
+            User::Authenticated(user) => UserResponse::Authenticated {
+                id: user.id.to_string(),
+                email_verified: user.is_email_verified(),
+                first_name: user.name.first,
+                last_name: user.name.last,
+                email: user.email.as_string(),
+            },
+        }
+    }
+}
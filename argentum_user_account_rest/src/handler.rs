@@ -0,0 +1,416 @@
+use crate::api::UserAccountApi;
+use crate::dto::{
+    ChangePasswordRequest, LoginRequest, RegisterWithPasswordRequest, RegisteredUserResponse,
+    RestoreTokenRequest, SessionResponse, UserResponse,
+};
+use crate::problem::Problem;
+
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_user_business::entity::user::User;
+use argentum_user_business::value_object::name::Name;
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{ConnectInfo, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+type ApiState = State<Arc<UserAccountApi>>;
+type JsonBody<T> = Result<Json<T>, JsonRejection>;
+
+pub(crate) async fn register_anonymous(
+    State(api): ApiState,
+) -> Result<(StatusCode, Json<SessionResponse>), Problem> {
+    let id = api.id_factory.create();
+    let (_, session, token) = api.anonymous_registers.execute(&id).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SessionResponse::new(&session, token)),
+    ))
+}
+
+pub(crate) async fn register_with_password(
+    State(api): ApiState,
+    body: JsonBody<RegisterWithPasswordRequest>,
+) -> Result<(StatusCode, Json<RegisteredUserResponse>), Problem> {
+    let Json(request) = body?;
+    let name = Name::new(request.first_name, request.last_name)?;
+    let email = EmailAddress::new(request.email)?;
+
+    let user = api
+        .user_registers_with_password
+        .execute(api.id_factory.create(), name, email, request.password)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(RegisteredUserResponse {
+            id: user.id.to_string(),
+        }),
+    ))
+}
+
+/// The anonymous session of the client is given as the bearer token, it's bound to the user
+pub(crate) async fn login(
+    State(api): ApiState,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    body: JsonBody<LoginRequest>,
+) -> Result<(StatusCode, Json<SessionResponse>), Problem> {
+    let Json(request) = body?;
+
+    let anonymous = match api
+        .user_authenticates_with_token
+        .execute(bearer_token(&headers)?)
+        .await?
+    {
+        User::Anonymous(anonymous) => anonymous,
+        User::Authenticated(_) => {
+            return Err(Problem::new(
+                StatusCode::CONFLICT,
+                "User is already logged in",
+            ))
+        }
+    };
+    let email = EmailAddress::new(request.email)?;
+    let client_id = match connect_info {
+        Some(ConnectInfo(address)) => address.ip().to_string(),
+        None => "unknown".to_string(),
+    };
+
+    let (session, token) = api
+        .user_logins_with_password
+        .execute(anonymous, email, request.password, client_id)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SessionResponse::new(&session, token)),
+    ))
+}
+
+pub(crate) async fn authenticate(
+    State(api): ApiState,
+    headers: HeaderMap,
+) -> Result<Json<UserResponse>, Problem> {
+    let user = api
+        .user_authenticates_with_token
+        .execute(bearer_token(&headers)?)
+        .await?;
+
+    Ok(Json(UserResponse::from(user)))
+}
+
+pub(crate) async fn logout(
+    State(api): ApiState,
+    headers: HeaderMap,
+) -> Result<StatusCode, Problem> {
+    api.user_logs_out.execute(bearer_token(&headers)?).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Accepted for unknown emails as well, so registered emails can't be found out
+pub(crate) async fn request_restore_token(
+    State(api): ApiState,
+    body: JsonBody<RestoreTokenRequest>,
+) -> Result<StatusCode, Problem> {
+    let Json(request) = body?;
+    let email = EmailAddress::new(request.email)?;
+
+    api.anonymous_requests_restore_token.execute(email).await?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+pub(crate) async fn change_password_with_token(
+    State(api): ApiState,
+    body: JsonBody<ChangePasswordRequest>,
+) -> Result<StatusCode, Problem> {
+    let Json(request) = body?;
+
+    api.anonymous_with_token_changes_password
+        .execute(request.token, request.password)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<String, Problem> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.to_string())
+        .ok_or_else(|| Problem::unauthorized("Bearer token is required"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::{SessionResponse, UserResponse};
+    use crate::problem::{Problem, CONTENT_TYPE_PROBLEM};
+    use crate::test_server::{TestServer, FREE_LOGIN_ATTEMPTS};
+    use reqwest::header::{CONTENT_TYPE, RETRY_AFTER};
+    use reqwest::{Response, StatusCode};
+    use serde_json::json;
+
+    const EMAIL: &str = "sarah-connor@example.com";
+    const PASSWORD: &str = "Judgment-Day-1997";
+
+    async fn register_anonymous(server: &TestServer) -> SessionResponse {
+        let response = server
+            .client
+            .post(server.url("/anonymous"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::CREATED, response.status());
+
+        response.json().await.unwrap()
+    }
+
+    async fn register(server: &TestServer, email: &str, password: &str) -> Response {
+        server
+            .client
+            .post(server.url("/users"))
+            .json(&json!({
+                "first_name": "Sarah",
+                "last_name": "Connor",
+                "email": email,
+                "password": password,
+            }))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    async fn login(server: &TestServer, email: &str, password: &str) -> Response {
+        let anonymous = register_anonymous(server).await;
+
+        server
+            .client
+            .post(server.url("/sessions"))
+            .bearer_auth(anonymous.token)
+            .json(&json!({ "email": email, "password": password }))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    async fn current_user(server: &TestServer, token: &str) -> Response {
+        server
+            .client
+            .get(server.url("/sessions/current"))
+            .bearer_auth(token)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    async fn problem(response: Response, status: StatusCode) -> Problem {
+        assert_eq!(status, response.status());
+        assert_eq!(
+            Some(CONTENT_TYPE_PROBLEM),
+            response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+        );
+
+        let problem: Problem = response.json().await.unwrap();
+        assert_eq!(status.as_u16(), problem.status);
+
+        problem
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_registers_and_authenticates() {
+        let server = TestServer::start().await;
+        let session = register_anonymous(&server).await;
+
+        let response = current_user(&server, &session.token).await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            UserResponse::Anonymous {
+                id: session.user_id
+            },
+            response.json().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_user_registers_logs_in_and_out() {
+        let server = TestServer::start().await;
+
+        let registered = register(&server, EMAIL, PASSWORD).await;
+        assert_eq!(StatusCode::CREATED, registered.status());
+
+        let logged_in = login(&server, EMAIL, PASSWORD).await;
+        assert_eq!(StatusCode::CREATED, logged_in.status());
+        let session: SessionResponse = logged_in.json().await.unwrap();
+
+        let user: UserResponse = current_user(&server, &session.token)
+            .await
+            .json()
+            .await
+            .unwrap();
+        assert!(matches!(
+            user,
+            UserResponse::Authenticated { ref id, ref email, .. }
+                if *id == session.user_id && email == EMAIL
+        ));
+
+        let logged_out = server
+            .client
+            .delete(server.url("/sessions/current"))
+            .bearer_auth(&session.token)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::NO_CONTENT, logged_out.status());
+
+        problem(
+            current_user(&server, &session.token).await,
+            StatusCode::UNAUTHORIZED,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_registration_problems() {
+        let server = TestServer::start().await;
+        register(&server, EMAIL, PASSWORD).await;
+
+        let taken = problem(
+            register(&server, EMAIL, PASSWORD).await,
+            StatusCode::CONFLICT,
+        )
+        .await;
+        assert_eq!("Conflict", taken.title);
+
+        let weak = problem(
+            register(&server, "kyle-reese@example.com", "short").await,
+            StatusCode::UNPROCESSABLE_ENTITY,
+        )
+        .await;
+        assert!(!weak.violations.is_empty());
+
+        problem(
+            register(&server, "not an email", PASSWORD).await,
+            StatusCode::UNPROCESSABLE_ENTITY,
+        )
+        .await;
+
+        let malformed = server
+            .client
+            .post(server.url("/users"))
+            .header(CONTENT_TYPE, "application/json")
+            .body("{\"email\":")
+            .send()
+            .await
+            .unwrap();
+        problem(malformed, StatusCode::BAD_REQUEST).await;
+    }
+
+    #[tokio::test]
+    async fn test_login_problems() {
+        let server = TestServer::start().await;
+        register(&server, EMAIL, PASSWORD).await;
+
+        let without_token = server
+            .client
+            .post(server.url("/sessions"))
+            .json(&json!({ "email": EMAIL, "password": PASSWORD }))
+            .send()
+            .await
+            .unwrap();
+        let unauthorized = problem(without_token, StatusCode::UNAUTHORIZED).await;
+        assert_eq!("Bearer token is required", unauthorized.detail);
+
+        for _ in 1..FREE_LOGIN_ATTEMPTS {
+            problem(
+                login(&server, EMAIL, "Wrong-Password-1").await,
+                StatusCode::UNAUTHORIZED,
+            )
+            .await;
+        }
+
+        // The failure over the free attempts is throttled right away
+        problem(
+            login(&server, EMAIL, "Wrong-Password-1").await,
+            StatusCode::TOO_MANY_REQUESTS,
+        )
+        .await;
+
+        let throttled = login(&server, EMAIL, PASSWORD).await;
+        let retry_after = throttled.headers().get(RETRY_AFTER).cloned();
+        let too_many = problem(throttled, StatusCode::TOO_MANY_REQUESTS).await;
+        assert!(too_many.retry_after.is_some());
+        assert_eq!(
+            too_many.retry_after.map(|s| s.to_string()),
+            retry_after.map(|v| v.to_str().unwrap().to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_user_restores_password() {
+        let server = TestServer::start().await;
+        register(&server, EMAIL, PASSWORD).await;
+        let new_password = "Skynet-Falls-2029";
+
+        let requested = server
+            .client
+            .post(server.url("/restore-password/tokens"))
+            .json(&json!({ "email": EMAIL }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::ACCEPTED, requested.status());
+        let token = server.sent_restore_token().expect("Token is not sent");
+
+        let change = |password: &str| {
+            server
+                .client
+                .post(server.url("/restore-password"))
+                .json(&json!({ "token": token, "password": password }))
+                .send()
+        };
+
+        let weak = problem(
+            change("weak").await.unwrap(),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        )
+        .await;
+        assert!(!weak.violations.is_empty());
+
+        assert_eq!(
+            StatusCode::NO_CONTENT,
+            change(new_password).await.unwrap().status()
+        );
+        problem(change(new_password).await.unwrap(), StatusCode::NOT_FOUND).await;
+
+        assert_eq!(
+            StatusCode::CREATED,
+            login(&server, EMAIL, new_password).await.status()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_token_is_not_sent_to_unknown_email() {
+        let server = TestServer::start().await;
+
+        let requested = server
+            .client
+            .post(server.url("/restore-password/tokens"))
+            .json(&json!({ "email": EMAIL }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::ACCEPTED, requested.status());
+        assert!(server.sent_restore_token().is_none());
+    }
+}
@@ -0,0 +1,6 @@
+pub mod api;
+pub mod dto;
+mod handler;
+pub mod problem;
+#[cfg(test)]
+mod test_server;
@@ -0,0 +1,198 @@
+//! Errors of use cases as `application/problem+json` responses (RFC 7807)
+use argentum_standard_business::data_type::email::WrongEmailError;
+use argentum_user_account_business::use_case::anonymous_registers::AnonymousRegistrationError;
+use argentum_user_account_business::use_case::restore_password::error::RestorePasswordError;
+use argentum_user_account_business::use_case::user_authenticates_with_token::AuthenticationError;
+use argentum_user_account_business::use_case::user_logins_with_password::LoginError;
+use argentum_user_account_business::use_case::user_logs_out::LogoutError;
+use argentum_user_account_business::use_case::user_registers_with_password::RegistrationError;
+use argentum_user_account_business::value_object::password::WeakPasswordError;
+use argentum_user_business::value_object::name::WrongNameError;
+use axum::extract::rejection::JsonRejection;
+use axum::http::header::{CONTENT_TYPE, RETRY_AFTER, WWW_AUTHENTICATE};
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+pub const CONTENT_TYPE_PROBLEM: &str = "application/problem+json";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    /// Rules a rejected password violates
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<String>,
+    /// Seconds to wait before the next attempt, sent in the `Retry-After` header as well
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u32>,
+}
+
+impl Problem {
+    pub fn new(status: StatusCode, detail: impl ToString) -> Problem {
+        Problem {
+            problem_type: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or_default().to_string(),
+            status: status.as_u16(),
+            detail: detail.to_string(),
+            violations: Vec::new(),
+            retry_after: None,
+        }
+    }
+
+    pub fn unauthorized(detail: impl ToString) -> Problem {
+        Problem::new(StatusCode::UNAUTHORIZED, detail)
+    }
+
+    /// Details of server side failures are generic messages of the errors, causes are not
+    /// disclosed
+    fn internal(detail: impl ToString) -> Problem {
+        Problem::new(StatusCode::INTERNAL_SERVER_ERROR, detail)
+    }
+
+    fn unavailable(detail: impl ToString) -> Problem {
+        Problem::new(StatusCode::SERVICE_UNAVAILABLE, detail)
+    }
+
+    fn weak_password(error: &WeakPasswordError) -> Problem {
+        Problem {
+            violations: error.violations.iter().map(|v| v.to_string()).collect(),
+            ..Problem::new(StatusCode::UNPROCESSABLE_ENTITY, error)
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let retry_after = self.retry_after;
+        let mut response = (status, Json(self)).into_response();
+        let headers = response.headers_mut();
+
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(CONTENT_TYPE_PROBLEM));
+
+        if status == StatusCode::UNAUTHORIZED {
+            headers.insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+
+        if let Some(seconds) = retry_after {
+            headers.insert(RETRY_AFTER, HeaderValue::from(seconds));
+        }
+
+        response
+    }
+}
+
+impl From<JsonRejection> for Problem {
+    fn from(rejection: JsonRejection) -> Self {
+        Problem::new(rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<WrongEmailError> for Problem {
+    fn from(error: WrongEmailError) -> Self {
+        Problem::new(StatusCode::UNPROCESSABLE_ENTITY, error)
+    }
+}
+
+impl From<WrongNameError> for Problem {
+    fn from(error: WrongNameError) -> Self {
+        Problem::new(StatusCode::UNPROCESSABLE_ENTITY, error)
+    }
+}
+
+impl From<AnonymousRegistrationError> for Problem {
+    fn from(error: AnonymousRegistrationError) -> Self {
+        Problem::internal(error)
+    }
+}
+
+impl From<RegistrationError> for Problem {
+    fn from(error: RegistrationError) -> Self {
+        match error {
+            RegistrationError::WeakPassword(e) => Problem::weak_password(&e),
+            RegistrationError::BreachedPassword => {
+                Problem::new(StatusCode::UNPROCESSABLE_ENTITY, error)
+            }
+            RegistrationError::EmailTaken => Problem::new(StatusCode::CONFLICT, error),
+            RegistrationError::BreachCheckError(_) => Problem::unavailable(error),
+            // A storage failure is not the client's fault, it must not look like a conflict
+            RegistrationError::SavingError(_)
+            | RegistrationError::EncryptionError(_)
+            | RegistrationError::TransactionError(_) => Problem::internal(error),
+        }
+    }
+}
+
+impl From<LoginError> for Problem {
+    fn from(error: LoginError) -> Self {
+        match error {
+            LoginError::WrongEmailOrPassword => Problem::unauthorized(error),
+            LoginError::EmailNotVerified => Problem::new(StatusCode::FORBIDDEN, error),
+            LoginError::TooManyAttempts { retry_after } => Problem {
+                retry_after: Some(retry_after),
+                ..Problem::new(StatusCode::TOO_MANY_REQUESTS, error)
+            },
+            LoginError::CredentialBackendError(_) => Problem::unavailable(error),
+            LoginError::SaveSession
+            | LoginError::DeleteAnonymousSession(_)
+            | LoginError::BindingError(_)
+            | LoginError::GetUserError(_)
+            | LoginError::MalformedCredential(_)
+            | LoginError::ThrottlingError(_)
+            | LoginError::TransactionError(_) => Problem::internal(error),
+        }
+    }
+}
+
+impl From<AuthenticationError> for Problem {
+    fn from(error: AuthenticationError) -> Self {
+        match error {
+            AuthenticationError::UserNotFound
+            | AuthenticationError::WrongToken
+            | AuthenticationError::SessionExpired
+            | AuthenticationError::SessionRevoked
+            | AuthenticationError::InvalidSignedToken(_) => Problem::unauthorized(error),
+            AuthenticationError::SessionRepositoryError(_)
+            | AuthenticationError::UserRepositoryError(_) => Problem::internal(error),
+        }
+    }
+}
+
+impl From<LogoutError> for Problem {
+    fn from(error: LogoutError) -> Self {
+        match error {
+            LogoutError::WrongToken => Problem::unauthorized(error),
+            LogoutError::DeleteSessionError(_) => Problem::internal(error),
+        }
+    }
+}
+
+impl From<RestorePasswordError> for Problem {
+    fn from(error: RestorePasswordError) -> Self {
+        match error {
+            RestorePasswordError::WeakPassword(e) => Problem::weak_password(&e),
+            RestorePasswordError::BreachedPassword => {
+                Problem::new(StatusCode::UNPROCESSABLE_ENTITY, error)
+            }
+            RestorePasswordError::UserNotFoundError | RestorePasswordError::TokenNotFoundError => {
+                Problem::new(StatusCode::NOT_FOUND, error)
+            }
+            RestorePasswordError::TokenExpired => Problem::new(StatusCode::GONE, error),
+            RestorePasswordError::BreachCheckError(_) => Problem::unavailable(error),
+            RestorePasswordError::GetUserError(_)
+            | RestorePasswordError::TokenRepositoryError(_)
+            | RestorePasswordError::SessionStorageError(_)
+            | RestorePasswordError::PasswordEncryptionError(_)
+            | RestorePasswordError::TransactionError(_) => Problem::internal(error),
+        }
+    }
+}
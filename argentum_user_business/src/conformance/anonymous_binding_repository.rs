@@ -0,0 +1,137 @@
+use crate::entity::anonymous_binding::AnonymousBinding;
+use crate::repository::anonymous_binding_repository::AnonymousBindingRepositoryTrait;
+use argentum_standard_business::conformance::{ensure, run, same_time, ConformanceError};
+use argentum_standard_business::data_type::id::IdFactory;
+use chrono::Duration;
+
+pub type Scenario =
+    fn(&dyn AnonymousBindingRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub type Test<'t> =
+    &'t dyn Fn(&dyn AnonymousBindingRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_binding", saves_and_finds_binding),
+    (
+        "finds_nothing_for_unknown_user",
+        finds_nothing_for_unknown_user,
+    ),
+    (
+        "refreshes_binding_saved_twice",
+        refreshes_binding_saved_twice,
+    ),
+    ("finds_latest_binding_of_user", finds_latest_binding_of_user),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+fn saves_and_finds_binding(
+    repository: &dyn AnonymousBindingRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let binding = AnonymousBinding::new(id_factory.create(), id_factory.create());
+    repository
+        .save(&binding)
+        .map_err(|_| "Can't save binding")?;
+
+    let found = repository
+        .find_by_user_id(&binding.user_id)
+        .ok_or("Saved binding is not found")?;
+
+    ensure(*binding.user_id == *found.user_id, "User id is changed")?;
+    ensure(
+        *binding.anonymous_id == *found.anonymous_id,
+        "Anonymous id is changed",
+    )?;
+    ensure(
+        same_time(&binding.created_at, &found.created_at),
+        "Creation time is changed",
+    )
+}
+
+fn finds_nothing_for_unknown_user(
+    repository: &dyn AnonymousBindingRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    repository
+        .save(&AnonymousBinding::new(
+            id_factory.create(),
+            id_factory.create(),
+        ))
+        .map_err(|_| "Can't save binding")?;
+
+    ensure(
+        repository.find_by_user_id(&id_factory.create()).is_none(),
+        "Binding of unknown user is found",
+    )
+}
+
+fn refreshes_binding_saved_twice(
+    repository: &dyn AnonymousBindingRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let mut binding = AnonymousBinding::new(id_factory.create(), id_factory.create());
+    repository
+        .save(&binding)
+        .map_err(|_| "Can't save binding")?;
+
+    binding.created_at = binding.created_at + Duration::seconds(1);
+    repository
+        .save(&binding)
+        .map_err(|_| "Anonymous user can't log in to the same account again")?;
+
+    let found = repository
+        .find_by_user_id(&binding.user_id)
+        .ok_or("Binding is not found")?;
+
+    ensure(
+        same_time(&binding.created_at, &found.created_at),
+        "Creation time is not refreshed",
+    )
+}
+
+/// A user may log in from several anonymous sessions
+fn finds_latest_binding_of_user(
+    repository: &dyn AnonymousBindingRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user_id = id_factory.create();
+    let first = AnonymousBinding::new(user_id.clone(), id_factory.create());
+    let mut latest = AnonymousBinding::new(user_id.clone(), id_factory.create());
+    latest.created_at = first.created_at + Duration::seconds(1);
+
+    repository.save(&latest).map_err(|_| "Can't save binding")?;
+    repository.save(&first).map_err(|_| "Can't save binding")?;
+
+    let found = repository
+        .find_by_user_id(&user_id)
+        .ok_or("Binding is not found")?;
+
+    ensure(
+        *latest.anonymous_id == *found.anonymous_id,
+        "Latest binding should be found",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::anonymous_binding_repository::check;
+    use crate::mock::repository::anonymous_binding_repository_mock::AnonymousBindingRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| {
+            test(
+                &AnonymousBindingRepositoryMock::new(),
+                &IdFactoryMock::new(),
+            )
+        })
+    }
+}
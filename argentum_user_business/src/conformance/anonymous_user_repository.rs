@@ -0,0 +1,82 @@
+use crate::entity::user::AnonymousUser;
+use crate::repository::user_repository::AnonymousUserRepositoryTrait;
+use argentum_standard_business::conformance::{ensure, run, same_time, ConformanceError};
+use argentum_standard_business::data_type::id::IdFactory;
+
+pub type Scenario =
+    fn(&dyn AnonymousUserRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub type Test<'t> =
+    &'t dyn Fn(&dyn AnonymousUserRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_user", saves_and_finds_user),
+    (
+        "finds_nothing_for_unknown_user",
+        finds_nothing_for_unknown_user,
+    ),
+    (
+        "rejects_saving_same_user_twice",
+        rejects_saving_same_user_twice,
+    ),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+fn saves_and_finds_user(
+    repository: &dyn AnonymousUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user = AnonymousUser::new(&id_factory.create());
+    repository.save(&user).map_err(|_| "Can't save user")?;
+
+    let found = repository.find(&user.id).ok_or("Saved user is not found")?;
+
+    ensure(*user.id == *found.id, "Id is changed")?;
+    ensure(
+        same_time(&user.created_at, &found.created_at),
+        "Creation time is changed",
+    )
+}
+
+fn finds_nothing_for_unknown_user(
+    repository: &dyn AnonymousUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    repository
+        .save(&AnonymousUser::new(&id_factory.create()))
+        .map_err(|_| "Can't save user")?;
+
+    ensure(
+        repository.find(&id_factory.create()).is_none(),
+        "Unknown id is found",
+    )
+}
+
+fn rejects_saving_same_user_twice(
+    repository: &dyn AnonymousUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user = AnonymousUser::new(&id_factory.create());
+    repository.save(&user).map_err(|_| "Can't save user")?;
+
+    ensure(repository.save(&user).is_err(), "User id should be unique")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::anonymous_user_repository::check;
+    use crate::mock::repository::anonymous_user_repository_mock::AnonymousUserRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| test(&AnonymousUserRepositoryMock::new(), &IdFactoryMock::new()))
+    }
+}
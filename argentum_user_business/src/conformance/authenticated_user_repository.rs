@@ -0,0 +1,221 @@
+use crate::entity::user::AuthenticatedUser;
+use crate::repository::user_repository::AuthenticatedUserRepositoryTrait;
+use crate::value_object::name::Name;
+use argentum_standard_business::conformance::{ensure, run, same_time, ConformanceError};
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_standard_business::data_type::id::IdFactory;
+
+pub type Scenario =
+    fn(&dyn AuthenticatedUserRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub type Test<'t> =
+    &'t dyn Fn(&dyn AuthenticatedUserRepositoryTrait, &dyn IdFactory) -> Result<(), &'static str>;
+
+pub const SCENARIOS: &[(&str, Scenario)] = &[
+    ("saves_and_finds_user", saves_and_finds_user),
+    (
+        "finds_user_by_email_in_any_case",
+        finds_user_by_email_in_any_case,
+    ),
+    (
+        "finds_nothing_for_unknown_user",
+        finds_nothing_for_unknown_user,
+    ),
+    (
+        "rejects_saving_same_user_twice",
+        rejects_saving_same_user_twice,
+    ),
+    ("rejects_taken_email", rejects_taken_email),
+    ("updates_saved_user", updates_saved_user),
+    (
+        "rejects_updating_unknown_user",
+        rejects_updating_unknown_user,
+    ),
+];
+
+pub fn check<F>(factory: F) -> Result<(), ConformanceError>
+where
+    F: Fn(Test) -> Result<(), &'static str>,
+{
+    run(SCENARIOS, |scenario| factory(scenario))
+}
+
+fn stub_user(id_factory: &dyn IdFactory, email: &str) -> AuthenticatedUser {
+    AuthenticatedUser::new(
+        &id_factory.create(),
+        Name::new("Dionne".to_string(), "Morrison".to_string()).unwrap(),
+        EmailAddress::new(email.to_string()).unwrap(),
+    )
+}
+
+fn saves_and_finds_user(
+    repository: &dyn AuthenticatedUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user = stub_user(id_factory, "dionne@example.com");
+    repository.save(&user).map_err(|_| "Can't save user")?;
+
+    let found = repository
+        .find(&user.id)
+        .map_err(|_| "Can't find user")?
+        .ok_or("Saved user is not found")?;
+
+    ensure(*user.id == *found.id, "Id is changed")?;
+    ensure(user.name.first == found.name.first, "First name is changed")?;
+    ensure(user.name.last == found.name.last, "Last name is changed")?;
+    ensure(user.email == found.email, "Email is changed")?;
+    ensure(
+        same_time(&user.created_at, &found.created_at),
+        "Creation time is changed",
+    )?;
+    ensure(
+        found.email_verified_at.is_none(),
+        "Email should not be verified",
+    )
+}
+
+fn finds_user_by_email_in_any_case(
+    repository: &dyn AuthenticatedUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user = stub_user(id_factory, "dionne@example.com");
+    repository.save(&user).map_err(|_| "Can't save user")?;
+
+    for email in ["dionne@example.com", "Dionne@Example.COM"].iter() {
+        let found = repository
+            .find_by_email(&EmailAddress::new(email.to_string()).unwrap())
+            .map_err(|_| "Can't find user")?
+            .ok_or("User is not found by email")?;
+
+        ensure(*user.id == *found.id, "Other user is found by email")?;
+    }
+
+    Ok(())
+}
+
+fn finds_nothing_for_unknown_user(
+    repository: &dyn AuthenticatedUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    repository
+        .save(&stub_user(id_factory, "dionne@example.com"))
+        .map_err(|_| "Can't save user")?;
+
+    let unknown = stub_user(id_factory, "other@example.com");
+
+    ensure(
+        repository
+            .find(&unknown.id)
+            .map_err(|_| "Can't find user")?
+            .is_none(),
+        "Unknown id is found",
+    )?;
+    ensure(
+        repository
+            .find_by_email(&unknown.email)
+            .map_err(|_| "Can't find user")?
+            .is_none(),
+        "Unknown email is found",
+    )
+}
+
+fn rejects_saving_same_user_twice(
+    repository: &dyn AuthenticatedUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user = stub_user(id_factory, "dionne@example.com");
+    repository.save(&user).map_err(|_| "Can't save user")?;
+
+    ensure(
+        repository.save(&user).is_err(),
+        "Saved user should be changed with update",
+    )
+}
+
+fn rejects_taken_email(
+    repository: &dyn AuthenticatedUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    repository
+        .save(&stub_user(id_factory, "dionne@example.com"))
+        .map_err(|_| "Can't save user")?;
+
+    ensure(
+        repository
+            .save(&stub_user(id_factory, "DIONNE@example.com"))
+            .is_err(),
+        "Email should belong to one user",
+    )
+}
+
+fn updates_saved_user(
+    repository: &dyn AuthenticatedUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let mut user = stub_user(id_factory, "dionne@example.com");
+    repository.save(&user).map_err(|_| "Can't save user")?;
+
+    user.name = Name::new("Dionne".to_string(), "Warwick".to_string()).unwrap();
+    user.email = EmailAddress::new("warwick@example.com".to_string()).unwrap();
+    user.verify_email();
+    repository.update(&user).map_err(|_| "Can't update user")?;
+
+    let found = repository
+        .find(&user.id)
+        .map_err(|_| "Can't find user")?
+        .ok_or("Updated user is not found")?;
+
+    ensure(found.name.last == "Warwick", "Name is not updated")?;
+    ensure(user.email == found.email, "Email is not updated")?;
+    ensure(
+        match (user.email_verified_at, found.email_verified_at) {
+            (Some(a), Some(b)) => same_time(&a, &b),
+            _ => false,
+        },
+        "Verification time is not updated",
+    )?;
+    ensure(
+        repository
+            .find_by_email(&EmailAddress::new("dionne@example.com".to_string()).unwrap())
+            .map_err(|_| "Can't find user")?
+            .is_none(),
+        "User is found by the previous email",
+    )
+}
+
+fn rejects_updating_unknown_user(
+    repository: &dyn AuthenticatedUserRepositoryTrait,
+    id_factory: &dyn IdFactory,
+) -> Result<(), &'static str> {
+    let user = stub_user(id_factory, "dionne@example.com");
+
+    ensure(
+        repository.update(&user).is_err(),
+        "Not saved user can't be updated",
+    )?;
+    ensure(
+        repository
+            .find(&user.id)
+            .map_err(|_| "Can't find user")?
+            .is_none(),
+        "Update should not save user",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::authenticated_user_repository::check;
+    use crate::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
+    use argentum_standard_business::conformance::ConformanceError;
+    use argentum_standard_business::mock::data_type::id_factory::IdFactoryMock;
+
+    #[test]
+    fn test_mock_conforms() -> Result<(), ConformanceError> {
+        check(|test| {
+            test(
+                &AuthenticatedUserRepositoryMock::new(),
+                &IdFactoryMock::new(),
+            )
+        })
+    }
+}
@@ -0,0 +1,20 @@
+//! Contracts of the user repository traits, built on `argentum_standard_business::conformance`.
+//! A module holds the `SCENARIOS` of one trait and `check`, which runs them against an
+//! implementation. The closure given to `check` is called once per scenario: it opens a clean
+//! storage, builds the repository over it and hands it to `test` along with the id factory
+//! the repository parses ids with:
+//!
+//! ```ignore
+//! check(|test| {
+//!     let connection = SqliteConnection::open_in_memory().map_err(|_| "Can't open")?;
+//!     let id_factory = UniqueIdFactory::new();
+//!
+//!     test(
+//!         &SqliteAuthenticatedUserRepository::new(&connection, &id_factory),
+//!         &id_factory,
+//!     )
+//! })
+//! ```
+pub mod anonymous_binding_repository;
+pub mod anonymous_user_repository;
+pub mod authenticated_user_repository;
@@ -14,6 +14,7 @@ pub struct AuthenticatedUser {
     pub created_at: DateTime<Utc>,
     pub name: Name,
     pub email: EmailAddress,
+    pub email_verified_at: Option<DateTime<Utc>>,
 }
 
 impl AuthenticatedUser {
@@ -23,6 +24,27 @@ impl AuthenticatedUser {
             created_at: Utc::now(),
             name,
             email,
+            email_verified_at: None,
+        }
+    }
+
+    pub fn is_email_verified(&self) -> bool {
+        self.email_verified_at.is_some()
+    }
+
+    pub fn verify_email(&mut self) {
+        self.email_verified_at = Some(Utc::now());
+    }
+}
+
+impl Clone for AuthenticatedUser {
+    fn clone(&self) -> AuthenticatedUser {
+        AuthenticatedUser {
+            id: self.id.clone(),
+            created_at: self.created_at,
+            name: self.name.clone(),
+            email: self.email.clone(),
+            email_verified_at: self.email_verified_at,
         }
     }
 }
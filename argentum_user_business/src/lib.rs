@@ -1,3 +1,4 @@
+pub mod conformance;
 pub mod entity;
 pub mod mock;
 pub mod repository;
@@ -1,20 +1,20 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::sync::RwLock;
 
 use crate::entity::anonymous_binding::AnonymousBinding;
 use crate::repository::anonymous_binding_repository::{
     AnonymousBindingRepositoryError, AnonymousBindingRepositoryTrait,
 };
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
 
 pub struct AnonymousBindingRepositoryMock {
-    sessions: RefCell<HashMap<Id, AnonymousBinding>>,
+    bindings: RwLock<Vec<AnonymousBinding>>,
 }
 
 impl AnonymousBindingRepositoryMock {
     pub fn new() -> AnonymousBindingRepositoryMock {
         AnonymousBindingRepositoryMock {
-            sessions: RefCell::new(HashMap::new()),
+            bindings: RwLock::new(Vec::new()),
         }
     }
 }
@@ -26,26 +26,43 @@ impl Default for AnonymousBindingRepositoryMock {
 }
 
 impl AnonymousBindingRepositoryTrait for AnonymousBindingRepositoryMock {
+    /// A user may log in from several anonymous sessions, the latest binding is returned
     fn find_by_user_id(&self, user_id: &Id) -> Option<AnonymousBinding> {
-        self.sessions
-            .borrow()
-            .get(user_id)
-            .map(|b| AnonymousBinding::new(b.user_id.clone(), b.anonymous_id.clone()))
+        self.bindings
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|b| &b.user_id == user_id)
+            .max_by_key(|b| b.created_at)
+            .map(|b| AnonymousBinding {
+                user_id: b.user_id.clone(),
+                anonymous_id: b.anonymous_id.clone(),
+                created_at: b.created_at,
+            })
     }
 
     fn save(&self, binding: &AnonymousBinding) -> Result<(), AnonymousBindingRepositoryError> {
-        // TODO: check if key exists; don't save if already bound
+        let mut bindings = self.bindings.write().unwrap();
 
-        let s = AnonymousBinding::new(binding.user_id.clone(), binding.anonymous_id.clone());
-
-        match self
-            .sessions
-            .borrow_mut()
-            .insert(binding.user_id.clone(), s)
-            .is_none()
+        if let Some(saved) = bindings
+            .iter_mut()
+            .find(|b| *b.user_id == *binding.user_id && *b.anonymous_id == *binding.anonymous_id)
         {
-            true => Ok(()),
-            false => Err(AnonymousBindingRepositoryError::Save),
+            saved.created_at = binding.created_at;
+
+            return Ok(());
         }
+
+        bindings.push(AnonymousBinding {
+            user_id: binding.user_id.clone(),
+            anonymous_id: binding.anonymous_id.clone(),
+            created_at: binding.created_at,
+        });
+
+        Ok(())
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn AnonymousBindingRepositoryTrait + '_> {
+        Bound::Shared(self)
     }
 }
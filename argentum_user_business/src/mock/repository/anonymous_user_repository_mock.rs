@@ -1,17 +1,18 @@
 use crate::entity::user::{AnonymousUser, UserTrait};
 use crate::repository::user_repository::{AnonymousUserRepositoryTrait, SavingUserError};
 use argentum_standard_business::data_type::id::Id;
-use std::cell::RefCell;
+use argentum_standard_business::transaction::{Bound, Transaction};
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 pub struct AnonymousUserRepositoryMock {
-    users: RefCell<HashMap<Id, AnonymousUser>>,
+    users: RwLock<HashMap<Id, AnonymousUser>>,
 }
 
 impl AnonymousUserRepositoryMock {
     pub fn new() -> AnonymousUserRepositoryMock {
         AnonymousUserRepositoryMock {
-            users: RefCell::new(HashMap::new()),
+            users: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -24,7 +25,7 @@ impl Default for AnonymousUserRepositoryMock {
 
 impl AnonymousUserRepositoryTrait for AnonymousUserRepositoryMock {
     fn find(&self, id: &Id) -> Option<AnonymousUser> {
-        self.users.borrow().get(id).map(|u| AnonymousUser {
+        self.users.read().unwrap().get(id).map(|u| AnonymousUser {
             id: u.id.clone(),
             created_at: u.created_at,
         })
@@ -40,7 +41,8 @@ impl AnonymousUserRepositoryTrait for AnonymousUserRepositoryMock {
 
         match self
             .users
-            .borrow_mut()
+            .write()
+            .unwrap()
             .insert(user.id().clone(), u)
             .is_none()
         {
@@ -48,4 +50,8 @@ impl AnonymousUserRepositoryTrait for AnonymousUserRepositoryMock {
             false => Err(SavingUserError::Anonymous),
         }
     }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn AnonymousUserRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
 }
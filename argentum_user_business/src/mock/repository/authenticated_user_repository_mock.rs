@@ -2,17 +2,18 @@ use crate::entity::user::{AuthenticatedUser, UserTrait};
 use crate::repository::user_repository::{AuthenticatedUserRepositoryTrait, SavingUserError};
 use argentum_standard_business::data_type::email::EmailAddress;
 use argentum_standard_business::data_type::id::Id;
-use std::cell::RefCell;
+use argentum_standard_business::transaction::{Bound, Transaction};
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 pub struct AuthenticatedUserRepositoryMock {
-    users: RefCell<HashMap<Id, AuthenticatedUser>>,
+    users: RwLock<HashMap<Id, AuthenticatedUser>>,
 }
 
 impl AuthenticatedUserRepositoryMock {
     pub fn new() -> AuthenticatedUserRepositoryMock {
         AuthenticatedUserRepositoryMock {
-            users: RefCell::new(HashMap::new()),
+            users: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -23,53 +24,55 @@ impl Default for AuthenticatedUserRepositoryMock {
     }
 }
 
+/// Emails are unique regardless of case
+fn same_email(a: &EmailAddress, b: &EmailAddress) -> bool {
+    a.as_string().to_lowercase() == b.as_string().to_lowercase()
+}
+
 impl AuthenticatedUserRepositoryTrait for AuthenticatedUserRepositoryMock {
     fn find(&self, id: &Id) -> Result<Option<AuthenticatedUser>, SavingUserError> {
-        Ok(self
-            .users
-            .borrow()
-            .get(id)
-            .map(|u| AuthenticatedUser::new(&u.id().clone(), u.name.clone(), u.email.clone())))
+        Ok(self.users.read().unwrap().get(id).cloned())
     }
 
     fn find_by_email(
         &self,
         email: &EmailAddress,
     ) -> Result<Option<AuthenticatedUser>, SavingUserError> {
-        for (_, u) in self.users.borrow().iter() {
-            if &u.email == email {
-                return Ok(Some(AuthenticatedUser::new(
-                    &u.id().clone(),
-                    u.name.clone(),
-                    u.email.clone(),
-                )));
-            }
-        }
-
-        Ok(None)
+        Ok(self
+            .users
+            .read()
+            .unwrap()
+            .values()
+            .find(|u| same_email(&u.email, email))
+            .cloned())
     }
 
     fn save(&self, user: &AuthenticatedUser) -> Result<(), SavingUserError> {
-        // TODO: check if key exists
-        // if self.users. contains_key(user.get_id().clone()) {
-        //     return Err("Already exists".parse().unwrap());
-        // }
-
-        let u = AuthenticatedUser {
-            id: user.id().clone(),
-            created_at: user.created_at,
-            name: user.name.clone(),
-            email: user.email.clone(),
-        };
+        let mut users = self.users.write().unwrap();
 
-        match self
-            .users
-            .borrow_mut()
-            .insert(user.id().clone(), u)
-            .is_none()
+        if users.contains_key(&user.id) || users.values().any(|u| same_email(&u.email, &user.email))
         {
-            true => Ok(()),
-            false => Err(SavingUserError::Authenticated),
+            return Err(SavingUserError::Authenticated);
+        }
+
+        users.insert(user.id().clone(), user.clone());
+
+        Ok(())
+    }
+
+    fn update(&self, user: &AuthenticatedUser) -> Result<(), SavingUserError> {
+        let mut users = self.users.write().unwrap();
+
+        match users.get_mut(&user.id) {
+            Some(u) => {
+                *u = user.clone();
+                Ok(())
+            }
+            None => Err(SavingUserError::Authenticated),
         }
     }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn AuthenticatedUserRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
 }
@@ -1,17 +1,18 @@
 use crate::entity::user::AnonymousUser;
 use crate::repository::user_repository::{AnonymousUserRepositoryTrait, SavingUserError};
 use argentum_standard_business::data_type::id::Id;
-use std::cell::RefCell;
+use argentum_standard_business::transaction::{Bound, Transaction};
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 pub struct AnonymousRepositoryMockWithBrokenSave {
-    users: RefCell<HashMap<Id, AnonymousUser>>,
+    users: RwLock<HashMap<Id, AnonymousUser>>,
 }
 
 impl AnonymousRepositoryMockWithBrokenSave {
     pub fn new() -> AnonymousRepositoryMockWithBrokenSave {
         AnonymousRepositoryMockWithBrokenSave {
-            users: RefCell::new(HashMap::new()),
+            users: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -24,7 +25,7 @@ impl Default for AnonymousRepositoryMockWithBrokenSave {
 
 impl AnonymousUserRepositoryTrait for AnonymousRepositoryMockWithBrokenSave {
     fn find(&self, id: &Id) -> Option<AnonymousUser> {
-        self.users.borrow().get(id).map(|u| AnonymousUser {
+        self.users.read().unwrap().get(id).map(|u| AnonymousUser {
             id: u.id.clone(),
             created_at: u.created_at,
         })
@@ -33,4 +34,8 @@ impl AnonymousUserRepositoryTrait for AnonymousRepositoryMockWithBrokenSave {
     fn save(&self, _user: &AnonymousUser) -> Result<(), SavingUserError> {
         Err(SavingUserError::Anonymous)
     }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn AnonymousUserRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
 }
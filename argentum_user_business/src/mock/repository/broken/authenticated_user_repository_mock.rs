@@ -1,18 +1,19 @@
-use crate::entity::user::{AuthenticatedUser, UserTrait};
+use crate::entity::user::AuthenticatedUser;
 use crate::repository::user_repository::{AuthenticatedUserRepositoryTrait, SavingUserError};
 use argentum_standard_business::data_type::email::EmailAddress;
 use argentum_standard_business::data_type::id::Id;
-use std::cell::RefCell;
+use argentum_standard_business::transaction::{Bound, Transaction};
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 pub struct AuthenticatedUserRepositoryMockWihBrokenSave {
-    users: RefCell<HashMap<Id, AuthenticatedUser>>,
+    users: RwLock<HashMap<Id, AuthenticatedUser>>,
 }
 
 impl AuthenticatedUserRepositoryMockWihBrokenSave {
     pub fn new() -> AuthenticatedUserRepositoryMockWihBrokenSave {
         AuthenticatedUserRepositoryMockWihBrokenSave {
-            users: RefCell::new(HashMap::new()),
+            users: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -25,24 +26,16 @@ impl Default for AuthenticatedUserRepositoryMockWihBrokenSave {
 
 impl AuthenticatedUserRepositoryTrait for AuthenticatedUserRepositoryMockWihBrokenSave {
     fn find(&self, id: &Id) -> Result<Option<AuthenticatedUser>, SavingUserError> {
-        Ok(self
-            .users
-            .borrow()
-            .get(id)
-            .map(|u| AuthenticatedUser::new(&u.id().clone(), u.name.clone(), u.email.clone())))
+        Ok(self.users.read().unwrap().get(id).cloned())
     }
 
     fn find_by_email(
         &self,
         email: &EmailAddress,
     ) -> Result<Option<AuthenticatedUser>, SavingUserError> {
-        for (_, u) in self.users.borrow().iter() {
+        for (_, u) in self.users.read().unwrap().iter() {
             if &u.email == email {
-                return Ok(Some(AuthenticatedUser::new(
-                    &u.id().clone(),
-                    u.name.clone(),
-                    u.email.clone(),
-                )));
+                return Ok(Some(u.clone()));
             }
         }
 
@@ -52,4 +45,12 @@ impl AuthenticatedUserRepositoryTrait for AuthenticatedUserRepositoryMockWihBrok
     fn save(&self, _user: &AuthenticatedUser) -> Result<(), SavingUserError> {
         Err(SavingUserError::Authenticated)
     }
+
+    fn update(&self, _user: &AuthenticatedUser) -> Result<(), SavingUserError> {
+        Err(SavingUserError::Authenticated)
+    }
+
+    fn within(&self, _: &Transaction) -> Bound<'_, dyn AuthenticatedUserRepositoryTrait + '_> {
+        Bound::Shared(self)
+    }
 }
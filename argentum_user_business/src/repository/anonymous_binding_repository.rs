@@ -1,9 +1,62 @@
 use crate::entity::anonymous_binding::AnonymousBinding;
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture};
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use std::sync::Arc;
 
 pub trait AnonymousBindingRepositoryTrait {
     fn find_by_user_id(&self, id: &Id) -> Option<AnonymousBinding>;
+    /// Saving a binding again only moves its creation time, the same anonymous user
+    /// may log in to the same account several times
     fn save(&self, binding: &AnonymousBinding) -> Result<(), AnonymousBindingRepositoryError>;
+    /// The repository writing within `transaction`
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Bound<'_, dyn AnonymousBindingRepositoryTrait + '_>;
+}
+
+/// Async counterpart of `AnonymousBindingRepositoryTrait`
+pub trait AsyncAnonymousBindingRepositoryTrait: Send + Sync {
+    fn find_by_user_id<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Option<AnonymousBinding>>;
+    fn save<'a>(
+        &'a self,
+        binding: &'a AnonymousBinding,
+    ) -> BoxFuture<'a, Result<(), AnonymousBindingRepositoryError>>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncAnonymousBindingRepositoryTrait>;
+}
+
+impl<T: AnonymousBindingRepositoryTrait + Send + 'static> AsyncAnonymousBindingRepositoryTrait
+    for Blocking<T>
+{
+    fn find_by_user_id<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Option<AnonymousBinding>> {
+        ready(call(self, |r| r.find_by_user_id(id)))
+    }
+
+    fn save<'a>(
+        &'a self,
+        binding: &'a AnonymousBinding,
+    ) -> BoxFuture<'a, Result<(), AnonymousBindingRepositoryError>> {
+        ready(call(self, |r| r.save(binding)))
+    }
+
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncAnonymousBindingRepositoryTrait> {
+        Arc::new(self.bind(transaction))
+    }
+}
+
+/// Calls the wrapped repository within the transaction `repository` is bound to
+fn call<T: AnonymousBindingRepositoryTrait, R>(
+    repository: &Blocking<T>,
+    call: impl FnOnce(&dyn AnonymousBindingRepositoryTrait) -> R,
+) -> R {
+    let inner = repository.inner();
+
+    match repository.transaction() {
+        Some(transaction) => call(&*inner.within(transaction)),
+        None => call(&*inner),
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
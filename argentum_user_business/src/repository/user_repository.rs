@@ -1,6 +1,9 @@
 use crate::entity::user::{AnonymousUser, AuthenticatedUser};
+use argentum_standard_business::asynchronous::{ready, Blocking, BoxFuture};
 use argentum_standard_business::data_type::email::EmailAddress;
 use argentum_standard_business::data_type::id::Id;
+use argentum_standard_business::transaction::{Bound, Transaction};
+use std::sync::Arc;
 
 // pub trait UserRepositoryTrait<I: IdTrait, U: UserTrait<I>> {
 //     // fn find(&self, id: I) -> Result<U, Error>;
@@ -14,11 +17,128 @@ pub trait AuthenticatedUserRepositoryTrait {
         email: &EmailAddress,
     ) -> Result<Option<AuthenticatedUser>, SavingUserError>;
     fn save(&self, user: &AuthenticatedUser) -> Result<(), SavingUserError>;
+    fn update(&self, user: &AuthenticatedUser) -> Result<(), SavingUserError>;
+    /// The repository writing within `transaction`
+    fn within(
+        &self,
+        transaction: &Transaction,
+    ) -> Bound<'_, dyn AuthenticatedUserRepositoryTrait + '_>;
 }
 
 pub trait AnonymousUserRepositoryTrait {
     fn find(&self, id: &Id) -> Option<AnonymousUser>;
     fn save(&self, user: &AnonymousUser) -> Result<(), SavingUserError>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction)
+        -> Bound<'_, dyn AnonymousUserRepositoryTrait + '_>;
+}
+
+/// Async counterpart of `AuthenticatedUserRepositoryTrait`
+pub trait AsyncAuthenticatedUserRepositoryTrait: Send + Sync {
+    fn find<'a>(
+        &'a self,
+        id: &'a Id,
+    ) -> BoxFuture<'a, Result<Option<AuthenticatedUser>, SavingUserError>>;
+    fn find_by_email<'a>(
+        &'a self,
+        email: &'a EmailAddress,
+    ) -> BoxFuture<'a, Result<Option<AuthenticatedUser>, SavingUserError>>;
+    fn save<'a>(
+        &'a self,
+        user: &'a AuthenticatedUser,
+    ) -> BoxFuture<'a, Result<(), SavingUserError>>;
+    fn update<'a>(
+        &'a self,
+        user: &'a AuthenticatedUser,
+    ) -> BoxFuture<'a, Result<(), SavingUserError>>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncAuthenticatedUserRepositoryTrait>;
+}
+
+/// Async counterpart of `AnonymousUserRepositoryTrait`
+pub trait AsyncAnonymousUserRepositoryTrait: Send + Sync {
+    fn find<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Option<AnonymousUser>>;
+    fn save<'a>(&'a self, user: &'a AnonymousUser) -> BoxFuture<'a, Result<(), SavingUserError>>;
+    /// The repository writing within `transaction`
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncAnonymousUserRepositoryTrait>;
+}
+
+impl<T: AuthenticatedUserRepositoryTrait + Send + 'static> AsyncAuthenticatedUserRepositoryTrait
+    for Blocking<T>
+{
+    fn find<'a>(
+        &'a self,
+        id: &'a Id,
+    ) -> BoxFuture<'a, Result<Option<AuthenticatedUser>, SavingUserError>> {
+        ready(call_authenticated(self, |r| r.find(id)))
+    }
+
+    fn find_by_email<'a>(
+        &'a self,
+        email: &'a EmailAddress,
+    ) -> BoxFuture<'a, Result<Option<AuthenticatedUser>, SavingUserError>> {
+        ready(call_authenticated(self, |r| r.find_by_email(email)))
+    }
+
+    fn save<'a>(
+        &'a self,
+        user: &'a AuthenticatedUser,
+    ) -> BoxFuture<'a, Result<(), SavingUserError>> {
+        ready(call_authenticated(self, |r| r.save(user)))
+    }
+
+    fn update<'a>(
+        &'a self,
+        user: &'a AuthenticatedUser,
+    ) -> BoxFuture<'a, Result<(), SavingUserError>> {
+        ready(call_authenticated(self, |r| r.update(user)))
+    }
+
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncAuthenticatedUserRepositoryTrait> {
+        Arc::new(self.bind(transaction))
+    }
+}
+
+/// Calls the wrapped repository within the transaction `repository` is bound to
+fn call_authenticated<T: AuthenticatedUserRepositoryTrait, R>(
+    repository: &Blocking<T>,
+    call: impl FnOnce(&dyn AuthenticatedUserRepositoryTrait) -> R,
+) -> R {
+    let inner = repository.inner();
+
+    match repository.transaction() {
+        Some(transaction) => call(&*inner.within(transaction)),
+        None => call(&*inner),
+    }
+}
+
+impl<T: AnonymousUserRepositoryTrait + Send + 'static> AsyncAnonymousUserRepositoryTrait
+    for Blocking<T>
+{
+    fn find<'a>(&'a self, id: &'a Id) -> BoxFuture<'a, Option<AnonymousUser>> {
+        ready(call_anonymous(self, |r| r.find(id)))
+    }
+
+    fn save<'a>(&'a self, user: &'a AnonymousUser) -> BoxFuture<'a, Result<(), SavingUserError>> {
+        ready(call_anonymous(self, |r| r.save(user)))
+    }
+
+    fn within(&self, transaction: &Transaction) -> Arc<dyn AsyncAnonymousUserRepositoryTrait> {
+        Arc::new(self.bind(transaction))
+    }
+}
+
+/// Calls the wrapped repository within the transaction `repository` is bound to
+fn call_anonymous<T: AnonymousUserRepositoryTrait, R>(
+    repository: &Blocking<T>,
+    call: impl FnOnce(&dyn AnonymousUserRepositoryTrait) -> R,
+) -> R {
+    let inner = repository.inner();
+
+    match repository.transaction() {
+        Some(transaction) => call(&*inner.within(transaction)),
+        None => call(&*inner),
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -1,5 +1,76 @@
 use argentum_standard_business::data_type::id::Id;
+use chrono::{DateTime, Utc};
 
 pub trait GeneratorTrait {
     fn generate(&self, user_id: &Id) -> String;
+
+    /// Generates a token bound to a session. Opaque tokens don't carry the session,
+    /// signed ones put its id into the claims.
+    fn generate_for_session(&self, user_id: &Id, _session_id: &Id) -> String {
+        self.generate(user_id)
+    }
+}
+
+/// Claims of a signed access token which has passed the verification
+pub struct AccessTokenClaims {
+    pub subject: Id,
+    pub session_id: Option<Id>,
+    pub issuer: String,
+    pub audience: String,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Checks signature, expiry, issuer and audience of a signed token offline
+pub trait SignedTokenVerifierTrait {
+    fn verify(&self, token: &str) -> Result<AccessTokenClaims, TokenVerificationError>;
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TokenVerificationError {
+    #[error("Token is malformed")]
+    Malformed,
+
+    #[error("Token signature is invalid")]
+    InvalidSignature,
+
+    #[error("Token expired")]
+    Expired,
+
+    #[error("Token claims are invalid: {0}")]
+    InvalidClaims(String),
+}
+
+/// Hashes bearer tokens before they are stored, so a leaked storage doesn't leak live tokens.
+/// Implementations should use a keyed hash, e.g. HMAC with a server-side secret.
+pub trait TokenHasherTrait {
+    fn hash(&self, token: &str) -> String;
+}
+
+/// Compares two token hashes without leaking the position of the first difference
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::token::constant_time_eq;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("abcdef", "abcdef"));
+        assert!(!constant_time_eq("abcdef", "abcdeg"));
+        assert!(!constant_time_eq("abcdef", "abcde"));
+        assert!(constant_time_eq("", ""));
+    }
 }
@@ -4,6 +4,8 @@ use argentum_standard_business::data_type::id::IdFactory;
 use argentum_user_account_business::use_case::anonymous_registers::AnonymousRegistersUc;
 use argentum_user_account_business::use_case::user_authenticates_with_token::UserAuthenticatesWithTokenUc;
 use argentum_user_account_business::use_case::user_logins_with_password::UserLoginsWithPasswordUc;
+use argentum_user_account_business::use_case::user_logs_out::UserLogsOutUc;
+use argentum_user_account_business::use_case::user_logs_out_everywhere::UserLogsOutEverywhereUc;
 use argentum_user_account_business::use_case::user_registers_with_password::UserRegistersWithPasswordUc;
 use argentum_user_business::entity::user::AnonymousUser;
 use argentum_user_business::value_object::name::Name;
@@ -14,16 +16,21 @@ pub struct App<'s> {
     user_logins_with_pw: &'s UserLoginsWithPasswordUc<'s>,
     user_registers_with_pw: &'s UserRegistersWithPasswordUc<'s>,
     user_authenticates_with_token: &'s UserAuthenticatesWithTokenUc<'s>,
+    user_logs_out: &'s UserLogsOutUc<'s>,
+    user_logs_out_everywhere: &'s UserLogsOutEverywhereUc<'s>,
     logger: &'s dyn LoggerTrait,
 }
 
 impl<'s> App<'s> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id_factory: &'s dyn IdFactory,
         anonymous_registers_uc: &'s AnonymousRegistersUc<'s>,
         user_logins_with_pw: &'s UserLoginsWithPasswordUc<'s>,
         user_registers_with_pw: &'s UserRegistersWithPasswordUc<'s>,
         user_authenticates_with_token: &'s UserAuthenticatesWithTokenUc<'s>,
+        user_logs_out: &'s UserLogsOutUc<'s>,
+        user_logs_out_everywhere: &'s UserLogsOutEverywhereUc<'s>,
         logger: &'s dyn LoggerTrait,
     ) -> App<'s> {
         App {
@@ -32,6 +39,8 @@ impl<'s> App<'s> {
             user_logins_with_pw,
             user_registers_with_pw,
             user_authenticates_with_token,
+            user_logs_out,
+            user_logs_out_everywhere,
             logger,
         }
     }
@@ -47,19 +56,17 @@ impl<'s> App<'s> {
         let anon_id = self.id_factory.create();
 
         let anon_registration_result = self.anonymous_registers_uc.execute(&anon_id);
-        let (_, anon_session) = match anon_registration_result {
+        let (_, _, anon_token) = match anon_registration_result {
             Ok(r) => r,
             Err(e) => return Err(e.to_string()),
         };
 
-        let anon_auth_result = self
-            .user_authenticates_with_token
-            .execute(anon_session.token);
+        let anon_auth_result = self.user_authenticates_with_token.execute(anon_token);
 
         match anon_auth_result {
             Ok(_) => {}
             Err(e) => {
-                println!("Error: {}", e.to_string());
+                println!("Error: {}", e);
                 return Err(e.to_string());
             }
         };
@@ -76,7 +83,7 @@ impl<'s> App<'s> {
             Ok(email) => email,
             Err(e) => return Err(e.to_string()),
         };
-        let password = String::from("111");
+        let password = String::from("Judgment-Day-1997");
 
         let res = self
             .user_registers_with_pw
@@ -93,36 +100,65 @@ impl<'s> App<'s> {
         let anon_id2 = self.id_factory.create();
         let anon2 = AnonymousUser::new(&anon_id2);
         let email_res2 = EmailAddress::new(String::from("sarah-connor@example.com"));
-        let password2 = String::from("111");
+        let password2 = String::from("Judgment-Day-1997");
 
         let email2 = match email_res2 {
             Ok(email) => email,
             Err(e) => return Err(e.to_string()),
         };
 
-        let login_result = self.user_logins_with_pw.execute(anon2, email2, password2);
+        let login_result =
+            self.user_logins_with_pw
+                .execute(anon2, email2, password2, "127.0.0.1".to_string());
 
-        let login = match login_result {
+        let (_, token) = match login_result {
             Ok(l) => {
                 println!("Logged In");
                 l
             }
             Err(e) => {
-                println!("Login error: {}", e.to_string());
+                println!("Login error: {}", e);
                 return Err(e.to_string());
             }
         };
 
-        let aut_result = self.user_authenticates_with_token.execute(login.token);
+        let aut_result = self.user_authenticates_with_token.execute(token.clone());
 
         match aut_result {
             Ok(_) => {
                 println!("Authenticated");
-                Ok(())
             }
             Err(e) => {
-                println!("Error: {}", e.to_string());
-                Err(e.to_string())
+                println!("Error: {}", e);
+                return Err(e.to_string());
+            }
+        }
+
+        let logout_everywhere_result = self.user_logs_out_everywhere.execute(token.clone(), true);
+
+        match logout_everywhere_result {
+            Ok(_) => println!("Logged out from other devices"),
+            Err(e) => {
+                println!("Error: {}", e);
+                return Err(e.to_string());
+            }
+        }
+
+        let logout_result = self.user_logs_out.execute(token.clone());
+
+        match logout_result {
+            Ok(_) => println!("Logged out"),
+            Err(e) => {
+                println!("Error: {}", e);
+                return Err(e.to_string());
+            }
+        }
+
+        match self.user_authenticates_with_token.execute(token) {
+            Ok(_) => Err("Session is still alive after logout".to_string()),
+            Err(e) => {
+                println!("Not authenticated after logout: {}", e);
+                Ok(())
             }
         }
     }
@@ -0,0 +1,197 @@
+use crate::event_log::EventLogSubscriber;
+
+use argentum_encryption_infrastructure::argon2::Argon2;
+use argentum_event_business::bus::SharedEventBus;
+use argentum_event_business::event::EventPublisherTrait;
+use argentum_log_business::LoggerTrait;
+use argentum_notification_business::queue::QueuedNotifier;
+use argentum_notification_business::template::DefaultTemplates;
+use argentum_notification_infrastructure::outbox::FileOutboxNotifier;
+use argentum_standard_business::asynchronous::{Blocking, BlockingExecutorTrait, Offloaded};
+use argentum_standard_business::data_type::id::IdFactory;
+use argentum_standard_business::mock::transaction::TransactionManagerMock;
+use argentum_standard_business::transaction::AsyncTransactionManagerTrait;
+use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+use argentum_user_account_business::mock::repository::login_attempt_repository_mock::LoginAttemptRepositoryMock;
+use argentum_user_account_business::mock::repository::password_credential_repository_mock::PasswordCredentialRepositoryMock;
+use argentum_user_account_business::mock::repository::restore_password_token_repository_mock::RestorePasswordTokenRepositoryMock;
+use argentum_user_account_business::mock::repository::session_repository_mock::SessionRepositoryMock;
+use argentum_user_account_business::repository::login_throttler::{
+    AsyncLoginThrottler, ThrottlingPolicy,
+};
+use argentum_user_account_business::repository::password_credential_checker::AsyncPasswordCredentialChecker;
+use argentum_user_account_business::repository::password_credential_writer::AsyncPasswordCredentialWriter;
+use argentum_user_account_business::use_case::anonymous_registers::AsyncAnonymousRegistersUc;
+use argentum_user_account_business::use_case::restore_password::anonymous_requests_restore_token::AsyncAnonymousRequestsRestoreToken;
+use argentum_user_account_business::use_case::user_authenticates_with_token::AsyncUserAuthenticatesWithTokenUc;
+use argentum_user_account_business::use_case::user_logins_with_password::AsyncUserLoginsWithPasswordUc;
+use argentum_user_account_business::use_case::user_logs_out::AsyncUserLogsOutUc;
+use argentum_user_account_business::use_case::user_registers_with_password::AsyncUserRegistersWithPasswordUc;
+use argentum_user_account_business::value_object::password::PasswordPolicy;
+use argentum_user_account_infrastructure::token::{
+    HmacTokenHasher, SecureTokenGenerator, TokenEncoding,
+};
+use argentum_user_business::mock::repository::anonymous_binding_repository_mock::AnonymousBindingRepositoryMock;
+use argentum_user_business::mock::repository::anonymous_user_repository_mock::AnonymousUserRepositoryMock;
+use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const SESSION_TTL: u32 = 30 * 24 * 60 * 60;
+const SESSION_IDLE_TTL: u32 = 24 * 60 * 60;
+
+/// Async use cases over thread-safe in-memory repositories. Events go through `SharedEventBus`,
+/// emails are queued by requests and written into an outbox directory by
+/// `deliver_notifications`. The container is `Send + Sync`, so one instance serves requests
+/// of all worker threads
+pub struct Container {
+    pub id_factory: Arc<dyn IdFactory + Send + Sync>,
+    notification_queue: Arc<QueuedNotifier>,
+    outbox: Offloaded<FileOutboxNotifier>,
+    pub anonymous_registers: AsyncAnonymousRegistersUc,
+    pub anonymous_requests_restore_token: AsyncAnonymousRequestsRestoreToken,
+    pub user_registers_with_password: AsyncUserRegistersWithPasswordUc,
+    pub user_logins_with_password: AsyncUserLoginsWithPasswordUc,
+    pub user_authenticates_with_token: AsyncUserAuthenticatesWithTokenUc,
+    pub user_logs_out: AsyncUserLogsOutUc,
+}
+
+impl Container {
+    /// Password hashing and writing of emails into `outbox_directory` are offloaded
+    /// to the blocking pool of the runtime behind `executor`
+    pub fn in_memory(
+        logger: Arc<dyn LoggerTrait + Send + Sync>,
+        token_hash_secret: &[u8],
+        outbox_directory: PathBuf,
+        executor: Arc<dyn BlockingExecutorTrait>,
+    ) -> Result<Container, String> {
+        let id_factory = Arc::new(UniqueIdFactory::new());
+
+        let anonymous_user_repository = Arc::new(Blocking::new(AnonymousUserRepositoryMock::new()));
+        let authenticated_user_repository =
+            Arc::new(Blocking::new(AuthenticatedUserRepositoryMock::new()));
+        let anonymous_binding_repository =
+            Arc::new(Blocking::new(AnonymousBindingRepositoryMock::new()));
+        let session_repository = Arc::new(Blocking::new(SessionRepositoryMock::new()));
+        let password_credential_repository =
+            Arc::new(Blocking::new(PasswordCredentialRepositoryMock::new()));
+        let login_attempt_repository = Arc::new(Blocking::new(LoginAttemptRepositoryMock::new()));
+        let restore_password_token_repository =
+            Arc::new(Blocking::new(RestorePasswordTokenRepositoryMock::new()));
+        let transaction_manager: Arc<dyn AsyncTransactionManagerTrait> =
+            Arc::new(Blocking::new(TransactionManagerMock::new()));
+
+        let event_bus = SharedEventBus::new();
+        event_bus.subscribe_to_all(Arc::new(EventLogSubscriber::new(logger.clone())));
+        let event_publisher: Arc<dyn EventPublisherTrait + Send + Sync> = Arc::new(event_bus);
+
+        let templates = Arc::new(DefaultTemplates::new(
+            "Argentum demo",
+            "https://example.com/restore-password?token={token}",
+            "https://example.com/verify-email?token={token}",
+        ));
+        let outbox = FileOutboxNotifier::new(outbox_directory, "no-reply@example.com", templates)
+            .map_err(|e| e.to_string())?;
+        let outbox = Offloaded::new(outbox, executor.clone());
+        let notification_queue = Arc::new(QueuedNotifier::new());
+
+        let token_generator = Arc::new(
+            SecureTokenGenerator::new(256, TokenEncoding::Base64Url)
+                .map_err(|e| e.to_string())?
+                .with_prefix("arg_demo_")
+                .with_checksum(),
+        );
+        let token_hasher = Arc::new(HmacTokenHasher::new(token_hash_secret));
+
+        let argon2_password = Arc::new(Offloaded::new(Argon2::default(), executor));
+        let password_credential_writer = Arc::new(AsyncPasswordCredentialWriter::new(
+            password_credential_repository.clone(),
+        ));
+        let password_credential_checker = Arc::new(AsyncPasswordCredentialChecker::new(
+            password_credential_repository,
+            argon2_password.clone(),
+            argon2_password.inner(),
+        ));
+        let login_throttler = Arc::new(AsyncLoginThrottler::new(
+            login_attempt_repository,
+            ThrottlingPolicy::for_account(),
+            ThrottlingPolicy::for_client(),
+        ));
+
+        Ok(Container {
+            id_factory: id_factory.clone(),
+            notification_queue: notification_queue.clone(),
+            outbox,
+            anonymous_registers: AsyncAnonymousRegistersUc::new(
+                id_factory.clone(),
+                anonymous_user_repository.clone(),
+                session_repository.clone(),
+                token_generator.clone(),
+                token_hasher.clone(),
+                SESSION_TTL,
+                event_publisher.clone(),
+                transaction_manager.clone(),
+            ),
+            anonymous_requests_restore_token: AsyncAnonymousRequestsRestoreToken::new(
+                id_factory.clone(),
+                authenticated_user_repository.clone(),
+                restore_password_token_repository,
+                token_generator.clone(),
+                token_hasher.clone(),
+                notification_queue,
+                event_publisher.clone(),
+                logger.clone(),
+                transaction_manager.clone(),
+            ),
+            user_registers_with_password: AsyncUserRegistersWithPasswordUc::new(
+                authenticated_user_repository.clone(),
+                password_credential_writer.clone(),
+                argon2_password.clone(),
+                Arc::new(PasswordPolicy::default()),
+                None,
+                event_publisher.clone(),
+                transaction_manager.clone(),
+            ),
+            user_logins_with_password: AsyncUserLoginsWithPasswordUc::new(
+                authenticated_user_repository.clone(),
+                anonymous_binding_repository,
+                session_repository.clone(),
+                password_credential_checker,
+                login_throttler,
+                password_credential_writer,
+                argon2_password,
+                id_factory,
+                token_generator,
+                token_hasher.clone(),
+                logger,
+                SESSION_TTL,
+                false,
+                event_publisher.clone(),
+                transaction_manager,
+            ),
+            user_authenticates_with_token: AsyncUserAuthenticatesWithTokenUc::new(
+                authenticated_user_repository,
+                anonymous_user_repository,
+                session_repository.clone(),
+                token_hasher.clone(),
+                SESSION_IDLE_TTL,
+            ),
+            user_logs_out: AsyncUserLogsOutUc::new(
+                session_repository,
+                token_hasher,
+                event_publisher,
+            ),
+        })
+    }
+
+    /// Writes the queued emails into the outbox directory and returns how many were written
+    pub async fn deliver_notifications(&self) -> Result<usize, String> {
+        let queue = self.notification_queue.clone();
+
+        self.outbox
+            .run(move |outbox| queue.deliver(outbox))
+            .await
+            .ok_or_else(|| "Delivery is interrupted".to_string())?
+            .map_err(|e| e.to_string())
+    }
+}
@@ -1,69 +1,211 @@
 use crate::app::App;
+use crate::container::Container;
+use crate::event_log::EventLogSubscriber;
+use crate::postgres;
+use crate::postgres::PostgresRepositories;
+use crate::workers;
 
-use argentum_encryption_infrastructure::pbkdf2::Pbkdf2;
+use argentum_encryption_business::breached_password::BreachedPasswordCheckerTrait;
+use argentum_encryption_infrastructure::argon2::Argon2;
+use argentum_encryption_infrastructure::bcrypt::Bcrypt;
+use argentum_encryption_infrastructure::breached_password::PwnedPasswordsDirectory;
+use argentum_encryption_infrastructure::dispatcher::ValidatorDispatcher;
+use argentum_encryption_infrastructure::pbkdf2::{LegacyPbkdf2, Pbkdf2};
+use argentum_event_business::bus::EventBus;
 use argentum_log_business::{DefaultLogger, Level};
 use argentum_log_infrastructure::stdout::PrettyWriter;
+use argentum_standard_business::mock::transaction::TransactionManagerMock;
+use argentum_standard_business::transaction::TransactionManagerTrait;
 use argentum_standard_infrastructure::data_type::unique_id::UniqueIdFactory;
+use argentum_standard_infrastructure::executor::TokioBlockingExecutor;
+use argentum_user_account_business::mock::repository::login_attempt_repository_mock::LoginAttemptRepositoryMock;
 use argentum_user_account_business::mock::repository::password_credential_repository_mock::PasswordCredentialRepositoryMock;
 use argentum_user_account_business::mock::repository::session_repository_mock::SessionRepositoryMock;
+use argentum_user_account_business::repository::login_throttler::{
+    LoginThrottler, ThrottlingPolicy,
+};
 use argentum_user_account_business::repository::password_credential_checker::PasswordCredentialChecker;
+use argentum_user_account_business::repository::password_credential_repository::PasswordCredentialRepository;
 use argentum_user_account_business::repository::password_credential_writer::PasswordCredentialWriter;
+use argentum_user_account_business::repository::session_repository::SessionRepositoryTrait;
 use argentum_user_account_business::use_case::anonymous_registers::AnonymousRegistersUc;
 use argentum_user_account_business::use_case::user_authenticates_with_token::UserAuthenticatesWithTokenUc;
 use argentum_user_account_business::use_case::user_logins_with_password::UserLoginsWithPasswordUc;
+use argentum_user_account_business::use_case::user_logs_out::UserLogsOutUc;
+use argentum_user_account_business::use_case::user_logs_out_everywhere::UserLogsOutEverywhereUc;
 use argentum_user_account_business::use_case::user_registers_with_password::UserRegistersWithPasswordUc;
-use argentum_user_account_infrastructure::token::StringTokenGenerator;
+use argentum_user_account_business::value_object::password::PasswordPolicy;
+use argentum_user_account_infrastructure::token::{
+    HmacTokenHasher, SecureTokenGenerator, TokenEncoding,
+};
 use argentum_user_business::mock::repository::anonymous_binding_repository_mock::AnonymousBindingRepositoryMock;
 use argentum_user_business::mock::repository::anonymous_user_repository_mock::AnonymousUserRepositoryMock;
 use argentum_user_business::mock::repository::authenticated_user_repository_mock::AuthenticatedUserRepositoryMock;
+use argentum_user_business::repository::anonymous_binding_repository::AnonymousBindingRepositoryTrait;
+use argentum_user_business::repository::user_repository::{
+    AnonymousUserRepositoryTrait, AuthenticatedUserRepositoryTrait,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const SESSION_TTL: u32 = 30 * 24 * 60 * 60;
+const SESSION_IDLE_TTL: u32 = 24 * 60 * 60;
+const WORKERS: usize = 4;
+
+static LOG_WRITER: PrettyWriter = PrettyWriter {};
 
 pub fn init() -> Result<(), String> {
-    let anonymous_user_repository = AnonymousUserRepositoryMock::new();
-    let session_repository = SessionRepositoryMock::new();
     let unique_id_factory = UniqueIdFactory::new();
-    let log_writer = PrettyWriter::new();
-    let logger = DefaultLogger::new(Level::Trace, &log_writer);
 
-    let token_generator = StringTokenGenerator::new();
+    // Postgres is used when DATABASE_URL is set, in-memory repositories otherwise
+    let postgres = postgres::connect()?;
+    let postgres_repositories = postgres
+        .as_ref()
+        .map(|connection| PostgresRepositories::new(connection, &unique_id_factory));
+
+    let anonymous_user_repository_mock = AnonymousUserRepositoryMock::new();
+    let authenticated_user_repository_mock = AuthenticatedUserRepositoryMock::new();
+    let anonymous_binding_repository_mock = AnonymousBindingRepositoryMock::new();
+    let session_repository_mock = SessionRepositoryMock::new();
+    let password_credential_repository_mock = PasswordCredentialRepositoryMock::new();
+    let transaction_manager_mock = TransactionManagerMock::new();
+
+    let (
+        anonymous_user_repository,
+        authenticated_user_repository,
+        anonymous_binding_repository,
+        session_repository,
+        password_credential_repository,
+    ): (
+        &dyn AnonymousUserRepositoryTrait,
+        &dyn AuthenticatedUserRepositoryTrait,
+        &dyn AnonymousBindingRepositoryTrait,
+        &dyn SessionRepositoryTrait,
+        &dyn PasswordCredentialRepository,
+    ) = match &postgres_repositories {
+        Some(r) => (
+            &r.anonymous_user,
+            &r.authenticated_user,
+            &r.anonymous_binding,
+            &r.session,
+            &r.password_credential,
+        ),
+        None => (
+            &anonymous_user_repository_mock,
+            &authenticated_user_repository_mock,
+            &anonymous_binding_repository_mock,
+            &session_repository_mock,
+            &password_credential_repository_mock,
+        ),
+    };
+    let transaction_manager: &dyn TransactionManagerTrait = match &postgres {
+        Some(connection) => connection,
+        None => &transaction_manager_mock,
+    };
+    let logger = Arc::new(DefaultLogger::new(Level::Trace, &LOG_WRITER));
+
+    let event_log_subscriber = EventLogSubscriber::new(logger.clone());
+    let event_bus = EventBus::new();
+    event_bus.subscribe_to_all(&event_log_subscriber);
+
+    let token_generator = SecureTokenGenerator::new(256, TokenEncoding::Base64Url)
+        .map_err(|e| e.to_string())?
+        .with_prefix("arg_demo_")
+        .with_checksum();
+    let token_hash_secret = match std::env::var("TOKEN_HASH_SECRET") {
+        Ok(secret) => secret.into_bytes(),
+        Err(_) => rand::random::<[u8; 32]>().to_vec(),
+    };
+    let token_hasher = HmacTokenHasher::new(&token_hash_secret);
 
     let anonymous_registers_uc = AnonymousRegistersUc::new(
         &unique_id_factory,
-        &anonymous_user_repository,
-        &session_repository,
+        anonymous_user_repository,
+        session_repository,
         &token_generator,
+        &token_hasher,
+        SESSION_TTL,
+        &event_bus,
+        transaction_manager,
     );
 
-    let authenticated_user_repository = AuthenticatedUserRepositoryMock::new();
-    let password_credential_repository = PasswordCredentialRepositoryMock::new();
-
-    let password_credential_writer = PasswordCredentialWriter::new(&password_credential_repository);
+    let password_credential_writer = PasswordCredentialWriter::new(password_credential_repository);
 
+    let argon2_password = Argon2::default();
+    let bcrypt_password = Bcrypt::default();
     let pbkdf2_password = Pbkdf2::new();
+    let legacy_password = LegacyPbkdf2::new();
+    let password_validator = ValidatorDispatcher::new(vec![
+        ("argon2id", &argon2_password),
+        ("2a", &bcrypt_password),
+        ("2b", &bcrypt_password),
+        ("2y", &bcrypt_password),
+        ("pbkdf2-sha512", &pbkdf2_password),
+    ])
+    .with_legacy(&legacy_password);
+
+    let password_policy = PasswordPolicy::default();
+    let pwned_passwords = std::env::var("PWNED_PASSWORDS_DIR")
+        .ok()
+        .map(|path| PwnedPasswordsDirectory::new(path.into()));
 
     let user_registers_uc = UserRegistersWithPasswordUc::new(
-        &authenticated_user_repository,
+        authenticated_user_repository,
         &password_credential_writer,
-        &pbkdf2_password,
+        &argon2_password,
+        &password_policy,
+        pwned_passwords
+            .as_ref()
+            .map(|checker| checker as &dyn BreachedPasswordCheckerTrait),
+        &event_bus,
+        transaction_manager,
     );
 
-    let password_credential_checker =
-        PasswordCredentialChecker::new(&password_credential_repository, &pbkdf2_password);
+    let password_credential_checker = PasswordCredentialChecker::new(
+        password_credential_repository,
+        &password_validator,
+        &argon2_password,
+    );
+
+    let login_attempt_repository = LoginAttemptRepositoryMock::new();
+    let login_throttler = LoginThrottler::new(
+        &login_attempt_repository,
+        ThrottlingPolicy::for_account(),
+        ThrottlingPolicy::for_client(),
+    );
 
-    let anonymous_binding_repository = AnonymousBindingRepositoryMock::new();
     let user_logins_with_password_uc = UserLoginsWithPasswordUc::new(
-        &authenticated_user_repository,
-        &anonymous_binding_repository,
-        &session_repository,
+        authenticated_user_repository,
+        anonymous_binding_repository,
+        session_repository,
         &password_credential_checker,
+        &login_throttler,
+        &password_credential_writer,
+        &argon2_password,
         &unique_id_factory,
         &token_generator,
-        &logger,
+        &token_hasher,
+        logger.as_ref(),
+        SESSION_TTL,
+        false,
+        &event_bus,
+        transaction_manager,
     );
 
     let user_authenticates_with_token_uc = UserAuthenticatesWithTokenUc::new(
-        &authenticated_user_repository,
-        &anonymous_user_repository,
-        &session_repository,
+        authenticated_user_repository,
+        anonymous_user_repository,
+        session_repository,
+        &token_hasher,
+        SESSION_IDLE_TTL,
+    );
+
+    let user_logs_out_uc = UserLogsOutUc::new(session_repository, &token_hasher, &event_bus);
+    let user_logs_out_everywhere_uc = UserLogsOutEverywhereUc::new(
+        session_repository,
+        &token_hasher,
+        &event_bus,
+        transaction_manager,
     );
 
     let app = App::new(
@@ -72,8 +214,26 @@ pub fn init() -> Result<(), String> {
         &user_logins_with_password_uc,
         &user_registers_uc,
         &user_authenticates_with_token_uc,
-        &logger,
+        &user_logs_out_uc,
+        &user_logs_out_everywhere_uc,
+        logger.as_ref(),
     );
 
-    app.run()
+    app.run()?;
+
+    // The same flow for several users at once, served by one container shared between threads
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let container = Container::in_memory(
+        logger,
+        &token_hash_secret,
+        std::env::var("OUTBOX_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| std::env::temp_dir().join("argentum-demo-outbox")),
+        Arc::new(TokioBlockingExecutor::new(runtime.handle().clone())),
+    )?;
+
+    workers::run(&runtime, Arc::new(container), WORKERS)
 }
@@ -0,0 +1,25 @@
+use argentum_event_business::event::{DomainEvent, SubscriberTrait};
+use argentum_log_business::LoggerTrait;
+use std::sync::Arc;
+
+/// Writes every published domain event to the log. Owns its logger, so it can subscribe
+/// to `EventBus` as well as to `SharedEventBus`
+pub struct EventLogSubscriber {
+    logger: Arc<dyn LoggerTrait + Send + Sync>,
+}
+
+impl EventLogSubscriber {
+    pub fn new(logger: Arc<dyn LoggerTrait + Send + Sync>) -> EventLogSubscriber {
+        EventLogSubscriber { logger }
+    }
+}
+
+impl SubscriberTrait for EventLogSubscriber {
+    fn handle(&self, event: &dyn DomainEvent) {
+        self.logger.debug(format!(
+            "Event {} occurred at {}",
+            event.name(),
+            event.occurred_at()
+        ));
+    }
+}
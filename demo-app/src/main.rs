@@ -1,11 +1,10 @@
 pub mod app;
+mod container;
 mod di;
+mod event_log;
+mod postgres;
+mod workers;
 
 fn main() -> Result<(), String> {
-    let res = di::init();
-    if let Err(e) = res {
-        return Err(e);
-    }
-
-    Ok(())
+    di::init()
 }
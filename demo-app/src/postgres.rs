@@ -0,0 +1,44 @@
+use argentum_postgres_infrastructure::connection::PostgresConnection;
+use argentum_postgres_infrastructure::migration::migrate;
+use argentum_postgres_infrastructure::repository::anonymous_binding_repository::PostgresAnonymousBindingRepository;
+use argentum_postgres_infrastructure::repository::anonymous_user_repository::PostgresAnonymousUserRepository;
+use argentum_postgres_infrastructure::repository::authenticated_user_repository::PostgresAuthenticatedUserRepository;
+use argentum_postgres_infrastructure::repository::password_credential_repository::PostgresPasswordCredentialRepository;
+use argentum_postgres_infrastructure::repository::session_repository::PostgresSessionRepository;
+use argentum_standard_business::data_type::id::IdFactory;
+
+/// Connects to `DATABASE_URL` and migrates the schema. None means in-memory storage
+pub fn connect() -> Result<Option<PostgresConnection>, String> {
+    let url = match std::env::var("DATABASE_URL") {
+        Ok(url) => url,
+        Err(_) => return Ok(None),
+    };
+
+    let connection = PostgresConnection::connect(&url).map_err(|e| e.to_string())?;
+    migrate(&connection).map_err(|e| e.to_string())?;
+
+    Ok(Some(connection))
+}
+
+pub struct PostgresRepositories<'s> {
+    pub anonymous_user: PostgresAnonymousUserRepository<'s>,
+    pub authenticated_user: PostgresAuthenticatedUserRepository<'s>,
+    pub anonymous_binding: PostgresAnonymousBindingRepository<'s>,
+    pub session: PostgresSessionRepository<'s>,
+    pub password_credential: PostgresPasswordCredentialRepository<'s>,
+}
+
+impl<'s> PostgresRepositories<'s> {
+    pub fn new(
+        connection: &'s PostgresConnection,
+        id_factory: &'s dyn IdFactory,
+    ) -> PostgresRepositories<'s> {
+        PostgresRepositories {
+            anonymous_user: PostgresAnonymousUserRepository::new(connection, id_factory),
+            authenticated_user: PostgresAuthenticatedUserRepository::new(connection, id_factory),
+            anonymous_binding: PostgresAnonymousBindingRepository::new(connection, id_factory),
+            session: PostgresSessionRepository::new(connection, id_factory),
+            password_credential: PostgresPasswordCredentialRepository::new(connection, id_factory),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::container::Container;
+
+use argentum_standard_business::data_type::email::EmailAddress;
+use argentum_user_business::entity::user::AnonymousUser;
+use argentum_user_business::value_object::name::Name;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+const PASSWORD: &str = "Judgment-Day-1997";
+
+/// Serves a user per worker concurrently. Workers are tasks of a multi-threaded runtime
+/// and share one container. Emails queued by the workers are delivered afterwards
+pub fn run(runtime: &Runtime, container: Arc<Container>, workers: usize) -> Result<(), String> {
+    runtime.block_on(async {
+        let handles: Vec<_> = (0..workers)
+            .map(|n| tokio::spawn(serve_user(container.clone(), n)))
+            .collect();
+
+        for handle in handles {
+            handle.await.map_err(|e| e.to_string())??;
+        }
+
+        let delivered = container.deliver_notifications().await?;
+        println!("Delivered {} notifications", delivered);
+
+        Ok(())
+    })
+}
+
+/// Registers a user, requests a restore password email, logs the user in and out
+async fn serve_user(container: Arc<Container>, n: usize) -> Result<(), String> {
+    let anon_id = container.id_factory.create();
+    let (_, _, anon_token) = container
+        .anonymous_registers
+        .execute(&anon_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    container
+        .user_authenticates_with_token
+        .execute(anon_token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let name = Name::new(String::from("Worker"), n.to_string()).map_err(|e| e.to_string())?;
+    let email =
+        EmailAddress::new(format!("worker-{}@example.com", n)).map_err(|e| e.to_string())?;
+    container
+        .user_registers_with_password
+        .execute(
+            container.id_factory.create(),
+            name,
+            email.clone(),
+            PASSWORD.to_string(),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    container
+        .anonymous_requests_restore_token
+        .execute(email.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let anonymous = AnonymousUser::new(&container.id_factory.create());
+    let (_, token) = container
+        .user_logins_with_password
+        .execute(
+            anonymous,
+            email,
+            PASSWORD.to_string(),
+            format!("worker-{}", n),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    container
+        .user_authenticates_with_token
+        .execute(token.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    container
+        .user_logs_out
+        .execute(token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!("Worker {} logged in and out", n);
+
+    Ok(())
+}
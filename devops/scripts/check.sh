@@ -5,13 +5,20 @@ set -ex
 packages=(
   'argentum_encryption_business'
   'argentum_encryption_infrastructure'
+  'argentum_event_business'
   'argentum_log_business'
   'argentum_log_infrastructure'
+  'argentum_notification_business'
+  'argentum_notification_infrastructure'
+  'argentum_postgres_infrastructure'
+  'argentum_sqlite_infrastructure'
   'argentum_standard_business'
   'argentum_standard_infrastructure'
   'argentum_user_business'
   'argentum_user_account_business'
   'argentum_user_account_infrastructure'
+  'argentum_user_account_rest'
+  'argentum_user_account_grpc'
   'demo-app'
 )
 